@@ -7,6 +7,27 @@ mod m20231012_094303_create_in_use_table;
 mod m20231012_094422_create_session_table;
 mod m20231012_094533_create_access_table;
 mod m20231111_205633_create_role_table;
+mod m20231214_101532_create_project_version_table;
+mod m20231215_093012_create_query_job_table;
+mod m20231216_141207_add_settings_columns;
+mod m20231217_101204_create_query_result_table;
+mod m20231218_084317_create_query_cache_table;
+mod m20231219_093541_create_backend_audit_table;
+mod m20231220_131842_add_timeout_columns;
+mod m20231221_101417_create_simulation_session_table;
+mod m20231221_101433_create_simulation_step_table;
+mod m20231222_140936_create_counterexample_table;
+mod m20231223_101250_create_component_table;
+mod m20231224_091506_add_project_revision_column;
+mod m20231225_083012_add_in_use_expires_at_column;
+mod m20231226_102133_add_project_collaborative_column;
+mod m20231227_091845_create_comment_table;
+mod m20231228_094512_add_role_permissions_column;
+mod m20231229_101204_create_team_table;
+mod m20231229_101219_create_team_member_table;
+mod m20231229_101233_create_team_access_table;
+mod m20231230_093127_create_invitation_table;
+mod m20231231_101512_create_share_link_table;
 
 pub struct Migrator;
 
@@ -21,6 +42,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20231012_094303_create_in_use_table::Migration),
             Box::new(m20231111_205633_create_role_table::Migration),
             Box::new(m20231012_094533_create_access_table::Migration),
+            Box::new(m20231214_101532_create_project_version_table::Migration),
+            Box::new(m20231215_093012_create_query_job_table::Migration),
+            Box::new(m20231216_141207_add_settings_columns::Migration),
+            Box::new(m20231217_101204_create_query_result_table::Migration),
+            Box::new(m20231218_084317_create_query_cache_table::Migration),
+            Box::new(m20231219_093541_create_backend_audit_table::Migration),
+            Box::new(m20231220_131842_add_timeout_columns::Migration),
+            Box::new(m20231221_101417_create_simulation_session_table::Migration),
+            Box::new(m20231221_101433_create_simulation_step_table::Migration),
+            Box::new(m20231222_140936_create_counterexample_table::Migration),
+            Box::new(m20231223_101250_create_component_table::Migration),
+            Box::new(m20231224_091506_add_project_revision_column::Migration),
+            Box::new(m20231225_083012_add_in_use_expires_at_column::Migration),
+            Box::new(m20231226_102133_add_project_collaborative_column::Migration),
+            Box::new(m20231227_091845_create_comment_table::Migration),
+            Box::new(m20231228_094512_add_role_permissions_column::Migration),
+            Box::new(m20231229_101204_create_team_table::Migration),
+            Box::new(m20231229_101219_create_team_member_table::Migration),
+            Box::new(m20231229_101233_create_team_access_table::Migration),
+            Box::new(m20231230_093127_create_invitation_table::Migration),
+            Box::new(m20231231_101512_create_share_link_table::Migration),
         ]
     }
 }
@@ -0,0 +1,94 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231012_094228_create_project_table::Project;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectVersion::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProjectVersion::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectVersion::ProjectId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectVersion::ComponentsInfo)
+                            .json()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProjectVersion::UserId).integer())
+                    .col(
+                        ColumnDef::new(ProjectVersion::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProjectVersion::Table, ProjectVersion::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProjectVersion::Table, ProjectVersion::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing projects start out with their current components as their first version
+        let insert = Query::insert()
+            .into_table(ProjectVersion::Table)
+            .columns([
+                ProjectVersion::ProjectId,
+                ProjectVersion::ComponentsInfo,
+                ProjectVersion::UserId,
+            ])
+            .select_from(
+                Query::select()
+                    .columns([Project::Id, Project::ComponentsInfo, Project::OwnerId])
+                    .from(Project::Table)
+                    .to_owned(),
+            )
+            .map_err(|err| DbErr::Custom(err.to_string()))?
+            .to_owned();
+
+        manager.exec_stmt(insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectVersion::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum ProjectVersion {
+    Table,
+    Id,
+    ProjectId,
+    ComponentsInfo,
+    UserId,
+    CreatedAt,
+}
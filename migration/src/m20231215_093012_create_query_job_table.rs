@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231012_094228_create_project_table::Project;
+use super::m20231012_094242_create_query_table::Query;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(QueryJob::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(QueryJob::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(QueryJob::QueryId).integer().not_null())
+                    .col(ColumnDef::new(QueryJob::ProjectId).integer().not_null())
+                    .col(ColumnDef::new(QueryJob::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(QueryJob::Status)
+                            .string()
+                            .default("Queued")
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(QueryJob::Result).json())
+                    .col(ColumnDef::new(QueryJob::Error).string())
+                    .col(
+                        ColumnDef::new(QueryJob::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QueryJob::UpdatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(QueryJob::Table, QueryJob::QueryId)
+                            .to(Query::Table, Query::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(QueryJob::Table, QueryJob::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(QueryJob::Table, QueryJob::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QueryJob::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum QueryJob {
+    Table,
+    Id,
+    QueryId,
+    ProjectId,
+    UserId,
+    Status,
+    Result,
+    Error,
+    CreatedAt,
+    UpdatedAt,
+}
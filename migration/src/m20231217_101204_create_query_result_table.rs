@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231012_094242_create_query_table::Query;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(QueryResult::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(QueryResult::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(QueryResult::QueryId).integer().not_null())
+                    .col(ColumnDef::new(QueryResult::UserId).integer())
+                    .col(ColumnDef::new(QueryResult::Result).json().not_null())
+                    .col(ColumnDef::new(QueryResult::Settings).json().not_null())
+                    .col(
+                        ColumnDef::new(QueryResult::ComponentsHash)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(QueryResult::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(QueryResult::Table, QueryResult::QueryId)
+                            .to(Query::Table, Query::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(QueryResult::Table, QueryResult::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QueryResult::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum QueryResult {
+    Table,
+    Id,
+    QueryId,
+    UserId,
+    Result,
+    Settings,
+    ComponentsHash,
+    CreatedAt,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(QueryCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(QueryCache::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(QueryCache::Hash)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(QueryCache::Result).json().not_null())
+                    .col(
+                        ColumnDef::new(QueryCache::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QueryCache::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum QueryCache {
+    Table,
+    Id,
+    Hash,
+    Result,
+    CreatedAt,
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BackendAudit::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BackendAudit::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BackendAudit::UserId).integer())
+                    .col(ColumnDef::new(BackendAudit::Method).string().not_null())
+                    .col(ColumnDef::new(BackendAudit::Code).integer().not_null())
+                    .col(
+                        ColumnDef::new(BackendAudit::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(BackendAudit::Table, BackendAudit::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BackendAudit::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum BackendAudit {
+    Table,
+    Id,
+    UserId,
+    Method,
+    Code,
+    CreatedAt,
+}
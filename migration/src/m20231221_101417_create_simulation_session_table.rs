@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231012_094228_create_project_table::Project;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SimulationSession::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SimulationSession::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SimulationSession::ProjectId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SimulationSession::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SimulationSession::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(SimulationSession::ComponentComposition)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SimulationSession::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SimulationSession::UpdatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SimulationSession::Table, SimulationSession::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SimulationSession::Table, SimulationSession::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SimulationSession::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SimulationSession {
+    Table,
+    Id,
+    ProjectId,
+    UserId,
+    Name,
+    ComponentComposition,
+    CreatedAt,
+    UpdatedAt,
+}
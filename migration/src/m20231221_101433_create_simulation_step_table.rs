@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231221_101417_create_simulation_session_table::SimulationSession;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SimulationStep::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SimulationStep::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SimulationStep::SimulationSessionId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SimulationStep::ChosenDecision).json())
+                    .col(
+                        ColumnDef::new(SimulationStep::DecisionPoints)
+                            .json()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SimulationStep::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SimulationStep::Table, SimulationStep::SimulationSessionId)
+                            .to(SimulationSession::Table, SimulationSession::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SimulationStep::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SimulationStep {
+    Table,
+    Id,
+    SimulationSessionId,
+    ChosenDecision,
+    DecisionPoints,
+    CreatedAt,
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094242_create_query_table::Query;
+use super::m20231217_101204_create_query_result_table::QueryResult;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Counterexample::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Counterexample::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Counterexample::QueryId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Counterexample::QueryResultId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Counterexample::Kind).string().not_null())
+                    .col(ColumnDef::new(Counterexample::System).string())
+                    .col(ColumnDef::new(Counterexample::Reason).string())
+                    .col(ColumnDef::new(Counterexample::Actions).json().not_null())
+                    .col(ColumnDef::new(Counterexample::State).json())
+                    .col(ColumnDef::new(Counterexample::Path).json().not_null())
+                    .col(
+                        ColumnDef::new(Counterexample::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Counterexample::Table, Counterexample::QueryId)
+                            .to(Query::Table, Query::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Counterexample::Table, Counterexample::QueryResultId)
+                            .to(QueryResult::Table, QueryResult::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Counterexample::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Counterexample {
+    Table,
+    Id,
+    QueryId,
+    QueryResultId,
+    Kind,
+    System,
+    Reason,
+    Actions,
+    State,
+    Path,
+    CreatedAt,
+}
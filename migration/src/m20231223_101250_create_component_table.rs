@@ -0,0 +1,215 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::prelude::Json;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+use super::m20231012_094228_create_project_table::Project;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Component::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Component::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Component::ProjectId).integer().not_null())
+                    .col(ColumnDef::new(Component::Name).string().not_null())
+                    .col(ColumnDef::new(Component::Component).json().not_null())
+                    .col(
+                        ColumnDef::new(Component::UpdatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .col(Component::ProjectId)
+                            .col(Component::Name)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Component::Table, Component::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Move the components of existing projects into their own rows
+        let db = manager.get_connection();
+        let select = Query::select()
+            .columns([Project::Id, Project::ComponentsInfo])
+            .from(Project::Table)
+            .to_owned();
+
+        for project in db
+            .query_all(manager.get_database_backend().build(&select))
+            .await?
+        {
+            let project_id: i32 = project.try_get("", "id")?;
+            let components_info: Json = project.try_get("", "components_info")?;
+
+            for (name, component) in named_components(&components_info) {
+                let insert = Query::insert()
+                    .into_table(Component::Table)
+                    .columns([Component::ProjectId, Component::Name, Component::Component])
+                    .values([project_id.into(), name.into(), component.into()])
+                    .map_err(|err| DbErr::Custom(err.to_string()))?
+                    .to_owned();
+
+                manager.exec_stmt(insert).await?;
+            }
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .drop_column(Project::ComponentsInfo)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .add_column(
+                        ColumnDef::new(Project::ComponentsInfo)
+                            .json()
+                            .not_null()
+                            .default("{}"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Put the components of every project back into a single components info object
+        let db = manager.get_connection();
+        let select = Query::select()
+            .columns([Component::ProjectId, Component::Component])
+            .from(Component::Table)
+            .order_by(Component::ProjectId, Order::Asc)
+            .order_by(Component::Name, Order::Asc)
+            .to_owned();
+
+        let mut projects: Vec<(i32, Vec<Json>)> = vec![];
+        for component in db
+            .query_all(manager.get_database_backend().build(&select))
+            .await?
+        {
+            let project_id: i32 = component.try_get("", "project_id")?;
+            let component: Json = component.try_get("", "component")?;
+
+            match projects.last_mut() {
+                Some((id, components)) if *id == project_id => components.push(component),
+                _ => projects.push((project_id, vec![component])),
+            }
+        }
+
+        for (project_id, components) in projects {
+            let update = Query::update()
+                .table(Project::Table)
+                .value(Project::ComponentsInfo, components_info(components))
+                .and_where(Expr::col(Project::Id).eq(project_id))
+                .to_owned();
+
+            manager.exec_stmt(update).await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Component::Table).to_owned())
+            .await
+    }
+}
+
+/// Splits a `components_info` object into its components, keyed by a name unique within the project.
+/// Components keep the name of their JSON representation unless an earlier component has it.
+/// The others are given a placeholder name like the API gives, `Component N` with the first `N` not taken.
+fn named_components(components_info: &Json) -> Vec<(String, Json)> {
+    let components = match components_info.get("components").and_then(Json::as_array) {
+        Some(components) => components.as_slice(),
+        None => &[],
+    };
+
+    // Own names are claimed first, so a placeholder never takes the name of a later component
+    let mut names = HashSet::new();
+    let own_names: Vec<Option<String>> = components
+        .iter()
+        .map(|component| component_name(component).filter(|name| names.insert(name.clone())))
+        .collect();
+
+    components
+        .iter()
+        .zip(own_names)
+        .map(|(component, name)| {
+            let name = name.unwrap_or_else(|| {
+                let name = (1..)
+                    .map(|number| format!("Component {}", number))
+                    .find(|name| !names.contains(name))
+                    .unwrap_or_default();
+                names.insert(name.clone());
+                name
+            });
+            (name, component.clone())
+        })
+        .collect()
+}
+
+/// Returns the `name` of a component's JSON representation, if it has a non-empty one.
+fn component_name(component: &Json) -> Option<String> {
+    let json = component
+        .get("rep")?
+        .get("Json")?
+        .as_str()?
+        .parse::<Json>()
+        .ok()?;
+    let name = json.get("name")?.as_str()?.trim();
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Assembles a `components_info` object from the components of a project.
+/// The components hash is derived from the components the same way the API derives it.
+fn components_info(components: Vec<Json>) -> Json {
+    let components = Json::Array(components);
+    let digest = Sha256::digest(components.to_string().as_bytes());
+    let components_hash = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+    Json::Object(
+        [
+            ("components".to_owned(), components),
+            ("components_hash".to_owned(), Json::from(components_hash)),
+        ]
+        .into_iter()
+        .collect(),
+    )
+}
+
+#[derive(DeriveIden)]
+pub enum Component {
+    Table,
+    Id,
+    ProjectId,
+    Name,
+    Component,
+    UpdatedAt,
+}
+
+#[cfg(test)]
+#[path = "tests/m20231223_101250_create_component_table.rs"]
+mod tests;
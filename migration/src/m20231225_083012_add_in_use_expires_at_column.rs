@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing locks were taken implicitly, so they start out released
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InUse::Table)
+                    .add_column(
+                        ColumnDef::new(InUse::ExpiresAt)
+                            .timestamp()
+                            .not_null()
+                            .default("1970-01-01 00:00:00"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InUse::Table)
+                    .drop_column(InUse::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InUse {
+    Table,
+    ExpiresAt,
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231012_094228_create_project_table::Project;
+use super::m20231012_094242_create_query_table::Query;
+use super::m20231223_101250_create_component_table::Component;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comment::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Comment::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Comment::ProjectId).integer().not_null())
+                    .col(ColumnDef::new(Comment::ParentId).integer())
+                    .col(ColumnDef::new(Comment::UserId).integer())
+                    .col(ColumnDef::new(Comment::ComponentId).integer())
+                    .col(ColumnDef::new(Comment::LocationId).string())
+                    .col(ColumnDef::new(Comment::EdgeId).string())
+                    .col(ColumnDef::new(Comment::QueryId).integer())
+                    .col(ColumnDef::new(Comment::Text).string().not_null())
+                    .col(
+                        ColumnDef::new(Comment::Resolved)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Comment::EditedAt).timestamp())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Comment::Table, Comment::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Comment::Table, Comment::ParentId)
+                            .to(Comment::Table, Comment::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Comment::Table, Comment::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    // Threads outlive what they were anchored to, and fall back to the project
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Comment::Table, Comment::ComponentId)
+                            .to(Component::Table, Component::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Comment::Table, Comment::QueryId)
+                            .to(Query::Table, Query::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Comment::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Comment {
+    Table,
+    Id,
+    ProjectId,
+    ParentId,
+    UserId,
+    ComponentId,
+    LocationId,
+    EdgeId,
+    QueryId,
+    Text,
+    Resolved,
+    CreatedAt,
+    EditedAt,
+}
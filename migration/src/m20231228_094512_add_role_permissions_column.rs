@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::prelude::Json;
+
+use super::m20231111_205633_create_role_table::Role;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Role::Table)
+                    .add_column(
+                        ColumnDef::new(Permissions::Permissions)
+                            .json()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Record what the seeded roles have always been allowed to do
+        let seeded_roles: [(&str, &[&str]); 3] = [
+            (
+                "Editor",
+                &[
+                    "ReadProject",
+                    "EditProject",
+                    "EditComponents",
+                    "EditQueries",
+                    "RunQuery",
+                    "ManageAccess",
+                    "Comment",
+                ],
+            ),
+            ("Commenter", &["ReadProject", "RunQuery", "Comment"]),
+            ("Reader", &["ReadProject", "RunQuery"]),
+        ];
+
+        for (name, permissions) in seeded_roles {
+            let permissions = Json::Array(permissions.iter().map(|p| Json::from(*p)).collect());
+
+            let update = Query::update()
+                .table(Role::Table)
+                .value(Permissions::Permissions, permissions)
+                .and_where(Expr::col(Role::Name).eq(name))
+                .to_owned();
+
+            manager.exec_stmt(update).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Role::Table)
+                    .drop_column(Permissions::Permissions)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Permissions {
+    Permissions,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231229_101204_create_team_table::Team;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TeamMember::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TeamMember::TeamId).integer().not_null())
+                    .col(ColumnDef::new(TeamMember::UserId).integer().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(TeamMember::TeamId)
+                            .col(TeamMember::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TeamMember::Table, TeamMember::TeamId)
+                            .to(Team::Table, Team::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TeamMember::Table, TeamMember::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TeamMember::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TeamMember {
+    Table,
+    TeamId,
+    UserId,
+}
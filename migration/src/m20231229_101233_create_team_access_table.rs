@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094228_create_project_table::Project;
+use super::m20231111_205633_create_role_table::Role;
+use super::m20231229_101204_create_team_table::Team;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TeamAccess::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TeamAccess::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TeamAccess::Role).string().not_null())
+                    .col(ColumnDef::new(TeamAccess::ProjectId).integer().not_null())
+                    .col(ColumnDef::new(TeamAccess::TeamId).integer().not_null())
+                    .index(
+                        Index::create()
+                            .col(TeamAccess::ProjectId)
+                            .col(TeamAccess::TeamId)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TeamAccess::Table, TeamAccess::Role)
+                            .to(Role::Table, Role::Name)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TeamAccess::Table, TeamAccess::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TeamAccess::Table, TeamAccess::TeamId)
+                            .to(Team::Table, Team::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TeamAccess::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TeamAccess {
+    Table,
+    Id,
+    Role,
+    ProjectId,
+    TeamId,
+}
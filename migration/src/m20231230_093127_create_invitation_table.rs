@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094213_create_user_table::User;
+use super::m20231012_094228_create_project_table::Project;
+use super::m20231111_205633_create_role_table::Role;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Invitation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Invitation::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Invitation::ProjectId).integer().not_null())
+                    .col(ColumnDef::new(Invitation::Role).string().not_null())
+                    .col(ColumnDef::new(Invitation::UserId).integer())
+                    .col(ColumnDef::new(Invitation::MaxUses).integer().not_null())
+                    .col(
+                        ColumnDef::new(Invitation::Uses)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Invitation::Revoked)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(Invitation::ExpiresAt).timestamp().not_null())
+                    .col(
+                        ColumnDef::new(Invitation::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Invitation::Table, Invitation::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Invitation::Table, Invitation::Role)
+                            .to(Role::Table, Role::Name)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Invitation::Table, Invitation::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Invitation::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Invitation {
+    Table,
+    Id,
+    ProjectId,
+    Role,
+    UserId,
+    MaxUses,
+    Uses,
+    Revoked,
+    ExpiresAt,
+    CreatedAt,
+}
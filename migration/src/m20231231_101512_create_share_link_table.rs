@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20231012_094228_create_project_table::Project;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShareLink::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShareLink::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ShareLink::ProjectId).integer().not_null())
+                    .col(
+                        ColumnDef::new(ShareLink::Token)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ShareLink::Revoked)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(ShareLink::ExpiresAt).timestamp())
+                    .col(
+                        ColumnDef::new(ShareLink::CreatedAt)
+                            .timestamp()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ShareLink::Table, ShareLink::ProjectId)
+                            .to(Project::Table, Project::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShareLink::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ShareLink {
+    Table,
+    Id,
+    ProjectId,
+    Token,
+    Revoked,
+    ExpiresAt,
+    CreatedAt,
+}
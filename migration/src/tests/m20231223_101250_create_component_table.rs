@@ -0,0 +1,90 @@
+use super::{components_info, named_components};
+use sea_orm_migration::sea_orm::prelude::Json;
+
+fn json_component(json: &str) -> Json {
+    Json::Object(
+        [(
+            "rep".to_owned(),
+            Json::Object(
+                [("Json".to_owned(), Json::from(json))]
+                    .into_iter()
+                    .collect(),
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    )
+}
+
+fn names(components: Vec<Json>) -> Vec<String> {
+    let components_info = Json::Object(
+        [("components".to_owned(), Json::Array(components))]
+            .into_iter()
+            .collect(),
+    );
+
+    named_components(&components_info)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+#[test]
+fn named_components_uses_json_names() {
+    let components = vec![
+        json_component("{\"name\":\"Machine\"}"),
+        json_component("{\"name\":\"Researcher\"}"),
+    ];
+
+    assert_eq!(names(components), vec!["Machine", "Researcher"]);
+}
+
+#[test]
+fn named_components_without_name_gets_placeholder_name() {
+    let components = vec![
+        json_component("{\"locations\":[]}"),
+        "{\"rep\":{\"Xml\":\"<nta/>\"}}".parse().unwrap(),
+    ];
+
+    assert_eq!(names(components), vec!["Component 1", "Component 2"]);
+}
+
+#[test]
+fn named_components_duplicate_name_gets_placeholder_name() {
+    let components = vec![
+        json_component("{\"name\":\"Machine\"}"),
+        json_component("{\"name\":\"Machine\"}"),
+    ];
+
+    assert_eq!(names(components), vec!["Machine", "Component 1"]);
+}
+
+#[test]
+fn named_components_placeholder_name_skips_taken_names() {
+    let components = vec![
+        json_component("{\"locations\":[]}"),
+        json_component("{\"name\":\"Component 1\"}"),
+        json_component("{\"name\":\"Component 3\"}"),
+        json_component("{\"locations\":[]}"),
+    ];
+
+    assert_eq!(
+        names(components),
+        vec!["Component 2", "Component 1", "Component 3", "Component 4"]
+    );
+}
+
+#[test]
+fn components_info_hashes_components_like_api() {
+    let components_info = components_info(vec![json_component("{\"name\":\"Machine\"}")]);
+
+    assert_eq!(components_info["components_hash"], Json::from(396339284u32));
+}
+
+#[test]
+fn components_info_of_different_components_gives_different_hash() {
+    let first = components_info(vec![json_component("{\"name\":\"Machine\"}")]);
+    let second = components_info(vec![json_component("{\"name\":\"Researcher\"}")]);
+
+    assert_ne!(first["components_hash"], second["components_hash"]);
+}
@@ -1,4 +1,4 @@
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{
     decode, encode, Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation,
 };
@@ -35,6 +35,38 @@ pub fn validation_interceptor(mut req: Request<()>) -> Result<Request<()>, Statu
     }
 }
 
+/// This method is used to authenticate callers of the EcdarBackend passthrough.
+///
+/// Callers either present an access token, which is validated like on the rest of the API,
+/// or the service credential set in `BACKEND_SERVICE_TOKEN`.
+/// Requests made with the service credential are marked with `service` in the request metadata instead of a `uid`.
+pub fn backend_interceptor(mut req: Request<()>) -> Result<Request<()>, Status> {
+    // Callers should not be able to choose who they are through the metadata
+    req.metadata_mut().remove("uid");
+    req.metadata_mut().remove("service");
+
+    let is_service = match (env::var("BACKEND_SERVICE_TOKEN"), req.token_str()) {
+        (Ok(service_token), Ok(Some(token))) => {
+            !service_token.is_empty()
+                && constant_time_eq(service_token.as_bytes(), token.as_bytes())
+        }
+        _ => false,
+    };
+
+    if is_service {
+        req.metadata_mut()
+            .insert("service", metadata::MetadataValue::from_static("true"));
+        return Ok(req);
+    }
+    validation_interceptor(req)
+}
+
+/// Compares two byte strings in time that only depends on their lengths,
+/// so the service credential cannot be guessed one byte at a time.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Claims {
     pub sub: String,
@@ -46,14 +78,16 @@ pub struct Claims {
 pub enum TokenType {
     AccessToken,
     RefreshToken,
+    InvitationToken,
 }
 
 impl TokenType {
     /// Get the duration for the token type.
-    fn duration(&self) -> Duration {
+    pub fn duration(&self) -> Duration {
         match self {
             TokenType::AccessToken => Duration::minutes(20),
             TokenType::RefreshToken => Duration::days(90),
+            TokenType::InvitationToken => Duration::days(7),
         }
     }
     /// Get the secret for the token type.
@@ -67,6 +101,8 @@ impl TokenType {
                 .expect("env variable `ACCESS_TOKEN_HS512_SECRET` is not set"),
             TokenType::RefreshToken => env::var("REFRESH_TOKEN_HS512_SECRET")
                 .expect("env variable `REFRESH_TOKEN_HS512_SECRET` is not set"),
+            TokenType::InvitationToken => env::var("INVITATION_TOKEN_HS512_SECRET")
+                .expect("env variable `INVITATION_TOKEN_HS512_SECRET` is not set"),
         }
     }
 }
@@ -106,15 +142,34 @@ impl Token {
     /// let token = Token::new(TokenType::AccessToken, "1").unwrap();
     /// ```
     pub fn new(token_type: TokenType, uid: &str) -> Result<Token, TokenError> {
-        let now = Utc::now();
-        let expiration = now
+        let expiration = Utc::now()
             .checked_add_signed(token_type.duration())
-            .ok_or(TokenError::InvalidSignature)?
-            .timestamp();
+            .ok_or(TokenError::InvalidSignature)?;
+
+        Token::with_expiration(token_type, uid, expiration)
+    }
 
+    /// Creates a new Json Web Token that expires at a given time rather than after the duration of its type.
+    ///
+    /// # Arguments
+    /// * `token_type` - The type of token to create.
+    /// * `sub` - The subject of the token, such as a user id.
+    /// * `expiration` - When the token expires.
+    ///
+    /// # Examples
+    /// ```
+    /// use ecdar_api::controllers::auth::{Token, TokenType};
+    ///
+    /// let token = Token::with_expiration(TokenType::InvitationToken, "1", Utc::now() + Duration::days(1)).unwrap();
+    /// ```
+    pub fn with_expiration(
+        token_type: TokenType,
+        sub: &str,
+        expiration: DateTime<Utc>,
+    ) -> Result<Token, TokenError> {
         let claims = Claims {
-            sub: uid.to_owned(),
-            exp: expiration as usize,
+            sub: sub.to_owned(),
+            exp: expiration.timestamp() as usize,
         };
 
         let header = Header::new(Algorithm::HS512);
@@ -286,6 +341,7 @@ pub trait RequestExt {
     fn token_str(&self) -> Result<Option<&str>, ToStrError>;
     fn token_string(&self) -> Result<Option<String>, ToStrError>;
     fn uid(&self) -> Result<Option<i32>, ToStrError>;
+    fn is_service(&self) -> bool;
 }
 
 impl<T> RequestExt for Request<T> {
@@ -317,6 +373,11 @@ impl<T> RequestExt for Request<T> {
             None => Ok(None),
         }
     }
+
+    /// Returns whether the request was made with the service credential.
+    fn is_service(&self) -> bool {
+        self.metadata().contains_key("service")
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,65 @@
+use crate::api::server::protobuf::component::Rep;
+use crate::api::server::protobuf::{Component, ComponentsInfo};
+use crate::entities::component;
+use sea_orm::prelude::Json;
+use sha2::{Digest, Sha256};
+
+/// Returns the name a component gives itself, which is the `name` of its JSON representation.
+/// Components in any other representation, or without a name, are stored under a [placeholder name](placeholder_component_name).
+pub fn component_name(component: &Component) -> Option<String> {
+    match &component.rep {
+        Some(Rep::Json(json)) => {
+            let json: Json = serde_json::from_str(json).ok()?;
+            let name = json.get("name")?.as_str()?.trim();
+            (!name.is_empty()).then(|| name.to_owned())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the name a component without a name of its own is stored under.
+/// Like the names the components migration gives, it is `Component N`, numbered by the first number whose name is not taken.
+pub fn placeholder_component_name(is_taken: impl Fn(&str) -> bool) -> String {
+    let mut number = 1;
+    loop {
+        let name = format!("Component {}", number);
+        if !is_taken(&name) {
+            return name;
+        }
+        number += 1;
+    }
+}
+
+/// Returns true if the given query string mentions any of the given component names as an identifier.
+/// Identifiers are runs of ASCII letters, digits and underscores that do not start with a digit.
+pub fn references_any_component(query: &str, names: &[&str]) -> bool {
+    query
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map(|word| word.trim_start_matches(|c: char| c.is_ascii_digit()))
+        .any(|identifier| !identifier.is_empty() && names.contains(&identifier))
+}
+
+/// Reassembles the components info of a project from its stored components.
+/// The components hash is derived from the components, so equal components always give the same hash.
+/// # Errors
+/// Errors if a stored component cannot be parsed.
+pub fn components_info(
+    components: Vec<component::Model>,
+) -> Result<ComponentsInfo, serde_json::Error> {
+    let components = components
+        .into_iter()
+        .map(|component| serde_json::from_value(component.component))
+        .collect::<Result<Vec<Component>, serde_json::Error>>()?;
+
+    let digest = Sha256::digest(serde_json::to_string(&components)?.as_bytes());
+    let components_hash = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+    Ok(ComponentsInfo {
+        components,
+        components_hash,
+    })
+}
+
+#[cfg(test)]
+#[path = "../tests/api/components.rs"]
+mod tests;
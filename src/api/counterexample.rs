@@ -0,0 +1,154 @@
+use crate::api::server::protobuf::counterexample::Kind;
+use crate::api::server::protobuf::query_response::{
+    consistency_failure, refinement_failure, DeterminismFailure, ImplementationFailure, Result,
+};
+use crate::api::server::protobuf::{Counterexample, Decision, State, StateAction};
+use crate::entities::{counterexample, query, query_result};
+use serde_json::json;
+
+/// Extracts the counterexample of a query run from its stored result.
+/// Only failed refinement, consistency, determinism and implementation checks
+/// and reachable states have one, every other result returns `None`.
+pub fn extract_counterexample(
+    query: &query::Model,
+    query_result: &query_result::Model,
+) -> Option<counterexample::Model> {
+    let result: Result = serde_json::from_value(query_result.result.clone()).ok()?;
+
+    let (kind, system, reason, actions, state, path) = match result {
+        Result::RefinementFailure(failure) => match failure.failure? {
+            refinement_failure::Failure::RefinementStateFailure(failure) => (
+                Kind::Refinement,
+                Some(failure.system.clone()),
+                Some(failure.reason().as_str_name().to_owned()),
+                failure
+                    .action_set
+                    .map(|set| set.actions)
+                    .unwrap_or_default(),
+                failure.state,
+                vec![],
+            ),
+            refinement_failure::Failure::ModelFailure(_) => return None,
+        },
+        Result::ConsistencyFailure(failure) => match failure.failure? {
+            consistency_failure::Failure::Implementation(failure) => {
+                implementation_counterexample(failure)
+            }
+            consistency_failure::Failure::Determinism(failure) => {
+                determinism_counterexample(failure)
+            }
+            consistency_failure::Failure::ModelFailure(_) => return None,
+        },
+        Result::ImplementationFailure(failure) => implementation_counterexample(failure),
+        Result::DeterminismFailure(failure) => determinism_counterexample(failure),
+        Result::ReachabilityPath(reachability) => {
+            let decisions = reachability.path?.decisions;
+            let state = decisions
+                .last()
+                .and_then(|decision| decision.destination.clone());
+            (
+                Kind::Reachability,
+                reachability_system(&query.string),
+                None,
+                decisions
+                    .iter()
+                    .map(|decision| decision.action.clone())
+                    .collect(),
+                state,
+                decisions,
+            )
+        }
+        _ => return None,
+    };
+
+    Some(counterexample::Model {
+        id: Default::default(),
+        query_id: query.id,
+        query_result_id: query_result.id,
+        kind: kind.as_str_name().to_owned(),
+        system: system.filter(|system| !system.is_empty()),
+        reason,
+        actions: json!(actions),
+        state: state.and_then(|state| serde_json::to_value(state).ok()),
+        path: json!(path),
+        created_at: Default::default(),
+    })
+}
+
+type Extracted = (
+    Kind,
+    Option<String>,
+    Option<String>,
+    Vec<String>,
+    Option<State>,
+    Vec<Decision>,
+);
+
+fn implementation_counterexample(failure: ImplementationFailure) -> Extracted {
+    state_action_counterexample(Kind::Implementation, failure.system, failure.failure_state)
+}
+
+fn determinism_counterexample(failure: DeterminismFailure) -> Extracted {
+    state_action_counterexample(Kind::Determinism, failure.system, failure.failure_state)
+}
+
+fn state_action_counterexample(
+    kind: Kind,
+    system: String,
+    failure_state: Option<StateAction>,
+) -> Extracted {
+    let (actions, state) = match failure_state {
+        Some(failure_state) => (vec![failure_state.action], failure_state.state),
+        None => (vec![], None),
+    };
+    (kind, Some(system), None, actions, state, vec![])
+}
+
+/// Converts a stored counterexample into its protobuf representation.
+/// # Errors
+/// Errors if the stored actions, state or path cannot be parsed.
+pub fn counterexample_info(
+    counterexample: counterexample::Model,
+) -> std::result::Result<Counterexample, serde_json::Error> {
+    Ok(Counterexample {
+        id: counterexample.id,
+        query_id: counterexample.query_id,
+        query_result_id: counterexample.query_result_id,
+        kind: Kind::from_str_name(&counterexample.kind)
+            .map(|kind| kind as i32)
+            .unwrap_or_default(),
+        system: counterexample.system,
+        reason: counterexample.reason,
+        actions: serde_json::from_value(counterexample.actions)?,
+        state: counterexample
+            .state
+            .map(serde_json::from_value)
+            .transpose()?,
+        path: serde_json::from_value(counterexample.path)?,
+        created_at: counterexample.created_at.and_utc().timestamp(),
+    })
+}
+
+/// Returns the composition to simulate to reach the state of a counterexample.
+/// A refinement counterexample is simulated on the refining side of the refinement,
+/// every other counterexample on its whole system.
+pub fn simulation_composition(counterexample: &counterexample::Model) -> Option<String> {
+    let system = counterexample.system.as_deref()?;
+    let composition = match Kind::from_str_name(&counterexample.kind) {
+        Some(Kind::Refinement) => system.split("<=").next()?,
+        _ => system,
+    }
+    .trim();
+    (!composition.is_empty()).then(|| composition.to_owned())
+}
+
+/// Returns the system of a reachability query, e.g. `(A || B)` of `reachability: (A || B) -> [L1](x>1)`.
+pub fn reachability_system(query: &str) -> Option<String> {
+    let (_, rest) = query.split_once(':')?;
+    let system = rest.split("->").next()?.trim();
+    (!system.is_empty()).then(|| system.to_owned())
+}
+
+#[cfg(test)]
+#[path = "../tests/api/counterexample.rs"]
+mod tests;
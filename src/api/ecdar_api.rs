@@ -21,17 +21,45 @@ impl ConcreteEcdarApi {
 mod routes {
     use super::super::server::protobuf::{
         ecdar_api_auth_server::EcdarApiAuth, ecdar_api_server::EcdarApi,
-        ecdar_backend_server::EcdarBackend, CreateAccessRequest, CreateProjectRequest,
-        CreateProjectResponse, CreateQueryRequest, CreateUserRequest, DeleteAccessRequest,
-        DeleteProjectRequest, DeleteQueryRequest, EndpointsResponse, GetAuthTokenRequest,
-        GetAuthTokenResponse, GetProjectRequest, GetProjectResponse, GetUsersRequest,
-        GetUsersResponse, ListAccessInfoRequest, ListAccessInfoResponse, ListProjectsInfoResponse,
-        QueryRequest, QueryResponse, SendQueryRequest, SendQueryResponse, SimulationStartRequest,
-        SimulationStepRequest, SimulationStepResponse, UpdateAccessRequest, UpdateProjectRequest,
-        UpdateQueryRequest, UpdateUserRequest, UserTokenResponse,
+        ecdar_backend_server::EcdarBackend, AcceptInvitationRequest, AcceptInvitationResponse,
+        AcquireLockRequest, AddTeamMembersRequest, CancelQueryJobRequest, CancelQueryRequest,
+        Comment, CreateAccessRequest, CreateCommentRequest, CreateComponentRequest,
+        CreateComponentResponse, CreateInvitationRequest, CreateInvitationResponse,
+        CreateProjectRequest, CreateProjectResponse, CreateQueryRequest, CreateRoleRequest,
+        CreateShareLinkRequest, CreateSimulationSessionRequest, CreateTeamAccessRequest,
+        CreateTeamRequest, CreateTeamResponse, CreateUserRequest, DeleteAccessRequest,
+        DeleteCommentRequest, DeleteComponentRequest, DeleteProjectRequest, DeleteQueryRequest,
+        DeleteRoleRequest, DeleteSimulationSessionRequest, DeleteTeamAccessRequest,
+        DeleteTeamRequest, EndpointsResponse, ExportSimulationTraceRequest,
+        ExportSimulationTraceResponse, ForceReleaseRequest, GetAuthTokenRequest,
+        GetAuthTokenResponse, GetBackendHealthResponse, GetComponentRequest, GetComponentResponse,
+        GetCounterexampleRequest, GetCounterexampleResponse, GetProjectRequest, GetProjectResponse,
+        GetProjectVersionRequest, GetProjectVersionResponse, GetQueryJobRequest,
+        GetQueryJobResponse, GetSharedProjectRequest, GetSimulationSessionRequest,
+        GetSimulationSessionResponse, GetTeamRequest, GetTeamResponse, GetUsersRequest,
+        GetUsersResponse, HeartbeatRequest, ListAccessInfoRequest, ListAccessInfoResponse,
+        ListCommentsRequest, ListCommentsResponse, ListInvitationsRequest, ListInvitationsResponse,
+        ListProjectVersionsRequest, ListProjectVersionsResponse, ListProjectsInfoResponse,
+        ListQueryJobsRequest, ListQueryJobsResponse, ListQueryResultsRequest,
+        ListQueryResultsResponse, ListRolesResponse, ListShareLinksRequest, ListShareLinksResponse,
+        ListSharedQueryResultsRequest, ListSimulationSessionsRequest,
+        ListSimulationSessionsResponse, ListTeamsResponse, LockInfo, ProjectEditOperation,
+        QueryRequest, QueryResponse, ReleaseLockRequest, RemoveTeamMemberRequest,
+        RenameSimulationSessionRequest, ReplaySimulationTraceRequest,
+        ReplaySimulationTraceResponse, ReplyToCommentRequest, ResolveCommentRequest,
+        RestoreProjectVersionRequest, RevokeInvitationRequest, RevokeShareLinkRequest, RoleInfo,
+        SendQueriesRequest, SendQueriesResponse, SendQueryRequest, SendQueryResponse,
+        ShareLinkInfo, SimulationSessionStep, SimulationStartRequest, SimulationStepRequest,
+        SimulationStepResponse, StartCounterexampleSimulationRequest, SubmitQueryJobRequest,
+        SubmitQueryJobResponse, SubscribeQueryEventsRequest, TakeSimulationSessionStepRequest,
+        UpdateAccessRequest, UpdateCommentRequest, UpdateComponentRequest, UpdateProjectRequest,
+        UpdateQueryRequest, UpdateRoleRequest, UpdateTeamAccessRequest, UpdateUserRequest,
+        UserTokenResponse,
     };
 
-    use tonic::{Request, Response, Status};
+    use crate::controllers::controller_traits::ProjectEditOperationStream;
+    use crate::services::service_traits::{ProjectEditStream, QueryEventStream};
+    use tonic::{Request, Response, Status, Streaming};
 
     /// Implementation of all the endpoits that the Ecdar API service expose through protobuffers.
     #[tonic::async_trait]
@@ -86,6 +114,181 @@ mod routes {
                 .await
         }
 
+        async fn list_project_versions(
+            &self,
+            request: Request<ListProjectVersionsRequest>,
+        ) -> Result<Response<ListProjectVersionsResponse>, Status> {
+            self.controllers
+                .project_controller
+                .list_project_versions(request)
+                .await
+        }
+
+        async fn get_project_version(
+            &self,
+            request: Request<GetProjectVersionRequest>,
+        ) -> Result<Response<GetProjectVersionResponse>, Status> {
+            self.controllers
+                .project_controller
+                .get_project_version(request)
+                .await
+        }
+
+        async fn restore_project_version(
+            &self,
+            request: Request<RestoreProjectVersionRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .project_controller
+                .restore_project_version(request)
+                .await
+        }
+
+        type CollaborateOnProjectStream = ProjectEditStream;
+
+        async fn collaborate_on_project(
+            &self,
+            request: Request<Streaming<ProjectEditOperation>>,
+        ) -> Result<Response<Self::CollaborateOnProjectStream>, Status> {
+            self.controllers
+                .collaboration_controller
+                .collaborate_on_project(
+                    request.map(|operations| Box::pin(operations) as ProjectEditOperationStream),
+                )
+                .await
+        }
+
+        async fn acquire_lock(
+            &self,
+            request: Request<AcquireLockRequest>,
+        ) -> Result<Response<LockInfo>, Status> {
+            self.controllers.lock_controller.acquire_lock(request).await
+        }
+
+        async fn heartbeat(
+            &self,
+            request: Request<HeartbeatRequest>,
+        ) -> Result<Response<LockInfo>, Status> {
+            self.controllers.lock_controller.heartbeat(request).await
+        }
+
+        async fn release_lock(
+            &self,
+            request: Request<ReleaseLockRequest>,
+        ) -> Result<Response<LockInfo>, Status> {
+            self.controllers.lock_controller.release_lock(request).await
+        }
+
+        async fn force_release(
+            &self,
+            request: Request<ForceReleaseRequest>,
+        ) -> Result<Response<LockInfo>, Status> {
+            self.controllers
+                .lock_controller
+                .force_release(request)
+                .await
+        }
+
+        async fn create_component(
+            &self,
+            request: Request<CreateComponentRequest>,
+        ) -> Result<Response<CreateComponentResponse>, Status> {
+            self.controllers
+                .component_controller
+                .create_component(request)
+                .await
+        }
+
+        async fn get_component(
+            &self,
+            request: Request<GetComponentRequest>,
+        ) -> Result<Response<GetComponentResponse>, Status> {
+            self.controllers
+                .component_controller
+                .get_component(request)
+                .await
+        }
+
+        async fn update_component(
+            &self,
+            request: Request<UpdateComponentRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .component_controller
+                .update_component(request)
+                .await
+        }
+
+        async fn delete_component(
+            &self,
+            request: Request<DeleteComponentRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .component_controller
+                .delete_component(request)
+                .await
+        }
+
+        async fn create_comment(
+            &self,
+            request: Request<CreateCommentRequest>,
+        ) -> Result<Response<Comment>, Status> {
+            self.controllers
+                .comment_controller
+                .create_comment(request)
+                .await
+        }
+
+        async fn reply_to_comment(
+            &self,
+            request: Request<ReplyToCommentRequest>,
+        ) -> Result<Response<Comment>, Status> {
+            self.controllers
+                .comment_controller
+                .reply_to_comment(request)
+                .await
+        }
+
+        async fn resolve_comment(
+            &self,
+            request: Request<ResolveCommentRequest>,
+        ) -> Result<Response<Comment>, Status> {
+            self.controllers
+                .comment_controller
+                .resolve_comment(request)
+                .await
+        }
+
+        async fn update_comment(
+            &self,
+            request: Request<UpdateCommentRequest>,
+        ) -> Result<Response<Comment>, Status> {
+            self.controllers
+                .comment_controller
+                .update_comment(request)
+                .await
+        }
+
+        async fn delete_comment(
+            &self,
+            request: Request<DeleteCommentRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .comment_controller
+                .delete_comment(request)
+                .await
+        }
+
+        async fn list_comments(
+            &self,
+            request: Request<ListCommentsRequest>,
+        ) -> Result<Response<ListCommentsResponse>, Status> {
+            self.controllers
+                .comment_controller
+                .list_comments(request)
+                .await
+        }
+
         async fn list_access_info(
             &self,
             request: Request<ListAccessInfoRequest>,
@@ -126,6 +329,182 @@ mod routes {
                 .await
         }
 
+        async fn create_team_access(
+            &self,
+            request: Request<CreateTeamAccessRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .access_controller
+                .create_team_access(request)
+                .await
+        }
+
+        async fn update_team_access(
+            &self,
+            request: Request<UpdateTeamAccessRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .access_controller
+                .update_team_access(request)
+                .await
+        }
+
+        async fn delete_team_access(
+            &self,
+            request: Request<DeleteTeamAccessRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .access_controller
+                .delete_team_access(request)
+                .await
+        }
+
+        async fn create_invitation(
+            &self,
+            request: Request<CreateInvitationRequest>,
+        ) -> Result<Response<CreateInvitationResponse>, Status> {
+            self.controllers
+                .invitation_controller
+                .create_invitation(request)
+                .await
+        }
+
+        async fn list_invitations(
+            &self,
+            request: Request<ListInvitationsRequest>,
+        ) -> Result<Response<ListInvitationsResponse>, Status> {
+            self.controllers
+                .invitation_controller
+                .list_invitations(request)
+                .await
+        }
+
+        async fn revoke_invitation(
+            &self,
+            request: Request<RevokeInvitationRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .invitation_controller
+                .revoke_invitation(request)
+                .await
+        }
+
+        async fn accept_invitation(
+            &self,
+            request: Request<AcceptInvitationRequest>,
+        ) -> Result<Response<AcceptInvitationResponse>, Status> {
+            self.controllers
+                .invitation_controller
+                .accept_invitation(request)
+                .await
+        }
+
+        async fn create_share_link(
+            &self,
+            request: Request<CreateShareLinkRequest>,
+        ) -> Result<Response<ShareLinkInfo>, Status> {
+            self.controllers
+                .share_link_controller
+                .create_share_link(request)
+                .await
+        }
+
+        async fn list_share_links(
+            &self,
+            request: Request<ListShareLinksRequest>,
+        ) -> Result<Response<ListShareLinksResponse>, Status> {
+            self.controllers
+                .share_link_controller
+                .list_share_links(request)
+                .await
+        }
+
+        async fn revoke_share_link(
+            &self,
+            request: Request<RevokeShareLinkRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .share_link_controller
+                .revoke_share_link(request)
+                .await
+        }
+
+        async fn create_team(
+            &self,
+            request: Request<CreateTeamRequest>,
+        ) -> Result<Response<CreateTeamResponse>, Status> {
+            self.controllers.team_controller.create_team(request).await
+        }
+
+        async fn get_team(
+            &self,
+            request: Request<GetTeamRequest>,
+        ) -> Result<Response<GetTeamResponse>, Status> {
+            self.controllers.team_controller.get_team(request).await
+        }
+
+        async fn list_teams(
+            &self,
+            request: Request<()>,
+        ) -> Result<Response<ListTeamsResponse>, Status> {
+            self.controllers.team_controller.list_teams(request).await
+        }
+
+        async fn delete_team(
+            &self,
+            request: Request<DeleteTeamRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers.team_controller.delete_team(request).await
+        }
+
+        async fn add_team_members(
+            &self,
+            request: Request<AddTeamMembersRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .team_controller
+                .add_team_members(request)
+                .await
+        }
+
+        async fn remove_team_member(
+            &self,
+            request: Request<RemoveTeamMemberRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .team_controller
+                .remove_team_member(request)
+                .await
+        }
+
+        async fn list_roles(
+            &self,
+            request: Request<()>,
+        ) -> Result<Response<ListRolesResponse>, Status> {
+            self.controllers.role_controller.list_roles(request).await
+        }
+
+        async fn create_role(
+            &self,
+            request: Request<CreateRoleRequest>,
+        ) -> Result<Response<RoleInfo>, Status> {
+            self.controllers.role_controller.create_role(request).await
+        }
+
+        async fn update_role(
+            &self,
+            request: Request<UpdateRoleRequest>,
+        ) -> Result<Response<RoleInfo>, Status> {
+            self.controllers.role_controller.update_role(request).await
+        }
+
+        async fn delete_role(
+            &self,
+            request: Request<DeleteRoleRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers.role_controller.delete_role(request).await
+        }
+
         async fn update_user(
             &self,
             request: Request<UpdateUserRequest>,
@@ -181,6 +560,198 @@ mod routes {
             self.controllers.query_controller.send_query(request).await
         }
 
+        async fn send_queries(
+            &self,
+            request: Request<SendQueriesRequest>,
+        ) -> Result<Response<SendQueriesResponse>, Status> {
+            self.controllers
+                .query_controller
+                .send_queries(request)
+                .await
+        }
+
+        type SubscribeQueryEventsStream = QueryEventStream;
+
+        async fn subscribe_query_events(
+            &self,
+            request: Request<SubscribeQueryEventsRequest>,
+        ) -> Result<Response<Self::SubscribeQueryEventsStream>, Status> {
+            self.controllers
+                .query_controller
+                .subscribe_query_events(request)
+                .await
+        }
+
+        async fn submit_query_job(
+            &self,
+            request: Request<SubmitQueryJobRequest>,
+        ) -> Result<Response<SubmitQueryJobResponse>, Status> {
+            self.controllers
+                .query_controller
+                .submit_query_job(request)
+                .await
+        }
+
+        async fn get_query_job(
+            &self,
+            request: Request<GetQueryJobRequest>,
+        ) -> Result<Response<GetQueryJobResponse>, Status> {
+            self.controllers
+                .query_controller
+                .get_query_job(request)
+                .await
+        }
+
+        async fn list_query_jobs(
+            &self,
+            request: Request<ListQueryJobsRequest>,
+        ) -> Result<Response<ListQueryJobsResponse>, Status> {
+            self.controllers
+                .query_controller
+                .list_query_jobs(request)
+                .await
+        }
+
+        async fn cancel_query_job(
+            &self,
+            request: Request<CancelQueryJobRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .query_controller
+                .cancel_query_job(request)
+                .await
+        }
+
+        async fn cancel_query(
+            &self,
+            request: Request<CancelQueryRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .query_controller
+                .cancel_query(request)
+                .await
+        }
+
+        async fn list_query_results(
+            &self,
+            request: Request<ListQueryResultsRequest>,
+        ) -> Result<Response<ListQueryResultsResponse>, Status> {
+            self.controllers
+                .query_controller
+                .list_query_results(request)
+                .await
+        }
+
+        async fn get_counterexample(
+            &self,
+            request: Request<GetCounterexampleRequest>,
+        ) -> Result<Response<GetCounterexampleResponse>, Status> {
+            self.controllers
+                .query_controller
+                .get_counterexample(request)
+                .await
+        }
+
+        async fn create_simulation_session(
+            &self,
+            request: Request<CreateSimulationSessionRequest>,
+        ) -> Result<Response<GetSimulationSessionResponse>, Status> {
+            self.controllers
+                .simulation_controller
+                .create_simulation_session(request)
+                .await
+        }
+
+        async fn take_simulation_session_step(
+            &self,
+            request: Request<TakeSimulationSessionStepRequest>,
+        ) -> Result<Response<SimulationSessionStep>, Status> {
+            self.controllers
+                .simulation_controller
+                .take_simulation_session_step(request)
+                .await
+        }
+
+        async fn get_simulation_session(
+            &self,
+            request: Request<GetSimulationSessionRequest>,
+        ) -> Result<Response<GetSimulationSessionResponse>, Status> {
+            self.controllers
+                .simulation_controller
+                .get_simulation_session(request)
+                .await
+        }
+
+        async fn list_simulation_sessions(
+            &self,
+            request: Request<ListSimulationSessionsRequest>,
+        ) -> Result<Response<ListSimulationSessionsResponse>, Status> {
+            self.controllers
+                .simulation_controller
+                .list_simulation_sessions(request)
+                .await
+        }
+
+        async fn rename_simulation_session(
+            &self,
+            request: Request<RenameSimulationSessionRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .simulation_controller
+                .rename_simulation_session(request)
+                .await
+        }
+
+        async fn delete_simulation_session(
+            &self,
+            request: Request<DeleteSimulationSessionRequest>,
+        ) -> Result<Response<()>, Status> {
+            self.controllers
+                .simulation_controller
+                .delete_simulation_session(request)
+                .await
+        }
+
+        async fn export_simulation_trace(
+            &self,
+            request: Request<ExportSimulationTraceRequest>,
+        ) -> Result<Response<ExportSimulationTraceResponse>, Status> {
+            self.controllers
+                .simulation_controller
+                .export_simulation_trace(request)
+                .await
+        }
+
+        async fn replay_simulation_trace(
+            &self,
+            request: Request<ReplaySimulationTraceRequest>,
+        ) -> Result<Response<ReplaySimulationTraceResponse>, Status> {
+            self.controllers
+                .simulation_controller
+                .replay_simulation_trace(request)
+                .await
+        }
+
+        async fn start_counterexample_simulation(
+            &self,
+            request: Request<StartCounterexampleSimulationRequest>,
+        ) -> Result<Response<GetSimulationSessionResponse>, Status> {
+            self.controllers
+                .simulation_controller
+                .start_counterexample_simulation(request)
+                .await
+        }
+
+        async fn get_backend_health(
+            &self,
+            request: Request<()>,
+        ) -> Result<Response<GetBackendHealthResponse>, Status> {
+            self.controllers
+                .reveaal_controller
+                .get_backend_health(request)
+                .await
+        }
+
         async fn delete_session(&self, request: Request<()>) -> Result<Response<()>, Status> {
             self.controllers
                 .session_controller
@@ -254,5 +825,25 @@ mod routes {
         ) -> Result<Response<()>, Status> {
             self.controllers.user_controller.create_user(request).await
         }
+
+        async fn get_shared_project(
+            &self,
+            request: Request<GetSharedProjectRequest>,
+        ) -> Result<Response<GetProjectResponse>, Status> {
+            self.controllers
+                .share_link_controller
+                .get_shared_project(request)
+                .await
+        }
+
+        async fn list_shared_query_results(
+            &self,
+            request: Request<ListSharedQueryResultsRequest>,
+        ) -> Result<Response<ListQueryResultsResponse>, Status> {
+            self.controllers
+                .share_link_controller
+                .list_shared_query_results(request)
+                .await
+        }
     }
 }
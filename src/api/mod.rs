@@ -1,3 +1,10 @@
 pub mod auth;
+pub mod components;
+pub mod counterexample;
 pub mod ecdar_api;
+pub mod logger;
+pub mod query_hash;
+pub mod query_settings;
+pub mod roles;
 pub mod server;
+pub mod simulation_trace;
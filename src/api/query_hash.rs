@@ -0,0 +1,26 @@
+use sea_orm::prelude::Json;
+use sha2::{Digest, Sha256};
+
+/// Returns a hex encoded SHA-256 hash of a project's components info.
+/// The keys of json objects are sorted when serialized, so equal components always give the same hash.
+pub fn hash_components_info(components_info: &Json) -> String {
+    let digest = Sha256::digest(components_info.to_string().as_bytes());
+    format!("{:x}", digest)
+}
+
+/// Returns a hex encoded SHA-256 hash of everything that decides the result of a query run:
+/// the query string, the components it runs against (given by their hash) and the settings it runs with.
+/// Equal runs always give the same hash, regardless of which project or query they belong to.
+pub fn hash_query(query: &str, components_hash: &str, settings: &Json) -> String {
+    let key = Json::Array(vec![
+        Json::String(query.to_owned()),
+        Json::String(components_hash.to_owned()),
+        settings.clone(),
+    ]);
+    let digest = Sha256::digest(key.to_string().as_bytes());
+    format!("{:x}", digest)
+}
+
+#[cfg(test)]
+#[path = "../tests/api/query_hash.rs"]
+mod tests;
@@ -0,0 +1,55 @@
+use crate::api::server::protobuf::query_request::Settings;
+use sea_orm::prelude::Json;
+use std::num::TryFromIntError;
+use std::time::Duration;
+
+/// Resolves the settings a query is run with on Reveaal.
+/// Settings given with the request take precedence over the settings stored on the query,
+/// which in turn take precedence over the settings stored on the project.
+/// If none of them are set, Reveaal's defaults are used.
+pub fn resolve_query_settings(
+    project_settings: Option<&Json>,
+    query_settings: Option<&Json>,
+    request_settings: Option<Settings>,
+) -> Result<Settings, serde_json::Error> {
+    if let Some(settings) = request_settings {
+        return Ok(settings);
+    }
+
+    match query_settings.or(project_settings) {
+        Some(settings) => serde_json::from_value(settings.clone()),
+        None => Ok(Settings::default()),
+    }
+}
+
+/// Resolves the deadline of a query run on Reveaal.
+/// The timeout stored on the query takes precedence over the timeout stored on the project.
+/// If neither is set, the query runs until Reveaal answers.
+pub fn resolve_query_timeout(
+    project_timeout_secs: Option<i32>,
+    query_timeout_secs: Option<i32>,
+) -> Option<Duration> {
+    query_timeout_secs
+        .or(project_timeout_secs)
+        .filter(|secs| *secs > 0)
+        .map(|secs| Duration::from_secs(secs as u64))
+}
+
+/// Returns the timeout to store when a project or query is updated with the requested timeout.
+/// No requested timeout keeps the stored one, and a requested timeout of zero removes it.
+/// # Errors
+/// Errors if the requested timeout does not fit in the database.
+pub fn update_timeout(
+    stored_timeout_secs: Option<i32>,
+    requested_timeout_secs: Option<u32>,
+) -> Result<Option<i32>, TryFromIntError> {
+    match requested_timeout_secs {
+        Some(0) => Ok(None),
+        Some(secs) => i32::try_from(secs).map(Some),
+        None => Ok(stored_timeout_secs),
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/api/query_settings.rs"]
+mod tests;
@@ -0,0 +1,175 @@
+use sea_orm::prelude::Json;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The roles seeded in the `role` table. Their permissions are fixed,
+/// while the permissions of roles created later are stored alongside them in the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Role {
+    Editor,
+    Commenter,
+    Reader,
+}
+
+/// The actions on a project that a role can be allowed to perform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Read the project along with its components, queries, results and comments.
+    ReadProject,
+    /// Rename the project, change its settings, restore versions of it and lock it.
+    EditProject,
+    /// Create, update and delete the components of the project.
+    EditComponents,
+    /// Create, update and delete queries, and cancel runs started by others.
+    EditQueries,
+    /// Run queries and simulations on the project.
+    RunQuery,
+    /// Grant, change and revoke the access of other users.
+    ManageAccess,
+    /// Write, reply to and resolve comments.
+    Comment,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::Editor, Role::Commenter, Role::Reader];
+
+    /// The name of the role in the `role` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Editor => "Editor",
+            Role::Commenter => "Commenter",
+            Role::Reader => "Reader",
+        }
+    }
+
+    /// The permissions granted by the role.
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::Editor => &[
+                Permission::ReadProject,
+                Permission::EditProject,
+                Permission::EditComponents,
+                Permission::EditQueries,
+                Permission::RunQuery,
+                Permission::ManageAccess,
+                Permission::Comment,
+            ],
+            Role::Commenter => &[
+                Permission::ReadProject,
+                Permission::RunQuery,
+                Permission::Comment,
+            ],
+            Role::Reader => &[Permission::ReadProject, Permission::RunQuery],
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.as_str() == s)
+            .ok_or_else(|| format!("'{}' is not a role", s))
+    }
+}
+
+impl Permission {
+    pub const ALL: [Permission; 7] = [
+        Permission::ReadProject,
+        Permission::EditProject,
+        Permission::EditComponents,
+        Permission::EditQueries,
+        Permission::RunQuery,
+        Permission::ManageAccess,
+        Permission::Comment,
+    ];
+
+    /// The name of the permission as stored in the `role` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ReadProject => "ReadProject",
+            Permission::EditProject => "EditProject",
+            Permission::EditComponents => "EditComponents",
+            Permission::EditQueries => "EditQueries",
+            Permission::RunQuery => "RunQuery",
+            Permission::ManageAccess => "ManageAccess",
+            Permission::Comment => "Comment",
+        }
+    }
+
+    /// Describes the permission as the action it allows, for use in error messages.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Permission::ReadProject => "read the project",
+            Permission::EditProject => "edit the project",
+            Permission::EditComponents => "edit components",
+            Permission::EditQueries => "edit queries",
+            Permission::RunQuery => "run queries",
+            Permission::ManageAccess => "manage access to the project",
+            Permission::Comment => "comment on the project",
+        }
+    }
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+            .ok_or_else(|| format!("'{}' is not a permission", s))
+    }
+}
+
+/// Parses permission names into a set of permissions, in the order of [`Permission::ALL`].
+/// # Errors
+/// Errors if any of the names is not a permission.
+pub fn parse_permissions<S: AsRef<str>>(names: &[S]) -> Result<Vec<Permission>, String> {
+    let permissions = names
+        .iter()
+        .map(|name| Permission::from_str(name.as_ref()))
+        .collect::<Result<Vec<Permission>, String>>()?;
+
+    Ok(Permission::ALL
+        .into_iter()
+        .filter(|permission| permissions.contains(permission))
+        .collect())
+}
+
+/// Returns the permissions stored for a role in the `role` table.
+/// # Errors
+/// Errors if the stored value is not a list of permission names.
+pub fn permissions_from_json(permissions: &Json) -> Result<Vec<Permission>, String> {
+    let names: Vec<String> =
+        serde_json::from_value(permissions.clone()).map_err(|err| err.to_string())?;
+    parse_permissions(&names)
+}
+
+/// Returns the permissions as they are stored for a role in the `role` table.
+pub fn permissions_to_json(permissions: &[Permission]) -> Json {
+    Json::Array(
+        permissions
+            .iter()
+            .map(|permission| Json::from(permission.as_str()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+#[path = "../tests/api/roles.rs"]
+mod tests;
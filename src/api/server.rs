@@ -33,7 +33,10 @@ pub async fn start_grpc_server(
             svc.clone(),
             auth::validation_interceptor,
         ))
-        .add_service(EcdarBackendServer::new(svc.clone()))
+        .add_service(EcdarBackendServer::with_interceptor(
+            svc.clone(),
+            auth::backend_interceptor,
+        ))
         .serve(addr)
         .await?;
     Ok(())
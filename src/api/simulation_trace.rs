@@ -0,0 +1,73 @@
+use crate::api::server::protobuf::Decision;
+use serde::{Deserialize, Serialize};
+
+/// The version of the trace format written by `SimulationTrace::new`.
+pub const SIMULATION_TRACE_VERSION: u32 = 1;
+
+/// A portable trace of a simulation, which can be replayed against other components.
+/// The first step holds the initial decision points, every later step the decision
+/// that was chosen and the decision points it led to.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationTrace {
+    pub version: u32,
+    pub component_composition: String,
+    pub steps: Vec<SimulationTraceStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationTraceStep {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chosen_decision: Option<Decision>,
+    pub decision_points: Vec<Decision>,
+}
+
+impl SimulationTrace {
+    pub fn new(component_composition: String, steps: Vec<SimulationTraceStep>) -> Self {
+        Self {
+            version: SIMULATION_TRACE_VERSION,
+            component_composition,
+            steps,
+        }
+    }
+
+    /// Parses a trace from its JSON representation.
+    /// # Errors
+    /// Errors if the trace is not valid JSON, has an unsupported version, has no steps,
+    /// or if only its first step lacks a chosen decision.
+    pub fn parse(trace: &str) -> Result<Self, String> {
+        let trace: SimulationTrace =
+            serde_json::from_str(trace).map_err(|err| format!("Invalid trace: {}", err))?;
+
+        if trace.version != SIMULATION_TRACE_VERSION {
+            return Err(format!("Unsupported trace version {}", trace.version));
+        }
+
+        match trace.steps.split_first() {
+            None => return Err("Trace has no steps".to_owned()),
+            Some((first, _)) if first.chosen_decision.is_some() => {
+                return Err("The first step of a trace cannot have a chosen decision".to_owned())
+            }
+            Some((_, rest)) => {
+                if let Some(index) = rest.iter().position(|step| step.chosen_decision.is_none()) {
+                    return Err(format!(
+                        "Step {} of the trace has no chosen decision",
+                        index + 1
+                    ));
+                }
+            }
+        }
+
+        Ok(trace)
+    }
+}
+
+/// Returns whether two sets of decision points are the same, regardless of their order.
+pub fn same_decision_points(expected: &[Decision], actual: &[Decision]) -> bool {
+    expected.len() == actual.len()
+        && expected.iter().all(|decision| actual.contains(decision))
+        && actual.iter().all(|decision| expected.contains(decision))
+}
+
+#[cfg(test)]
+#[path = "../tests/api/simulation_trace.rs"]
+mod tests;
@@ -9,8 +9,13 @@ fn main() {
             "Component",
             "#[derive(serde::Serialize, serde::Deserialize)]",
         )
+        .type_attribute(
+            "Settings",
+            "#[derive(serde::Serialize, serde::Deserialize)] #[serde(default)]",
+        )
         .type_attribute("ProjectInfo", "#[derive(sea_orm::FromQueryResult)]")
         .type_attribute("AccessInfo", "#[derive(sea_orm::FromQueryResult)]")
+        .type_attribute("TeamAccessInfo", "#[derive(sea_orm::FromQueryResult)]")
         .type_attribute("Error", "#[derive(serde::Serialize, serde::Deserialize)]")
         .type_attribute(
             "ComponentsNotInCache",
@@ -4,9 +4,24 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct ContextCollection {
     pub(crate) access_context: Arc<dyn AccessContextTrait>,
+    pub(crate) backend_audit_context: Arc<dyn BackendAuditContextTrait>,
+    pub(crate) comment_context: Arc<dyn CommentContextTrait>,
+    pub(crate) component_context: Arc<dyn ComponentContextTrait>,
+    pub(crate) counterexample_context: Arc<dyn CounterexampleContextTrait>,
     pub(crate) in_use_context: Arc<dyn InUseContextTrait>,
+    pub(crate) invitation_context: Arc<dyn InvitationContextTrait>,
     pub(crate) project_context: Arc<dyn ProjectContextTrait>,
+    pub(crate) project_version_context: Arc<dyn ProjectVersionContextTrait>,
+    pub(crate) query_cache_context: Arc<dyn QueryCacheContextTrait>,
     pub(crate) query_context: Arc<dyn QueryContextTrait>,
+    pub(crate) query_job_context: Arc<dyn QueryJobContextTrait>,
+    pub(crate) query_result_context: Arc<dyn QueryResultContextTrait>,
+    pub(crate) role_context: Arc<dyn RoleContextTrait>,
     pub(crate) session_context: Arc<dyn SessionContextTrait>,
+    pub(crate) share_link_context: Arc<dyn ShareLinkContextTrait>,
+    pub(crate) simulation_session_context: Arc<dyn SimulationSessionContextTrait>,
+    pub(crate) simulation_step_context: Arc<dyn SimulationStepContextTrait>,
+    pub(crate) team_access_context: Arc<dyn TeamAccessContextTrait>,
+    pub(crate) team_context: Arc<dyn TeamContextTrait>,
     pub(crate) user_context: Arc<dyn UserContextTrait>,
 }
@@ -1,11 +1,14 @@
 use crate::api::server::protobuf::AccessInfo;
 use crate::contexts::context_traits::{
-    AccessContextTrait, DatabaseContextTrait, EntityContextTrait,
+    AccessContextTrait, DatabaseContextTrait, EffectiveAccess, EntityContextTrait,
 };
-use crate::entities::access;
+use crate::entities::{access, team_access, team_member};
 use sea_orm::prelude::async_trait::async_trait;
+use sea_orm::sea_query::Query;
 use sea_orm::ActiveValue::{Set, Unchanged};
-use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
 use std::sync::Arc;
 
 pub struct AccessContext {
@@ -18,15 +21,42 @@ impl AccessContextTrait for AccessContext {
         &self,
         uid: i32,
         project_id: i32,
-    ) -> Result<Option<access::Model>, DbErr> {
-        access::Entity::find()
+    ) -> Result<Option<EffectiveAccess>, DbErr> {
+        let direct = access::Entity::find()
             .filter(
                 Condition::all()
                     .add(access::Column::UserId.eq(uid))
                     .add(access::Column::ProjectId.eq(project_id)),
             )
             .one(&self.db_context.get_connection())
-            .await
+            .await?;
+
+        let team_roles = team_access::Entity::find()
+            .filter(
+                Condition::all()
+                    .add(team_access::Column::ProjectId.eq(project_id))
+                    .add(
+                        team_access::Column::TeamId.in_subquery(
+                            Query::select()
+                                .column(team_member::Column::TeamId)
+                                .from(team_member::Entity)
+                                .and_where(team_member::Column::UserId.eq(uid))
+                                .to_owned(),
+                        ),
+                    ),
+            )
+            .order_by_asc(team_access::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await?
+            .into_iter()
+            .map(|team_access| team_access.role)
+            .collect::<Vec<String>>();
+
+        if direct.is_none() && team_roles.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(EffectiveAccess { direct, team_roles }))
     }
 
     async fn get_access_by_project_id(&self, project_id: i32) -> Result<Vec<AccessInfo>, DbErr> {
@@ -36,6 +66,13 @@ impl AccessContextTrait for AccessContext {
             .all(&self.db_context.get_connection())
             .await
     }
+
+    async fn get_access_by_role(&self, role: String) -> Result<Vec<access::Model>, DbErr> {
+        access::Entity::find()
+            .filter(access::Column::Role.eq(role))
+            .all(&self.db_context.get_connection())
+            .await
+    }
 }
 
 impl AccessContext {
@@ -0,0 +1,111 @@
+use crate::contexts::context_traits::{
+    BackendAuditContextTrait, DatabaseContextTrait, EntityContextTrait,
+};
+use crate::entities::backend_audit;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, Set, Unchanged};
+use std::sync::Arc;
+
+pub struct BackendAuditContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+impl BackendAuditContextTrait for BackendAuditContext {}
+
+impl BackendAuditContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> BackendAuditContext {
+        BackendAuditContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<backend_audit::Model> for BackendAuditContext {
+    /// Used for creating a backend_audit::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let backend_audit = backend_audit::Model {
+    ///     id: Default::default(),
+    ///     user_id: Some(1),
+    ///     method: "SendQuery".to_owned(),
+    ///     code: 0,
+    ///     created_at: Default::default(),
+    /// };
+    /// let backend_audit_context: BackendAuditContext = BackendAuditContext::new(...);
+    /// backend_audit_context.create(backend_audit);
+    /// ```
+    async fn create(&self, entity: backend_audit::Model) -> Result<backend_audit::Model, DbErr> {
+        let backend_audit = backend_audit::ActiveModel {
+            id: Default::default(),
+            user_id: Set(entity.user_id),
+            method: Set(entity.method),
+            code: Set(entity.code),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        backend_audit
+            .insert(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns a single backend audit entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let backend_audit_context: BackendAuditContext = BackendAuditContext::new(...);
+    /// let backend_audit = backend_audit_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<backend_audit::Model>, DbErr> {
+        backend_audit::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all backend audit entities
+    /// # Example
+    /// ```
+    /// let backend_audit_context: BackendAuditContext = BackendAuditContext::new(...);
+    /// let backend_audits = backend_audit_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<backend_audit::Model>, DbErr> {
+        backend_audit::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single backend audit entity
+    /// # Note
+    /// Only the status code can change, the rest records who called what and when.
+    async fn update(&self, entity: backend_audit::Model) -> Result<backend_audit::Model, DbErr> {
+        backend_audit::ActiveModel {
+            id: Unchanged(entity.id),
+            user_id: Unchanged(entity.user_id),
+            method: Unchanged(entity.method),
+            code: Set(entity.code),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single backend audit entity
+    /// # Example
+    /// ```
+    /// let backend_audit_context: BackendAuditContext = BackendAuditContext::new(...);
+    /// let backend_audit = backend_audit_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<backend_audit::Model, DbErr> {
+        let backend_audit = self.get_by_id(entity_id).await?;
+        match backend_audit {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(backend_audit) => {
+                backend_audit::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(backend_audit)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/backend_audit_context.rs"]
+mod backend_audit_context_tests;
@@ -0,0 +1,141 @@
+use crate::contexts::context_traits::{
+    CommentContextTrait, DatabaseContextTrait, EntityContextTrait,
+};
+use crate::entities::comment;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct CommentContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl CommentContextTrait for CommentContext {
+    async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<comment::Model>, DbErr> {
+        comment::Entity::find()
+            .filter(comment::Column::ProjectId.eq(project_id))
+            .order_by_asc(comment::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl CommentContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> CommentContext {
+        CommentContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<comment::Model> for CommentContext {
+    /// Used for creating a comment::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let comment = comment::Model {
+    ///     id: Default::default(),
+    ///     project_id: 1,
+    ///     parent_id: None,
+    ///     user_id: Some(1),
+    ///     component_id: Some(1),
+    ///     location_id: Some("L0".to_owned()),
+    ///     edge_id: None,
+    ///     query_id: None,
+    ///     text: "Is this location reachable?".to_owned(),
+    ///     resolved: false,
+    ///     created_at: Default::default(),
+    ///     edited_at: None,
+    /// };
+    /// let comment_context: CommentContext = CommentContext::new(...);
+    /// comment_context.create(comment);
+    /// ```
+    async fn create(&self, entity: comment::Model) -> Result<comment::Model, DbErr> {
+        let comment = comment::ActiveModel {
+            id: Default::default(),
+            project_id: Set(entity.project_id),
+            parent_id: Set(entity.parent_id),
+            user_id: Set(entity.user_id),
+            component_id: Set(entity.component_id),
+            location_id: Set(entity.location_id),
+            edge_id: Set(entity.edge_id),
+            query_id: Set(entity.query_id),
+            text: Set(entity.text),
+            resolved: Set(entity.resolved),
+            created_at: Set(Utc::now().naive_utc()),
+            edited_at: Set(entity.edited_at),
+        };
+        comment.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single comment entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let comment_context: CommentContext = CommentContext::new(...);
+    /// let comment = comment_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<comment::Model>, DbErr> {
+        comment::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all comment entities
+    /// # Example
+    /// ```
+    /// let comment_context: CommentContext = CommentContext::new(...);
+    /// let comments = comment_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<comment::Model>, DbErr> {
+        comment::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates the text, resolution and edit time of a single comment entity
+    /// # Note
+    /// The project, thread, author, anchor and creation time of a comment cannot change.
+    async fn update(&self, entity: comment::Model) -> Result<comment::Model, DbErr> {
+        comment::ActiveModel {
+            id: Unchanged(entity.id),
+            project_id: Unchanged(entity.project_id),
+            parent_id: Unchanged(entity.parent_id),
+            user_id: Unchanged(entity.user_id),
+            component_id: Unchanged(entity.component_id),
+            location_id: Unchanged(entity.location_id),
+            edge_id: Unchanged(entity.edge_id),
+            query_id: Unchanged(entity.query_id),
+            text: Set(entity.text),
+            resolved: Set(entity.resolved),
+            created_at: Unchanged(entity.created_at),
+            edited_at: Set(entity.edited_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single comment entity, along with the replies to it
+    /// # Example
+    /// ```
+    /// let comment_context: CommentContext = CommentContext::new(...);
+    /// let comment = comment_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<comment::Model, DbErr> {
+        let comment = self.get_by_id(entity_id).await?;
+        match comment {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(comment) => {
+                comment::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(comment)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/comment_context.rs"]
+mod comment_context_tests;
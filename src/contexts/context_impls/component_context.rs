@@ -0,0 +1,135 @@
+use crate::api::components::components_info;
+use crate::api::server::protobuf::ComponentsInfo;
+use crate::contexts::context_traits::{
+    ComponentContextTrait, DatabaseContextTrait, EntityContextTrait,
+};
+use crate::entities::component;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct ComponentContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl ComponentContextTrait for ComponentContext {
+    async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<component::Model>, DbErr> {
+        component::Entity::find()
+            .filter(component::Column::ProjectId.eq(project_id))
+            .order_by_asc(component::Column::Name)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn get_components_info_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<ComponentsInfo, DbErr> {
+        let components = self.get_all_by_project_id(project_id).await?;
+        components_info(components).map_err(|err| DbErr::Json(err.to_string()))
+    }
+}
+
+impl ComponentContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> ComponentContext {
+        ComponentContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<component::Model> for ComponentContext {
+    /// Used for creating a component::Model entity, the update time is always set to now.
+    /// # Example
+    /// ```
+    /// let component = component::Model {
+    ///     id: Default::default(),
+    ///     project_id: 1,
+    ///     name: "Machine".to_owned(),
+    ///     component: "{\"rep\":{\"Json\":\"{\\\"name\\\":\\\"Machine\\\"}\"}}".to_owned().parse().unwrap(),
+    ///     updated_at: Default::default(),
+    /// };
+    /// let component_context: ComponentContext = ComponentContext::new(...);
+    /// component_context.create(component);
+    /// ```
+    async fn create(&self, entity: component::Model) -> Result<component::Model, DbErr> {
+        let component = component::ActiveModel {
+            id: Default::default(),
+            project_id: Set(entity.project_id),
+            name: Set(entity.name),
+            component: Set(entity.component),
+            updated_at: Set(Utc::now().naive_utc()),
+        };
+        component.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single component entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let component_context: ComponentContext = ComponentContext::new(...);
+    /// let component = component_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<component::Model>, DbErr> {
+        component::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all component entities
+    /// # Example
+    /// ```
+    /// let component_context: ComponentContext = ComponentContext::new(...);
+    /// let components = component_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<component::Model>, DbErr> {
+        component::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single component entity, the update time is always set to now.
+    /// # Note
+    /// The project of a component cannot change.
+    async fn update(&self, entity: component::Model) -> Result<component::Model, DbErr> {
+        let existing_component = self
+            .get_by_id(entity.id)
+            .await?
+            .ok_or(DbErr::RecordNotUpdated)?;
+
+        component::ActiveModel {
+            id: Unchanged(entity.id),
+            project_id: Unchanged(existing_component.project_id),
+            name: Set(entity.name),
+            component: Set(entity.component),
+            updated_at: Set(Utc::now().naive_utc()),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single component entity.
+    /// # Example
+    /// ```
+    /// let component_context: ComponentContext = ComponentContext::new(...);
+    /// let component = component_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<component::Model, DbErr> {
+        let component = self.get_by_id(entity_id).await?;
+        match component {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(component) => {
+                component::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(component)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/component_context.rs"]
+mod component_context_tests;
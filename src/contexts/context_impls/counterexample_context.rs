@@ -0,0 +1,150 @@
+use crate::contexts::context_traits::{
+    CounterexampleContextTrait, DatabaseContextTrait, EntityContextTrait,
+};
+use crate::entities::counterexample;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct CounterexampleContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl CounterexampleContextTrait for CounterexampleContext {
+    async fn get_latest_by_query_id(
+        &self,
+        query_id: i32,
+    ) -> Result<Option<counterexample::Model>, DbErr> {
+        counterexample::Entity::find()
+            .filter(counterexample::Column::QueryId.eq(query_id))
+            .order_by_desc(counterexample::Column::QueryResultId)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn get_by_query_result_id(
+        &self,
+        query_result_id: i32,
+    ) -> Result<Option<counterexample::Model>, DbErr> {
+        counterexample::Entity::find()
+            .filter(counterexample::Column::QueryResultId.eq(query_result_id))
+            .one(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl CounterexampleContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> CounterexampleContext {
+        CounterexampleContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<counterexample::Model> for CounterexampleContext {
+    /// Used for creating a counterexample::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let counterexample = counterexample::Model {
+    ///     id: Default::default(),
+    ///     query_id: 1,
+    ///     query_result_id: 1,
+    ///     kind: "REFINEMENT".to_owned(),
+    ///     system: Some("A <= B".to_owned()),
+    ///     reason: None,
+    ///     actions: "[]".to_owned().parse().unwrap(),
+    ///     state: None,
+    ///     path: "[]".to_owned().parse().unwrap(),
+    ///     created_at: Default::default(),
+    /// };
+    /// let counterexample_context: CounterexampleContext = CounterexampleContext::new(...);
+    /// counterexample_context.create(counterexample);
+    /// ```
+    async fn create(&self, entity: counterexample::Model) -> Result<counterexample::Model, DbErr> {
+        let counterexample = counterexample::ActiveModel {
+            id: Default::default(),
+            query_id: Set(entity.query_id),
+            query_result_id: Set(entity.query_result_id),
+            kind: Set(entity.kind),
+            system: Set(entity.system),
+            reason: Set(entity.reason),
+            actions: Set(entity.actions),
+            state: Set(entity.state),
+            path: Set(entity.path),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        counterexample
+            .insert(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns a single counterexample entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let counterexample_context: CounterexampleContext = CounterexampleContext::new(...);
+    /// let counterexample = counterexample_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<counterexample::Model>, DbErr> {
+        counterexample::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all counterexample entities
+    /// # Example
+    /// ```
+    /// let counterexample_context: CounterexampleContext = CounterexampleContext::new(...);
+    /// let counterexamples = counterexample_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<counterexample::Model>, DbErr> {
+        counterexample::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single counterexample entity
+    /// # Note
+    /// The query, query result and creation time of a counterexample cannot change.
+    async fn update(&self, entity: counterexample::Model) -> Result<counterexample::Model, DbErr> {
+        counterexample::ActiveModel {
+            id: Unchanged(entity.id),
+            query_id: Unchanged(entity.query_id),
+            query_result_id: Unchanged(entity.query_result_id),
+            kind: Set(entity.kind),
+            system: Set(entity.system),
+            reason: Set(entity.reason),
+            actions: Set(entity.actions),
+            state: Set(entity.state),
+            path: Set(entity.path),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single counterexample entity
+    /// # Example
+    /// ```
+    /// let counterexample_context: CounterexampleContext = CounterexampleContext::new(...);
+    /// let counterexample = counterexample_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<counterexample::Model, DbErr> {
+        let counterexample = self.get_by_id(entity_id).await?;
+        match counterexample {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(counterexample) => {
+                counterexample::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(counterexample)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/counterexample_context.rs"]
+mod counterexample_context_tests;
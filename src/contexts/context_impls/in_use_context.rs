@@ -4,14 +4,96 @@ use crate::contexts::context_traits::{
 use crate::entities::in_use;
 use async_trait::async_trait;
 use chrono::Utc;
-use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, Set, Unchanged};
+use sea_orm::prelude::DateTime;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, Set, Unchanged,
+};
 use std::sync::Arc;
 
 pub struct InUseContext {
     db_context: Arc<dyn DatabaseContextTrait>,
 }
 
-impl InUseContextTrait for InUseContext {}
+#[async_trait]
+impl InUseContextTrait for InUseContext {
+    async fn acquire(
+        &self,
+        project_id: i32,
+        session_id: i32,
+        expires_at: DateTime,
+    ) -> Result<bool, DbErr> {
+        let now = Utc::now().naive_utc();
+
+        let result = in_use::Entity::update_many()
+            .col_expr(in_use::Column::SessionId, Expr::value(session_id))
+            .col_expr(in_use::Column::LatestActivity, Expr::value(now))
+            .col_expr(in_use::Column::ExpiresAt, Expr::value(expires_at))
+            .filter(in_use::Column::ProjectId.eq(project_id))
+            .filter(
+                Condition::any()
+                    .add(in_use::Column::ExpiresAt.lte(now))
+                    .add(in_use::Column::SessionId.eq(session_id)),
+            )
+            .exec(&self.db_context.get_connection())
+            .await?;
+
+        if result.rows_affected == 1 {
+            return Ok(true);
+        }
+
+        // Projects whose lock row is gone, e.g. because the session holding it was deleted, are free
+        if self.get_by_id(project_id).await?.is_none() {
+            self.create(in_use::Model {
+                project_id,
+                session_id,
+                latest_activity: now,
+                expires_at,
+            })
+            .await?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn renew(
+        &self,
+        project_id: i32,
+        session_id: i32,
+        expires_at: DateTime,
+    ) -> Result<bool, DbErr> {
+        let now = Utc::now().naive_utc();
+
+        let result = in_use::Entity::update_many()
+            .col_expr(in_use::Column::LatestActivity, Expr::value(now))
+            .col_expr(in_use::Column::ExpiresAt, Expr::value(expires_at))
+            .filter(in_use::Column::ProjectId.eq(project_id))
+            .filter(in_use::Column::SessionId.eq(session_id))
+            .filter(in_use::Column::ExpiresAt.gt(now))
+            .exec(&self.db_context.get_connection())
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn release(&self, project_id: i32, session_id: Option<i32>) -> Result<bool, DbErr> {
+        let now = Utc::now().naive_utc();
+
+        let mut update = in_use::Entity::update_many()
+            .col_expr(in_use::Column::ExpiresAt, Expr::value(now))
+            .filter(in_use::Column::ProjectId.eq(project_id));
+
+        if let Some(session_id) = session_id {
+            update = update
+                .filter(in_use::Column::SessionId.eq(session_id))
+                .filter(in_use::Column::ExpiresAt.gt(now));
+        }
+
+        let result = update.exec(&self.db_context.get_connection()).await?;
+        Ok(result.rows_affected == 1)
+    }
+}
 
 impl InUseContext {
     pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> InUseContext {
@@ -26,6 +108,7 @@ impl EntityContextTrait<in_use::Model> for InUseContext {
             project_id: Set(entity.project_id),
             session_id: Set(entity.session_id),
             latest_activity: Set(Utc::now().naive_local()),
+            expires_at: Set(entity.expires_at),
         };
         let in_use: in_use::Model = in_use.insert(&self.db_context.get_connection()).await?;
         Ok(in_use)
@@ -48,6 +131,7 @@ impl EntityContextTrait<in_use::Model> for InUseContext {
             project_id: Unchanged(entity.project_id),
             session_id: Set(entity.session_id),
             latest_activity: Set(entity.latest_activity),
+            expires_at: Set(entity.expires_at),
         }
         .update(&self.db_context.get_connection())
         .await
@@ -0,0 +1,166 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, InvitationContextTrait,
+};
+use crate::entities::invitation;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder, Set,
+    Unchanged,
+};
+use std::sync::Arc;
+
+pub struct InvitationContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl InvitationContextTrait for InvitationContext {
+    async fn get_all_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<invitation::Model>, DbErr> {
+        invitation::Entity::find()
+            .filter(invitation::Column::ProjectId.eq(project_id))
+            .order_by_asc(invitation::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn redeem(&self, id: i32) -> Result<bool, DbErr> {
+        // The checks are part of the update, so concurrent uses cannot go past the cap
+        let result = invitation::Entity::update_many()
+            .col_expr(
+                invitation::Column::Uses,
+                Expr::col(invitation::Column::Uses).add(1),
+            )
+            .filter(redeemable().add(invitation::Column::Id.eq(id)))
+            .exec(&self.db_context.get_connection())
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn get_redeemable_by_role(&self, role: String) -> Result<Vec<invitation::Model>, DbErr> {
+        invitation::Entity::find()
+            .filter(redeemable().add(invitation::Column::Role.eq(role)))
+            .order_by_asc(invitation::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl InvitationContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> InvitationContext {
+        InvitationContext { db_context }
+    }
+}
+
+/// Matches the invitations that have not been revoked, have not expired and have uses left.
+fn redeemable() -> Condition {
+    Condition::all()
+        .add(invitation::Column::Revoked.eq(false))
+        .add(invitation::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .add(Expr::col(invitation::Column::Uses).lt(Expr::col(invitation::Column::MaxUses)))
+}
+
+#[async_trait]
+impl EntityContextTrait<invitation::Model> for InvitationContext {
+    /// Used for creating an invitation::Model entity, the creation time is always set to now and no uses are counted
+    /// # Example
+    /// ```
+    /// let invitation = invitation::Model {
+    ///     id: Default::default(),
+    ///     project_id: 1,
+    ///     role: "Reader".to_owned(),
+    ///     user_id: Some(1),
+    ///     max_uses: 5,
+    ///     uses: Default::default(),
+    ///     revoked: false,
+    ///     expires_at: Utc::now().naive_utc() + Duration::days(7),
+    ///     created_at: Default::default(),
+    /// };
+    /// let invitation_context: InvitationContext = InvitationContext::new(...);
+    /// invitation_context.create(invitation);
+    /// ```
+    async fn create(&self, entity: invitation::Model) -> Result<invitation::Model, DbErr> {
+        let invitation = invitation::ActiveModel {
+            id: Default::default(),
+            project_id: Set(entity.project_id),
+            role: Set(entity.role),
+            user_id: Set(entity.user_id),
+            max_uses: Set(entity.max_uses),
+            uses: Set(0),
+            revoked: Set(entity.revoked),
+            expires_at: Set(entity.expires_at),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        invitation.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single invitation entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let invitation_context: InvitationContext = InvitationContext::new(...);
+    /// let invitation = invitation_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<invitation::Model>, DbErr> {
+        invitation::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all invitation entities
+    /// # Example
+    /// ```
+    /// let invitation_context: InvitationContext = InvitationContext::new(...);
+    /// let invitations = invitation_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<invitation::Model>, DbErr> {
+        invitation::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates whether a single invitation entity is revoked
+    /// # Note
+    /// Everything else about an invitation is fixed when it is created, and uses are only counted through [`InvitationContextTrait::redeem`].
+    async fn update(&self, entity: invitation::Model) -> Result<invitation::Model, DbErr> {
+        invitation::ActiveModel {
+            id: Unchanged(entity.id),
+            project_id: Unchanged(entity.project_id),
+            role: Unchanged(entity.role),
+            user_id: Unchanged(entity.user_id),
+            max_uses: Unchanged(entity.max_uses),
+            uses: Unchanged(entity.uses),
+            revoked: Set(entity.revoked),
+            expires_at: Unchanged(entity.expires_at),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single invitation entity
+    /// # Example
+    /// ```
+    /// let invitation_context: InvitationContext = InvitationContext::new(...);
+    /// let invitation = invitation_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<invitation::Model, DbErr> {
+        let invitation = self.get_by_id(entity_id).await?;
+        match invitation {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(invitation) => {
+                invitation::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(invitation)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/invitation_context.rs"]
+mod invitation_context_tests;
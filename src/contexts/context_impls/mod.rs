@@ -1,17 +1,47 @@
 pub mod access_context;
+pub mod backend_audit_context;
+pub mod comment_context;
+pub mod component_context;
+pub mod counterexample_context;
 pub mod in_use_context;
+pub mod invitation_context;
 pub mod postgres_database_context;
 pub mod project_context;
+pub mod project_version_context;
+pub mod query_cache_context;
 pub mod query_context;
+pub mod query_job_context;
+pub mod query_result_context;
+pub mod role_context;
 pub mod session_context;
+pub mod share_link_context;
+pub mod simulation_session_context;
+pub mod simulation_step_context;
 pub mod sqlite_database_context;
+pub mod team_access_context;
+pub mod team_context;
 pub mod user_context;
 
 pub use access_context::AccessContext;
+pub use backend_audit_context::BackendAuditContext;
+pub use comment_context::CommentContext;
+pub use component_context::ComponentContext;
+pub use counterexample_context::CounterexampleContext;
 pub use in_use_context::InUseContext;
+pub use invitation_context::InvitationContext;
 pub use postgres_database_context::PostgresDatabaseContext;
 pub use project_context::ProjectContext;
+pub use project_version_context::ProjectVersionContext;
+pub use query_cache_context::QueryCacheContext;
 pub use query_context::QueryContext;
+pub use query_job_context::QueryJobContext;
+pub use query_result_context::QueryResultContext;
+pub use role_context::RoleContext;
 pub use session_context::SessionContext;
+pub use share_link_context::ShareLinkContext;
+pub use simulation_session_context::SimulationSessionContext;
+pub use simulation_step_context::SimulationStepContext;
 pub use sqlite_database_context::SQLiteDatabaseContext;
+pub use team_access_context::TeamAccessContext;
+pub use team_context::TeamContext;
 pub use user_context::UserContext;
@@ -1,13 +1,14 @@
 use crate::contexts::context_traits::{
     DatabaseContextTrait, EntityContextTrait, ProjectContextTrait,
 };
-use crate::entities::{access, project, query};
+use crate::entities::{access, project, team_access, team_member};
 
 use crate::api::server::protobuf::ProjectInfo;
 use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Query};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, IntoActiveModel, JoinType, ModelTrait,
-    QueryFilter, QuerySelect, RelationTrait, Set, Unchanged,
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, JoinType, NotSet, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Set, Unchanged,
 };
 use std::sync::Arc;
 
@@ -19,7 +20,7 @@ pub struct ProjectContext {
 impl ProjectContextTrait for ProjectContext {
     async fn get_project_info_by_uid(&self, uid: i32) -> Result<Vec<ProjectInfo>, DbErr> {
         //join project, access and role tables
-        access::Entity::find()
+        let mut project_infos = access::Entity::find()
             .select_only()
             .column_as(project::Column::Id, "project_id")
             .column_as(project::Column::Name, "project_name")
@@ -32,7 +33,89 @@ impl ProjectContextTrait for ProjectContext {
             .filter(access::Column::UserId.eq(uid))
             .into_model::<ProjectInfo>()
             .all(&self.db_context.get_connection())
-            .await
+            .await?;
+
+        //join project and team_access tables for the teams the user is a member of
+        let team_project_infos = team_access::Entity::find()
+            .select_only()
+            .column_as(project::Column::Id, "project_id")
+            .column_as(project::Column::Name, "project_name")
+            .column_as(project::Column::OwnerId, "project_owner_id")
+            .column_as(team_access::Column::Role, "user_role_on_project")
+            .join(JoinType::InnerJoin, team_access::Relation::Project.def())
+            .order_by_asc(team_access::Column::Id)
+            .filter(
+                team_access::Column::TeamId.in_subquery(
+                    Query::select()
+                        .column(team_member::Column::TeamId)
+                        .from(team_member::Entity)
+                        .and_where(team_member::Column::UserId.eq(uid))
+                        .to_owned(),
+                ),
+            )
+            .into_model::<ProjectInfo>()
+            .all(&self.db_context.get_connection())
+            .await?;
+
+        // A project shared with the user in several ways is listed once,
+        // with the role granted to the user themself before those granted to their teams
+        for team_project_info in team_project_infos {
+            if !project_infos
+                .iter()
+                .any(|info| info.project_id == team_project_info.project_id)
+            {
+                project_infos.push(team_project_info);
+            }
+        }
+
+        Ok(project_infos)
+    }
+
+    async fn advance_revision(
+        &self,
+        project_id: i32,
+        expected_revision: Option<i32>,
+    ) -> Result<bool, DbErr> {
+        let mut update = project::Entity::update_many()
+            .col_expr(
+                project::Column::Revision,
+                Expr::col(project::Column::Revision).add(1),
+            )
+            .filter(project::Column::Id.eq(project_id));
+
+        if let Some(revision) = expected_revision {
+            update = update.filter(project::Column::Revision.eq(revision));
+        }
+
+        let result = update.exec(&self.db_context.get_connection()).await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn update_at_revision(
+        &self,
+        entity: project::Model,
+        expected_revision: i32,
+    ) -> Result<bool, DbErr> {
+        let result = project::Entity::update_many()
+            .col_expr(project::Column::Name, Expr::value(entity.name))
+            .col_expr(project::Column::Settings, Expr::value(entity.settings))
+            .col_expr(
+                project::Column::TimeoutSecs,
+                Expr::value(entity.timeout_secs),
+            )
+            .col_expr(
+                project::Column::Collaborative,
+                Expr::value(entity.collaborative),
+            )
+            .col_expr(
+                project::Column::Revision,
+                Expr::col(project::Column::Revision).add(1),
+            )
+            .filter(project::Column::Id.eq(entity.id))
+            .filter(project::Column::Revision.eq(expected_revision))
+            .exec(&self.db_context.get_connection())
+            .await?;
+        Ok(result.rows_affected == 1)
     }
 }
 
@@ -50,8 +133,11 @@ impl EntityContextTrait<project::Model> for ProjectContext {
     /// let project = project::Model {
     ///     id: Default::default(),
     ///     name: "project::Model name".to_owned(),
-    ///     components_info: "{}".to_owned().parse().unwrap(),
-    ///     owner_id: 1
+    ///     owner_id: 1,
+    ///     settings: None,
+    ///     timeout_secs: None,
+    ///     revision: Default::default(),
+    ///     collaborative: false,
     /// };
     /// let project_context: ProjectContext = ProjectContext::new(...);
     /// project_context.create(project);
@@ -60,8 +146,11 @@ impl EntityContextTrait<project::Model> for ProjectContext {
         let project = project::ActiveModel {
             id: Default::default(),
             name: Set(entity.name),
-            components_info: Set(entity.components_info),
             owner_id: Set(entity.owner_id),
+            settings: Set(entity.settings),
+            timeout_secs: Set(entity.timeout_secs),
+            revision: Default::default(),
+            collaborative: Set(entity.collaborative),
         };
         let project: project::Model = project.insert(&self.db_context.get_connection()).await?;
         Ok(project)
@@ -91,7 +180,7 @@ impl EntityContextTrait<project::Model> for ProjectContext {
             .await
     }
 
-    /// Updates a single project entity
+    /// Updates a single project entity, the revision is left as is
     /// # Example
     /// ```
     /// let update_project = project::Model {
@@ -103,30 +192,17 @@ impl EntityContextTrait<project::Model> for ProjectContext {
     /// let project = project_context.update(update_project).unwrap();
     /// ```
     async fn update(&self, entity: project::Model) -> Result<project::Model, DbErr> {
-        let existing_project = self.get_by_id(entity.id).await?;
-
-        return match existing_project {
-            None => Err(DbErr::RecordNotUpdated),
-            Some(existing_project) => {
-                let queries: Vec<query::Model> = existing_project
-                    .find_related(query::Entity)
-                    .all(&self.db_context.get_connection())
-                    .await?;
-                for q in queries.iter() {
-                    let mut aq = q.clone().into_active_model();
-                    aq.outdated = Set(true);
-                    aq.update(&self.db_context.get_connection()).await?;
-                }
-                project::ActiveModel {
-                    id: Unchanged(entity.id),
-                    name: Set(entity.name),
-                    components_info: Set(entity.components_info),
-                    owner_id: Unchanged(entity.id),
-                }
-                .update(&self.db_context.get_connection())
-                .await
-            }
-        };
+        project::ActiveModel {
+            id: Unchanged(entity.id),
+            name: Set(entity.name),
+            owner_id: Unchanged(entity.id),
+            settings: Set(entity.settings),
+            timeout_secs: Set(entity.timeout_secs),
+            revision: NotSet,
+            collaborative: Set(entity.collaborative),
+        }
+        .update(&self.db_context.get_connection())
+        .await
     }
 
     /// Returns and deletes a single project entity
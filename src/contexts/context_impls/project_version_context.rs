@@ -0,0 +1,132 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, ProjectVersionContextTrait,
+};
+use crate::entities::project_version;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct ProjectVersionContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl ProjectVersionContextTrait for ProjectVersionContext {
+    async fn get_all_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<project_version::Model>, DbErr> {
+        project_version::Entity::find()
+            .filter(project_version::Column::ProjectId.eq(project_id))
+            .order_by_desc(project_version::Column::CreatedAt)
+            .order_by_desc(project_version::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl ProjectVersionContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> ProjectVersionContext {
+        ProjectVersionContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<project_version::Model> for ProjectVersionContext {
+    /// Used for creating a project_version::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let project_version = project_version::Model {
+    ///     id: Default::default(),
+    ///     project_id: 1,
+    ///     components_info: "{}".to_owned().parse().unwrap(),
+    ///     user_id: Some(1),
+    ///     created_at: Default::default(),
+    /// };
+    /// let project_version_context: ProjectVersionContext = ProjectVersionContext::new(...);
+    /// project_version_context.create(project_version);
+    /// ```
+    async fn create(
+        &self,
+        entity: project_version::Model,
+    ) -> Result<project_version::Model, DbErr> {
+        let project_version = project_version::ActiveModel {
+            id: Default::default(),
+            project_id: Set(entity.project_id),
+            components_info: Set(entity.components_info),
+            user_id: Set(entity.user_id),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        project_version
+            .insert(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns a single project version entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let project_version_context: ProjectVersionContext = ProjectVersionContext::new(...);
+    /// let project_version = project_version_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<project_version::Model>, DbErr> {
+        project_version::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all project version entities
+    /// # Example
+    /// ```
+    /// let project_version_context: ProjectVersionContext = ProjectVersionContext::new(...);
+    /// let project_versions = project_version_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<project_version::Model>, DbErr> {
+        project_version::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single project version entity
+    /// # Note
+    /// Only the snapshot itself can change, the project, author and creation time are kept as is.
+    async fn update(
+        &self,
+        entity: project_version::Model,
+    ) -> Result<project_version::Model, DbErr> {
+        project_version::ActiveModel {
+            id: Unchanged(entity.id),
+            project_id: Unchanged(entity.project_id),
+            components_info: Set(entity.components_info),
+            user_id: Unchanged(entity.user_id),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single project version entity
+    /// # Example
+    /// ```
+    /// let project_version_context: ProjectVersionContext = ProjectVersionContext::new(...);
+    /// let project_version = project_version_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<project_version::Model, DbErr> {
+        let project_version = self.get_by_id(entity_id).await?;
+        match project_version {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(project_version) => {
+                project_version::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(project_version)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/project_version_context.rs"]
+mod project_version_context_tests;
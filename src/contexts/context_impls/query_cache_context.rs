@@ -0,0 +1,129 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, QueryCacheContextTrait,
+};
+use crate::entities::query_cache;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, Set, Unchanged};
+use std::sync::Arc;
+
+/// How long a cached result is used, after which it is evicted to keep the cache from growing without bound.
+const QUERY_CACHE_MAX_AGE_DAYS: i64 = 7;
+
+/// Returns the creation time before which cached results have expired.
+fn expiry_cutoff() -> NaiveDateTime {
+    Utc::now().naive_utc() - Duration::days(QUERY_CACHE_MAX_AGE_DAYS)
+}
+
+pub struct QueryCacheContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl QueryCacheContextTrait for QueryCacheContext {
+    async fn get_by_hash(&self, hash: &str) -> Result<Option<query_cache::Model>, DbErr> {
+        query_cache::Entity::find()
+            .filter(query_cache::Column::Hash.eq(hash))
+            .filter(query_cache::Column::CreatedAt.gt(expiry_cutoff()))
+            .one(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl QueryCacheContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> QueryCacheContext {
+        QueryCacheContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<query_cache::Model> for QueryCacheContext {
+    /// Used for creating a query_cache::Model entity, the creation time is always set to now.
+    /// Expired entities are evicted first, including an expired entity with the same hash.
+    /// # Example
+    /// ```
+    /// let query_cache = query_cache::Model {
+    ///     id: Default::default(),
+    ///     hash: "e3b0c442".to_owned(),
+    ///     result: "{}".to_owned().parse().unwrap(),
+    ///     created_at: Default::default(),
+    /// };
+    /// let query_cache_context: QueryCacheContext = QueryCacheContext::new(...);
+    /// query_cache_context.create(query_cache);
+    /// ```
+    async fn create(&self, entity: query_cache::Model) -> Result<query_cache::Model, DbErr> {
+        query_cache::Entity::delete_many()
+            .filter(query_cache::Column::CreatedAt.lte(expiry_cutoff()))
+            .exec(&self.db_context.get_connection())
+            .await?;
+
+        let query_cache = query_cache::ActiveModel {
+            id: Default::default(),
+            hash: Set(entity.hash),
+            result: Set(entity.result),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        query_cache.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single query cache entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let query_cache_context: QueryCacheContext = QueryCacheContext::new(...);
+    /// let query_cache = query_cache_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<query_cache::Model>, DbErr> {
+        query_cache::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all query cache entities
+    /// # Example
+    /// ```
+    /// let query_cache_context: QueryCacheContext = QueryCacheContext::new(...);
+    /// let query_caches = query_cache_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<query_cache::Model>, DbErr> {
+        query_cache::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single query cache entity
+    /// # Note
+    /// Only the result can change, the hash identifies the query run it is the result of.
+    async fn update(&self, entity: query_cache::Model) -> Result<query_cache::Model, DbErr> {
+        query_cache::ActiveModel {
+            id: Unchanged(entity.id),
+            hash: Unchanged(entity.hash),
+            result: Set(entity.result),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single query cache entity
+    /// # Example
+    /// ```
+    /// let query_cache_context: QueryCacheContext = QueryCacheContext::new(...);
+    /// let query_cache = query_cache_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<query_cache::Model, DbErr> {
+        let query_cache = self.get_by_id(entity_id).await?;
+        match query_cache {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(query_cache) => {
+                query_cache::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(query_cache)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/query_cache_context.rs"]
+mod query_cache_context_tests;
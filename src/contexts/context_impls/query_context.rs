@@ -1,10 +1,14 @@
 use crate::contexts::context_traits::{
     DatabaseContextTrait, EntityContextTrait, QueryContextTrait,
 };
-use crate::entities::query;
+use crate::entities::{project, query};
 use sea_orm::prelude::async_trait::async_trait;
+use sea_orm::prelude::Json;
+use sea_orm::sea_query::Expr;
 use sea_orm::ActiveValue::{Set, Unchanged};
-use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, NotSet, QueryFilter};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, NotSet, QueryFilter, TransactionTrait,
+};
 use std::sync::Arc;
 
 pub struct QueryContext {
@@ -18,6 +22,63 @@ impl QueryContextTrait for QueryContext {
             .all(&self.db_context.get_connection())
             .await
     }
+
+    async fn update_result(&self, id: i32, result: Json, outdated: bool) -> Result<bool, DbErr> {
+        // Only the result columns are written, so concurrent edits of the query are kept
+        let result = query::Entity::update_many()
+            .col_expr(query::Column::Result, Expr::value(result))
+            .col_expr(query::Column::Outdated, Expr::value(outdated))
+            .filter(query::Column::Id.eq(id))
+            .exec(&self.db_context.get_connection())
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn mark_outdated(&self, ids: Vec<i32>) -> Result<(), DbErr> {
+        query::Entity::update_many()
+            .col_expr(query::Column::Outdated, Expr::value(true))
+            .filter(query::Column::Id.is_in(ids))
+            .exec(&self.db_context.get_connection())
+            .await?;
+        Ok(())
+    }
+
+    async fn update_at_revision(
+        &self,
+        entity: query::Model,
+        expected_revision: i32,
+    ) -> Result<bool, DbErr> {
+        // Dropping the transaction without committing it rolls back the revision
+        let transaction = self.db_context.get_connection().begin().await?;
+
+        let advanced = project::Entity::update_many()
+            .col_expr(
+                project::Column::Revision,
+                Expr::col(project::Column::Revision).add(1),
+            )
+            .filter(project::Column::Id.eq(entity.project_id))
+            .filter(project::Column::Revision.eq(expected_revision))
+            .exec(&transaction)
+            .await?;
+        if advanced.rows_affected != 1 {
+            return Ok(false);
+        }
+
+        query::ActiveModel {
+            id: Unchanged(entity.id),
+            string: Set(entity.string),
+            result: Set(entity.result),
+            outdated: Set(entity.outdated),
+            project_id: Unchanged(entity.project_id),
+            settings: Set(entity.settings),
+            timeout_secs: Set(entity.timeout_secs),
+        }
+        .update(&transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(true)
+    }
 }
 
 impl QueryContext {
@@ -48,6 +109,8 @@ impl EntityContextTrait<query::Model> for QueryContext {
             project_id: Set(entity.project_id),
             result: NotSet,
             outdated: NotSet,
+            settings: Set(entity.settings),
+            timeout_secs: Set(entity.timeout_secs),
         };
         let query = query.insert(&self.db_context.get_connection()).await?;
         Ok(query)
@@ -108,6 +171,8 @@ impl EntityContextTrait<query::Model> for QueryContext {
             result: Set(entity.result),
             outdated: Set(entity.outdated),
             project_id: Unchanged(entity.project_id),
+            settings: Set(entity.settings),
+            timeout_secs: Set(entity.timeout_secs),
         }
         .update(&self.db_context.get_connection())
         .await
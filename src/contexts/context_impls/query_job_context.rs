@@ -0,0 +1,169 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, QueryJobContextTrait,
+};
+use crate::entities::query_job;
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct QueryJobContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl QueryJobContextTrait for QueryJobContext {
+    async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<query_job::Model>, DbErr> {
+        query_job::Entity::find()
+            .filter(query_job::Column::ProjectId.eq(project_id))
+            .order_by_desc(query_job::Column::CreatedAt)
+            .order_by_desc(query_job::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn get_all_by_status(
+        &self,
+        status: QueryJobStatus,
+    ) -> Result<Vec<query_job::Model>, DbErr> {
+        query_job::Entity::find()
+            .filter(query_job::Column::Status.eq(status))
+            .order_by_asc(query_job::Column::CreatedAt)
+            .order_by_asc(query_job::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn update_if_status(
+        &self,
+        entity: query_job::Model,
+        expected: QueryJobStatus,
+    ) -> Result<bool, DbErr> {
+        // The status check is part of the update, so a job cannot be moved on by two callers at once
+        let result = query_job::Entity::update_many()
+            .col_expr(query_job::Column::Status, Expr::value(entity.status))
+            .col_expr(query_job::Column::Result, Expr::value(entity.result))
+            .col_expr(query_job::Column::Error, Expr::value(entity.error))
+            .col_expr(
+                query_job::Column::UpdatedAt,
+                Expr::value(Utc::now().naive_utc()),
+            )
+            .filter(query_job::Column::Id.eq(entity.id))
+            .filter(query_job::Column::Status.eq(expected))
+            .exec(&self.db_context.get_connection())
+            .await?;
+        Ok(result.rows_affected == 1)
+    }
+}
+
+impl QueryJobContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> QueryJobContext {
+        QueryJobContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<query_job::Model> for QueryJobContext {
+    /// Used for creating a query_job::Model entity, the timestamps are always set to now
+    /// # Example
+    /// ```
+    /// let query_job = query_job::Model {
+    ///     id: Default::default(),
+    ///     query_id: 1,
+    ///     project_id: 1,
+    ///     user_id: 1,
+    ///     status: QueryJobStatus::Queued,
+    ///     result: None,
+    ///     error: None,
+    ///     created_at: Default::default(),
+    ///     updated_at: Default::default(),
+    /// };
+    /// let query_job_context: QueryJobContext = QueryJobContext::new(...);
+    /// query_job_context.create(query_job);
+    /// ```
+    async fn create(&self, entity: query_job::Model) -> Result<query_job::Model, DbErr> {
+        let now = Utc::now().naive_utc();
+        let query_job = query_job::ActiveModel {
+            id: Default::default(),
+            query_id: Set(entity.query_id),
+            project_id: Set(entity.project_id),
+            user_id: Set(entity.user_id),
+            status: Set(entity.status),
+            result: Set(entity.result),
+            error: Set(entity.error),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        query_job.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single query job entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let query_job_context: QueryJobContext = QueryJobContext::new(...);
+    /// let query_job = query_job_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<query_job::Model>, DbErr> {
+        query_job::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all query job entities
+    /// # Example
+    /// ```
+    /// let query_job_context: QueryJobContext = QueryJobContext::new(...);
+    /// let query_jobs = query_job_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<query_job::Model>, DbErr> {
+        query_job::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single query job entity
+    /// # Note
+    /// Only the status, result and error can change, `updated_at` is always set to now.
+    async fn update(&self, entity: query_job::Model) -> Result<query_job::Model, DbErr> {
+        query_job::ActiveModel {
+            id: Unchanged(entity.id),
+            query_id: Unchanged(entity.query_id),
+            project_id: Unchanged(entity.project_id),
+            user_id: Unchanged(entity.user_id),
+            status: Set(entity.status),
+            result: Set(entity.result),
+            error: Set(entity.error),
+            created_at: Unchanged(entity.created_at),
+            updated_at: Set(Utc::now().naive_utc()),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single query job entity
+    /// # Example
+    /// ```
+    /// let query_job_context: QueryJobContext = QueryJobContext::new(...);
+    /// let query_job = query_job_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<query_job::Model, DbErr> {
+        let query_job = self.get_by_id(entity_id).await?;
+        match query_job {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(query_job) => {
+                query_job::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(query_job)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/query_job_context.rs"]
+mod query_job_context_tests;
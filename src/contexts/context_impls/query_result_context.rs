@@ -0,0 +1,137 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, QueryResultContextTrait,
+};
+use crate::entities::query_result;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct QueryResultContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl QueryResultContextTrait for QueryResultContext {
+    async fn get_page_by_query_id(
+        &self,
+        query_id: i32,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(Vec<query_result::Model>, u64), DbErr> {
+        let connection = self.db_context.get_connection();
+        let paginator = query_result::Entity::find()
+            .filter(query_result::Column::QueryId.eq(query_id))
+            .order_by_desc(query_result::Column::CreatedAt)
+            .order_by_desc(query_result::Column::Id)
+            .paginate(&connection, page_size);
+
+        let total = paginator.num_items().await?;
+        let query_results = paginator.fetch_page(page).await?;
+        Ok((query_results, total))
+    }
+}
+
+impl QueryResultContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> QueryResultContext {
+        QueryResultContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<query_result::Model> for QueryResultContext {
+    /// Used for creating a query_result::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let query_result = query_result::Model {
+    ///     id: Default::default(),
+    ///     query_id: 1,
+    ///     user_id: Some(1),
+    ///     result: "{}".to_owned().parse().unwrap(),
+    ///     settings: "{}".to_owned().parse().unwrap(),
+    ///     components_hash: "e3b0c442".to_owned(),
+    ///     created_at: Default::default(),
+    /// };
+    /// let query_result_context: QueryResultContext = QueryResultContext::new(...);
+    /// query_result_context.create(query_result);
+    /// ```
+    async fn create(&self, entity: query_result::Model) -> Result<query_result::Model, DbErr> {
+        let query_result = query_result::ActiveModel {
+            id: Default::default(),
+            query_id: Set(entity.query_id),
+            user_id: Set(entity.user_id),
+            result: Set(entity.result),
+            settings: Set(entity.settings),
+            components_hash: Set(entity.components_hash),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        query_result.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single query result entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let query_result_context: QueryResultContext = QueryResultContext::new(...);
+    /// let query_result = query_result_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<query_result::Model>, DbErr> {
+        query_result::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all query result entities
+    /// # Example
+    /// ```
+    /// let query_result_context: QueryResultContext = QueryResultContext::new(...);
+    /// let query_results = query_result_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<query_result::Model>, DbErr> {
+        query_result::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single query result entity
+    /// # Note
+    /// The query, user and creation time of a query result cannot change.
+    async fn update(&self, entity: query_result::Model) -> Result<query_result::Model, DbErr> {
+        query_result::ActiveModel {
+            id: Unchanged(entity.id),
+            query_id: Unchanged(entity.query_id),
+            user_id: Unchanged(entity.user_id),
+            result: Set(entity.result),
+            settings: Set(entity.settings),
+            components_hash: Set(entity.components_hash),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single query result entity
+    /// # Example
+    /// ```
+    /// let query_result_context: QueryResultContext = QueryResultContext::new(...);
+    /// let query_result = query_result_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<query_result::Model, DbErr> {
+        let query_result = self.get_by_id(entity_id).await?;
+        match query_result {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(query_result) => {
+                query_result::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(query_result)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/query_result_context.rs"]
+mod query_result_context_tests;
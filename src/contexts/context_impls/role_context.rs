@@ -0,0 +1,96 @@
+use crate::contexts::context_traits::{DatabaseContextTrait, RoleContextTrait};
+use crate::entities::role;
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, QueryOrder, Set, Unchanged};
+use std::sync::Arc;
+
+pub struct RoleContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+impl RoleContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> RoleContext {
+        RoleContext { db_context }
+    }
+}
+
+#[async_trait]
+impl RoleContextTrait for RoleContext {
+    /// Used for creating a role::Model entity
+    /// # Example
+    /// ```
+    /// let role = role::Model {
+    ///     name: "Verifier".to_owned(),
+    ///     permissions: json!(["ReadProject", "RunQuery"]),
+    /// };
+    /// let role_context: RoleContext = RoleContext::new(...);
+    /// role_context.create(role);
+    /// ```
+    async fn create(&self, entity: role::Model) -> Result<role::Model, DbErr> {
+        let role = role::ActiveModel {
+            name: Set(entity.name),
+            permissions: Set(entity.permissions),
+        };
+        role.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single role entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let role_context: RoleContext = RoleContext::new(...);
+    /// let role = role_context.get_by_name("Verifier".to_owned()).unwrap();
+    /// ```
+    async fn get_by_name(&self, name: String) -> Result<Option<role::Model>, DbErr> {
+        role::Entity::find_by_id(name)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all role entities
+    /// # Example
+    /// ```
+    /// let role_context: RoleContext = RoleContext::new(...);
+    /// let roles = role_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<role::Model>, DbErr> {
+        role::Entity::find()
+            .order_by_asc(role::Column::Name)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates the permissions of a single role entity
+    /// # Note
+    /// The name of a role cannot change, as access to projects refers to roles by name.
+    async fn update(&self, entity: role::Model) -> Result<role::Model, DbErr> {
+        role::ActiveModel {
+            name: Unchanged(entity.name),
+            permissions: Set(entity.permissions),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single role entity, along with every access granted with it
+    /// # Example
+    /// ```
+    /// let role_context: RoleContext = RoleContext::new(...);
+    /// let role = role_context.delete("Verifier".to_owned()).unwrap();
+    /// ```
+    async fn delete(&self, name: String) -> Result<role::Model, DbErr> {
+        let role = self.get_by_name(name.clone()).await?;
+        match role {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(role) => {
+                role::Entity::delete_by_id(name)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(role)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/role_context.rs"]
+mod role_context_tests;
@@ -33,6 +33,8 @@ impl SessionContextTrait for SessionContext {
                     .one(&self.db_context.get_connection())
                     .await
             }
+            // Invitation tokens are handed out to projects, not sessions
+            TokenType::InvitationToken => Ok(None),
         }
     }
 
@@ -0,0 +1,133 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, ShareLinkContextTrait,
+};
+use crate::entities::share_link;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct ShareLinkContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl ShareLinkContextTrait for ShareLinkContext {
+    async fn get_by_token(&self, token: String) -> Result<Option<share_link::Model>, DbErr> {
+        share_link::Entity::find()
+            .filter(share_link::Column::Token.eq(token))
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn get_all_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<share_link::Model>, DbErr> {
+        share_link::Entity::find()
+            .filter(share_link::Column::ProjectId.eq(project_id))
+            .order_by_asc(share_link::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl ShareLinkContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> ShareLinkContext {
+        ShareLinkContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<share_link::Model> for ShareLinkContext {
+    /// Used for creating a share_link::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let share_link = share_link::Model {
+    ///     id: Default::default(),
+    ///     project_id: 1,
+    ///     token: "a7e4f1c0d2b94c6e8f3a5b7d9c1e2f40".to_owned(),
+    ///     revoked: false,
+    ///     expires_at: None,
+    ///     created_at: Default::default(),
+    /// };
+    /// let share_link_context: ShareLinkContext = ShareLinkContext::new(...);
+    /// share_link_context.create(share_link);
+    /// ```
+    async fn create(&self, entity: share_link::Model) -> Result<share_link::Model, DbErr> {
+        let share_link = share_link::ActiveModel {
+            id: Default::default(),
+            project_id: Set(entity.project_id),
+            token: Set(entity.token),
+            revoked: Set(entity.revoked),
+            expires_at: Set(entity.expires_at),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        share_link.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single share link entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let share_link_context: ShareLinkContext = ShareLinkContext::new(...);
+    /// let share_link = share_link_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<share_link::Model>, DbErr> {
+        share_link::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all share link entities
+    /// # Example
+    /// ```
+    /// let share_link_context: ShareLinkContext = ShareLinkContext::new(...);
+    /// let share_links = share_link_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<share_link::Model>, DbErr> {
+        share_link::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates whether a single share link entity is revoked
+    /// # Note
+    /// The project, token and expiry of a share link are fixed when it is created.
+    async fn update(&self, entity: share_link::Model) -> Result<share_link::Model, DbErr> {
+        share_link::ActiveModel {
+            id: Unchanged(entity.id),
+            project_id: Unchanged(entity.project_id),
+            token: Unchanged(entity.token),
+            revoked: Set(entity.revoked),
+            expires_at: Unchanged(entity.expires_at),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single share link entity
+    /// # Example
+    /// ```
+    /// let share_link_context: ShareLinkContext = ShareLinkContext::new(...);
+    /// let share_link = share_link_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<share_link::Model, DbErr> {
+        let share_link = self.get_by_id(entity_id).await?;
+        match share_link {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(share_link) => {
+                share_link::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(share_link)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/share_link_context.rs"]
+mod share_link_context_tests;
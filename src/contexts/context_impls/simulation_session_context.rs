@@ -0,0 +1,141 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, SimulationSessionContextTrait,
+};
+use crate::entities::simulation_session;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct SimulationSessionContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl SimulationSessionContextTrait for SimulationSessionContext {
+    async fn get_all_by_project_id_and_user_id(
+        &self,
+        project_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<simulation_session::Model>, DbErr> {
+        simulation_session::Entity::find()
+            .filter(simulation_session::Column::ProjectId.eq(project_id))
+            .filter(simulation_session::Column::UserId.eq(user_id))
+            .order_by_desc(simulation_session::Column::UpdatedAt)
+            .order_by_desc(simulation_session::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl SimulationSessionContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> SimulationSessionContext {
+        SimulationSessionContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<simulation_session::Model> for SimulationSessionContext {
+    /// Used for creating a simulation_session::Model entity, the timestamps are always set to now
+    /// # Example
+    /// ```
+    /// let simulation_session = simulation_session::Model {
+    ///     id: Default::default(),
+    ///     project_id: 1,
+    ///     user_id: 1,
+    ///     name: "simulation".to_owned(),
+    ///     component_composition: "(A || B)".to_owned(),
+    ///     created_at: Default::default(),
+    ///     updated_at: Default::default(),
+    /// };
+    /// let simulation_session_context: SimulationSessionContext = SimulationSessionContext::new(...);
+    /// simulation_session_context.create(simulation_session);
+    /// ```
+    async fn create(
+        &self,
+        entity: simulation_session::Model,
+    ) -> Result<simulation_session::Model, DbErr> {
+        let now = Utc::now().naive_utc();
+        let simulation_session = simulation_session::ActiveModel {
+            id: Default::default(),
+            project_id: Set(entity.project_id),
+            user_id: Set(entity.user_id),
+            name: Set(entity.name),
+            component_composition: Set(entity.component_composition),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        simulation_session
+            .insert(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns a single simulation session entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let simulation_session_context: SimulationSessionContext = SimulationSessionContext::new(...);
+    /// let simulation_session = simulation_session_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<simulation_session::Model>, DbErr> {
+        simulation_session::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all simulation session entities
+    /// # Example
+    /// ```
+    /// let simulation_session_context: SimulationSessionContext = SimulationSessionContext::new(...);
+    /// let simulation_sessions = simulation_session_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<simulation_session::Model>, DbErr> {
+        simulation_session::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single simulation session entity
+    /// # Note
+    /// Only the name can change, `updated_at` is always set to now.
+    async fn update(
+        &self,
+        entity: simulation_session::Model,
+    ) -> Result<simulation_session::Model, DbErr> {
+        simulation_session::ActiveModel {
+            id: Unchanged(entity.id),
+            project_id: Unchanged(entity.project_id),
+            user_id: Unchanged(entity.user_id),
+            name: Set(entity.name),
+            component_composition: Unchanged(entity.component_composition),
+            created_at: Unchanged(entity.created_at),
+            updated_at: Set(Utc::now().naive_utc()),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single simulation session entity, along with its steps
+    /// # Example
+    /// ```
+    /// let simulation_session_context: SimulationSessionContext = SimulationSessionContext::new(...);
+    /// let simulation_session = simulation_session_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<simulation_session::Model, DbErr> {
+        let simulation_session = self.get_by_id(entity_id).await?;
+        match simulation_session {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(simulation_session) => {
+                simulation_session::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(simulation_session)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/simulation_session_context.rs"]
+mod simulation_session_context_tests;
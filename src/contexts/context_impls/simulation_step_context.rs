@@ -0,0 +1,131 @@
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, SimulationStepContextTrait,
+};
+use crate::entities::simulation_step;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct SimulationStepContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl SimulationStepContextTrait for SimulationStepContext {
+    async fn get_all_by_simulation_session_id(
+        &self,
+        simulation_session_id: i32,
+    ) -> Result<Vec<simulation_step::Model>, DbErr> {
+        simulation_step::Entity::find()
+            .filter(simulation_step::Column::SimulationSessionId.eq(simulation_session_id))
+            .order_by_asc(simulation_step::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl SimulationStepContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> SimulationStepContext {
+        SimulationStepContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<simulation_step::Model> for SimulationStepContext {
+    /// Used for creating a simulation_step::Model entity, the creation time is always set to now
+    /// # Example
+    /// ```
+    /// let simulation_step = simulation_step::Model {
+    ///     id: Default::default(),
+    ///     simulation_session_id: 1,
+    ///     chosen_decision: None,
+    ///     decision_points: "[]".to_owned().parse().unwrap(),
+    ///     created_at: Default::default(),
+    /// };
+    /// let simulation_step_context: SimulationStepContext = SimulationStepContext::new(...);
+    /// simulation_step_context.create(simulation_step);
+    /// ```
+    async fn create(
+        &self,
+        entity: simulation_step::Model,
+    ) -> Result<simulation_step::Model, DbErr> {
+        let simulation_step = simulation_step::ActiveModel {
+            id: Default::default(),
+            simulation_session_id: Set(entity.simulation_session_id),
+            chosen_decision: Set(entity.chosen_decision),
+            decision_points: Set(entity.decision_points),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+        simulation_step
+            .insert(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns a single simulation step entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let simulation_step_context: SimulationStepContext = SimulationStepContext::new(...);
+    /// let simulation_step = simulation_step_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<simulation_step::Model>, DbErr> {
+        simulation_step::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all simulation step entities
+    /// # Example
+    /// ```
+    /// let simulation_step_context: SimulationStepContext = SimulationStepContext::new(...);
+    /// let simulation_steps = simulation_step_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<simulation_step::Model>, DbErr> {
+        simulation_step::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates a single simulation step entity
+    /// # Note
+    /// The session and creation time of a simulation step cannot change.
+    async fn update(
+        &self,
+        entity: simulation_step::Model,
+    ) -> Result<simulation_step::Model, DbErr> {
+        simulation_step::ActiveModel {
+            id: Unchanged(entity.id),
+            simulation_session_id: Unchanged(entity.simulation_session_id),
+            chosen_decision: Set(entity.chosen_decision),
+            decision_points: Set(entity.decision_points),
+            created_at: Unchanged(entity.created_at),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single simulation step entity
+    /// # Example
+    /// ```
+    /// let simulation_step_context: SimulationStepContext = SimulationStepContext::new(...);
+    /// let simulation_step = simulation_step_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<simulation_step::Model, DbErr> {
+        let simulation_step = self.get_by_id(entity_id).await?;
+        match simulation_step {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(simulation_step) => {
+                simulation_step::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(simulation_step)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/simulation_step_context.rs"]
+mod simulation_step_context_tests;
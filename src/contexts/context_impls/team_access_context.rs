@@ -0,0 +1,139 @@
+use crate::api::server::protobuf::TeamAccessInfo;
+use crate::contexts::context_traits::{
+    DatabaseContextTrait, EntityContextTrait, TeamAccessContextTrait,
+};
+use crate::entities::{team, team_access};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, JoinType, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct TeamAccessContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl TeamAccessContextTrait for TeamAccessContext {
+    async fn get_team_access_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<TeamAccessInfo>, DbErr> {
+        team_access::Entity::find()
+            .select_only()
+            .column(team_access::Column::Id)
+            .column(team_access::Column::Role)
+            .column(team_access::Column::ProjectId)
+            .column(team_access::Column::TeamId)
+            .column_as(team::Column::Name, "team_name")
+            .join(JoinType::InnerJoin, team_access::Relation::Team.def())
+            .filter(team_access::Column::ProjectId.eq(project_id))
+            .order_by_asc(team_access::Column::Id)
+            .into_model::<TeamAccessInfo>()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn get_team_access_by_role(
+        &self,
+        role: String,
+    ) -> Result<Vec<team_access::Model>, DbErr> {
+        team_access::Entity::find()
+            .filter(team_access::Column::Role.eq(role))
+            .all(&self.db_context.get_connection())
+            .await
+    }
+}
+
+impl TeamAccessContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> TeamAccessContext {
+        TeamAccessContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<team_access::Model> for TeamAccessContext {
+    /// Used for creating a team_access::Model entity
+    /// # Example
+    /// ```
+    /// let team_access = team_access::Model {
+    ///     id: Default::default(),
+    ///     role: "Reader".to_owned(),
+    ///     project_id: 1,
+    ///     team_id: 1,
+    /// };
+    /// let team_access_context: TeamAccessContext = TeamAccessContext::new(...);
+    /// team_access_context.create(team_access);
+    /// ```
+    async fn create(&self, entity: team_access::Model) -> Result<team_access::Model, DbErr> {
+        let team_access = team_access::ActiveModel {
+            id: Default::default(),
+            role: Set(entity.role),
+            project_id: Set(entity.project_id),
+            team_id: Set(entity.team_id),
+        };
+        team_access.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single team access entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let team_access_context: TeamAccessContext = TeamAccessContext::new(...);
+    /// let team_access = team_access_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<team_access::Model>, DbErr> {
+        team_access::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all team access entities
+    /// # Example
+    /// ```
+    /// let team_access_context: TeamAccessContext = TeamAccessContext::new(...);
+    /// let team_accesses = team_access_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<team_access::Model>, DbErr> {
+        team_access::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates the role of a single team access entity
+    /// # Note
+    /// The project and team of a team access entity cannot change.
+    async fn update(&self, entity: team_access::Model) -> Result<team_access::Model, DbErr> {
+        team_access::ActiveModel {
+            id: Unchanged(entity.id),
+            role: Set(entity.role),
+            project_id: Unchanged(entity.project_id),
+            team_id: Unchanged(entity.team_id),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single team access entity
+    /// # Example
+    /// ```
+    /// let team_access_context: TeamAccessContext = TeamAccessContext::new(...);
+    /// let team_access = team_access_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<team_access::Model, DbErr> {
+        let team_access = self.get_by_id(entity_id).await?;
+        match team_access {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(team_access) => {
+                team_access::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(team_access)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/team_access_context.rs"]
+mod team_access_context_tests;
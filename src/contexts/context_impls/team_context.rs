@@ -0,0 +1,161 @@
+use crate::contexts::context_traits::{DatabaseContextTrait, EntityContextTrait, TeamContextTrait};
+use crate::entities::{team, team_member};
+use async_trait::async_trait;
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, Unchanged,
+};
+use std::sync::Arc;
+
+pub struct TeamContext {
+    db_context: Arc<dyn DatabaseContextTrait>,
+}
+
+#[async_trait]
+impl TeamContextTrait for TeamContext {
+    async fn get_all_by_uid(&self, uid: i32) -> Result<Vec<team::Model>, DbErr> {
+        team::Entity::find()
+            .filter(
+                team::Column::Id.in_subquery(
+                    Query::select()
+                        .column(team_member::Column::TeamId)
+                        .from(team_member::Entity)
+                        .and_where(team_member::Column::UserId.eq(uid))
+                        .to_owned(),
+                ),
+            )
+            .order_by_asc(team::Column::Id)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn get_members(&self, team_id: i32) -> Result<Vec<team_member::Model>, DbErr> {
+        team_member::Entity::find()
+            .filter(team_member::Column::TeamId.eq(team_id))
+            .order_by_asc(team_member::Column::UserId)
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    async fn add_members(&self, team_id: i32, user_ids: Vec<i32>) -> Result<(), DbErr> {
+        if user_ids.is_empty() {
+            return Ok(());
+        }
+
+        team_member::Entity::insert_many(user_ids.into_iter().map(|user_id| {
+            team_member::ActiveModel {
+                team_id: Set(team_id),
+                user_id: Set(user_id),
+            }
+        }))
+        .exec(&self.db_context.get_connection())
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_member(&self, team_id: i32, user_id: i32) -> Result<team_member::Model, DbErr> {
+        let member = team_member::Entity::find_by_id((team_id, user_id))
+            .one(&self.db_context.get_connection())
+            .await?;
+        match member {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(member) => {
+                team_member::Entity::delete_by_id((team_id, user_id))
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(member)
+            }
+        }
+    }
+}
+
+impl TeamContext {
+    pub fn new(db_context: Arc<dyn DatabaseContextTrait>) -> TeamContext {
+        TeamContext { db_context }
+    }
+}
+
+#[async_trait]
+impl EntityContextTrait<team::Model> for TeamContext {
+    /// Used for creating a team::Model entity
+    /// # Example
+    /// ```
+    /// let team = team::Model {
+    ///     id: Default::default(),
+    ///     name: "Verification".to_owned(),
+    ///     owner_id: 1,
+    /// };
+    /// let team_context: TeamContext = TeamContext::new(...);
+    /// team_context.create(team);
+    /// ```
+    /// # Note
+    /// The owner is not made a member of the team by this.
+    async fn create(&self, entity: team::Model) -> Result<team::Model, DbErr> {
+        let team = team::ActiveModel {
+            id: Default::default(),
+            name: Set(entity.name),
+            owner_id: Set(entity.owner_id),
+        };
+        team.insert(&self.db_context.get_connection()).await
+    }
+
+    /// Returns a single team entity (Uses primary key)
+    /// # Example
+    /// ```
+    /// let team_context: TeamContext = TeamContext::new(...);
+    /// let team = team_context.get_by_id(1).unwrap();
+    /// ```
+    async fn get_by_id(&self, entity_id: i32) -> Result<Option<team::Model>, DbErr> {
+        team::Entity::find_by_id(entity_id)
+            .one(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Returns all team entities
+    /// # Example
+    /// ```
+    /// let team_context: TeamContext = TeamContext::new(...);
+    /// let teams = team_context.get_all().unwrap();
+    /// ```
+    async fn get_all(&self) -> Result<Vec<team::Model>, DbErr> {
+        team::Entity::find()
+            .all(&self.db_context.get_connection())
+            .await
+    }
+
+    /// Updates the name of a single team entity
+    /// # Note
+    /// The owner of a team cannot change.
+    async fn update(&self, entity: team::Model) -> Result<team::Model, DbErr> {
+        team::ActiveModel {
+            id: Unchanged(entity.id),
+            name: Set(entity.name),
+            owner_id: Unchanged(entity.owner_id),
+        }
+        .update(&self.db_context.get_connection())
+        .await
+    }
+
+    /// Returns and deletes a single team entity, along with its memberships and the access granted to it
+    /// # Example
+    /// ```
+    /// let team_context: TeamContext = TeamContext::new(...);
+    /// let team = team_context.delete(1).unwrap();
+    /// ```
+    async fn delete(&self, entity_id: i32) -> Result<team::Model, DbErr> {
+        let team = self.get_by_id(entity_id).await?;
+        match team {
+            None => Err(DbErr::RecordNotFound("No record was deleted".into())),
+            Some(team) => {
+                team::Entity::delete_by_id(entity_id)
+                    .exec(&self.db_context.get_connection())
+                    .await?;
+                Ok(team)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/contexts/team_context.rs"]
+mod team_context_tests;
@@ -4,17 +4,50 @@ use crate::entities::access;
 use async_trait::async_trait;
 use sea_orm::DbErr;
 
+/// The access a `User` has to a `Project`, made up of every grant that applies to them.
+/// The user has the permissions of all of their roles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EffectiveAccess {
+    /// The access granted to the user themself, if any.
+    pub direct: Option<access::Model>,
+    /// The roles granted to the teams the user is a member of.
+    pub team_roles: Vec<String>,
+}
+
+impl EffectiveAccess {
+    /// Returns the names of the roles the user has on the project, the one granted to them directly first.
+    pub fn roles(&self) -> impl Iterator<Item = &str> {
+        self.direct
+            .iter()
+            .map(|access| access.role.as_str())
+            .chain(self.team_roles.iter().map(String::as_str))
+    }
+}
+
+impl From<access::Model> for EffectiveAccess {
+    fn from(access: access::Model) -> Self {
+        EffectiveAccess {
+            direct: Some(access),
+            team_roles: vec![],
+        }
+    }
+}
+
 #[async_trait]
 pub trait AccessContextTrait: EntityContextTrait<access::Model> {
-    /// Searches for an access entity by `User` and `Project` id,
-    /// returning [`Some`] if any entity was found, [`None`] otherwise
+    /// Searches for the access a `User` has to a `Project`, either granted to them directly
+    /// or to a team they are a member of, returning [`None`] if they have no access.
     /// # Errors
     /// Errors on failed connection, execution error or constraint violations.
     async fn get_access_by_uid_and_project_id(
         &self,
         uid: i32,
         project_id: i32,
-    ) -> Result<Option<access::Model>, DbErr>;
+    ) -> Result<Option<EffectiveAccess>, DbErr>;
     /// Returns all [`access::Model`] that are associated with a given `Project``
     async fn get_access_by_project_id(&self, project_id: i32) -> Result<Vec<AccessInfo>, DbErr>;
+    /// Returns all [`access::Model`] that grant a given role
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_access_by_role(&self, role: String) -> Result<Vec<access::Model>, DbErr>;
 }
@@ -0,0 +1,4 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::backend_audit;
+
+pub trait BackendAuditContextTrait: EntityContextTrait<backend_audit::Model> {}
@@ -0,0 +1,12 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::comment;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait CommentContextTrait: EntityContextTrait<comment::Model> {
+    /// Returns the comments of a given project id, threads and replies alike, in the order they were written
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<comment::Model>, DbErr>;
+}
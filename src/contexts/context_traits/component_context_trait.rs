@@ -0,0 +1,21 @@
+use crate::api::server::protobuf::ComponentsInfo;
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::component;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait ComponentContextTrait: EntityContextTrait<component::Model> {
+    /// Returns the components of a given project id ordered by name
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<component::Model>, DbErr>;
+
+    /// Returns the components of a given project id reassembled into a components info object
+    /// # Errors
+    /// Errors on failed connection, execution error or if a stored component cannot be parsed.
+    async fn get_components_info_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<ComponentsInfo, DbErr>;
+}
@@ -0,0 +1,23 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::counterexample;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait CounterexampleContextTrait: EntityContextTrait<counterexample::Model> {
+    /// Returns the counterexample of the most recent failed run of a given query id
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_latest_by_query_id(
+        &self,
+        query_id: i32,
+    ) -> Result<Option<counterexample::Model>, DbErr>;
+
+    /// Returns the counterexample extracted from a given query result id
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_by_query_result_id(
+        &self,
+        query_result_id: i32,
+    ) -> Result<Option<counterexample::Model>, DbErr>;
+}
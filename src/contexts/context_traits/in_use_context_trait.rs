@@ -1,4 +1,38 @@
 use crate::contexts::context_traits::EntityContextTrait;
 use crate::entities::in_use;
+use async_trait::async_trait;
+use sea_orm::prelude::DateTime;
+use sea_orm::DbErr;
 
-pub trait InUseContextTrait: EntityContextTrait<in_use::Model> {}
+#[async_trait]
+pub trait InUseContextTrait: EntityContextTrait<in_use::Model> {
+    /// Gives the lock on a project to a session until the given time,
+    /// if the lock is released, has expired or is already held by the session.
+    /// Returns whether the session got the lock.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn acquire(
+        &self,
+        project_id: i32,
+        session_id: i32,
+        expires_at: DateTime,
+    ) -> Result<bool, DbErr>;
+
+    /// Extends the lock on a project until the given time, if the session holds it and it has not expired.
+    /// Returns whether the lock was extended.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn renew(
+        &self,
+        project_id: i32,
+        session_id: i32,
+        expires_at: DateTime,
+    ) -> Result<bool, DbErr>;
+
+    /// Releases the lock on a project.
+    /// If a session is given, the lock is only released if that session holds it and it has not expired.
+    /// Returns whether the lock was released.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn release(&self, project_id: i32, session_id: Option<i32>) -> Result<bool, DbErr>;
+}
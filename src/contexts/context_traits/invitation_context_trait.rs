@@ -0,0 +1,22 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::invitation;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait InvitationContextTrait: EntityContextTrait<invitation::Model> {
+    /// Returns the invitations to a given project id, revoked and used up ones included, in the order they were created
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_all_by_project_id(&self, project_id: i32)
+        -> Result<Vec<invitation::Model>, DbErr>;
+    /// Counts a use of an invitation, as long as it has not been revoked, has not expired and has uses left.
+    /// Returns whether the use was counted.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn redeem(&self, id: i32) -> Result<bool, DbErr>;
+    /// Returns the invitations that grant a given role and can still be redeemed
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_redeemable_by_role(&self, role: String) -> Result<Vec<invitation::Model>, DbErr>;
+}
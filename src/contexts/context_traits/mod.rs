@@ -1,17 +1,47 @@
 pub mod access_context_trait;
+pub mod backend_audit_context_trait;
+pub mod comment_context_trait;
+pub mod component_context_trait;
+pub mod counterexample_context_trait;
 pub mod database_context_trait;
 pub mod entity_context_trait;
 pub mod in_use_context_trait;
+pub mod invitation_context_trait;
 pub mod project_context_trait;
+pub mod project_version_context_trait;
+pub mod query_cache_context_trait;
 pub mod query_context_trait;
+pub mod query_job_context_trait;
+pub mod query_result_context_trait;
+pub mod role_context_trait;
 pub mod session_context_trait;
+pub mod share_link_context_trait;
+pub mod simulation_session_context_trait;
+pub mod simulation_step_context_trait;
+pub mod team_access_context_trait;
+pub mod team_context_trait;
 pub mod user_context_trait;
 
-pub use access_context_trait::AccessContextTrait;
+pub use access_context_trait::{AccessContextTrait, EffectiveAccess};
+pub use backend_audit_context_trait::BackendAuditContextTrait;
+pub use comment_context_trait::CommentContextTrait;
+pub use component_context_trait::ComponentContextTrait;
+pub use counterexample_context_trait::CounterexampleContextTrait;
 pub use database_context_trait::DatabaseContextTrait;
 pub use entity_context_trait::EntityContextTrait;
 pub use in_use_context_trait::InUseContextTrait;
+pub use invitation_context_trait::InvitationContextTrait;
 pub use project_context_trait::ProjectContextTrait;
+pub use project_version_context_trait::ProjectVersionContextTrait;
+pub use query_cache_context_trait::QueryCacheContextTrait;
 pub use query_context_trait::QueryContextTrait;
+pub use query_job_context_trait::QueryJobContextTrait;
+pub use query_result_context_trait::QueryResultContextTrait;
+pub use role_context_trait::RoleContextTrait;
 pub use session_context_trait::SessionContextTrait;
+pub use share_link_context_trait::ShareLinkContextTrait;
+pub use simulation_session_context_trait::SimulationSessionContextTrait;
+pub use simulation_step_context_trait::SimulationStepContextTrait;
+pub use team_access_context_trait::TeamAccessContextTrait;
+pub use team_context_trait::TeamContextTrait;
 pub use user_context_trait::UserContextTrait;
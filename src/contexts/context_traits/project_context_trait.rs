@@ -6,8 +6,31 @@ use sea_orm::DbErr;
 
 #[async_trait]
 pub trait ProjectContextTrait: EntityContextTrait<project::Model> {
-    /// Returns the projects owned by a given user id
+    /// Returns the projects a given user id has access to, either directly or through a team,
+    /// each with the role granted to the user directly, or otherwise the first role granted to one of their teams
     /// # Errors
     /// Errors on failed connection, execution error or constraint violations.
     async fn get_project_info_by_uid(&self, uid: i32) -> Result<Vec<ProjectInfo>, DbErr>;
+
+    /// Moves a project to its next revision.
+    /// If an expected revision is given, the project is only moved if it is still at that revision.
+    /// Returns whether the project was moved.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn advance_revision(
+        &self,
+        project_id: i32,
+        expected_revision: Option<i32>,
+    ) -> Result<bool, DbErr>;
+
+    /// Updates a project like [`update`](EntityContextTrait::update) and moves it to its next revision in the same write,
+    /// but only if it is still at the expected revision.
+    /// Returns whether the project was updated.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn update_at_revision(
+        &self,
+        entity: project::Model,
+        expected_revision: i32,
+    ) -> Result<bool, DbErr>;
 }
@@ -0,0 +1,15 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::project_version;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait ProjectVersionContextTrait: EntityContextTrait<project_version::Model> {
+    /// Returns the versions of a given project id, newest first
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<project_version::Model>, DbErr>;
+}
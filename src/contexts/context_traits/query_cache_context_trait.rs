@@ -0,0 +1,12 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::query_cache;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait QueryCacheContextTrait: EntityContextTrait<query_cache::Model> {
+    /// Returns the cached result of the query run with the given hash, unless it has expired
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_by_hash(&self, hash: &str) -> Result<Option<query_cache::Model>, DbErr>;
+}
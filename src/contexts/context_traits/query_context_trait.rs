@@ -1,10 +1,30 @@
 use crate::contexts::context_traits::EntityContextTrait;
 use crate::entities::query;
 use async_trait::async_trait;
+use sea_orm::prelude::Json;
 use sea_orm::DbErr;
 
 #[async_trait]
 pub trait QueryContextTrait: EntityContextTrait<query::Model> {
     /// Returns the queries associated with a given project id
     async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<query::Model>, DbErr>;
+    /// Sets the result of a query and whether it is outdated, leaving the rest of the query as it is.
+    /// Returns whether the query still exists.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn update_result(&self, id: i32, result: Json, outdated: bool) -> Result<bool, DbErr>;
+    /// Marks the queries with the given ids as outdated, leaving the rest of them as they are.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn mark_outdated(&self, ids: Vec<i32>) -> Result<(), DbErr>;
+    /// Updates a query like [`update`](EntityContextTrait::update) and moves its project to the next revision
+    /// in the same transaction, but only if the project is still at the expected revision.
+    /// Returns whether the query was updated. Neither is changed if the update fails.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn update_at_revision(
+        &self,
+        entity: query::Model,
+        expected_revision: i32,
+    ) -> Result<bool, DbErr>;
 }
@@ -0,0 +1,31 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::query_job;
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait QueryJobContextTrait: EntityContextTrait<query_job::Model> {
+    /// Returns the query jobs of a given project id, newest first
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<query_job::Model>, DbErr>;
+
+    /// Returns all query jobs with the given status, oldest first
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all_by_status(
+        &self,
+        status: QueryJobStatus,
+    ) -> Result<Vec<query_job::Model>, DbErr>;
+
+    /// Updates the status, result and error of a query job, but only if the job currently has the expected status.
+    /// Returns whether the job was updated.
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn update_if_status(
+        &self,
+        entity: query_job::Model,
+        expected: QueryJobStatus,
+    ) -> Result<bool, DbErr>;
+}
@@ -0,0 +1,19 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::query_result;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait QueryResultContextTrait: EntityContextTrait<query_result::Model> {
+    /// Returns a page of the results of a given query id, newest first,
+    /// together with the total number of results of the query.
+    /// Pages are zero-indexed.
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_page_by_query_id(
+        &self,
+        query_id: i32,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(Vec<query_result::Model>, u64), DbErr>;
+}
@@ -0,0 +1,29 @@
+use crate::entities::role;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+/// Roles are keyed by their name rather than an id, so unlike the other contexts
+/// this does not build on [`EntityContextTrait`](super::EntityContextTrait).
+#[async_trait]
+pub trait RoleContextTrait: Send + Sync {
+    /// Inserts a role into the database
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn create(&self, entity: role::Model) -> Result<role::Model, DbErr>;
+    /// Searches for a role by its name, returning [`Some`] if a role is found, [`None`] otherwise
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_by_name(&self, name: String) -> Result<Option<role::Model>, DbErr>;
+    /// Returns all the roles, ordered by name
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all(&self) -> Result<Vec<role::Model>, DbErr>;
+    /// Updates the permissions of a role
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn update(&self, entity: role::Model) -> Result<role::Model, DbErr>;
+    /// Searches for a role by name and deletes it
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn delete(&self, name: String) -> Result<role::Model, DbErr>;
+}
@@ -0,0 +1,17 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::share_link;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait ShareLinkContextTrait: EntityContextTrait<share_link::Model> {
+    /// Returns the share link with a given token, whether or not it has been revoked or has expired
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_by_token(&self, token: String) -> Result<Option<share_link::Model>, DbErr>;
+    /// Returns the share links to a given project id, revoked and expired ones included, in the order they were created
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_all_by_project_id(&self, project_id: i32)
+        -> Result<Vec<share_link::Model>, DbErr>;
+}
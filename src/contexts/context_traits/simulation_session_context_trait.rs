@@ -0,0 +1,17 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::simulation_session;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait SimulationSessionContextTrait: EntityContextTrait<simulation_session::Model> {
+    /// Returns the simulation sessions a given user has saved in a given project,
+    /// most recently updated first
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all_by_project_id_and_user_id(
+        &self,
+        project_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<simulation_session::Model>, DbErr>;
+}
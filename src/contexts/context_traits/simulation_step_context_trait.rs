@@ -0,0 +1,15 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::simulation_step;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait SimulationStepContextTrait: EntityContextTrait<simulation_step::Model> {
+    /// Returns the steps of a given simulation session id in the order they were taken
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations.
+    async fn get_all_by_simulation_session_id(
+        &self,
+        simulation_session_id: i32,
+    ) -> Result<Vec<simulation_step::Model>, DbErr>;
+}
@@ -0,0 +1,21 @@
+use crate::api::server::protobuf::TeamAccessInfo;
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::team_access;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait TeamAccessContextTrait: EntityContextTrait<team_access::Model> {
+    /// Returns the access granted to teams on a given project, along with the names of the teams
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_team_access_by_project_id(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<TeamAccessInfo>, DbErr>;
+    /// Returns all [`team_access::Model`] that grant a given role
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_team_access_by_role(&self, role: String)
+        -> Result<Vec<team_access::Model>, DbErr>;
+}
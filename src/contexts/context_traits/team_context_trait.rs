@@ -0,0 +1,25 @@
+use crate::contexts::context_traits::EntityContextTrait;
+use crate::entities::{team, team_member};
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait TeamContextTrait: EntityContextTrait<team::Model> {
+    /// Returns the teams a given user is a member of, ordered by id
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_all_by_uid(&self, uid: i32) -> Result<Vec<team::Model>, DbErr>;
+    /// Returns the members of a given team, ordered by user id
+    /// # Errors
+    /// Errors on failed connection or execution error.
+    async fn get_members(&self, team_id: i32) -> Result<Vec<team_member::Model>, DbErr>;
+    /// Adds the given users to a team, all or none of them
+    /// # Errors
+    /// Errors on failed connection, execution error or constraint violations,
+    /// such as one of the users already being a member.
+    async fn add_members(&self, team_id: i32, user_ids: Vec<i32>) -> Result<(), DbErr>;
+    /// Removes a user from a team, returning the removed membership
+    /// # Errors
+    /// Errors on failed connection, execution error or if the user is not a member of the team.
+    async fn remove_member(&self, team_id: i32, user_id: i32) -> Result<team_member::Model, DbErr>;
+}
@@ -1,13 +1,21 @@
-use crate::api::server::protobuf::ecdar_backend_server::EcdarBackend;
 use crate::controllers::controller_traits::*;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct ControllerCollection {
     pub(crate) access_controller: Arc<dyn AccessControllerTrait>,
+    pub(crate) collaboration_controller: Arc<dyn CollaborationControllerTrait>,
+    pub(crate) comment_controller: Arc<dyn CommentControllerTrait>,
+    pub(crate) component_controller: Arc<dyn ComponentControllerTrait>,
+    pub(crate) invitation_controller: Arc<dyn InvitationControllerTrait>,
+    pub(crate) lock_controller: Arc<dyn LockControllerTrait>,
     pub(crate) project_controller: Arc<dyn ProjectControllerTrait>,
     pub(crate) query_controller: Arc<dyn QueryControllerTrait>,
+    pub(crate) role_controller: Arc<dyn RoleControllerTrait>,
     pub(crate) session_controller: Arc<dyn SessionControllerTrait>,
+    pub(crate) share_link_controller: Arc<dyn ShareLinkControllerTrait>,
+    pub(crate) simulation_controller: Arc<dyn SimulationControllerTrait>,
+    pub(crate) team_controller: Arc<dyn TeamControllerTrait>,
     pub(crate) user_controller: Arc<dyn UserControllerTrait>,
-    pub(crate) reveaal_controller: Arc<dyn EcdarBackend>,
+    pub(crate) reveaal_controller: Arc<dyn ReveaalControllerTrait>,
 }
@@ -1,14 +1,18 @@
 use crate::api::auth::RequestExt;
+use crate::api::roles::{permissions_from_json, Permission, Role};
 use crate::api::server::protobuf::create_access_request::User;
 use crate::api::server::protobuf::{
-    CreateAccessRequest, DeleteAccessRequest, ListAccessInfoRequest, ListAccessInfoResponse,
-    UpdateAccessRequest,
+    CreateAccessRequest, CreateTeamAccessRequest, DeleteAccessRequest, DeleteTeamAccessRequest,
+    ListAccessInfoRequest, ListAccessInfoResponse, UpdateAccessRequest, UpdateTeamAccessRequest,
 };
 use crate::contexts::context_collection::ContextCollection;
-use crate::contexts::context_traits::{AccessContextTrait, UserContextTrait};
+use crate::contexts::context_traits::{EffectiveAccess, UserContextTrait};
 use crate::controllers::controller_traits::AccessControllerTrait;
-use crate::entities::{access, user};
+use crate::entities::{access, team_access, user};
 use async_trait::async_trait;
+use futures::{stream, Stream, StreamExt};
+use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
 use tonic::{Code, Request, Response, Status};
 
@@ -39,41 +43,39 @@ impl AccessControllerTrait for AccessController {
             })?
             .ok_or(Status::internal("Could not get uid from request metadata"))?;
 
-        match self
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        let access_info_list = self
             .contexts
             .access_context
-            .get_access_by_uid_and_project_id(uid, message.project_id)
+            .get_access_by_project_id(message.project_id)
             .await
-        {
-            Ok(access) => {
-                if access.is_none() {
-                    return Err(Status::new(
-                        Code::PermissionDenied,
-                        "User does not have access to model",
-                    ));
-                }
-            }
-            Err(error) => return Err(Status::new(Code::Internal, error.to_string())),
-        };
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
 
-        match self
+        let team_access_info_list = self
             .contexts
-            .access_context
-            .get_access_by_project_id(message.project_id)
+            .team_access_context
+            .get_team_access_by_project_id(message.project_id)
             .await
-        {
-            Ok(access_info_list) => {
-                if access_info_list.is_empty() {
-                    return Err(Status::new(
-                        Code::NotFound,
-                        "No access found for given user",
-                    ));
-                } else {
-                    Ok(Response::new(ListAccessInfoResponse { access_info_list }))
-                }
-            }
-            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        if access_info_list.is_empty() && team_access_info_list.is_empty() {
+            return Err(Status::new(
+                Code::NotFound,
+                "No access found for given user",
+            ));
         }
+
+        Ok(Response::new(ListAccessInfoResponse {
+            access_info_list,
+            team_access_info_list,
+        }))
     }
 
     async fn create_access(
@@ -92,14 +94,16 @@ impl AccessControllerTrait for AccessController {
             })?
             .ok_or(Status::internal("Could not get uid from request metadata"))?;
 
-        // Check if the requester has access to model with role 'Editor'
-        check_editor_role_helper(
-            Arc::clone(&self.contexts.access_context),
+        let granter = authorize_helper(
+            &self.contexts,
             uid,
             message.project_id,
+            Permission::ManageAccess,
         )
         .await?;
 
+        check_role_helper(&self.contexts, &granter, &message.role).await?;
+
         if let Some(user) = message.user {
             let user_from_db =
                 create_access_find_user_helper(Arc::clone(&self.contexts.user_context), user)
@@ -107,7 +111,7 @@ impl AccessControllerTrait for AccessController {
 
             let access = access::Model {
                 id: Default::default(),
-                role: message.role.to_string(),
+                role: message.role,
                 project_id: message.project_id,
                 user_id: user_from_db.id,
             };
@@ -153,13 +157,16 @@ impl AccessControllerTrait for AccessController {
                 )
             })?;
 
-        check_editor_role_helper(
-            Arc::clone(&self.contexts.access_context),
+        let granter = authorize_helper(
+            &self.contexts,
             uid,
             user_access.project_id,
+            Permission::ManageAccess,
         )
         .await?;
 
+        check_role_helper(&self.contexts, &granter, &message.role).await?;
+
         let model = self
             .contexts
             .project_context
@@ -218,10 +225,11 @@ impl AccessControllerTrait for AccessController {
                 )
             })?;
 
-        check_editor_role_helper(
-            Arc::clone(&self.contexts.access_context),
+        authorize_helper(
+            &self.contexts,
             uid,
             user_access.project_id,
+            Permission::ManageAccess,
         )
         .await?;
 
@@ -251,32 +259,291 @@ impl AccessControllerTrait for AccessController {
             },
         }
     }
+
+    async fn create_team_access(
+        &self,
+        request: Request<CreateTeamAccessRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal("Could not get uid from request metadata"))?;
+
+        let granter = authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ManageAccess,
+        )
+        .await?;
+
+        check_role_helper(&self.contexts, &granter, &message.role).await?;
+
+        self.contexts
+            .team_context
+            .get_by_id(message.team_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No team found with given id"))?;
+
+        let team_accesses = self
+            .contexts
+            .team_access_context
+            .get_team_access_by_project_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        if team_accesses
+            .iter()
+            .any(|team_access| team_access.team_id == message.team_id)
+        {
+            return Err(Status::already_exists(
+                "The team already has access to the project",
+            ));
+        }
+
+        let team_access = team_access::Model {
+            id: Default::default(),
+            role: message.role,
+            project_id: message.project_id,
+            team_id: message.team_id,
+        };
+
+        match self.contexts.team_access_context.create(team_access).await {
+            Ok(_) => Ok(Response::new(())),
+            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+        }
+    }
+
+    async fn update_team_access(
+        &self,
+        request: Request<UpdateTeamAccessRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal("Could not get uid from request metadata"))?;
+
+        let team_access = self
+            .contexts
+            .team_access_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No access entity found for team"))?;
+
+        let granter = authorize_helper(
+            &self.contexts,
+            uid,
+            team_access.project_id,
+            Permission::ManageAccess,
+        )
+        .await?;
+
+        check_role_helper(&self.contexts, &granter, &message.role).await?;
+
+        match self
+            .contexts
+            .team_access_context
+            .update(team_access::Model {
+                role: message.role,
+                ..team_access
+            })
+            .await
+        {
+            Ok(_) => Ok(Response::new(())),
+            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+        }
+    }
+
+    async fn delete_team_access(
+        &self,
+        request: Request<DeleteTeamAccessRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal("Could not get uid from request metadata"))?;
+
+        let team_access = self
+            .contexts
+            .team_access_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No access entity found for team"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            team_access.project_id,
+            Permission::ManageAccess,
+        )
+        .await?;
+
+        match self.contexts.team_access_context.delete(message.id).await {
+            Ok(_) => Ok(Response::new(())),
+            Err(error) => match error {
+                sea_orm::DbErr::RecordNotFound(message) => {
+                    Err(Status::new(Code::NotFound, message))
+                }
+                _ => Err(Status::new(Code::Internal, error.to_string())),
+            },
+        }
+    }
 }
-async fn check_editor_role_helper(
-    access_context: Arc<dyn AccessContextTrait>,
-    user_id: i32,
+/// Returns the access of the user to the project, as long as one of their roles on it grants the permission.
+/// # Errors
+/// Errors if the user does not have access to the project, or none of their roles grant the permission.
+pub(crate) async fn authorize_helper(
+    contexts: &ContextCollection,
+    uid: i32,
     project_id: i32,
-) -> Result<(), Status> {
-    let access = access_context
-        .get_access_by_uid_and_project_id(user_id, project_id)
+    permission: Permission,
+) -> Result<EffectiveAccess, Status> {
+    let access = contexts
+        .access_context
+        .get_access_by_uid_and_project_id(uid, project_id)
         .await
         .map_err(|err| Status::new(Code::Internal, err.to_string()))?
         .ok_or_else(|| {
             Status::new(
                 Code::PermissionDenied,
-                "User does not have access to model".to_string(),
+                "User does not have access to project",
             )
         })?;
 
-    // Check if the requester has role 'Editor'
-    if access.role != "Editor" {
-        return Err(Status::new(
-            Code::PermissionDenied,
-            "User does not have 'Editor' role for this model",
-        ));
+    let roles: Vec<String> = access.roles().map(str::to_owned).collect();
+    for role in &roles {
+        let permissions = role_permissions_helper(contexts, role)
+            .await?
+            .ok_or_else(|| Status::internal(format!("role '{}' does not exist", role)))?;
+
+        if permissions.contains(&permission) {
+            return Ok(access);
+        }
+    }
+
+    let quoted: Vec<String> = roles.iter().map(|role| format!("'{}'", role)).collect();
+    let message = match quoted.as_slice() {
+        [role] => format!(
+            "Role {} does not have permission to {}",
+            role,
+            permission.description()
+        ),
+        _ => format!(
+            "Roles {} do not have permission to {}",
+            quoted.join(", "),
+            permission.description()
+        ),
+    };
+    Err(Status::new(Code::PermissionDenied, message))
+}
+
+/// Passes on the events of a project stream for as long as the user keeps the permission on the project.
+/// Access is checked again before every event, and the stream ends with the error once it is revoked.
+pub(crate) fn authorized_stream_helper<T: Send + 'static>(
+    contexts: ContextCollection,
+    uid: i32,
+    project_id: i32,
+    permission: Permission,
+    events: Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>,
+) -> Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>> {
+    Box::pin(stream::unfold(Some(events), move |events| {
+        let contexts = contexts.clone();
+        async move {
+            let mut events = events?;
+            let event = events.next().await?;
+
+            match authorize_helper(&contexts, uid, project_id, permission).await {
+                Ok(_) => Some((event, Some(events))),
+                Err(status) => Some((Err(status), None)),
+            }
+        }
+    }))
+}
+
+/// Returns the permissions of a role, whether it is one of the seeded roles or one created later,
+/// or [`None`] if there is no role with the name.
+pub(crate) async fn role_permissions_helper(
+    contexts: &ContextCollection,
+    name: &str,
+) -> Result<Option<Vec<Permission>>, Status> {
+    if let Ok(role) = Role::from_str(name) {
+        return Ok(Some(role.permissions().to_vec()));
     }
 
-    Ok(())
+    contexts
+        .role_context
+        .get_by_name(name.to_owned())
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .map(|role| {
+            permissions_from_json(&role.permissions).map_err(|err| {
+                Status::internal(format!(
+                    "failed to parse role permissions, internal error: {}",
+                    err
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Checks that a role can be granted by a user with the given access to the project.
+/// The role must exist, and the user must have every permission of the role through one of their roles,
+/// so no one can hand out more than they have themselves.
+pub(crate) async fn check_role_helper(
+    contexts: &ContextCollection,
+    granter: &EffectiveAccess,
+    name: &str,
+) -> Result<(), Status> {
+    let permissions = role_permissions_helper(contexts, name)
+        .await?
+        .ok_or_else(|| Status::invalid_argument(format!("'{}' is not a role", name)))?;
+
+    let mut granter_permissions = vec![];
+    for role in granter.roles() {
+        granter_permissions.extend(
+            role_permissions_helper(contexts, role)
+                .await?
+                .ok_or_else(|| Status::internal(format!("role '{}' does not exist", role)))?,
+        );
+    }
+
+    match permissions
+        .iter()
+        .find(|permission| !granter_permissions.contains(permission))
+    {
+        Some(permission) => Err(Status::new(
+            Code::PermissionDenied,
+            format!(
+                "Cannot grant role '{}', as it has permission to {} which you do not have",
+                name,
+                permission.description()
+            ),
+        )),
+        None => Ok(()),
+    }
 }
 
 async fn create_access_find_user_helper(
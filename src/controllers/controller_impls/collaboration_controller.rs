@@ -0,0 +1,352 @@
+use crate::api::auth::RequestExt;
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::project_edit_event::Change;
+use crate::api::server::protobuf::project_edit_operation::Change as Operation;
+use crate::api::server::protobuf::{ComponentInfo, ProjectEditEvent, ProjectEditOperation};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::{
+    authorize_helper, authorized_stream_helper,
+};
+use crate::controllers::controller_impls::component_controller::{
+    component_info, components_changed_helper, named_component_helper, write_error,
+};
+use crate::controllers::controller_impls::project_controller::{
+    advance_revision_helper, outdate_queries_helper, up_to_date_query_ids_helper,
+};
+use crate::controllers::controller_traits::{
+    CollaborationControllerTrait, ProjectEditOperationStream,
+};
+use crate::entities::component;
+use crate::services::service_collection::ServiceCollection;
+use crate::services::service_traits::ProjectEditStream;
+use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tonic::{Code, Request, Response, Status};
+
+pub struct CollaborationController {
+    contexts: ContextCollection,
+    services: ServiceCollection,
+    /// Makes the operations on a project apply one at a time, in the order of the revisions they produce.
+    project_locks: ProjectLocks,
+}
+
+type ProjectLocks = Arc<Mutex<HashMap<i32, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// The share of a collaborator in the lock of a project.
+/// The lock is dropped from the project locks once the last collaborator on the project leaves.
+struct ProjectLock {
+    project_id: i32,
+    lock: Arc<tokio::sync::Mutex<()>>,
+    project_locks: ProjectLocks,
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        if let Ok(mut project_locks) = self.project_locks.lock() {
+            // The project locks hold one reference, and this collaborator the other
+            if Arc::strong_count(&self.lock) == 2 {
+                project_locks.remove(&self.project_id);
+            }
+        }
+    }
+}
+
+impl CollaborationController {
+    pub fn new(contexts: ContextCollection, services: ServiceCollection) -> Self {
+        CollaborationController {
+            contexts,
+            services,
+            project_locks: Default::default(),
+        }
+    }
+
+    fn project_lock(&self, project_id: i32) -> Result<ProjectLock, Status> {
+        let mut project_locks = self
+            .project_locks
+            .lock()
+            .map_err(|_| Status::internal("failed to lock the project locks"))?;
+
+        Ok(ProjectLock {
+            project_id,
+            lock: project_locks.entry(project_id).or_default().clone(),
+            project_locks: self.project_locks.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl CollaborationControllerTrait for CollaborationController {
+    async fn collaborate_on_project(
+        &self,
+        request: Request<ProjectEditOperationStream>,
+    ) -> Result<Response<ProjectEditStream>, Status> {
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let mut operations = request.into_inner();
+
+        let first_operation = operations.next().await.ok_or_else(|| {
+            Status::invalid_argument("The first operation must name the project to collaborate on")
+        })??;
+
+        let project_id = first_operation.project_id;
+
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::not_found("No project found with given id"))?;
+
+        authorize_helper(&self.contexts, uid, project_id, Permission::EditComponents).await?;
+
+        if !project.collaborative {
+            return Err(Status::failed_precondition(
+                "The project must be made collaborative before editing it together",
+            ));
+        }
+
+        // Subscribe before applying anything, such that the author also sees its own first operation
+        let events = authorized_stream_helper(
+            self.contexts.clone(),
+            uid,
+            project_id,
+            Permission::ReadProject,
+            self.services.project_edit_service.subscribe(project_id),
+        );
+
+        let editor = ProjectEditor {
+            contexts: self.contexts.clone(),
+            services: self.services.clone(),
+            lock: self.project_lock(project_id)?,
+            uid,
+            project_id,
+        };
+
+        // Rejected operations are only reported back to their author
+        let (rejections, rejections_receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut operations = stream::iter(Some(Ok(first_operation))).chain(operations);
+
+            while let Some(Ok(operation)) = operations.next().await {
+                let client_op_id = operation.client_op_id.clone();
+
+                if let Err(status) = editor.apply(operation).await {
+                    let rejection = ProjectEditEvent {
+                        project_id,
+                        user_id: uid,
+                        client_op_id,
+                        error: Some(status.message().to_owned()),
+                        ..Default::default()
+                    };
+
+                    if rejections.send(Ok(rejection)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let rejections = stream::unfold(rejections_receiver, |mut receiver| async move {
+            receiver.recv().await.map(|rejection| (rejection, receiver))
+        });
+
+        Ok(Response::new(Box::pin(stream::select(events, rejections))))
+    }
+}
+
+/// Applies the operations of a single collaborator to a project.
+struct ProjectEditor {
+    contexts: ContextCollection,
+    services: ServiceCollection,
+    lock: ProjectLock,
+    uid: i32,
+    project_id: i32,
+}
+
+impl ProjectEditor {
+    /// Applies an operation and sends it to every collaborator on the project.
+    /// Operations without a change are ignored.
+    async fn apply(&self, operation: ProjectEditOperation) -> Result<(), Status> {
+        let Some(change) = operation.change else {
+            return Ok(());
+        };
+
+        if operation.project_id != self.project_id {
+            return Err(Status::invalid_argument(
+                "All operations on a stream must be on the same project",
+            ));
+        }
+
+        let _guard = self.lock.lock.lock().await;
+
+        // The access of the collaborator may have changed since it joined
+        authorize_helper(
+            &self.contexts,
+            self.uid,
+            self.project_id,
+            Permission::EditComponents,
+        )
+        .await?;
+
+        let up_to_date_query_ids =
+            up_to_date_query_ids_helper(&self.contexts, self.project_id).await?;
+
+        let revision = match &change {
+            Operation::CreateComponent(message) => message.revision,
+            Operation::UpdateComponent(message) => message.revision,
+            Operation::DeleteComponent(message) => message.revision,
+        };
+
+        let change = match change {
+            Operation::CreateComponent(message) => {
+                if message.project_id != self.project_id {
+                    return Err(Status::invalid_argument(
+                        "All operations on a stream must be on the same project",
+                    ));
+                }
+
+                let (name, component) = named_component_helper(
+                    &self.contexts,
+                    self.project_id,
+                    None,
+                    message.component,
+                )
+                .await?;
+
+                advance_revision_helper(&self.contexts, self.project_id, Some(revision)).await?;
+
+                let component = self
+                    .contexts
+                    .component_context
+                    .create(component::Model {
+                        id: Default::default(),
+                        project_id: self.project_id,
+                        name,
+                        component,
+                        updated_at: Default::default(),
+                    })
+                    .await
+                    .map_err(write_error)?;
+
+                outdate_queries_helper(&self.contexts, self.project_id, &[&component.name]).await?;
+
+                Change::ComponentCreated(to_component_info(component)?)
+            }
+            Operation::UpdateComponent(message) => {
+                let existing_component = self.get_component_by_id(message.id).await?;
+
+                let (name, component) = named_component_helper(
+                    &self.contexts,
+                    self.project_id,
+                    Some(&existing_component),
+                    message.component,
+                )
+                .await?;
+
+                advance_revision_helper(&self.contexts, self.project_id, Some(revision)).await?;
+
+                let component = self
+                    .contexts
+                    .component_context
+                    .update(component::Model {
+                        name,
+                        component,
+                        ..existing_component.clone()
+                    })
+                    .await
+                    .map_err(write_error)?;
+
+                if existing_component.name != component.name
+                    || existing_component.component != component.component
+                {
+                    outdate_queries_helper(
+                        &self.contexts,
+                        self.project_id,
+                        &[&existing_component.name, &component.name],
+                    )
+                    .await?;
+                }
+
+                Change::ComponentUpdated(to_component_info(component)?)
+            }
+            Operation::DeleteComponent(message) => {
+                let component = self.get_component_by_id(message.id).await?;
+
+                advance_revision_helper(&self.contexts, self.project_id, Some(revision)).await?;
+
+                self.contexts
+                    .component_context
+                    .delete(component.id)
+                    .await
+                    .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+                outdate_queries_helper(&self.contexts, self.project_id, &[&component.name]).await?;
+
+                Change::ComponentDeleted(component.id)
+            }
+        };
+
+        components_changed_helper(
+            &self.contexts,
+            &self.services,
+            self.uid,
+            self.project_id,
+            up_to_date_query_ids,
+        )
+        .await?;
+
+        // The operation moved the project from the revision it was made at to the next one,
+        // whatever other writes happened since
+        self.services
+            .project_edit_service
+            .publish(ProjectEditEvent {
+                project_id: self.project_id,
+                revision: revision + 1,
+                user_id: self.uid,
+                client_op_id: operation.client_op_id,
+                change: Some(change),
+                error: None,
+            });
+
+        Ok(())
+    }
+
+    /// Returns a component of the project being edited.
+    async fn get_component_by_id(&self, id: i32) -> Result<component::Model, Status> {
+        self.contexts
+            .component_context
+            .get_by_id(id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .filter(|component| component.project_id == self.project_id)
+            .ok_or_else(|| Status::new(Code::NotFound, "No component found with given id"))
+    }
+}
+
+fn to_component_info(component: component::Model) -> Result<ComponentInfo, Status> {
+    component_info(component).map_err(|err| {
+        Status::internal(format!(
+            "failed to parse component object, internal error: {}",
+            err
+        ))
+    })
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/collaboration_controller.rs"]
+mod collaboration_controller_tests;
@@ -0,0 +1,441 @@
+use crate::api::auth::RequestExt;
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::{
+    Comment, CommentThread, CreateCommentRequest, DeleteCommentRequest, ListCommentsRequest,
+    ListCommentsResponse, ReplyToCommentRequest, ResolveCommentRequest, UpdateCommentRequest,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::authorize_helper;
+use crate::controllers::controller_traits::CommentControllerTrait;
+use crate::entities::comment;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use tonic::{Code, Request, Response, Status};
+
+pub struct CommentController {
+    contexts: ContextCollection,
+}
+
+impl CommentController {
+    pub fn new(contexts: ContextCollection) -> Self {
+        CommentController { contexts }
+    }
+
+    async fn get_comment_by_id(&self, id: i32) -> Result<comment::Model, Status> {
+        self.contexts
+            .comment_context
+            .get_by_id(id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No comment found with given id"))
+    }
+
+    /// Returns the comment that starts the thread of the given comment.
+    async fn get_thread(&self, comment: comment::Model) -> Result<comment::Model, Status> {
+        match comment.parent_id {
+            Some(parent_id) => self.get_comment_by_id(parent_id).await,
+            None => Ok(comment),
+        }
+    }
+
+    /// Checks that a component or query a thread is anchored to belongs to the project.
+    async fn check_anchor(&self, message: &CreateCommentRequest) -> Result<(), Status> {
+        if message.component_id.is_some() && message.query_id.is_some() {
+            return Err(Status::invalid_argument(
+                "A thread can be anchored to either a component or a query",
+            ));
+        }
+
+        if message.location_id.is_some() && message.edge_id.is_some() {
+            return Err(Status::invalid_argument(
+                "A thread can be anchored to either a location or an edge",
+            ));
+        }
+
+        if (message.location_id.is_some() || message.edge_id.is_some())
+            && message.component_id.is_none()
+        {
+            return Err(Status::invalid_argument(
+                "A location or edge must be given with the component it is in",
+            ));
+        }
+
+        if let Some(component_id) = message.component_id {
+            self.contexts
+                .component_context
+                .get_by_id(component_id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+                .filter(|component| component.project_id == message.project_id)
+                .ok_or_else(|| Status::not_found("No component found with given id"))?;
+        }
+
+        if let Some(query_id) = message.query_id {
+            self.contexts
+                .query_context
+                .get_by_id(query_id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+                .filter(|query| query.project_id == message.project_id)
+                .ok_or_else(|| Status::not_found("No query found with given id"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the usernames of the authors of the given comments.
+    async fn get_usernames(
+        &self,
+        comments: &[comment::Model],
+    ) -> Result<HashMap<i32, String>, Status> {
+        let mut user_ids: Vec<i32> = comments
+            .iter()
+            .filter_map(|comment| comment.user_id)
+            .collect();
+        user_ids.sort_unstable();
+        user_ids.dedup();
+
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        Ok(self
+            .contexts
+            .user_context
+            .get_by_ids(user_ids)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(|user| (user.id, user.username))
+            .collect())
+    }
+
+    /// Converts a single comment, looking up the username of its author.
+    async fn to_comment(&self, comment: comment::Model) -> Result<Comment, Status> {
+        let usernames = self.get_usernames(std::slice::from_ref(&comment)).await?;
+        Ok(to_comment(comment, &usernames))
+    }
+}
+
+#[async_trait]
+impl CommentControllerTrait for CommentController {
+    async fn create_comment(
+        &self,
+        request: Request<CreateCommentRequest>,
+    ) -> Result<Response<Comment>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let text = comment_text(message.text.clone())?;
+
+        authorize_helper(&self.contexts, uid, message.project_id, Permission::Comment).await?;
+        self.check_anchor(&message).await?;
+
+        let comment = self
+            .contexts
+            .comment_context
+            .create(comment::Model {
+                id: Default::default(),
+                project_id: message.project_id,
+                parent_id: None,
+                user_id: Some(uid),
+                component_id: message.component_id,
+                location_id: message.location_id,
+                edge_id: message.edge_id,
+                query_id: message.query_id,
+                text,
+                resolved: false,
+                created_at: Default::default(),
+                edited_at: None,
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(self.to_comment(comment).await?))
+    }
+
+    async fn reply_to_comment(
+        &self,
+        request: Request<ReplyToCommentRequest>,
+    ) -> Result<Response<Comment>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let text = comment_text(message.text)?;
+
+        let comment = self.get_comment_by_id(message.comment_id).await?;
+
+        authorize_helper(&self.contexts, uid, comment.project_id, Permission::Comment).await?;
+
+        // Threads are flat, so replies to a reply go to the thread it is in
+        let thread = self.get_thread(comment).await?;
+
+        let reply = self
+            .contexts
+            .comment_context
+            .create(comment::Model {
+                id: Default::default(),
+                project_id: thread.project_id,
+                parent_id: Some(thread.id),
+                user_id: Some(uid),
+                component_id: None,
+                location_id: None,
+                edge_id: None,
+                query_id: None,
+                text,
+                resolved: false,
+                created_at: Default::default(),
+                edited_at: None,
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(self.to_comment(reply).await?))
+    }
+
+    async fn resolve_comment(
+        &self,
+        request: Request<ResolveCommentRequest>,
+    ) -> Result<Response<Comment>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let comment = self.get_comment_by_id(message.comment_id).await?;
+
+        authorize_helper(&self.contexts, uid, comment.project_id, Permission::Comment).await?;
+
+        let thread = self.get_thread(comment).await?;
+
+        let thread = self
+            .contexts
+            .comment_context
+            .update(comment::Model {
+                resolved: message.resolved,
+                ..thread
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(self.to_comment(thread).await?))
+    }
+
+    async fn update_comment(
+        &self,
+        request: Request<UpdateCommentRequest>,
+    ) -> Result<Response<Comment>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let text = comment_text(message.text)?;
+
+        let comment = self.get_comment_by_id(message.id).await?;
+
+        if comment.user_id != Some(uid) {
+            return Err(Status::permission_denied(
+                "You can only edit your own comments",
+            ));
+        }
+
+        authorize_helper(&self.contexts, uid, comment.project_id, Permission::Comment).await?;
+
+        let comment = self
+            .contexts
+            .comment_context
+            .update(comment::Model {
+                text,
+                edited_at: Some(Utc::now().naive_utc()),
+                ..comment
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(self.to_comment(comment).await?))
+    }
+
+    async fn delete_comment(
+        &self,
+        request: Request<DeleteCommentRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let comment = self.get_comment_by_id(message.id).await?;
+
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(comment.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::not_found("No project found with given id"))?;
+
+        // Owners can clean up any comment, authors only their own while they can still comment
+        if project.owner_id != uid {
+            if comment.user_id != Some(uid) {
+                return Err(Status::permission_denied(
+                    "You can only delete your own comments",
+                ));
+            }
+            authorize_helper(&self.contexts, uid, comment.project_id, Permission::Comment).await?;
+        }
+
+        self.contexts
+            .comment_context
+            .delete(comment.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        let comments = self
+            .contexts
+            .comment_context
+            .get_all_by_project_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        let usernames = self.get_usernames(&comments).await?;
+
+        let (threads, replies): (Vec<_>, Vec<_>) = comments
+            .into_iter()
+            .partition(|comment| comment.parent_id.is_none());
+
+        let mut replies_by_thread: HashMap<i32, Vec<Comment>> = HashMap::new();
+        for reply in replies {
+            if let Some(parent_id) = reply.parent_id {
+                replies_by_thread
+                    .entry(parent_id)
+                    .or_default()
+                    .push(to_comment(reply, &usernames));
+            }
+        }
+
+        let threads = threads
+            .into_iter()
+            .filter(|thread| message.include_resolved || !thread.resolved)
+            .map(|thread| CommentThread {
+                replies: replies_by_thread.remove(&thread.id).unwrap_or_default(),
+                comment: Some(to_comment(thread, &usernames)),
+            })
+            .collect();
+
+        Ok(Response::new(ListCommentsResponse { threads }))
+    }
+}
+
+/// Returns the text of a comment, as long as there is any.
+fn comment_text(text: String) -> Result<String, Status> {
+    if text.trim().is_empty() {
+        return Err(Status::invalid_argument("A comment cannot be empty"));
+    }
+    Ok(text)
+}
+
+fn to_comment(comment: comment::Model, usernames: &HashMap<i32, String>) -> Comment {
+    Comment {
+        id: comment.id,
+        project_id: comment.project_id,
+        parent_id: comment.parent_id,
+        user_id: comment.user_id,
+        username: comment
+            .user_id
+            .and_then(|user_id| usernames.get(&user_id).cloned()),
+        component_id: comment.component_id,
+        location_id: comment.location_id,
+        edge_id: comment.edge_id,
+        query_id: comment.query_id,
+        text: comment.text,
+        resolved: comment.resolved,
+        created_at: comment.created_at.and_utc().timestamp(),
+        edited_at: comment
+            .edited_at
+            .map(|edited_at| edited_at.and_utc().timestamp()),
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/comment_controller.rs"]
+mod comment_controller_tests;
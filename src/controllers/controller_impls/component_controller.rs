@@ -0,0 +1,388 @@
+use crate::api::auth::RequestExt;
+use crate::api::components::{component_name, placeholder_component_name};
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::{
+    Component, ComponentInfo, CreateComponentRequest, CreateComponentResponse,
+    DeleteComponentRequest, GetComponentRequest, GetComponentResponse, UpdateComponentRequest,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::authorize_helper;
+use crate::controllers::controller_impls::project_controller::{
+    advance_revision_helper, check_lock_helper, create_project_version_helper,
+    outdate_queries_helper, publish_outdated_queries_helper, up_to_date_query_ids_helper,
+};
+use crate::controllers::controller_traits::ComponentControllerTrait;
+use crate::entities::component;
+use crate::services::service_collection::ServiceCollection;
+use async_trait::async_trait;
+use sea_orm::{DbErr, SqlErr};
+use std::collections::HashSet;
+use tonic::{Code, Request, Response, Status};
+
+pub struct ComponentController {
+    contexts: ContextCollection,
+    services: ServiceCollection,
+}
+
+impl ComponentController {
+    pub fn new(contexts: ContextCollection, services: ServiceCollection) -> Self {
+        ComponentController { contexts, services }
+    }
+
+    async fn get_component_by_id(&self, id: i32) -> Result<component::Model, Status> {
+        self.contexts
+            .component_context
+            .get_by_id(id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No component found with given id"))
+    }
+
+    /// Checks that the session of the request holds the edit lock on the project, unless the project is collaborative.
+    async fn check_lock<T>(&self, request: &Request<T>, project_id: i32) -> Result<(), Status> {
+        let access_token = request
+            .token_string()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get token from request metadata",
+            ))?;
+
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::not_found("No project found with given id"))?;
+
+        check_lock_helper(&self.contexts, &project, access_token).await
+    }
+}
+
+#[async_trait]
+impl ComponentControllerTrait for ComponentController {
+    async fn create_component(
+        &self,
+        request: Request<CreateComponentRequest>,
+    ) -> Result<Response<CreateComponentResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::EditComponents,
+        )
+        .await?;
+
+        self.check_lock(&request, message.project_id).await?;
+
+        let up_to_date_query_ids =
+            up_to_date_query_ids_helper(&self.contexts, message.project_id).await?;
+
+        let (name, component) =
+            named_component_helper(&self.contexts, message.project_id, None, message.component)
+                .await?;
+
+        // Reject the change if someone else changed the project since the client last saw it
+        advance_revision_helper(&self.contexts, message.project_id, Some(message.revision)).await?;
+
+        let component = self
+            .contexts
+            .component_context
+            .create(component::Model {
+                id: Default::default(),
+                project_id: message.project_id,
+                name,
+                component,
+                updated_at: Default::default(),
+            })
+            .await
+            .map_err(write_error)?;
+
+        outdate_queries_helper(&self.contexts, component.project_id, &[&component.name]).await?;
+
+        components_changed_helper(
+            &self.contexts,
+            &self.services,
+            uid,
+            component.project_id,
+            up_to_date_query_ids,
+        )
+        .await?;
+
+        Ok(Response::new(CreateComponentResponse { id: component.id }))
+    }
+
+    async fn get_component(
+        &self,
+        request: Request<GetComponentRequest>,
+    ) -> Result<Response<GetComponentResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let component = self.get_component_by_id(message.id).await?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            component.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        Ok(Response::new(GetComponentResponse {
+            component: Some(component_info(component).map_err(|err| {
+                Status::internal(format!(
+                    "failed to parse component object, internal error: {}",
+                    err
+                ))
+            })?),
+        }))
+    }
+
+    async fn update_component(
+        &self,
+        request: Request<UpdateComponentRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let existing_component = self.get_component_by_id(message.id).await?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            existing_component.project_id,
+            Permission::EditComponents,
+        )
+        .await?;
+
+        self.check_lock(&request, existing_component.project_id)
+            .await?;
+
+        let up_to_date_query_ids =
+            up_to_date_query_ids_helper(&self.contexts, existing_component.project_id).await?;
+
+        let (name, component) = named_component_helper(
+            &self.contexts,
+            existing_component.project_id,
+            Some(&existing_component),
+            message.component,
+        )
+        .await?;
+
+        // Reject the change if someone else changed the project since the client last saw it
+        advance_revision_helper(
+            &self.contexts,
+            existing_component.project_id,
+            Some(message.revision),
+        )
+        .await?;
+
+        let component = self
+            .contexts
+            .component_context
+            .update(component::Model {
+                name,
+                component,
+                ..existing_component.clone()
+            })
+            .await
+            .map_err(write_error)?;
+
+        if existing_component.name != component.name
+            || existing_component.component != component.component
+        {
+            outdate_queries_helper(
+                &self.contexts,
+                component.project_id,
+                &[&existing_component.name, &component.name],
+            )
+            .await?;
+        }
+
+        components_changed_helper(
+            &self.contexts,
+            &self.services,
+            uid,
+            component.project_id,
+            up_to_date_query_ids,
+        )
+        .await?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn delete_component(
+        &self,
+        request: Request<DeleteComponentRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let component = self.get_component_by_id(message.id).await?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            component.project_id,
+            Permission::EditComponents,
+        )
+        .await?;
+
+        self.check_lock(&request, component.project_id).await?;
+
+        let up_to_date_query_ids =
+            up_to_date_query_ids_helper(&self.contexts, component.project_id).await?;
+
+        // Reject the change if someone else changed the project since the client last saw it
+        advance_revision_helper(&self.contexts, component.project_id, Some(message.revision))
+            .await?;
+
+        self.contexts
+            .component_context
+            .delete(component.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        outdate_queries_helper(&self.contexts, component.project_id, &[&component.name]).await?;
+
+        components_changed_helper(
+            &self.contexts,
+            &self.services,
+            uid,
+            component.project_id,
+            up_to_date_query_ids,
+        )
+        .await?;
+
+        Ok(Response::new(()))
+    }
+}
+
+/// Records a change of the components of a project in its history,
+/// and tells the subscribers of the project which queries it made outdated.
+/// The project must already have been moved to its next revision.
+pub(crate) async fn components_changed_helper(
+    contexts: &ContextCollection,
+    services: &ServiceCollection,
+    uid: i32,
+    project_id: i32,
+    up_to_date_query_ids: Vec<i32>,
+) -> Result<(), Status> {
+    create_project_version_helper(contexts, project_id, uid).await?;
+    publish_outdated_queries_helper(contexts, services, project_id, up_to_date_query_ids).await
+}
+
+/// Returns the name a component is stored under along with its JSON representation.
+/// A component without a name of its own keeps the name the existing component is stored under,
+/// and a new one is given a placeholder name.
+pub(crate) async fn named_component_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+    existing_component: Option<&component::Model>,
+    component: Option<Component>,
+) -> Result<(String, sea_orm::prelude::Json), Status> {
+    let component = component.ok_or_else(|| Status::invalid_argument("No component provided"))?;
+
+    let name = match (component_name(&component), existing_component) {
+        (Some(name), _) => name,
+        (None, Some(existing_component)) => existing_component.name.clone(),
+        (None, None) => {
+            let names: HashSet<String> = contexts
+                .component_context
+                .get_all_by_project_id(project_id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+                .into_iter()
+                .map(|component| component.name)
+                .collect();
+            placeholder_component_name(|name| names.contains(name))
+        }
+    };
+
+    let component = serde_json::to_value(component).map_err(|err| {
+        Status::internal(format!(
+            "failed to parse component object, internal error: {}",
+            err
+        ))
+    })?;
+
+    Ok((name, component))
+}
+
+pub(crate) fn write_error(err: DbErr) -> Status {
+    match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            Status::already_exists("A component with that name already exists in the project")
+        }
+        _ => Status::new(Code::Internal, err.to_string()),
+    }
+}
+
+pub(crate) fn component_info(
+    component: component::Model,
+) -> Result<ComponentInfo, serde_json::Error> {
+    Ok(ComponentInfo {
+        id: component.id,
+        project_id: component.project_id,
+        name: component.name,
+        component: Some(serde_json::from_value(component.component)?),
+        updated_at: component.updated_at.and_utc().timestamp(),
+    })
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/component_controller.rs"]
+mod component_controller_tests;
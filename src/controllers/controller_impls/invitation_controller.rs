@@ -0,0 +1,317 @@
+use crate::api::auth::{RequestExt, Token, TokenType};
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::{
+    AcceptInvitationRequest, AcceptInvitationResponse, CreateInvitationRequest,
+    CreateInvitationResponse, InvitationInfo, ListInvitationsRequest, ListInvitationsResponse,
+    RevokeInvitationRequest,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::{
+    authorize_helper, check_role_helper,
+};
+use crate::controllers::controller_traits::InvitationControllerTrait;
+use crate::entities::{access, invitation};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tonic::{Code, Request, Response, Status};
+
+/// The longest an invitation can be valid for.
+const MAX_LIFETIME_SECS: u32 = 30 * 24 * 60 * 60;
+
+pub struct InvitationController {
+    contexts: ContextCollection,
+}
+
+impl InvitationController {
+    pub fn new(contexts: ContextCollection) -> Self {
+        InvitationController { contexts }
+    }
+
+    async fn get_invitation_by_id(&self, id: i32) -> Result<invitation::Model, Status> {
+        self.contexts
+            .invitation_context
+            .get_by_id(id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No invitation found with given id"))
+    }
+}
+
+#[async_trait]
+impl InvitationControllerTrait for InvitationController {
+    async fn create_invitation(
+        &self,
+        request: Request<CreateInvitationRequest>,
+    ) -> Result<Response<CreateInvitationResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let max_uses = message.max_uses.unwrap_or(1);
+        if max_uses < 1 {
+            return Err(Status::invalid_argument(
+                "An invitation must allow at least 1 use",
+            ));
+        }
+
+        let lifetime = match message.expires_in_secs {
+            Some(0) => {
+                return Err(Status::invalid_argument(
+                    "An invitation must be valid for at least 1 second",
+                ))
+            }
+            Some(secs) if secs > MAX_LIFETIME_SECS => {
+                return Err(Status::invalid_argument(format!(
+                    "An invitation can be valid for at most {} seconds",
+                    MAX_LIFETIME_SECS
+                )))
+            }
+            Some(secs) => Duration::seconds(secs.into()),
+            None => TokenType::InvitationToken.duration(),
+        };
+
+        let granter = authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ManageAccess,
+        )
+        .await?;
+
+        check_role_helper(&self.contexts, &granter, &message.role).await?;
+
+        let expires_at = Utc::now() + lifetime;
+
+        let invitation = self
+            .contexts
+            .invitation_context
+            .create(invitation::Model {
+                id: Default::default(),
+                project_id: message.project_id,
+                role: message.role,
+                user_id: Some(uid),
+                max_uses,
+                uses: Default::default(),
+                revoked: false,
+                expires_at: expires_at.naive_utc(),
+                created_at: Default::default(),
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        // The token only names the invitation, so revoking or using it up takes effect straight away
+        let token = Token::with_expiration(
+            TokenType::InvitationToken,
+            &invitation.id.to_string(),
+            expires_at,
+        )
+        .map_err(|err| Status::internal(format!("failed to sign invitation, {}", err)))?;
+
+        Ok(Response::new(CreateInvitationResponse {
+            invitation: Some(invitation_info(invitation)),
+            token: token.to_string(),
+        }))
+    }
+
+    async fn list_invitations(
+        &self,
+        request: Request<ListInvitationsRequest>,
+    ) -> Result<Response<ListInvitationsResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ManageAccess,
+        )
+        .await?;
+
+        let invitations = self
+            .contexts
+            .invitation_context
+            .get_all_by_project_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(invitation_info)
+            .collect();
+
+        Ok(Response::new(ListInvitationsResponse { invitations }))
+    }
+
+    async fn revoke_invitation(
+        &self,
+        request: Request<RevokeInvitationRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let invitation = self.get_invitation_by_id(message.id).await?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            invitation.project_id,
+            Permission::ManageAccess,
+        )
+        .await?;
+
+        if invitation.revoked {
+            return Ok(Response::new(()));
+        }
+
+        self.contexts
+            .invitation_context
+            .update(invitation::Model {
+                revoked: true,
+                ..invitation
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn accept_invitation(
+        &self,
+        request: Request<AcceptInvitationRequest>,
+    ) -> Result<Response<AcceptInvitationResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let invitation_id: i32 = Token::from_str(TokenType::InvitationToken, &message.token)
+            .validate()
+            .ok()
+            .and_then(|token_data| token_data.claims.sub.parse().ok())
+            .ok_or_else(|| Status::invalid_argument("The invitation is invalid or has expired"))?;
+
+        let invitation = self.get_invitation_by_id(invitation_id).await?;
+
+        if invitation.revoked {
+            return Err(Status::failed_precondition(
+                "The invitation has been revoked",
+            ));
+        }
+        if invitation.expires_at <= Utc::now().naive_utc() {
+            return Err(Status::failed_precondition("The invitation has expired"));
+        }
+        if invitation.uses >= invitation.max_uses {
+            return Err(Status::failed_precondition(
+                "The invitation has been used up",
+            ));
+        }
+
+        // Access through a team is not granted to the user themself, so it does not stop them from joining
+        let existing_access = self
+            .contexts
+            .access_context
+            .get_access_by_uid_and_project_id(uid, invitation.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        if existing_access.is_some_and(|access| access.direct.is_some()) {
+            return Err(Status::already_exists(
+                "User already has access to the project",
+            ));
+        }
+
+        // The access is granted before the use is counted, so a failure in between cannot use up the invitation
+        let access = self
+            .contexts
+            .access_context
+            .create(access::Model {
+                id: Default::default(),
+                role: invitation.role.clone(),
+                project_id: invitation.project_id,
+                user_id: uid,
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        let redeemed = self
+            .contexts
+            .invitation_context
+            .redeem(invitation.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()));
+        if !matches!(redeemed, Ok(true)) {
+            // The invitation could not be used, so the access it granted is taken back
+            self.contexts
+                .access_context
+                .delete(access.id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+            redeemed?;
+            return Err(Status::failed_precondition(
+                "The invitation can no longer be used",
+            ));
+        }
+
+        Ok(Response::new(AcceptInvitationResponse {
+            project_id: invitation.project_id,
+            role: invitation.role,
+        }))
+    }
+}
+
+fn invitation_info(invitation: invitation::Model) -> InvitationInfo {
+    InvitationInfo {
+        id: invitation.id,
+        project_id: invitation.project_id,
+        role: invitation.role,
+        user_id: invitation.user_id,
+        max_uses: invitation.max_uses,
+        uses: invitation.uses,
+        revoked: invitation.revoked,
+        expires_at: invitation.expires_at.and_utc().timestamp(),
+        created_at: invitation.created_at.and_utc().timestamp(),
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/invitation_controller.rs"]
+mod invitation_controller_tests;
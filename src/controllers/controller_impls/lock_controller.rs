@@ -0,0 +1,306 @@
+use crate::api::auth::{RequestExt, TokenType};
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::{
+    AcquireLockRequest, ForceReleaseRequest, HeartbeatRequest, LockInfo, ReleaseLockRequest,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::authorize_helper;
+use crate::controllers::controller_traits::LockControllerTrait;
+use crate::entities::session;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use tonic::{Code, Request, Response, Status};
+
+/// The longest lease a client can ask for.
+const MAX_LEASE_SECS: u32 = 60 * 60;
+
+pub struct LockController {
+    contexts: ContextCollection,
+    lease_secs: u32,
+}
+
+impl LockController {
+    /// Creates a controller that hands out leases of `lease_secs` seconds when the client does not ask for a length.
+    pub fn new(contexts: ContextCollection, lease_secs: u32) -> Self {
+        LockController {
+            contexts,
+            lease_secs,
+        }
+    }
+
+    /// Returns the session of the access token of the request.
+    async fn get_session<T>(&self, request: &Request<T>) -> Result<session::Model, Status> {
+        let access_token = request
+            .token_string()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get token from request metadata",
+            ))?;
+
+        self.contexts
+            .session_context
+            .get_by_token(TokenType::AccessToken, access_token)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::unauthenticated("No session found with given access token"))
+    }
+
+    /// Returns when a lease starting now ends.
+    fn lease_end(&self, lease_secs: Option<u32>) -> Result<NaiveDateTime, Status> {
+        let lease_secs = match lease_secs {
+            Some(0) => {
+                return Err(Status::invalid_argument(
+                    "The lease must be at least 1 second",
+                ))
+            }
+            Some(lease_secs) if lease_secs > MAX_LEASE_SECS => {
+                return Err(Status::invalid_argument(format!(
+                    "The lease can be at most {} seconds",
+                    MAX_LEASE_SECS
+                )))
+            }
+            Some(lease_secs) => lease_secs,
+            None => self.lease_secs,
+        };
+
+        Ok(Utc::now().naive_utc() + Duration::seconds(lease_secs.into()))
+    }
+
+    /// Returns who holds the lock on a project and until when.
+    async fn lock_info(&self, project_id: i32) -> Result<LockInfo, Status> {
+        let in_use = self
+            .contexts
+            .in_use_context
+            .get_by_id(project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .filter(|in_use| in_use.expires_at > Utc::now().naive_utc());
+
+        let in_use = match in_use {
+            Some(in_use) => in_use,
+            None => {
+                return Ok(LockInfo {
+                    project_id,
+                    locked: false,
+                    user_id: None,
+                    username: None,
+                    expires_at: None,
+                })
+            }
+        };
+
+        let session = self
+            .contexts
+            .session_context
+            .get_by_id(in_use.session_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::internal("No session found for the lock"))?;
+
+        let user = self
+            .contexts
+            .user_context
+            .get_by_id(session.user_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::internal("No user found for the lock"))?;
+
+        Ok(LockInfo {
+            project_id,
+            locked: true,
+            user_id: Some(user.id),
+            username: Some(user.username),
+            expires_at: Some(in_use.expires_at.and_utc().timestamp()),
+        })
+    }
+
+    /// Returns an error telling who holds the lock on a project, or that nobody does.
+    async fn not_holder_error(&self, project_id: i32) -> Status {
+        match self.lock_info(project_id).await {
+            Ok(LockInfo {
+                locked: true,
+                username: Some(username),
+                expires_at: Some(expires_at),
+                ..
+            }) => Status::failed_precondition(format!(
+                "The project is locked by {} until {}",
+                username,
+                DateTime::<Utc>::from_timestamp(expires_at, 0)
+                    .map(|expires_at| expires_at.to_rfc3339())
+                    .unwrap_or_default()
+            )),
+            Ok(_) => Status::failed_precondition("You do not hold the lock on the project"),
+            Err(status) => status,
+        }
+    }
+}
+
+#[async_trait]
+impl LockControllerTrait for LockController {
+    async fn acquire_lock(
+        &self,
+        request: Request<AcquireLockRequest>,
+    ) -> Result<Response<LockInfo>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::EditProject,
+        )
+        .await?;
+
+        let expires_at = self.lease_end(message.lease_secs)?;
+        let session = self.get_session(&request).await?;
+
+        let acquired = self
+            .contexts
+            .in_use_context
+            .acquire(message.project_id, session.id, expires_at)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        if !acquired {
+            return Err(self.not_holder_error(message.project_id).await);
+        }
+
+        Ok(Response::new(self.lock_info(message.project_id).await?))
+    }
+
+    async fn heartbeat(
+        &self,
+        request: Request<HeartbeatRequest>,
+    ) -> Result<Response<LockInfo>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let expires_at = self.lease_end(message.lease_secs)?;
+        let session = self.get_session(&request).await?;
+
+        // The holder may have lost access since the lock was acquired, in which case the lock is given up
+        if let Err(status) = authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::EditProject,
+        )
+        .await
+        {
+            self.contexts
+                .in_use_context
+                .release(message.project_id, Some(session.id))
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+            return Err(status);
+        }
+
+        let renewed = self
+            .contexts
+            .in_use_context
+            .renew(message.project_id, session.id, expires_at)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        if !renewed {
+            return Err(self.not_holder_error(message.project_id).await);
+        }
+
+        Ok(Response::new(self.lock_info(message.project_id).await?))
+    }
+
+    async fn release_lock(
+        &self,
+        request: Request<ReleaseLockRequest>,
+    ) -> Result<Response<LockInfo>, Status> {
+        let message = request.get_ref();
+
+        let session = self.get_session(&request).await?;
+
+        let released = self
+            .contexts
+            .in_use_context
+            .release(message.project_id, Some(session.id))
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        if !released {
+            return Err(self.not_holder_error(message.project_id).await);
+        }
+
+        Ok(Response::new(self.lock_info(message.project_id).await?))
+    }
+
+    async fn force_release(
+        &self,
+        request: Request<ForceReleaseRequest>,
+    ) -> Result<Response<LockInfo>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::not_found("No project found with given id"))?;
+
+        if project.owner_id != uid {
+            return Err(Status::permission_denied(
+                "Only the owner of the project can force the lock to be released",
+            ));
+        }
+
+        self.contexts
+            .in_use_context
+            .release(project.id, None)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(self.lock_info(project.id).await?))
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/lock_controller.rs"]
+mod lock_controller_tests;
@@ -1,13 +1,31 @@
 pub mod access_controller;
+pub mod collaboration_controller;
+pub mod comment_controller;
+pub mod component_controller;
+pub mod invitation_controller;
+pub mod lock_controller;
 pub mod project_controller;
 pub mod query_controller;
 pub mod reveaal_controller;
+pub mod role_controller;
 pub mod session_controller;
+pub mod share_link_controller;
+pub mod simulation_controller;
+pub mod team_controller;
 pub mod user_controller;
 
 pub use access_controller::AccessController;
+pub use collaboration_controller::CollaborationController;
+pub use comment_controller::CommentController;
+pub use component_controller::ComponentController;
+pub use invitation_controller::InvitationController;
+pub use lock_controller::LockController;
 pub use project_controller::ProjectController;
 pub use query_controller::QueryController;
 pub use reveaal_controller::ReveaalController;
+pub use role_controller::RoleController;
 pub use session_controller::SessionController;
+pub use share_link_controller::ShareLinkController;
+pub use simulation_controller::SimulationController;
+pub use team_controller::TeamController;
 pub use user_controller::UserController;
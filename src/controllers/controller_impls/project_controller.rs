@@ -1,25 +1,35 @@
 use crate::api::auth::{RequestExt, TokenType};
+use crate::api::components::{
+    component_name, placeholder_component_name, references_any_component,
+};
+use crate::api::query_settings::update_timeout;
+use crate::api::roles::{Permission, Role};
+use crate::api::server::protobuf::query_event::Kind;
 use crate::api::server::protobuf::{
-    CreateProjectRequest, CreateProjectResponse, DeleteProjectRequest, GetProjectRequest,
-    GetProjectResponse, ListProjectsInfoResponse, Project, Query, UpdateProjectRequest,
+    Component, ComponentsInfo, CreateProjectRequest, CreateProjectResponse, DeleteProjectRequest,
+    GetProjectRequest, GetProjectResponse, GetProjectVersionRequest, GetProjectVersionResponse,
+    ListProjectVersionsRequest, ListProjectVersionsResponse, ListProjectsInfoResponse, Project,
+    ProjectVersionInfo, Query, QueryEvent, RestoreProjectVersionRequest, UpdateProjectRequest,
 };
 use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::authorize_helper;
 use crate::controllers::controller_traits::ProjectControllerTrait;
-use crate::entities::{access, in_use, project};
+use crate::entities::{access, component, in_use, project, project_version};
+use crate::services::service_collection::ServiceCollection;
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
+use chrono::Utc;
 use sea_orm::SqlErr;
+use std::collections::{HashMap, HashSet};
 use tonic::{Code, Request, Response, Status};
 
-const IN_USE_DURATION_MINUTES: i64 = 10;
-
 pub struct ProjectController {
     contexts: ContextCollection,
+    services: ServiceCollection,
 }
 
 impl ProjectController {
-    pub fn new(contexts: ContextCollection) -> Self {
-        ProjectController { contexts }
+    pub fn new(contexts: ContextCollection, services: ServiceCollection) -> Self {
+        ProjectController { contexts, services }
     }
 }
 
@@ -43,119 +53,11 @@ impl ProjectControllerTrait for ProjectController {
             })?
             .ok_or(Status::internal("Could not get uid from request metadata"))?;
 
-        let access = self
-            .contexts
-            .access_context
-            .get_access_by_uid_and_project_id(uid, project_id)
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-            .ok_or_else(|| {
-                Status::new(
-                    Code::PermissionDenied,
-                    "User does not have access to project",
-                )
-            })?;
-
-        let project = self
-            .contexts
-            .project_context
-            .get_by_id(project_id)
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-            .ok_or_else(|| Status::new(Code::Internal, "Model not found"))?;
+        authorize_helper(&self.contexts, uid, project_id, Permission::ReadProject).await?;
 
-        let project = Project {
-            id: project.id,
-            name: project.name,
-            components_info: serde_json::from_value(project.components_info).map_err(|err| {
-                Status::internal(format!(
-                    "failed to parse components info object, internal error: {}",
-                    err
-                ))
-            })?,
-            owner_id: project.owner_id,
-        };
-
-        let mut in_use_bool = true;
-        match self.contexts.in_use_context.get_by_id(project_id).await {
-            Ok(Some(in_use)) => {
-                // If project is not in use and user is an Editor, update the in use with the users session.
-                if in_use.latest_activity
-                    <= (Utc::now().naive_utc() - Duration::minutes(IN_USE_DURATION_MINUTES))
-                {
-                    in_use_bool = false;
-
-                    if access.role == "Editor" {
-                        let session = self
-                            .contexts
-                            .session_context
-                            .get_by_token(
-                                TokenType::AccessToken,
-                                request
-                                    .token_string()
-                                    .map_err(|err| Status::internal(format!("could not stringify user id in request metadata, internal error {}",err)))?
-                                    .ok_or(Status::invalid_argument("failed to get token from request metadata"))?,
-                            )
-                            .await
-                            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-                            .ok_or_else(|| {
-                                Status::new(
-                                    Code::Unauthenticated,
-                                    "No session found with given access token",
-                                )
-                            })?;
-
-                        let in_use = in_use::Model {
-                            project_id: in_use.project_id,
-                            session_id: session.id,
-                            latest_activity: Utc::now().naive_utc(),
-                        };
-
-                        self.contexts
-                            .in_use_context
-                            .update(in_use)
-                            .await
-                            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
-                    }
-                }
-            }
-            Ok(None) => return Err(Status::new(Code::Internal, "No in use found for project")),
-            Err(err) => return Err(Status::new(Code::Internal, err.to_string())),
-        }
-
-        let queries = self
-            .contexts
-            .query_context
-            .get_all_by_project_id(project_id)
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
-
-        let queries = queries
-            .into_iter()
-            .map(|query| {
-                let result = serde_json::from_value(query.result.unwrap_or_else(|| "".into()))?;
-
-                Ok(Query {
-                    id: query.id,
-                    project_id: query.project_id,
-                    query: query.string,
-                    result,
-                    outdated: query.outdated,
-                })
-            })
-            .collect::<Result<Vec<Query>, serde_json::Error>>()
-            .map_err(|err| {
-                Status::internal(format!(
-                    "failed to parse json result, inner error:  {}",
-                    err
-                ))
-            })?;
-
-        Ok(Response::new(GetProjectResponse {
-            project: Some(project),
-            queries,
-            in_use: in_use_bool,
-        }))
+        Ok(Response::new(
+            get_project_helper(&self.contexts, project_id).await?,
+        ))
     }
 
     async fn create_project(
@@ -173,21 +75,19 @@ impl ProjectControllerTrait for ProjectController {
             })?
             .ok_or(Status::internal("Could not get uid from request metadata"))?;
 
-        let components_info = match message.clone().components_info {
-            Some(components_info) => serde_json::to_value(components_info).map_err(|err| {
-                Status::internal(format!(
-                    "failed to parse components info object, internal error: {}",
-                    err
-                ))
-            })?,
+        let components = match message.clone().components_info {
+            Some(components_info) => named_components(components_info)?,
             None => return Err(Status::invalid_argument("No components info provided")),
         };
 
         let mut project = project::Model {
             id: Default::default(),
             name: message.clone().name,
-            components_info,
             owner_id: uid,
+            settings: None,
+            timeout_secs: None,
+            revision: Default::default(),
+            collaborative: false,
         };
 
         project = match self.contexts.project_context.create(project).await {
@@ -217,7 +117,7 @@ impl ProjectControllerTrait for ProjectController {
 
         let access = access::Model {
             id: Default::default(),
-            role: "Editor".to_string(), //todo!("Use role enum")
+            role: Role::Editor.to_string(),
             project_id: project.clone().id,
             user_id: uid,
         };
@@ -247,6 +147,7 @@ impl ProjectControllerTrait for ProjectController {
             project_id: project.clone().id,
             session_id: session.id,
             latest_activity: Default::default(),
+            expires_at: Default::default(),
         };
 
         self.contexts
@@ -264,6 +165,10 @@ impl ProjectControllerTrait for ProjectController {
                 Status::internal(format!("a database error occured, internal error: {}", err))
             })?;
 
+        replace_components_helper(&self.contexts, project.id, components).await?;
+
+        create_project_version_helper(&self.contexts, project.id, uid).await?;
+
         Ok(Response::new(CreateProjectResponse { id: project.id }))
     }
 
@@ -289,87 +194,32 @@ impl ProjectControllerTrait for ProjectController {
             Err(error) => return Err(Status::internal(error.to_string())),
         };
 
-        // Check if the user has access to the project
-        match self
-            .contexts
-            .access_context
-            .get_access_by_uid_and_project_id(uid, project.id)
-            .await
-        {
-            Ok(access) => {
-                let mut is_editor = false;
-                let access = match access {
-                    Some(access) => {
-                        is_editor = access.role == "Editor";
-                        Some(access)
-                    }
-                    None => None,
-                };
-
-                if !is_editor || access.is_none() {
-                    return Err(Status::permission_denied(
-                        "You do not have permission to update this project",
-                    ));
-                }
-            }
-            Err(error) => return Err(Status::internal(error.to_string())),
-        };
+        // Check if the user is allowed to update the project
+        authorize_helper(&self.contexts, uid, project.id, Permission::EditProject).await?;
 
-        // Get user session
-        let session = match self
-            .contexts
-            .session_context
-            .get_by_token(
-                TokenType::AccessToken,
-                request
-                    .token_string()
-                    .map_err(|err| {
-                        Status::internal(format!(
-                            "could not stringify user id in request metadata, internal error {}",
-                            err
-                        ))
-                    })?
-                    .ok_or(Status::internal(
-                        "failed to get token from request metadata",
-                    ))?,
-            )
-            .await
-        {
-            Ok(Some(session)) => session,
-            Ok(None) => {
-                return Err(Status::unauthenticated(
-                    "No session found with given access token",
-                ));
-            }
-            Err(error) => return Err(Status::internal(error.to_string())),
-        };
+        let access_token = request
+            .token_string()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get token from request metadata",
+            ))?;
 
-        // Get in_use for project
-        match self.contexts.in_use_context.get_by_id(project.id).await {
-            Ok(Some(in_use)) => {
-                // Check if in_use latest activity is older than the max allowed
-                if in_use.latest_activity
-                    > (Utc::now().naive_utc() - Duration::minutes(IN_USE_DURATION_MINUTES))
-                    && in_use.session_id != session.id
-                {
-                    return Err(Status::failed_precondition(
-                        "Model is currently in use by another session",
-                    ));
-                }
+        check_lock_helper(&self.contexts, &project, access_token).await?;
 
-                let new_in_use = in_use::Model {
-                    project_id: in_use.project_id,
-                    session_id: session.id,
-                    latest_activity: Utc::now().naive_utc(),
-                };
+        let components = message
+            .clone()
+            .components_info
+            .map(named_components)
+            .transpose()?;
 
-                match self.contexts.in_use_context.update(new_in_use).await {
-                    Ok(_) => (),
-                    Err(error) => return Err(Status::internal(error.to_string())),
-                }
-            }
-            Ok(None) => return Err(Status::internal("No in_use found for project")),
-            Err(error) => return Err(Status::internal(error.to_string())),
+        let up_to_date_query_ids = match components {
+            Some(_) => up_to_date_query_ids_helper(&self.contexts, project.id).await?,
+            None => vec![],
         };
 
         let new_project = project::Model {
@@ -378,15 +228,6 @@ impl ProjectControllerTrait for ProjectController {
                 Some(name) => name,
                 None => project.name,
             },
-            components_info: match message.clone().components_info {
-                Some(components_info) => serde_json::to_value(components_info).map_err(|err| {
-                    Status::internal(format!(
-                        "failed to parse components info object, internal error: {}",
-                        err
-                    ))
-                })?,
-                None => project.components_info,
-            },
             owner_id: match message.clone().owner_id {
                 Some(new_owner_id) => {
                     if project.owner_id == uid {
@@ -399,12 +240,52 @@ impl ProjectControllerTrait for ProjectController {
                 }
                 None => project.owner_id,
             },
+            settings: match message.clone().settings {
+                Some(settings) => Some(serde_json::to_value(settings).map_err(|err| {
+                    Status::internal(format!(
+                        "failed to parse settings object, internal error: {}",
+                        err
+                    ))
+                })?),
+                None => project.settings,
+            },
+            timeout_secs: update_timeout(project.timeout_secs, message.timeout_secs)
+                .map_err(|_| Status::invalid_argument("Timeout is too long"))?,
+            revision: project.revision,
+            collaborative: message.collaborative.unwrap_or(project.collaborative),
         };
 
-        match self.contexts.project_context.update(new_project).await {
-            Ok(_) => Ok(Response::new(())),
-            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+        // Reject the update if someone else changed the project since the client last saw it.
+        // The project moves to its next revision in the same write, so a failed update leaves the revision as it was.
+        if !self
+            .contexts
+            .project_context
+            .update_at_revision(new_project, message.revision)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        {
+            return Err(revision_conflict_helper(
+                &self.contexts,
+                project.id,
+                Some(message.revision),
+            )
+            .await);
         }
+
+        // Snapshot the saved components, such that they can be restored later
+        if let Some(components) = components {
+            replace_components_helper(&self.contexts, project.id, components).await?;
+            create_project_version_helper(&self.contexts, project.id, uid).await?;
+            publish_outdated_queries_helper(
+                &self.contexts,
+                &self.services,
+                project.id,
+                up_to_date_query_ids,
+            )
+            .await?;
+        }
+
+        Ok(Response::new(()))
     }
 
     async fn delete_project(
@@ -487,6 +368,591 @@ impl ProjectControllerTrait for ProjectController {
             Err(error) => Err(Status::new(Code::Internal, error.to_string())),
         }
     }
+
+    async fn list_project_versions(
+        &self,
+        request: Request<ListProjectVersionsRequest>,
+    ) -> Result<Response<ListProjectVersionsResponse>, Status> {
+        let message = request.get_ref().clone();
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal("Could not get uid from request metadata"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        let project_versions = self
+            .contexts
+            .project_version_context
+            .get_all_by_project_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(project_version_info)
+            .collect();
+
+        Ok(Response::new(ListProjectVersionsResponse {
+            project_versions,
+        }))
+    }
+
+    async fn get_project_version(
+        &self,
+        request: Request<GetProjectVersionRequest>,
+    ) -> Result<Response<GetProjectVersionResponse>, Status> {
+        let message = request.get_ref().clone();
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal("Could not get uid from request metadata"))?;
+
+        let project_version = self
+            .contexts
+            .project_version_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No project version found with given id"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            project_version.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        let components_info = serde_json::from_value(project_version.components_info.clone())
+            .map_err(|err| {
+                Status::internal(format!(
+                    "failed to parse components info object, internal error: {}",
+                    err
+                ))
+            })?;
+
+        Ok(Response::new(GetProjectVersionResponse {
+            project_version: Some(project_version_info(project_version)),
+            components_info: Some(components_info),
+        }))
+    }
+
+    async fn restore_project_version(
+        &self,
+        request: Request<RestoreProjectVersionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal("Could not get uid from request metadata"))?;
+
+        let project_version = self
+            .contexts
+            .project_version_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No project version found with given id"))?;
+
+        // Check if the user is allowed to restore the project
+        authorize_helper(
+            &self.contexts,
+            uid,
+            project_version.project_id,
+            Permission::EditProject,
+        )
+        .await?;
+
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(project_version.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No project found with given id"))?;
+
+        let access_token = request
+            .token_string()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get token from request metadata",
+            ))?;
+
+        check_lock_helper(&self.contexts, &project, access_token).await?;
+
+        let components_info: ComponentsInfo =
+            serde_json::from_value(project_version.components_info).map_err(|err| {
+                Status::internal(format!(
+                    "failed to parse components info object, internal error: {}",
+                    err
+                ))
+            })?;
+        let components = named_components(components_info)?;
+
+        let up_to_date_query_ids = up_to_date_query_ids_helper(&self.contexts, project.id).await?;
+
+        advance_revision_helper(&self.contexts, project.id, None).await?;
+
+        replace_components_helper(&self.contexts, project.id, components).await?;
+
+        // The restore is itself a save, so it is added to the history as the newest version
+        create_project_version_helper(&self.contexts, project.id, uid).await?;
+
+        publish_outdated_queries_helper(
+            &self.contexts,
+            &self.services,
+            project.id,
+            up_to_date_query_ids,
+        )
+        .await?;
+
+        Ok(Response::new(()))
+    }
+}
+
+/// Returns a project together with its queries and whether it is in use,
+/// without checking that anyone may read it.
+pub(crate) async fn get_project_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+) -> Result<GetProjectResponse, Status> {
+    let project = contexts
+        .project_context
+        .get_by_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .ok_or_else(|| Status::new(Code::Internal, "Model not found"))?;
+
+    let project = Project {
+        id: project.id,
+        name: project.name,
+        components_info: Some(
+            contexts
+                .component_context
+                .get_components_info_by_project_id(project.id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?,
+        ),
+        owner_id: project.owner_id,
+        settings: project
+            .settings
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "failed to parse settings object, internal error: {}",
+                    err
+                ))
+            })?,
+        timeout_secs: project.timeout_secs.map(|secs| secs as u32),
+        revision: project.revision,
+        collaborative: project.collaborative,
+    };
+
+    let in_use = contexts
+        .in_use_context
+        .get_by_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .is_some_and(|in_use| in_use.expires_at > Utc::now().naive_utc());
+
+    let queries = contexts
+        .query_context
+        .get_all_by_project_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+    let queries = queries
+        .into_iter()
+        .map(|query| {
+            let result = serde_json::from_value(query.result.unwrap_or_else(|| "".into()))?;
+            let settings = query.settings.map(serde_json::from_value).transpose()?;
+
+            Ok(Query {
+                id: query.id,
+                project_id: query.project_id,
+                query: query.string,
+                result,
+                outdated: query.outdated,
+                settings,
+                timeout_secs: query.timeout_secs.map(|secs| secs as u32),
+            })
+        })
+        .collect::<Result<Vec<Query>, serde_json::Error>>()
+        .map_err(|err| {
+            Status::internal(format!(
+                "failed to parse json result, inner error:  {}",
+                err
+            ))
+        })?;
+
+    Ok(GetProjectResponse {
+        project: Some(project),
+        queries,
+        in_use,
+    })
+}
+
+/// Moves a project to its next revision, which every change to the project or its queries must do.
+///
+/// # Errors
+/// If a revision is given and the project has moved past it, this function returns
+/// `FAILED_PRECONDITION` with the current revision in the message and in the `revision` metadata.
+pub(crate) async fn advance_revision_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+    revision: Option<i32>,
+) -> Result<(), Status> {
+    if contexts
+        .project_context
+        .advance_revision(project_id, revision)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+    {
+        return Ok(());
+    }
+
+    Err(revision_conflict_helper(contexts, project_id, revision).await)
+}
+
+/// Explains why a project could not be moved to its next revision:
+/// either it has moved past the given revision, or it does not exist.
+pub(crate) async fn revision_conflict_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+    revision: Option<i32>,
+) -> Status {
+    let current_project = match contexts.project_context.get_by_id(project_id).await {
+        Ok(current_project) => current_project,
+        Err(err) => return Status::new(Code::Internal, err.to_string()),
+    };
+
+    match (current_project, revision) {
+        (Some(current_project), Some(revision)) => {
+            let mut status = Status::failed_precondition(format!(
+                "The project has changed since revision {}, the current revision is {}",
+                revision, current_project.revision
+            ));
+            status
+                .metadata_mut()
+                .insert("revision", current_project.revision.into());
+            status
+        }
+        _ => Status::not_found("No project found with given id"),
+    }
+}
+
+/// Returns the ids of the queries of a project that are not outdated.
+pub(crate) async fn up_to_date_query_ids_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+) -> Result<Vec<i32>, Status> {
+    Ok(contexts
+        .query_context
+        .get_all_by_project_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .into_iter()
+        .filter(|query| !query.outdated)
+        .map(|query| query.id)
+        .collect())
+}
+
+/// Marks the up to date queries of a project that mention any of the given component names as outdated,
+/// as their results may not hold for the changed components.
+pub(crate) async fn outdate_queries_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+    names: &[&str],
+) -> Result<(), Status> {
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    let query_ids: Vec<i32> = contexts
+        .query_context
+        .get_all_by_project_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .into_iter()
+        .filter(|query| !query.outdated && references_any_component(&query.string, names))
+        .map(|query| query.id)
+        .collect();
+
+    if query_ids.is_empty() {
+        return Ok(());
+    }
+
+    contexts
+        .query_context
+        .mark_outdated(query_ids)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))
+}
+
+/// Tells the subscribers of a project which of the previously up to date queries have become outdated.
+pub(crate) async fn publish_outdated_queries_helper(
+    contexts: &ContextCollection,
+    services: &ServiceCollection,
+    project_id: i32,
+    up_to_date_query_ids: Vec<i32>,
+) -> Result<(), Status> {
+    let still_up_to_date_query_ids = up_to_date_query_ids_helper(contexts, project_id).await?;
+
+    for query_id in up_to_date_query_ids
+        .into_iter()
+        .filter(|query_id| !still_up_to_date_query_ids.contains(query_id))
+    {
+        services.query_event_service.publish(QueryEvent {
+            query_id,
+            project_id,
+            kind: Kind::Outdated.into(),
+            ..Default::default()
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the session of the given access token holds the edit lock on the project.
+/// Collaborative projects are edited without taking the lock.
+///
+/// # Errors
+/// This function will return an error if no session exists for the access token,
+/// or if the session does not hold an unexpired lock on a project that is not collaborative.
+pub(crate) async fn check_lock_helper(
+    contexts: &ContextCollection,
+    project: &project::Model,
+    access_token: String,
+) -> Result<(), Status> {
+    if project.collaborative {
+        return Ok(());
+    }
+
+    // Get user session
+    let session = match contexts
+        .session_context
+        .get_by_token(TokenType::AccessToken, access_token)
+        .await
+    {
+        Ok(Some(session)) => session,
+        Ok(None) => {
+            return Err(Status::unauthenticated(
+                "No session found with given access token",
+            ));
+        }
+        Err(error) => return Err(Status::internal(error.to_string())),
+    };
+
+    match contexts.in_use_context.get_by_id(project.id).await {
+        Ok(Some(in_use))
+            if in_use.session_id == session.id && in_use.expires_at > Utc::now().naive_utc() =>
+        {
+            Ok(())
+        }
+        Ok(_) => Err(Status::failed_precondition(
+            "Acquire the lock on the project before editing it",
+        )),
+        Err(error) => Err(Status::internal(error.to_string())),
+    }
+}
+
+/// Stores the current components of a project as a new version made by the given user.
+pub(crate) async fn create_project_version_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+    uid: i32,
+) -> Result<(), Status> {
+    let components_info = contexts
+        .component_context
+        .get_components_info_by_project_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+    let project_version = project_version::Model {
+        id: Default::default(),
+        project_id,
+        components_info: serde_json::to_value(components_info).map_err(|err| {
+            Status::internal(format!(
+                "failed to parse components info object, internal error: {}",
+                err
+            ))
+        })?,
+        user_id: Some(uid),
+        created_at: Default::default(),
+    };
+
+    contexts
+        .project_version_context
+        .create(project_version)
+        .await
+        .map_err(|err| {
+            Status::internal(format!("a database error occured, internal error: {}", err))
+        })?;
+
+    Ok(())
+}
+
+/// Splits a components info object into its components, along with the names they give themselves.
+///
+/// # Errors
+/// This function will return an error if two components have the same name.
+fn named_components(
+    components_info: ComponentsInfo,
+) -> Result<Vec<(Option<String>, Component)>, Status> {
+    let mut names = HashSet::new();
+
+    components_info
+        .components
+        .into_iter()
+        .map(|component| {
+            let name = component_name(&component);
+            if let Some(name) = &name {
+                if !names.insert(name.clone()) {
+                    return Err(Status::invalid_argument(format!(
+                        "There is more than one component named {}",
+                        name
+                    )));
+                }
+            }
+            Ok((name, component))
+        })
+        .collect()
+}
+
+/// Makes the stored components of a project match the given components.
+/// Only components that were added, changed or removed are written.
+/// A component without a name of its own keeps the name of an equal stored component,
+/// or is otherwise given a placeholder name.
+async fn replace_components_helper(
+    contexts: &ContextCollection,
+    project_id: i32,
+    components: Vec<(Option<String>, Component)>,
+) -> Result<(), Status> {
+    let mut existing_components: HashMap<String, component::Model> = contexts
+        .component_context
+        .get_all_by_project_id(project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .into_iter()
+        .map(|component| (component.name.clone(), component))
+        .collect();
+
+    let mut names: HashSet<String> = components
+        .iter()
+        .filter_map(|(name, _)| name.clone())
+        .collect();
+
+    let components = components
+        .into_iter()
+        .map(|(name, component)| {
+            let component = serde_json::to_value(component).map_err(|err| {
+                Status::internal(format!(
+                    "failed to parse component object, internal error: {}",
+                    err
+                ))
+            })?;
+
+            let name = match name {
+                Some(name) => name,
+                None => {
+                    let name = existing_components
+                        .values()
+                        .find(|existing_component| {
+                            existing_component.component == component
+                                && !names.contains(&existing_component.name)
+                        })
+                        .map(|existing_component| existing_component.name.clone())
+                        .unwrap_or_else(|| {
+                            placeholder_component_name(|name| {
+                                names.contains(name) || existing_components.contains_key(name)
+                            })
+                        });
+                    names.insert(name.clone());
+                    name
+                }
+            };
+
+            Ok((name, component))
+        })
+        .collect::<Result<Vec<_>, Status>>()?;
+
+    let mut changed_names = vec![];
+    for (name, component) in components {
+        let result = match existing_components.remove(&name) {
+            Some(existing_component) if existing_component.component == component => continue,
+            Some(existing_component) => {
+                contexts
+                    .component_context
+                    .update(component::Model {
+                        component,
+                        ..existing_component
+                    })
+                    .await
+            }
+            None => {
+                contexts
+                    .component_context
+                    .create(component::Model {
+                        id: Default::default(),
+                        project_id,
+                        name: name.clone(),
+                        component,
+                        updated_at: Default::default(),
+                    })
+                    .await
+            }
+        };
+        result.map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        changed_names.push(name);
+    }
+
+    for removed_component in existing_components.into_values() {
+        contexts
+            .component_context
+            .delete(removed_component.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        changed_names.push(removed_component.name);
+    }
+
+    let changed_names: Vec<&str> = changed_names.iter().map(String::as_str).collect();
+    outdate_queries_helper(contexts, project_id, &changed_names).await
+}
+
+fn project_version_info(project_version: project_version::Model) -> ProjectVersionInfo {
+    ProjectVersionInfo {
+        id: project_version.id,
+        project_id: project_version.project_id,
+        user_id: project_version.user_id,
+        created_at: project_version.created_at.and_utc().timestamp(),
+    }
 }
 
 #[cfg(test)]
@@ -1,23 +1,64 @@
 use crate::api::auth::RequestExt;
+use crate::api::counterexample::counterexample_info;
+use crate::api::query_settings::{resolve_query_settings, resolve_query_timeout, update_timeout};
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::send_queries_response::{query_result::Outcome, QueryResult};
+use crate::api::server::protobuf::QueryJobStatus as QueryJobStatusInfo;
 use crate::api::server::protobuf::{
-    CreateQueryRequest, DeleteQueryRequest, QueryRequest, SendQueryRequest, SendQueryResponse,
+    CancelQueryJobRequest, CancelQueryRequest, CreateQueryRequest, DeleteQueryRequest,
+    GetCounterexampleRequest, GetCounterexampleResponse, GetQueryJobRequest, GetQueryJobResponse,
+    ListQueryJobsRequest, ListQueryJobsResponse, ListQueryResultsRequest, ListQueryResultsResponse,
+    QueryJob, QueryResultInfo, SendQueriesRequest, SendQueriesResponse, SendQueryRequest,
+    SendQueryResponse, SubmitQueryJobRequest, SubmitQueryJobResponse, SubscribeQueryEventsRequest,
     UpdateQueryRequest,
 };
 use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::{
+    authorize_helper, authorized_stream_helper,
+};
+use crate::controllers::controller_impls::project_controller::{
+    advance_revision_helper, revision_conflict_helper,
+};
 use crate::controllers::controller_traits::QueryControllerTrait;
-use crate::entities::query;
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use crate::entities::{query, query_job, query_result};
 use crate::services::service_collection::ServiceCollection;
+use crate::services::service_impls::query_runner::QueryRunner;
+use crate::services::service_traits::QueryEventStream;
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
 use tonic::{Code, Request, Response, Status};
 
+const DEFAULT_QUERY_RESULT_PAGE_SIZE: u64 = 20;
+const MAX_QUERY_RESULT_PAGE_SIZE: u64 = 100;
+
 pub struct QueryController {
     contexts: ContextCollection,
     services: ServiceCollection,
+    max_concurrent_queries: usize,
 }
 
 impl QueryController {
-    pub fn new(contexts: ContextCollection, services: ServiceCollection) -> Self {
-        Self { contexts, services }
+    pub fn new(
+        contexts: ContextCollection,
+        services: ServiceCollection,
+        max_concurrent_queries: usize,
+    ) -> Self {
+        Self {
+            contexts,
+            services,
+            max_concurrent_queries,
+        }
+    }
+
+    fn runner(&self) -> QueryRunner<'_> {
+        QueryRunner {
+            contexts: &self.contexts,
+            reveaal_service: self.services.reveaal_service.as_ref(),
+            query_event_service: self.services.query_event_service.as_ref(),
+            query_run_service: self.services.query_run_service.as_ref(),
+            job_id: None,
+        }
     }
 }
 
@@ -29,38 +70,23 @@ impl QueryControllerTrait for QueryController {
     ) -> Result<Response<()>, Status> {
         let query_request = request.get_ref();
 
-        let access = self
-            .contexts
-            .access_context
-            .get_access_by_uid_and_project_id(
-                request
-                    .uid()
-                    .map_err(|err| {
-                        Status::invalid_argument(format!(
-                            "could not stringify user id in request metadata, inner error {}",
-                            err
-                        ))
-                    })?
-                    .ok_or(Status::invalid_argument(
-                        "failed to get user id from request metadata",
-                    ))?,
-                query_request.project_id,
-            )
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-            .ok_or_else(|| {
-                Status::new(
-                    Code::PermissionDenied,
-                    "User does not have access to project",
-                )
-            })?;
-
-        if access.role != "Editor" {
-            return Err(Status::new(
-                Code::PermissionDenied,
-                "Role does not have permission to create query",
-            ));
-        }
+        authorize_helper(
+            &self.contexts,
+            request
+                .uid()
+                .map_err(|err| {
+                    Status::invalid_argument(format!(
+                        "could not stringify user id in request metadata, inner error {}",
+                        err
+                    ))
+                })?
+                .ok_or(Status::invalid_argument(
+                    "failed to get user id from request metadata",
+                ))?,
+            query_request.project_id,
+            Permission::EditQueries,
+        )
+        .await?;
 
         let query = query::Model {
             id: Default::default(),
@@ -68,12 +94,29 @@ impl QueryControllerTrait for QueryController {
             result: Default::default(),
             outdated: Default::default(),
             project_id: query_request.project_id,
+            settings: query_request
+                .settings
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(|err| {
+                    Status::internal(format!(
+                        "error serializing query settings, internal error: {}",
+                        err
+                    ))
+                })?,
+            timeout_secs: update_timeout(None, query_request.timeout_secs)
+                .map_err(|_| Status::invalid_argument("Timeout is too long"))?,
         };
 
-        match self.contexts.query_context.create(query).await {
-            Ok(_) => Ok(Response::new(())),
-            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
-        }
+        let query = match self.contexts.query_context.create(query).await {
+            Ok(query) => query,
+            Err(error) => return Err(Status::new(Code::Internal, error.to_string())),
+        };
+
+        advance_revision_helper(&self.contexts, query.project_id, None).await?;
+
+        Ok(Response::new(()))
     }
 
     async fn update_query(
@@ -94,51 +137,63 @@ impl QueryControllerTrait for QueryController {
             None => return Err(Status::new(Code::NotFound, "Query not found".to_string())),
         };
 
-        let access = self
-            .contexts
-            .access_context
-            .get_access_by_uid_and_project_id(
-                request
-                    .uid()
-                    .map_err(|err| {
-                        Status::internal(format!(
-                            "could not stringify user id in request metadata, internal error {}",
-                            err
-                        ))
-                    })?
-                    .ok_or(Status::internal(
-                        "failed to get user id from request metadata",
-                    ))?,
-                old_query.project_id,
-            )
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-            .ok_or_else(|| {
-                Status::new(
-                    Code::PermissionDenied,
-                    "User does not have access to project",
-                )
-            })?;
-
-        if access.role != "Editor" {
-            return Err(Status::new(
-                Code::PermissionDenied,
-                "Role does not have permission to update query",
-            ));
-        }
+        authorize_helper(
+            &self.contexts,
+            request
+                .uid()
+                .map_err(|err| {
+                    Status::internal(format!(
+                        "could not stringify user id in request metadata, internal error {}",
+                        err
+                    ))
+                })?
+                .ok_or(Status::internal(
+                    "failed to get user id from request metadata",
+                ))?,
+            old_query.project_id,
+            Permission::EditQueries,
+        )
+        .await?;
 
         let query = query::Model {
             id: message.id,
-            project_id: Default::default(),
+            project_id: old_query.project_id,
             string: message.string,
             result: old_query.result,
             outdated: old_query.outdated,
+            settings: message
+                .settings
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(|err| {
+                    Status::internal(format!(
+                        "error serializing query settings, internal error: {}",
+                        err
+                    ))
+                })?
+                .or(old_query.settings),
+            timeout_secs: update_timeout(old_query.timeout_secs, message.timeout_secs)
+                .map_err(|_| Status::invalid_argument("Timeout is too long"))?,
         };
 
-        match self.contexts.query_context.update(query).await {
-            Ok(_) => Ok(Response::new(())),
-            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+        // Reject the update if someone else changed the project since the client last saw it
+        if !self
+            .contexts
+            .query_context
+            .update_at_revision(query, message.revision)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        {
+            return Err(revision_conflict_helper(
+                &self.contexts,
+                old_query.project_id,
+                Some(message.revision),
+            )
+            .await);
         }
+
+        Ok(Response::new(()))
     }
 
     async fn delete_query(
@@ -155,48 +210,36 @@ impl QueryControllerTrait for QueryController {
             .map_err(|err| Status::new(Code::Internal, err.to_string()))?
             .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
 
-        let access = self
-            .contexts
-            .access_context
-            .get_access_by_uid_and_project_id(
-                request
-                    .uid()
-                    .map_err(|err| {
-                        Status::internal(format!(
-                            "could not stringify user id in request metadata, internal error {}",
-                            err
-                        ))
-                    })?
-                    .ok_or(Status::internal(
-                        "failed to get user id from request metadata",
-                    ))?,
-                query.project_id,
-            )
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-            .ok_or_else(|| {
-                Status::new(
-                    Code::PermissionDenied,
-                    "User does not have access to project",
-                )
-            })?;
-
-        if access.role != "Editor" {
-            return Err(Status::new(
-                Code::PermissionDenied,
-                "Role does not have permission to update query",
-            ));
-        }
+        authorize_helper(
+            &self.contexts,
+            request
+                .uid()
+                .map_err(|err| {
+                    Status::internal(format!(
+                        "could not stringify user id in request metadata, internal error {}",
+                        err
+                    ))
+                })?
+                .ok_or(Status::internal(
+                    "failed to get user id from request metadata",
+                ))?,
+            query.project_id,
+            Permission::EditQueries,
+        )
+        .await?;
 
-        match self.contexts.query_context.delete(message.id).await {
-            Ok(_) => Ok(Response::new(())),
-            Err(error) => match error {
+        if let Err(error) = self.contexts.query_context.delete(message.id).await {
+            return match error {
                 sea_orm::DbErr::RecordNotFound(message) => {
                     Err(Status::new(Code::NotFound, message))
                 }
                 _ => Err(Status::new(Code::Internal, error.to_string())),
-            },
+            };
         }
+
+        advance_revision_helper(&self.contexts, query.project_id, None).await?;
+
+        Ok(Response::new(()))
     }
 
     async fn send_query(
@@ -218,17 +261,13 @@ impl QueryControllerTrait for QueryController {
             ))?;
 
         // Verify user access
-        self.contexts
-            .access_context
-            .get_access_by_uid_and_project_id(uid, message.project_id)
-            .await
-            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
-            .ok_or_else(|| {
-                Status::new(
-                    Code::PermissionDenied,
-                    "User does not have access to project",
-                )
-            })?;
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
 
         // Get project from contexts
         let project = self
@@ -239,67 +278,613 @@ impl QueryControllerTrait for QueryController {
             .map_err(|err| Status::new(Code::Internal, err.to_string()))?
             .ok_or_else(|| Status::new(Code::NotFound, "Model not found"))?;
 
-        // Get query from contexts
+        // Get query from contexts, the access checked above only covers queries of the given project
         let query = self
             .contexts
             .query_context
             .get_by_id(message.id)
             .await
             .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .filter(|query| query.project_id == message.project_id)
             .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
 
-        // Construct query request to send to Reveaal
-        let query_request = Request::new(QueryRequest {
-            user_id: uid,
-            query_id: message.id,
-            query: query.string.clone(),
-            components_info: serde_json::from_value(project.components_info).map_err(|err| {
+        let (components_info, components_hash) = self.runner().components(project.id).await?;
+
+        let settings = resolve_query_settings(
+            project.settings.as_ref(),
+            query.settings.as_ref(),
+            message.settings.clone(),
+        )
+        .map_err(|err| {
+            Status::internal(format!(
+                "error parsing query settings, internal error: {}",
+                err
+            ))
+        })?;
+
+        let timeout = resolve_query_timeout(project.timeout_secs, query.timeout_secs);
+        let (query_result, cached) = self
+            .runner()
+            .run(
+                uid,
+                query,
+                components_info,
+                components_hash,
+                settings,
+                timeout,
+            )
+            .await?;
+
+        Ok(Response::new(SendQueryResponse {
+            response: Some(query_result),
+            cached,
+        }))
+    }
+
+    async fn send_queries(
+        &self,
+        request: Request<SendQueriesRequest>,
+    ) -> Result<Response<SendQueriesResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
                 Status::internal(format!(
-                    "error parsing query result, internal error: {}",
+                    "could not stringify user id in request metadata, internal error {}",
                     err
                 ))
-            })?,
-            settings: Default::default(), //TODO
-        });
-
-        // Run query on Reveaal
-        let query_result = self
-            .services
-            .reveaal_service
-            .send_query(query_request)
-            .await?;
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        // Verify user access
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
+
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Model not found"))?;
+
+        let (components_info, components_hash) = self.runner().components(project.id).await?;
+
+        let queries = self
+            .contexts
+            .query_context
+            .get_all_by_project_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .filter(|query| !message.only_outdated || query.outdated);
+
+        // Run the queries on Reveaal, at most max_concurrent_queries at a time
+        let results = stream::iter(queries)
+            .map(|query| {
+                let components_info = components_info.clone();
+                let components_hash = components_hash.clone();
+                let settings = resolve_query_settings(
+                    project.settings.as_ref(),
+                    query.settings.as_ref(),
+                    message.settings.clone(),
+                );
+                let timeout = resolve_query_timeout(project.timeout_secs, query.timeout_secs);
+                async move {
+                    let query_id = query.id;
+                    let (outcome, cached) = match settings {
+                        Ok(settings) => {
+                            match self
+                                .runner()
+                                .run(
+                                    uid,
+                                    query,
+                                    components_info,
+                                    components_hash,
+                                    settings,
+                                    timeout,
+                                )
+                                .await
+                            {
+                                Ok((response, cached)) => (Outcome::Response(response), cached),
+                                Err(err) => (Outcome::Error(err.message().to_string()), false),
+                            }
+                        }
+                        Err(err) => (
+                            Outcome::Error(format!(
+                                "error parsing query settings, internal error: {}",
+                                err
+                            )),
+                            false,
+                        ),
+                    };
+                    QueryResult {
+                        query_id,
+                        outcome: Some(outcome),
+                        cached,
+                    }
+                }
+            })
+            .buffered(self.max_concurrent_queries.max(1))
+            .collect()
+            .await;
+
+        Ok(Response::new(SendQueriesResponse { results }))
+    }
+
+    async fn subscribe_query_events(
+        &self,
+        request: Request<SubscribeQueryEventsRequest>,
+    ) -> Result<Response<QueryEventStream>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        // Verify user access
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        Ok(Response::new(authorized_stream_helper(
+            self.contexts.clone(),
+            uid,
+            message.project_id,
+            Permission::ReadProject,
+            self.services
+                .query_event_service
+                .subscribe(message.project_id),
+        )))
+    }
 
-        // Update query result in contexts
-        self.contexts
+    async fn submit_query_job(
+        &self,
+        request: Request<SubmitQueryJobRequest>,
+    ) -> Result<Response<SubmitQueryJobResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        // Verify user access
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
+
+        let query = self
+            .contexts
             .query_context
-            .update(query::Model {
-                id: query.id,
-                string: query.string.clone(),
-                result: Some(
-                    serde_json::to_value(
-                        query_result
-                            .get_ref()
-                            .result
-                            .clone()
-                            .ok_or(Status::internal("failed to get query result"))?, //TODO better error message ?
-                    )
-                    .map_err(|err| {
-                        Status::internal(format!(
-                            "error parsing query result, internal error: {}",
-                            err
-                        ))
-                    })?,
-                ),
-                outdated: false,
+            .get_by_id(message.query_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+        if query.project_id != message.project_id {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                "Query does not belong to project",
+            ));
+        }
+
+        let job = self
+            .contexts
+            .query_job_context
+            .create(query_job::Model {
+                id: Default::default(),
+                query_id: query.id,
                 project_id: query.project_id,
+                user_id: uid,
+                status: QueryJobStatus::Queued,
+                result: None,
+                error: None,
+                created_at: Default::default(),
+                updated_at: Default::default(),
             })
             .await
             .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
 
-        Ok(Response::new(SendQueryResponse {
-            response: Some(query_result.into_inner()),
+        self.services.query_job_service.enqueue(job.id)?;
+
+        Ok(Response::new(SubmitQueryJobResponse { id: job.id }))
+    }
+
+    async fn get_query_job(
+        &self,
+        request: Request<GetQueryJobRequest>,
+    ) -> Result<Response<GetQueryJobResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let job = self
+            .contexts
+            .query_job_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query job not found"))?;
+
+        authorize_helper(&self.contexts, uid, job.project_id, Permission::ReadProject).await?;
+
+        Ok(Response::new(GetQueryJobResponse {
+            query_job: Some(query_job_info(job)),
         }))
     }
+
+    async fn list_query_jobs(
+        &self,
+        request: Request<ListQueryJobsRequest>,
+    ) -> Result<Response<ListQueryJobsResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        let query_jobs = self
+            .contexts
+            .query_job_context
+            .get_all_by_project_id(message.project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(query_job_info)
+            .collect();
+
+        Ok(Response::new(ListQueryJobsResponse { query_jobs }))
+    }
+
+    async fn cancel_query_job(
+        &self,
+        request: Request<CancelQueryJobRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let job = self
+            .contexts
+            .query_job_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query job not found"))?;
+
+        // Anyone who can run queries can cancel their own jobs, but the jobs of others are
+        // only for those who can edit the queries
+        let permission = if job.user_id == uid {
+            Permission::RunQuery
+        } else {
+            Permission::EditQueries
+        };
+        authorize_helper(&self.contexts, uid, job.project_id, permission).await?;
+
+        let job_id = job.id;
+        let cancelled_job = query_job::Model {
+            status: QueryJobStatus::Cancelled,
+            ..job
+        };
+
+        // A worker may pick up or finish the job at any moment, so the status is changed conditionally
+        if self
+            .contexts
+            .query_job_context
+            .update_if_status(cancelled_job.clone(), QueryJobStatus::Queued)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        {
+            return Ok(Response::new(()));
+        }
+
+        if !self
+            .contexts
+            .query_job_context
+            .update_if_status(cancelled_job, QueryJobStatus::Running)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        {
+            return Err(Status::new(
+                Code::FailedPrecondition,
+                "Query job has already finished",
+            ));
+        }
+
+        // Stop the job's own run on Reveaal, so the worker can move on to the next job
+        // while other runs of the query carry on
+        self.services.query_run_service.cancel_job(job_id);
+
+        Ok(Response::new(()))
+    }
+
+    async fn cancel_query(
+        &self,
+        request: Request<CancelQueryRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let query = self
+            .contexts
+            .query_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            query.project_id,
+            Permission::EditQueries,
+        )
+        .await?;
+
+        // The runs themselves record the query as cancelled once they are aborted
+        if !self.services.query_run_service.cancel(query.id) {
+            return Err(Status::new(
+                Code::FailedPrecondition,
+                "Query is not running",
+            ));
+        }
+
+        Ok(Response::new(()))
+    }
+
+    async fn list_query_results(
+        &self,
+        request: Request<ListQueryResultsRequest>,
+    ) -> Result<Response<ListQueryResultsResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let query = self
+            .contexts
+            .query_context
+            .get_by_id(message.query_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            query.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        Ok(Response::new(
+            list_query_results_helper(&self.contexts, query.id, message.page, message.page_size)
+                .await?,
+        ))
+    }
+
+    async fn get_counterexample(
+        &self,
+        request: Request<GetCounterexampleRequest>,
+    ) -> Result<Response<GetCounterexampleResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let query = self
+            .contexts
+            .query_context
+            .get_by_id(message.query_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            query.project_id,
+            Permission::ReadProject,
+        )
+        .await?;
+
+        let counterexample = match message.query_result_id {
+            Some(query_result_id) => self
+                .contexts
+                .counterexample_context
+                .get_by_query_result_id(query_result_id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+                .filter(|counterexample| counterexample.query_id == query.id),
+            None => self
+                .contexts
+                .counterexample_context
+                .get_latest_by_query_id(query.id)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?,
+        }
+        .ok_or_else(|| Status::new(Code::NotFound, "Query has no counterexample"))?;
+
+        let counterexample = counterexample_info(counterexample).map_err(|err| {
+            Status::internal(format!(
+                "failed to parse counterexample, internal error: {}",
+                err
+            ))
+        })?;
+
+        Ok(Response::new(GetCounterexampleResponse {
+            counterexample: Some(counterexample),
+        }))
+    }
+}
+
+/// Returns a page of the results of a query, without checking that anyone may read them.
+/// A page size of 0 gives the default page size, and page sizes above the maximum are capped.
+pub(crate) async fn list_query_results_helper(
+    contexts: &ContextCollection,
+    query_id: i32,
+    page: u64,
+    page_size: u64,
+) -> Result<ListQueryResultsResponse, Status> {
+    let page_size = match page_size {
+        0 => DEFAULT_QUERY_RESULT_PAGE_SIZE,
+        page_size => page_size.min(MAX_QUERY_RESULT_PAGE_SIZE),
+    };
+
+    let (query_results, total) = contexts
+        .query_result_context
+        .get_page_by_query_id(query_id, page, page_size)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+    let query_results = query_results
+        .into_iter()
+        .map(query_result_info)
+        .collect::<Result<Vec<QueryResultInfo>, serde_json::Error>>()
+        .map_err(|err| {
+            Status::internal(format!(
+                "failed to parse query result settings, internal error: {}",
+                err
+            ))
+        })?;
+
+    Ok(ListQueryResultsResponse {
+        query_results,
+        total,
+    })
+}
+
+fn query_job_info(job: query_job::Model) -> QueryJob {
+    let status = match job.status {
+        QueryJobStatus::Queued => QueryJobStatusInfo::Queued,
+        QueryJobStatus::Running => QueryJobStatusInfo::Running,
+        QueryJobStatus::Done => QueryJobStatusInfo::Done,
+        QueryJobStatus::Failed => QueryJobStatusInfo::Failed,
+        QueryJobStatus::Cancelled => QueryJobStatusInfo::Cancelled,
+    };
+
+    QueryJob {
+        id: job.id,
+        query_id: job.query_id,
+        project_id: job.project_id,
+        user_id: job.user_id,
+        status: status.into(),
+        result: job.result.map(|result| result.to_string()),
+        error: job.error,
+        created_at: job.created_at.and_utc().timestamp(),
+        updated_at: job.updated_at.and_utc().timestamp(),
+    }
+}
+
+fn query_result_info(
+    query_result: query_result::Model,
+) -> Result<QueryResultInfo, serde_json::Error> {
+    Ok(QueryResultInfo {
+        id: query_result.id,
+        query_id: query_result.query_id,
+        user_id: query_result.user_id,
+        result: query_result.result.to_string(),
+        settings: Some(serde_json::from_value(query_result.settings)?),
+        components_hash: query_result.components_hash,
+        created_at: query_result.created_at.and_utc().timestamp(),
+    })
 }
 
 #[cfg(test)]
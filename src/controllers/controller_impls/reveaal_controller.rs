@@ -1,60 +1,234 @@
+use crate::api::auth::RequestExt;
+use crate::api::server::protobuf::backend_health::Status as BackendStatus;
 use crate::api::server::protobuf::ecdar_backend_server::EcdarBackend;
 use crate::api::server::protobuf::{
-    QueryRequest, QueryResponse, SimulationStartRequest, SimulationStepRequest,
-    SimulationStepResponse, UserTokenResponse,
+    BackendHealth, GetBackendHealthResponse, QueryRequest, QueryResponse, SimulationStartRequest,
+    SimulationStepRequest, SimulationStepResponse, UserTokenResponse,
 };
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_traits::ReveaalControllerTrait;
+use crate::entities::backend_audit;
 use crate::services::service_collection::ServiceCollection;
+use crate::services::service_traits::ReveaalHealth;
 use async_trait::async_trait;
-use tonic::{Request, Response, Status};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tonic::{Code, Request, Response, Status};
 
 pub struct ReveaalController {
+    contexts: ContextCollection,
     services: ServiceCollection,
+    max_concurrent_requests_per_user: usize,
+    user_requests: Mutex<HashMap<i32, Arc<Semaphore>>>,
 }
 
 impl ReveaalController {
-    pub fn new(services: ServiceCollection) -> Self {
-        Self { services }
+    pub fn new(
+        contexts: ContextCollection,
+        services: ServiceCollection,
+        max_concurrent_requests_per_user: usize,
+    ) -> Self {
+        Self {
+            contexts,
+            services,
+            max_concurrent_requests_per_user,
+            user_requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the id of the user making the request, or `None` if it is made with the service credential.
+    fn caller<T>(request: &Request<T>) -> Result<Option<i32>, Status> {
+        if request.is_service() {
+            return Ok(None);
+        }
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        Ok(Some(uid))
+    }
+
+    /// Reserves one of the user's concurrent requests until the permit is dropped.
+    /// Requests made with the service credential are not limited.
+    async fn acquire(&self, uid: Option<i32>) -> Result<Option<OwnedSemaphorePermit>, Status> {
+        let uid = match uid {
+            Some(uid) => uid,
+            None => return Ok(None),
+        };
+
+        let semaphore = self
+            .user_requests
+            .lock()
+            .await
+            .entry(uid)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.max_concurrent_requests_per_user)))
+            .clone();
+
+        semaphore.try_acquire_owned().map(Some).map_err(|_| {
+            Status::resource_exhausted(format!(
+                "A user can have at most {} requests running on Reveaal at once",
+                self.max_concurrent_requests_per_user
+            ))
+        })
+    }
+
+    /// Forwards the request within the caller's limit and records the call in the audit log.
+    async fn forward<T, R, F, Fut>(
+        &self,
+        method: &str,
+        request: Request<T>,
+        forward: F,
+    ) -> Result<Response<R>, Status>
+    where
+        F: FnOnce(Request<T>, Option<i32>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        let uid = Self::caller(&request)?;
+
+        let res = match self.acquire(uid).await {
+            Ok(_permit) => forward(request, uid).await,
+            Err(err) => Err(err),
+        };
+
+        let code = match &res {
+            Ok(_) => Code::Ok,
+            Err(err) => err.code(),
+        };
+        let audit = backend_audit::Model {
+            id: Default::default(),
+            user_id: uid,
+            method: method.to_string(),
+            code: code as i32,
+            created_at: Default::default(),
+        };
+        if let Err(err) = self.contexts.backend_audit_context.create(audit).await {
+            log::error!("Failed to audit {} call, internal error: {}", method, err);
+        }
+
+        res
     }
 }
 
 #[async_trait]
 impl EcdarBackend for ReveaalController {
+    /// Returns the id of the calling user.
+    /// Only requests made with the service credential are forwarded to Reveaal.
     async fn get_user_token(
         &self,
         request: Request<()>,
     ) -> Result<Response<UserTokenResponse>, Status> {
-        self.services.reveaal_service.get_user_token(request).await
+        self.forward("GetUserToken", request, |request, uid| async move {
+            match uid {
+                Some(uid) => Ok(Response::new(UserTokenResponse { user_id: uid })),
+                None => self.services.reveaal_service.get_user_token(request).await,
+            }
+        })
+        .await
     }
-    /// Forwards the query to the Reveaal backend
+
+    /// Forwards the query to the Reveaal backend on behalf of the caller
     /// # Errors
-    /// Errors when Reveaal backend errors
+    /// Errors when Reveaal backend errors or the caller has too many requests running
     async fn send_query(
         &self,
         request: Request<QueryRequest>,
     ) -> Result<Response<QueryResponse>, Status> {
-        self.services.reveaal_service.send_query(request).await
+        self.forward("SendQuery", request, |mut request, uid| async move {
+            if let Some(uid) = uid {
+                request.get_mut().user_id = uid;
+            }
+            self.services.reveaal_service.send_query(request).await
+        })
+        .await
     }
-    /// Forwards the query to the Reveaal backend
+
+    /// Forwards the start of a simulation to the Reveaal backend on behalf of the caller
     /// # Errors
-    /// Errors when Reveaal backend errors
-    //TODO Bedre doc comment
+    /// Errors when Reveaal backend errors or the caller has too many requests running
     async fn start_simulation(
         &self,
         request: Request<SimulationStartRequest>,
     ) -> Result<Response<SimulationStepResponse>, Status> {
-        self.services
-            .reveaal_service
-            .start_simulation(request)
-            .await
+        self.forward("StartSimulation", request, |mut request, uid| async move {
+            if let (Some(uid), Some(simulation_info)) =
+                (uid, request.get_mut().simulation_info.as_mut())
+            {
+                simulation_info.user_id = uid;
+            }
+            self.services
+                .reveaal_service
+                .start_simulation(None, request)
+                .await
+        })
+        .await
     }
-    //TODO Bedre doc comment
+
+    /// Forwards a simulation step to the Reveaal backend on behalf of the caller
+    /// # Errors
+    /// Errors when Reveaal backend errors or the caller has too many requests running
     async fn take_simulation_step(
         &self,
         request: Request<SimulationStepRequest>,
     ) -> Result<Response<SimulationStepResponse>, Status> {
-        self.services
+        self.forward(
+            "TakeSimulationStep",
+            request,
+            |mut request, uid| async move {
+                if let (Some(uid), Some(simulation_info)) =
+                    (uid, request.get_mut().simulation_info.as_mut())
+                {
+                    simulation_info.user_id = uid;
+                }
+                self.services
+                    .reveaal_service
+                    .take_simulation_step(None, request)
+                    .await
+            },
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl ReveaalControllerTrait for ReveaalController {
+    async fn get_backend_health(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<GetBackendHealthResponse>, Status> {
+        let backends = self
+            .services
             .reveaal_service
-            .take_simulation_step(request)
-            .await
+            .health()
+            .into_iter()
+            .map(|(address, health)| {
+                let (status, error) = match health {
+                    ReveaalHealth::Unknown => (BackendStatus::Unknown, None),
+                    ReveaalHealth::Serving => (BackendStatus::Serving, None),
+                    ReveaalHealth::NotServing(reason) => (BackendStatus::NotServing, Some(reason)),
+                };
+                BackendHealth {
+                    address,
+                    status: status.into(),
+                    error,
+                }
+            })
+            .collect();
+
+        Ok(Response::new(GetBackendHealthResponse { backends }))
     }
 }
+
+#[cfg(test)]
+#[path = "../../tests/controllers/reveaal_controller.rs"]
+mod reveaal_controller_tests;
@@ -0,0 +1,238 @@
+use crate::api::auth::RequestExt;
+use crate::api::roles::{
+    parse_permissions, permissions_from_json, permissions_to_json, Permission, Role,
+};
+use crate::api::server::protobuf::{
+    CreateRoleRequest, DeleteRoleRequest, ListRolesResponse, RoleInfo, UpdateRoleRequest,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_traits::RoleControllerTrait;
+use crate::entities::role;
+use async_trait::async_trait;
+use std::str::FromStr;
+use tonic::{Code, Request, Response, Status};
+
+pub struct RoleController {
+    contexts: ContextCollection,
+    admin_ids: Vec<i32>,
+}
+
+impl RoleController {
+    /// Creates a role controller where the users with the given ids can manage the roles.
+    pub fn new(contexts: ContextCollection, admin_ids: Vec<i32>) -> Self {
+        RoleController {
+            contexts,
+            admin_ids,
+        }
+    }
+
+    /// Checks that the user of the request is an admin.
+    fn check_admin<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        if !self.admin_ids.contains(&uid) {
+            return Err(Status::permission_denied(
+                "Only admins can manage the roles",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn get_role_by_name(&self, name: String) -> Result<role::Model, Status> {
+        self.contexts
+            .role_context
+            .get_by_name(name)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No role found with given name"))
+    }
+}
+
+#[async_trait]
+impl RoleControllerTrait for RoleController {
+    async fn list_roles(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<ListRolesResponse>, Status> {
+        let roles = self
+            .contexts
+            .role_context
+            .get_all()
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(role_info)
+            .collect::<Result<Vec<RoleInfo>, Status>>()?;
+
+        Ok(Response::new(ListRolesResponse { roles }))
+    }
+
+    async fn create_role(
+        &self,
+        request: Request<CreateRoleRequest>,
+    ) -> Result<Response<RoleInfo>, Status> {
+        self.check_admin(&request)?;
+
+        let message = request.into_inner();
+
+        let name = message.name.trim().to_owned();
+        if name.is_empty() {
+            return Err(Status::invalid_argument("A role must have a name"));
+        }
+
+        let permissions =
+            parse_permissions(&message.permissions).map_err(Status::invalid_argument)?;
+
+        let existing_role = self
+            .contexts
+            .role_context
+            .get_by_name(name.clone())
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        if existing_role.is_some() || Role::from_str(&name).is_ok() {
+            return Err(Status::already_exists(format!(
+                "A role named '{}' already exists",
+                name
+            )));
+        }
+
+        let role = self
+            .contexts
+            .role_context
+            .create(role::Model {
+                name,
+                permissions: permissions_to_json(&permissions),
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(role_info(role)?))
+    }
+
+    async fn update_role(
+        &self,
+        request: Request<UpdateRoleRequest>,
+    ) -> Result<Response<RoleInfo>, Status> {
+        self.check_admin(&request)?;
+
+        let message = request.into_inner();
+
+        if Role::from_str(&message.name).is_ok() {
+            return Err(Status::failed_precondition(
+                "The permissions of the seeded roles cannot be changed",
+            ));
+        }
+
+        let permissions =
+            parse_permissions(&message.permissions).map_err(Status::invalid_argument)?;
+
+        let role = self.get_role_by_name(message.name).await?;
+
+        let role = self
+            .contexts
+            .role_context
+            .update(role::Model {
+                permissions: permissions_to_json(&permissions),
+                ..role
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(role_info(role)?))
+    }
+
+    async fn delete_role(
+        &self,
+        request: Request<DeleteRoleRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.check_admin(&request)?;
+
+        let message = request.into_inner();
+
+        if Role::from_str(&message.name).is_ok() {
+            return Err(Status::failed_precondition(
+                "The seeded roles cannot be deleted",
+            ));
+        }
+
+        let role = self.get_role_by_name(message.name).await?;
+
+        // Deleting a role deletes the access granted with it, so that has to be done deliberately first
+        let accesses = self
+            .contexts
+            .access_context
+            .get_access_by_role(role.name.clone())
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        let team_accesses = self
+            .contexts
+            .team_access_context
+            .get_team_access_by_role(role.name.clone())
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        if !accesses.is_empty() || !team_accesses.is_empty() {
+            return Err(Status::failed_precondition(format!(
+                "The role '{}' is still granted on {} project(s)",
+                role.name,
+                accesses.len() + team_accesses.len()
+            )));
+        }
+
+        // Deleting a role also deletes the invitations with it, so open invitations have to be revoked first
+        let invitations = self
+            .contexts
+            .invitation_context
+            .get_redeemable_by_role(role.name.clone())
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        if !invitations.is_empty() {
+            return Err(Status::failed_precondition(format!(
+                "The role '{}' is still offered by {} open invitation(s)",
+                role.name,
+                invitations.len()
+            )));
+        }
+
+        self.contexts
+            .role_context
+            .delete(role.name)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
+}
+
+fn role_info(role: role::Model) -> Result<RoleInfo, Status> {
+    let built_in = Role::from_str(&role.name).ok();
+
+    let permissions = match built_in {
+        Some(built_in) => built_in.permissions().to_vec(),
+        None => permissions_from_json(&role.permissions).map_err(|err| {
+            Status::internal(format!(
+                "failed to parse role permissions, internal error: {}",
+                err
+            ))
+        })?,
+    };
+
+    Ok(RoleInfo {
+        name: role.name,
+        permissions: permissions.iter().map(Permission::to_string).collect(),
+        built_in: built_in.is_some(),
+    })
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/role_controller.rs"]
+mod role_controller_tests;
@@ -0,0 +1,261 @@
+use crate::api::auth::RequestExt;
+use crate::api::server::protobuf::{
+    CreateShareLinkRequest, GetProjectResponse, GetSharedProjectRequest, ListQueryResultsResponse,
+    ListShareLinksRequest, ListShareLinksResponse, ListSharedQueryResultsRequest,
+    RevokeShareLinkRequest, ShareLinkInfo,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::project_controller::get_project_helper;
+use crate::controllers::controller_impls::query_controller::list_query_results_helper;
+use crate::controllers::controller_traits::ShareLinkControllerTrait;
+use crate::entities::{project, share_link};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use tonic::{Code, Request, Response, Status};
+use uuid::Uuid;
+
+/// The longest a share link can be valid for, which is 30 days.
+const MAX_LIFETIME_SECS: u32 = 30 * 24 * 60 * 60;
+
+pub struct ShareLinkController {
+    contexts: ContextCollection,
+}
+
+impl ShareLinkController {
+    pub fn new(contexts: ContextCollection) -> Self {
+        ShareLinkController { contexts }
+    }
+
+    /// Returns the project, as long as the user owns it.
+    async fn owned_project(&self, uid: i32, project_id: i32) -> Result<project::Model, Status> {
+        let project = self
+            .contexts
+            .project_context
+            .get_by_id(project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::not_found("No project found with given id"))?;
+
+        if project.owner_id != uid {
+            return Err(Status::permission_denied(
+                "Only the owner of the project can manage its share links",
+            ));
+        }
+
+        Ok(project)
+    }
+
+    /// Returns the share link with the token, as long as it has not been revoked or expired.
+    /// The same error is given whatever the reason, so tokens cannot be probed.
+    async fn usable_share_link(&self, token: String) -> Result<share_link::Model, Status> {
+        self.contexts
+            .share_link_context
+            .get_by_token(token)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .filter(|share_link| {
+                !share_link.revoked
+                    && match share_link.expires_at {
+                        Some(expires_at) => expires_at > Utc::now().naive_utc(),
+                        None => true,
+                    }
+            })
+            .ok_or_else(|| Status::not_found("The share link is invalid or has expired"))
+    }
+}
+
+#[async_trait]
+impl ShareLinkControllerTrait for ShareLinkController {
+    async fn create_share_link(
+        &self,
+        request: Request<CreateShareLinkRequest>,
+    ) -> Result<Response<ShareLinkInfo>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let lifetime = match message.expires_in_secs {
+            Some(0) => {
+                return Err(Status::invalid_argument(
+                    "A share link must be valid for at least 1 second",
+                ))
+            }
+            Some(secs) if secs > MAX_LIFETIME_SECS => {
+                return Err(Status::invalid_argument(format!(
+                    "A share link can be valid for at most {} seconds",
+                    MAX_LIFETIME_SECS
+                )))
+            }
+            Some(secs) => Some(Duration::seconds(secs.into())),
+            None => None,
+        };
+
+        let project = self.owned_project(uid, message.project_id).await?;
+
+        // Version 4 uuids are drawn from the random number generator of the operating system
+        let share_link = self
+            .contexts
+            .share_link_context
+            .create(share_link::Model {
+                id: Default::default(),
+                project_id: project.id,
+                token: Uuid::new_v4().simple().to_string(),
+                revoked: false,
+                expires_at: lifetime.map(|lifetime| (Utc::now() + lifetime).naive_utc()),
+                created_at: Default::default(),
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(share_link_info(share_link)))
+    }
+
+    async fn list_share_links(
+        &self,
+        request: Request<ListShareLinksRequest>,
+    ) -> Result<Response<ListShareLinksResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let project = self.owned_project(uid, message.project_id).await?;
+
+        let share_links = self
+            .contexts
+            .share_link_context
+            .get_all_by_project_id(project.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(share_link_info)
+            .collect();
+
+        Ok(Response::new(ListShareLinksResponse { share_links }))
+    }
+
+    async fn revoke_share_link(
+        &self,
+        request: Request<RevokeShareLinkRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let share_link = self
+            .contexts
+            .share_link_context
+            .get_by_id(message.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::not_found("No share link found with given id"))?;
+
+        self.owned_project(uid, share_link.project_id).await?;
+
+        if share_link.revoked {
+            return Ok(Response::new(()));
+        }
+
+        self.contexts
+            .share_link_context
+            .update(share_link::Model {
+                revoked: true,
+                ..share_link
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn get_shared_project(
+        &self,
+        request: Request<GetSharedProjectRequest>,
+    ) -> Result<Response<GetProjectResponse>, Status> {
+        let message = request.into_inner();
+
+        let share_link = self.usable_share_link(message.token).await?;
+
+        // Viewers of a share link are anonymous, so they are not told who owns or is editing the project
+        let mut response = get_project_helper(&self.contexts, share_link.project_id).await?;
+        if let Some(project) = response.project.as_mut() {
+            project.owner_id = Default::default();
+        }
+        response.in_use = false;
+
+        Ok(Response::new(response))
+    }
+
+    async fn list_shared_query_results(
+        &self,
+        request: Request<ListSharedQueryResultsRequest>,
+    ) -> Result<Response<ListQueryResultsResponse>, Status> {
+        let message = request.into_inner();
+
+        let share_link = self.usable_share_link(message.token).await?;
+
+        let query = self
+            .contexts
+            .query_context
+            .get_by_id(message.query_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .filter(|query| query.project_id == share_link.project_id)
+            .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+        let mut response =
+            list_query_results_helper(&self.contexts, query.id, message.page, message.page_size)
+                .await?;
+        for query_result in &mut response.query_results {
+            query_result.user_id = None;
+        }
+
+        Ok(Response::new(response))
+    }
+}
+
+fn share_link_info(share_link: share_link::Model) -> ShareLinkInfo {
+    ShareLinkInfo {
+        id: share_link.id,
+        project_id: share_link.project_id,
+        token: share_link.token,
+        revoked: share_link.revoked,
+        expires_at: share_link
+            .expires_at
+            .map(|expires_at| expires_at.and_utc().timestamp()),
+        created_at: share_link.created_at.and_utc().timestamp(),
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/share_link_controller.rs"]
+mod share_link_controller_tests;
@@ -0,0 +1,687 @@
+use crate::api::auth::RequestExt;
+use crate::api::counterexample::simulation_composition;
+use crate::api::roles::Permission;
+use crate::api::server::protobuf::{
+    CreateSimulationSessionRequest, Decision, DeleteSimulationSessionRequest,
+    ExportSimulationTraceRequest, ExportSimulationTraceResponse, GetSimulationSessionRequest,
+    GetSimulationSessionResponse, ListSimulationSessionsRequest, ListSimulationSessionsResponse,
+    RenameSimulationSessionRequest, ReplaySimulationTraceRequest, ReplaySimulationTraceResponse,
+    SimulationInfo, SimulationSession, SimulationSessionStep, SimulationStartRequest,
+    SimulationStepRequest, SimulationStepResponse, SimulationTraceDivergence,
+    StartCounterexampleSimulationRequest, TakeSimulationSessionStepRequest,
+};
+use crate::api::simulation_trace::{same_decision_points, SimulationTrace, SimulationTraceStep};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_impls::access_controller::authorize_helper;
+use crate::controllers::controller_traits::SimulationControllerTrait;
+use crate::entities::{simulation_session, simulation_step};
+use crate::services::service_collection::ServiceCollection;
+use async_trait::async_trait;
+use tonic::{Code, Request, Response, Status};
+
+pub struct SimulationController {
+    contexts: ContextCollection,
+    services: ServiceCollection,
+}
+
+impl SimulationController {
+    pub fn new(contexts: ContextCollection, services: ServiceCollection) -> Self {
+        Self { contexts, services }
+    }
+
+    /// Returns a simulation session of the user in a project the user still has access to.
+    /// Sessions of other users are reported as not found, as they are private.
+    async fn get_own_session(
+        &self,
+        uid: i32,
+        id: i32,
+    ) -> Result<simulation_session::Model, Status> {
+        let simulation_session = self
+            .contexts
+            .simulation_session_context
+            .get_by_id(id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .filter(|simulation_session| simulation_session.user_id == uid)
+            .ok_or_else(|| Status::new(Code::NotFound, "Simulation session not found"))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            simulation_session.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
+
+        Ok(simulation_session)
+    }
+
+    /// Returns the simulation info Reveaal needs to simulate the composition on the project's current components.
+    async fn simulation_info(
+        &self,
+        uid: i32,
+        project_id: i32,
+        component_composition: String,
+    ) -> Result<SimulationInfo, Status> {
+        let components_info = self
+            .contexts
+            .component_context
+            .get_components_info_by_project_id(project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(SimulationInfo {
+            component_composition,
+            components_info: Some(components_info),
+            user_id: uid,
+        })
+    }
+
+    /// Stores a step of a simulation session and marks the session as updated.
+    async fn store_step(
+        &self,
+        simulation_session: simulation_session::Model,
+        chosen_decision: Option<Decision>,
+        decision_points: Vec<Decision>,
+    ) -> Result<simulation_step::Model, Status> {
+        let step = simulation_step::Model {
+            id: Default::default(),
+            simulation_session_id: simulation_session.id,
+            chosen_decision: chosen_decision
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(serialize_error)?,
+            decision_points: serde_json::to_value(decision_points).map_err(serialize_error)?,
+            created_at: Default::default(),
+        };
+
+        let step = self
+            .contexts
+            .simulation_step_context
+            .create(step)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        self.contexts
+            .simulation_session_context
+            .update(simulation_session)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(step)
+    }
+
+    /// Starts a simulation of the composition on Reveaal and stores it as a new session with its initial step.
+    async fn start_session(
+        &self,
+        uid: i32,
+        project_id: i32,
+        name: String,
+        component_composition: String,
+    ) -> Result<GetSimulationSessionResponse, Status> {
+        let simulation_info = self
+            .simulation_info(uid, project_id, component_composition.clone())
+            .await?;
+
+        let simulation_session = self
+            .contexts
+            .simulation_session_context
+            .create(simulation_session::Model {
+                id: Default::default(),
+                project_id,
+                user_id: uid,
+                name,
+                component_composition,
+                created_at: Default::default(),
+                updated_at: Default::default(),
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        // The session is created first, so the simulation can be pinned to its id
+        let response = match self
+            .services
+            .reveaal_service
+            .start_simulation(
+                Some(simulation_session.id),
+                Request::new(SimulationStartRequest {
+                    simulation_info: Some(simulation_info),
+                }),
+            )
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                self.contexts
+                    .simulation_session_context
+                    .delete(simulation_session.id)
+                    .await
+                    .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+                return Err(status);
+            }
+        };
+
+        let step = self
+            .store_step(
+                simulation_session.clone(),
+                None,
+                response.new_decision_points,
+            )
+            .await?;
+
+        Ok(GetSimulationSessionResponse {
+            simulation_session: Some(simulation_session_info(simulation_session)),
+            steps: vec![simulation_session_step(step).map_err(parse_error)?],
+        })
+    }
+}
+
+#[async_trait]
+impl SimulationControllerTrait for SimulationController {
+    async fn create_simulation_session(
+        &self,
+        request: Request<CreateSimulationSessionRequest>,
+    ) -> Result<Response<GetSimulationSessionResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        if message.component_composition.trim().is_empty() {
+            return Err(Status::invalid_argument(
+                "Component composition cannot be empty",
+            ));
+        }
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
+
+        let name = match message.name.trim() {
+            "" => message.component_composition.clone(),
+            name => name.to_owned(),
+        };
+
+        Ok(Response::new(
+            self.start_session(uid, message.project_id, name, message.component_composition)
+                .await?,
+        ))
+    }
+
+    async fn take_simulation_session_step(
+        &self,
+        request: Request<TakeSimulationSessionStepRequest>,
+    ) -> Result<Response<SimulationSessionStep>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let simulation_session = self.get_own_session(uid, message.id).await?;
+
+        let chosen_decision = message
+            .chosen_decision
+            .ok_or_else(|| Status::invalid_argument("No decision was chosen"))?;
+
+        let steps = self
+            .contexts
+            .simulation_step_context
+            .get_all_by_simulation_session_id(simulation_session.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        let decision_points: Vec<Decision> = match steps.last() {
+            Some(step) => {
+                serde_json::from_value(step.decision_points.clone()).map_err(parse_error)?
+            }
+            None => vec![],
+        };
+
+        if !decision_points.contains(&chosen_decision) {
+            return Err(Status::invalid_argument(
+                "Decision is not one of the current decision points",
+            ));
+        }
+
+        let simulation_info = self
+            .simulation_info(
+                uid,
+                simulation_session.project_id,
+                simulation_session.component_composition.clone(),
+            )
+            .await?;
+
+        let response = self
+            .services
+            .reveaal_service
+            .take_simulation_step(
+                Some(simulation_session.id),
+                Request::new(SimulationStepRequest {
+                    simulation_info: Some(simulation_info),
+                    chosen_decision: Some(chosen_decision.clone()),
+                }),
+            )
+            .await?
+            .into_inner();
+
+        let step = self
+            .store_step(
+                simulation_session,
+                Some(chosen_decision),
+                response.new_decision_points,
+            )
+            .await?;
+
+        Ok(Response::new(
+            simulation_session_step(step).map_err(parse_error)?,
+        ))
+    }
+
+    async fn get_simulation_session(
+        &self,
+        request: Request<GetSimulationSessionRequest>,
+    ) -> Result<Response<GetSimulationSessionResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let simulation_session = self.get_own_session(uid, message.id).await?;
+
+        let steps = self
+            .contexts
+            .simulation_step_context
+            .get_all_by_simulation_session_id(simulation_session.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(simulation_session_step)
+            .collect::<Result<Vec<SimulationSessionStep>, serde_json::Error>>()
+            .map_err(parse_error)?;
+
+        Ok(Response::new(GetSimulationSessionResponse {
+            simulation_session: Some(simulation_session_info(simulation_session)),
+            steps,
+        }))
+    }
+
+    async fn list_simulation_sessions(
+        &self,
+        request: Request<ListSimulationSessionsRequest>,
+    ) -> Result<Response<ListSimulationSessionsResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
+
+        let simulation_sessions = self
+            .contexts
+            .simulation_session_context
+            .get_all_by_project_id_and_user_id(message.project_id, uid)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(simulation_session_info)
+            .collect();
+
+        Ok(Response::new(ListSimulationSessionsResponse {
+            simulation_sessions,
+        }))
+    }
+
+    async fn rename_simulation_session(
+        &self,
+        request: Request<RenameSimulationSessionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let name = message.name.trim();
+        if name.is_empty() {
+            return Err(Status::invalid_argument("Name cannot be empty"));
+        }
+
+        let simulation_session = self.get_own_session(uid, message.id).await?;
+
+        self.contexts
+            .simulation_session_context
+            .update(simulation_session::Model {
+                name: name.to_owned(),
+                ..simulation_session
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn delete_simulation_session(
+        &self,
+        request: Request<DeleteSimulationSessionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let simulation_session = self.get_own_session(uid, message.id).await?;
+
+        self.contexts
+            .simulation_session_context
+            .delete(simulation_session.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        self.services
+            .reveaal_service
+            .end_simulation(simulation_session.id)
+            .await;
+
+        Ok(Response::new(()))
+    }
+
+    async fn export_simulation_trace(
+        &self,
+        request: Request<ExportSimulationTraceRequest>,
+    ) -> Result<Response<ExportSimulationTraceResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let simulation_session = self.get_own_session(uid, message.id).await?;
+
+        let steps = self
+            .contexts
+            .simulation_step_context
+            .get_all_by_simulation_session_id(simulation_session.id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(|step| {
+                Ok(SimulationTraceStep {
+                    chosen_decision: step
+                        .chosen_decision
+                        .map(serde_json::from_value)
+                        .transpose()?,
+                    decision_points: serde_json::from_value(step.decision_points)?,
+                })
+            })
+            .collect::<Result<Vec<SimulationTraceStep>, serde_json::Error>>()
+            .map_err(parse_error)?;
+
+        let trace = SimulationTrace::new(simulation_session.component_composition, steps);
+
+        Ok(Response::new(ExportSimulationTraceResponse {
+            trace: serde_json::to_string(&trace).map_err(serialize_error)?,
+        }))
+    }
+
+    async fn replay_simulation_trace(
+        &self,
+        request: Request<ReplaySimulationTraceRequest>,
+    ) -> Result<Response<ReplaySimulationTraceResponse>, Status> {
+        let message = request.get_ref();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let trace = SimulationTrace::parse(&message.trace).map_err(Status::invalid_argument)?;
+
+        authorize_helper(
+            &self.contexts,
+            uid,
+            message.project_id,
+            Permission::RunQuery,
+        )
+        .await?;
+
+        let simulation_info = self
+            .simulation_info(uid, message.project_id, trace.component_composition)
+            .await?;
+
+        for (index, step) in trace.steps.into_iter().enumerate() {
+            let response = match step.chosen_decision {
+                None => {
+                    self.services
+                        .reveaal_service
+                        .start_simulation(
+                            None,
+                            Request::new(SimulationStartRequest {
+                                simulation_info: Some(simulation_info.clone()),
+                            }),
+                        )
+                        .await
+                }
+                Some(chosen_decision) => {
+                    self.services
+                        .reveaal_service
+                        .take_simulation_step(
+                            None,
+                            Request::new(SimulationStepRequest {
+                                simulation_info: Some(simulation_info.clone()),
+                                chosen_decision: Some(chosen_decision),
+                            }),
+                        )
+                        .await
+                }
+            };
+
+            let divergence = match replayed_decision_points(response)? {
+                Ok(actual) if same_decision_points(&step.decision_points, &actual) => continue,
+                Ok(actual) => SimulationTraceDivergence {
+                    step: index as u32,
+                    reason: "Decision points differ from the trace".to_owned(),
+                    expected_decision_points: step.decision_points,
+                    actual_decision_points: actual,
+                },
+                Err(reason) => SimulationTraceDivergence {
+                    step: index as u32,
+                    reason,
+                    expected_decision_points: step.decision_points,
+                    actual_decision_points: vec![],
+                },
+            };
+
+            return Ok(Response::new(ReplaySimulationTraceResponse {
+                divergence: Some(divergence),
+            }));
+        }
+
+        Ok(Response::new(ReplaySimulationTraceResponse {
+            divergence: None,
+        }))
+    }
+
+    async fn start_counterexample_simulation(
+        &self,
+        request: Request<StartCounterexampleSimulationRequest>,
+    ) -> Result<Response<GetSimulationSessionResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let counterexample = self
+            .contexts
+            .counterexample_context
+            .get_by_id(message.counterexample_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Counterexample not found"))?;
+
+        let query = self
+            .contexts
+            .query_context
+            .get_by_id(counterexample.query_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+        authorize_helper(&self.contexts, uid, query.project_id, Permission::RunQuery).await?;
+
+        let component_composition = simulation_composition(&counterexample).ok_or_else(|| {
+            Status::failed_precondition("Counterexample has no system to simulate")
+        })?;
+
+        let name = match message.name.trim() {
+            "" => format!("Counterexample of {}", query.string),
+            name => name.to_owned(),
+        };
+
+        Ok(Response::new(
+            self.start_session(uid, query.project_id, name, component_composition)
+                .await?,
+        ))
+    }
+}
+
+/// Returns the decision points Reveaal answered a replayed step with,
+/// or the reason Reveaal rejected the step, which makes the replay diverge.
+/// # Errors
+/// Errors if Reveaal could not be asked, e.g. because it is unavailable.
+fn replayed_decision_points(
+    response: Result<Response<SimulationStepResponse>, Status>,
+) -> Result<Result<Vec<Decision>, String>, Status> {
+    match response {
+        Ok(response) => Ok(Ok(response.into_inner().new_decision_points)),
+        Err(err) if err.code() == Code::InvalidArgument => Ok(Err(err.message().to_owned())),
+        Err(err) => Err(err),
+    }
+}
+
+fn serialize_error(err: serde_json::Error) -> Status {
+    Status::internal(format!(
+        "error serializing simulation step, internal error: {}",
+        err
+    ))
+}
+
+fn parse_error(err: serde_json::Error) -> Status {
+    Status::internal(format!(
+        "error parsing simulation step, internal error: {}",
+        err
+    ))
+}
+
+fn simulation_session_info(simulation_session: simulation_session::Model) -> SimulationSession {
+    SimulationSession {
+        id: simulation_session.id,
+        project_id: simulation_session.project_id,
+        name: simulation_session.name,
+        component_composition: simulation_session.component_composition,
+        created_at: simulation_session.created_at.and_utc().timestamp(),
+        updated_at: simulation_session.updated_at.and_utc().timestamp(),
+    }
+}
+
+fn simulation_session_step(
+    step: simulation_step::Model,
+) -> Result<SimulationSessionStep, serde_json::Error> {
+    Ok(SimulationSessionStep {
+        chosen_decision: step
+            .chosen_decision
+            .map(serde_json::from_value)
+            .transpose()?,
+        decision_points: serde_json::from_value(step.decision_points)?,
+    })
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/simulation_controller.rs"]
+mod simulation_controller_tests;
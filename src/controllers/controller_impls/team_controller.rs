@@ -0,0 +1,315 @@
+use crate::api::auth::RequestExt;
+use crate::api::server::protobuf::{
+    AddTeamMembersRequest, CreateTeamRequest, CreateTeamResponse, DeleteTeamRequest,
+    GetTeamRequest, GetTeamResponse, ListTeamsResponse, RemoveTeamMemberRequest, TeamInfo,
+    TeamMemberInfo,
+};
+use crate::contexts::context_collection::ContextCollection;
+use crate::controllers::controller_traits::TeamControllerTrait;
+use crate::entities::team;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use tonic::{Code, Request, Response, Status};
+
+pub struct TeamController {
+    contexts: ContextCollection,
+}
+
+impl TeamController {
+    pub fn new(contexts: ContextCollection) -> Self {
+        TeamController { contexts }
+    }
+
+    async fn get_team_by_id(&self, id: i32) -> Result<team::Model, Status> {
+        self.contexts
+            .team_context
+            .get_by_id(id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .ok_or_else(|| Status::new(Code::NotFound, "No team found with given id"))
+    }
+
+    /// Returns the ids of the members of a team.
+    async fn get_member_ids(&self, team_id: i32) -> Result<Vec<i32>, Status> {
+        Ok(self
+            .contexts
+            .team_context
+            .get_members(team_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(|member| member.user_id)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TeamControllerTrait for TeamController {
+    async fn create_team(
+        &self,
+        request: Request<CreateTeamRequest>,
+    ) -> Result<Response<CreateTeamResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let name = message.name.trim().to_owned();
+        if name.is_empty() {
+            return Err(Status::invalid_argument("A team must have a name"));
+        }
+
+        let team = self
+            .contexts
+            .team_context
+            .create(team::Model {
+                id: Default::default(),
+                name,
+                owner_id: uid,
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        self.contexts
+            .team_context
+            .add_members(team.id, vec![uid])
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(CreateTeamResponse { team_id: team.id }))
+    }
+
+    async fn get_team(
+        &self,
+        request: Request<GetTeamRequest>,
+    ) -> Result<Response<GetTeamResponse>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let team = self.get_team_by_id(message.team_id).await?;
+
+        let member_ids = self.get_member_ids(team.id).await?;
+        if !member_ids.contains(&uid) {
+            return Err(Status::permission_denied(
+                "User is not a member of the team",
+            ));
+        }
+
+        let members = self
+            .contexts
+            .user_context
+            .get_by_ids(member_ids)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(|user| TeamMemberInfo {
+                user_id: user.id,
+                username: user.username,
+            })
+            .collect();
+
+        Ok(Response::new(GetTeamResponse {
+            team: Some(team_info(team)),
+            members,
+        }))
+    }
+
+    async fn list_teams(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<ListTeamsResponse>, Status> {
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let teams = self
+            .contexts
+            .team_context
+            .get_all_by_uid(uid)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+            .into_iter()
+            .map(team_info)
+            .collect();
+
+        Ok(Response::new(ListTeamsResponse { teams }))
+    }
+
+    async fn delete_team(
+        &self,
+        request: Request<DeleteTeamRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let team = self.get_team_by_id(message.team_id).await?;
+
+        if team.owner_id != uid {
+            return Err(Status::permission_denied(
+                "Only the owner of a team can delete it",
+            ));
+        }
+
+        match self.contexts.team_context.delete(team.id).await {
+            Ok(_) => Ok(Response::new(())),
+            Err(DbErr::RecordNotFound(message)) => Err(Status::new(Code::NotFound, message)),
+            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+        }
+    }
+
+    async fn add_team_members(
+        &self,
+        request: Request<AddTeamMembersRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        if message.usernames.is_empty() {
+            return Err(Status::invalid_argument("No usernames provided"));
+        }
+
+        let team = self.get_team_by_id(message.team_id).await?;
+
+        if team.owner_id != uid {
+            return Err(Status::permission_denied(
+                "Only the owner of a team can add members to it",
+            ));
+        }
+
+        let member_ids = self.get_member_ids(team.id).await?;
+
+        // Every username is looked up before anyone is added, so a typo does not leave the team half updated
+        let mut user_ids = vec![];
+        for username in message.usernames {
+            let user = self
+                .contexts
+                .user_context
+                .get_by_username(username.clone())
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+                .ok_or_else(|| {
+                    Status::not_found(format!("No user found with username '{}'", username))
+                })?;
+
+            if !member_ids.contains(&user.id) && !user_ids.contains(&user.id) {
+                user_ids.push(user.id);
+            }
+        }
+
+        self.contexts
+            .team_context
+            .add_members(team.id, user_ids)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn remove_team_member(
+        &self,
+        request: Request<RemoveTeamMemberRequest>,
+    ) -> Result<Response<()>, Status> {
+        let message = request.get_ref().clone();
+
+        let uid = request
+            .uid()
+            .map_err(|err| {
+                Status::internal(format!(
+                    "could not stringify user id in request metadata, internal error {}",
+                    err
+                ))
+            })?
+            .ok_or(Status::internal(
+                "failed to get user id from request metadata",
+            ))?;
+
+        let team = self.get_team_by_id(message.team_id).await?;
+
+        if message.user_id == team.owner_id {
+            return Err(Status::failed_precondition(
+                "The owner of a team cannot be removed from it",
+            ));
+        }
+
+        if uid != team.owner_id && uid != message.user_id {
+            return Err(Status::permission_denied(
+                "Only the owner of a team can remove other members from it",
+            ));
+        }
+
+        match self
+            .contexts
+            .team_context
+            .remove_member(team.id, message.user_id)
+            .await
+        {
+            Ok(_) => Ok(Response::new(())),
+            Err(DbErr::RecordNotFound(_)) => {
+                Err(Status::not_found("The user is not a member of the team"))
+            }
+            Err(error) => Err(Status::new(Code::Internal, error.to_string())),
+        }
+    }
+}
+
+fn team_info(team: team::Model) -> TeamInfo {
+    TeamInfo {
+        id: team.id,
+        name: team.name,
+        owner_id: team.owner_id,
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/controllers/team_controller.rs"]
+mod team_controller_tests;
@@ -1,6 +1,6 @@
 use crate::api::server::protobuf::{
-    CreateAccessRequest, DeleteAccessRequest, ListAccessInfoRequest, ListAccessInfoResponse,
-    UpdateAccessRequest,
+    CreateAccessRequest, CreateTeamAccessRequest, DeleteAccessRequest, DeleteTeamAccessRequest,
+    ListAccessInfoRequest, ListAccessInfoResponse, UpdateAccessRequest, UpdateTeamAccessRequest,
 };
 use async_trait::async_trait;
 use tonic::{Request, Response, Status};
@@ -42,4 +42,29 @@ pub trait AccessControllerTrait: Send + Sync {
         &self,
         request: Request<DeleteAccessRequest>,
     ) -> Result<Response<()>, Status>;
+
+    /// Grants a team access to a project, giving its members the role on it.
+    /// # Errors
+    /// Errors if the user may not manage access to the project, the team or role does not exist,
+    /// or the team already has access to the project.
+    async fn create_team_access(
+        &self,
+        request: Request<CreateTeamAccessRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Changes the role a team has on a project.
+    /// # Errors
+    /// Errors if the team access or role does not exist, or the user may not manage access to the project.
+    async fn update_team_access(
+        &self,
+        request: Request<UpdateTeamAccessRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Revokes the access of a team to a project.
+    /// # Errors
+    /// Errors if the team access does not exist, or the user may not manage access to the project.
+    async fn delete_team_access(
+        &self,
+        request: Request<DeleteTeamAccessRequest>,
+    ) -> Result<Response<()>, Status>;
 }
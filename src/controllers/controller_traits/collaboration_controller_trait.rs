@@ -0,0 +1,24 @@
+use crate::api::server::protobuf::ProjectEditOperation;
+use crate::services::service_traits::ProjectEditStream;
+use async_trait::async_trait;
+use futures::Stream;
+use std::pin::Pin;
+use tonic::{Request, Response, Status};
+
+/// A stream of the edits a collaborator wants to make to a project.
+pub type ProjectEditOperationStream =
+    Pin<Box<dyn Stream<Item = Result<ProjectEditOperation, Status>> + Send>>;
+
+#[async_trait]
+pub trait CollaborationControllerTrait: Send + Sync {
+    /// Lets an editor change the components of a collaborative project together with other editors.
+    /// The operations of all collaborators are applied one at a time, and each applied operation
+    /// is sent to every collaborator along with the revision it produced.
+    /// Operations that could not be applied are answered with an error to their author only.
+    /// # Errors
+    /// Errors if the first operation does not name a collaborative project the user can edit.
+    async fn collaborate_on_project(
+        &self,
+        request: Request<ProjectEditOperationStream>,
+    ) -> Result<Response<ProjectEditStream>, Status>;
+}
@@ -0,0 +1,57 @@
+use crate::api::server::protobuf::{
+    Comment, CreateCommentRequest, DeleteCommentRequest, ListCommentsRequest, ListCommentsResponse,
+    ReplyToCommentRequest, ResolveCommentRequest, UpdateCommentRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait CommentControllerTrait: Send + Sync {
+    /// Starts a comment thread on a project, optionally anchored to a component, a location or edge in it, or a query.
+    /// # Errors
+    /// Errors if the user is not an Editor or Commenter of the project, or the anchor is not in the project.
+    async fn create_comment(
+        &self,
+        request: Request<CreateCommentRequest>,
+    ) -> Result<Response<Comment>, Status>;
+
+    /// Adds a reply to the thread of a comment.
+    /// # Errors
+    /// Errors if the user is not an Editor or Commenter of the project of the comment.
+    async fn reply_to_comment(
+        &self,
+        request: Request<ReplyToCommentRequest>,
+    ) -> Result<Response<Comment>, Status>;
+
+    /// Marks the thread of a comment as resolved or reopens it, and returns the comment that starts the thread.
+    /// # Errors
+    /// Errors if the user is not an Editor or Commenter of the project of the comment.
+    async fn resolve_comment(
+        &self,
+        request: Request<ResolveCommentRequest>,
+    ) -> Result<Response<Comment>, Status>;
+
+    /// Changes the text of a comment.
+    /// # Errors
+    /// Errors if the user is not the author of the comment or can no longer write comments on the project.
+    async fn update_comment(
+        &self,
+        request: Request<UpdateCommentRequest>,
+    ) -> Result<Response<Comment>, Status>;
+
+    /// Deletes a comment along with its replies.
+    /// # Errors
+    /// Errors if the user is neither the author of the comment nor the owner of the project.
+    async fn delete_comment(
+        &self,
+        request: Request<DeleteCommentRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Returns the comment threads of a project, oldest first.
+    /// # Errors
+    /// Errors if the user does not have access to the project.
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status>;
+}
@@ -0,0 +1,41 @@
+use crate::api::server::protobuf::{
+    CreateComponentRequest, CreateComponentResponse, DeleteComponentRequest, GetComponentRequest,
+    GetComponentResponse, UpdateComponentRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait ComponentControllerTrait: Send + Sync {
+    /// Adds a single component to a project, the component is stored under its name.
+    /// # Errors
+    /// Errors on a component without a name, a name that is already taken or invalid rights.
+    async fn create_component(
+        &self,
+        request: Request<CreateComponentRequest>,
+    ) -> Result<Response<CreateComponentResponse>, Status>;
+
+    /// Returns a single component of a project.
+    /// # Errors
+    /// Errors on non existent component or missing access to the project.
+    async fn get_component(
+        &self,
+        request: Request<GetComponentRequest>,
+    ) -> Result<Response<GetComponentResponse>, Status>;
+
+    /// Replaces a single component of a project, leaving the other components untouched.
+    /// # Errors
+    /// Errors on non existent component, a component without a name, a name that is already taken or invalid rights.
+    async fn update_component(
+        &self,
+        request: Request<UpdateComponentRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Removes a single component from a project.
+    /// # Errors
+    /// Errors on non existent component or invalid rights.
+    async fn delete_component(
+        &self,
+        request: Request<DeleteComponentRequest>,
+    ) -> Result<Response<()>, Status>;
+}
@@ -0,0 +1,45 @@
+use crate::api::server::protobuf::{
+    AcceptInvitationRequest, AcceptInvitationResponse, CreateInvitationRequest,
+    CreateInvitationResponse, ListInvitationsRequest, ListInvitationsResponse,
+    RevokeInvitationRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait InvitationControllerTrait: Send + Sync {
+    /// Creates an invitation to a project with a role, returning the signed token to share with the invitees.
+    /// The invitation can be used once unless more uses are asked for, and expires after a week unless told otherwise.
+    /// # Errors
+    /// Errors if the user may not manage access to the project, the role does not exist,
+    /// or the number of uses or the expiry is out of range.
+    async fn create_invitation(
+        &self,
+        request: Request<CreateInvitationRequest>,
+    ) -> Result<Response<CreateInvitationResponse>, Status>;
+
+    /// Returns the invitations to a project, including revoked, expired and used up ones.
+    /// # Errors
+    /// Errors if the user may not manage access to the project.
+    async fn list_invitations(
+        &self,
+        request: Request<ListInvitationsRequest>,
+    ) -> Result<Response<ListInvitationsResponse>, Status>;
+
+    /// Revokes an invitation, so its token can no longer be accepted.
+    /// # Errors
+    /// Errors if the invitation does not exist or the user may not manage access to its project.
+    async fn revoke_invitation(
+        &self,
+        request: Request<RevokeInvitationRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Accepts an invitation, giving the user the role of the invitation on its project.
+    /// # Errors
+    /// Errors if the token is invalid, the invitation has been revoked, has expired or is used up,
+    /// or the user already has access to the project.
+    async fn accept_invitation(
+        &self,
+        request: Request<AcceptInvitationRequest>,
+    ) -> Result<Response<AcceptInvitationResponse>, Status>;
+}
@@ -0,0 +1,41 @@
+use crate::api::server::protobuf::{
+    AcquireLockRequest, ForceReleaseRequest, HeartbeatRequest, LockInfo, ReleaseLockRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait LockControllerTrait: Send + Sync {
+    /// Gives the session of the request the edit lock on a project for the length of a lease.
+    /// Acquiring a lock the session already holds starts a new lease.
+    /// # Errors
+    /// Errors if the lock is held by another session, on an invalid lease length or invalid rights.
+    async fn acquire_lock(
+        &self,
+        request: Request<AcquireLockRequest>,
+    ) -> Result<Response<LockInfo>, Status>;
+
+    /// Extends the edit lock held by the session of the request with a new lease.
+    /// # Errors
+    /// Errors if the session does not hold the lock or on an invalid lease length.
+    async fn heartbeat(
+        &self,
+        request: Request<HeartbeatRequest>,
+    ) -> Result<Response<LockInfo>, Status>;
+
+    /// Releases the edit lock held by the session of the request.
+    /// # Errors
+    /// Errors if the session does not hold the lock.
+    async fn release_lock(
+        &self,
+        request: Request<ReleaseLockRequest>,
+    ) -> Result<Response<LockInfo>, Status>;
+
+    /// Releases the edit lock on a project no matter which session holds it.
+    /// # Errors
+    /// Errors if the user is not the owner of the project.
+    async fn force_release(
+        &self,
+        request: Request<ForceReleaseRequest>,
+    ) -> Result<Response<LockInfo>, Status>;
+}
@@ -1,11 +1,33 @@
 mod access_controller_trait;
+mod collaboration_controller_trait;
+mod comment_controller_trait;
+mod component_controller_trait;
+mod invitation_controller_trait;
+mod lock_controller_trait;
 mod project_controller_trait;
 mod query_controller_trait;
+mod reveaal_controller_trait;
+mod role_controller_trait;
 mod session_controller_trait;
+mod share_link_controller_trait;
+mod simulation_controller_trait;
+mod team_controller_trait;
 mod user_controller_trait;
 
 pub use access_controller_trait::AccessControllerTrait;
+pub use collaboration_controller_trait::{
+    CollaborationControllerTrait, ProjectEditOperationStream,
+};
+pub use comment_controller_trait::CommentControllerTrait;
+pub use component_controller_trait::ComponentControllerTrait;
+pub use invitation_controller_trait::InvitationControllerTrait;
+pub use lock_controller_trait::LockControllerTrait;
 pub use project_controller_trait::ProjectControllerTrait;
 pub use query_controller_trait::QueryControllerTrait;
+pub use reveaal_controller_trait::ReveaalControllerTrait;
+pub use role_controller_trait::RoleControllerTrait;
 pub use session_controller_trait::SessionControllerTrait;
+pub use share_link_controller_trait::ShareLinkControllerTrait;
+pub use simulation_controller_trait::SimulationControllerTrait;
+pub use team_controller_trait::TeamControllerTrait;
 pub use user_controller_trait::UserControllerTrait;
@@ -1,6 +1,8 @@
 use crate::api::server::protobuf::{
     CreateProjectRequest, CreateProjectResponse, DeleteProjectRequest, GetProjectRequest,
-    GetProjectResponse, ListProjectsInfoResponse, UpdateProjectRequest,
+    GetProjectResponse, GetProjectVersionRequest, GetProjectVersionResponse,
+    ListProjectVersionsRequest, ListProjectVersionsResponse, ListProjectsInfoResponse,
+    RestoreProjectVersionRequest, UpdateProjectRequest,
 };
 use async_trait::async_trait;
 use tonic::{Request, Response, Status};
@@ -48,4 +50,35 @@ pub trait ProjectControllerTrait: Send + Sync {
         &self,
         request: Request<()>,
     ) -> Result<Response<ListProjectsInfoResponse>, Status>;
+
+    /// Lists the saved versions of a project, newest first.
+    ///
+    /// # Errors
+    /// This function will return an error if the user does not have access to the project.
+    async fn list_project_versions(
+        &self,
+        request: Request<ListProjectVersionsRequest>,
+    ) -> Result<Response<ListProjectVersionsResponse>, Status>;
+
+    /// Gets a saved version of a project along with its components.
+    ///
+    /// # Errors
+    /// This function will return an error if the version does not exist in the contexts
+    /// or if the user does not have access to the project.
+    async fn get_project_version(
+        &self,
+        request: Request<GetProjectVersionRequest>,
+    ) -> Result<Response<GetProjectVersionResponse>, Status>;
+
+    /// Restores a saved version of a project as its current components.
+    /// The restored components are saved as the newest version, so a restore can itself be undone.
+    ///
+    /// # Errors
+    /// This function will return an error if the version does not exist in the contexts,
+    /// if the user does not have access to the project with role 'Editor'
+    /// or if the project is in use by another session.
+    async fn restore_project_version(
+        &self,
+        request: Request<RestoreProjectVersionRequest>,
+    ) -> Result<Response<()>, Status>;
 }
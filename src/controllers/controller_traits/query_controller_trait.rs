@@ -1,6 +1,11 @@
 use crate::api::server::protobuf::{
-    CreateQueryRequest, DeleteQueryRequest, SendQueryRequest, SendQueryResponse, UpdateQueryRequest,
+    CancelQueryJobRequest, CancelQueryRequest, CreateQueryRequest, DeleteQueryRequest,
+    GetCounterexampleRequest, GetCounterexampleResponse, GetQueryJobRequest, GetQueryJobResponse,
+    ListQueryJobsRequest, ListQueryJobsResponse, ListQueryResultsRequest, ListQueryResultsResponse,
+    SendQueriesRequest, SendQueriesResponse, SendQueryRequest, SendQueryResponse,
+    SubmitQueryJobRequest, SubmitQueryJobResponse, SubscribeQueryEventsRequest, UpdateQueryRequest,
 };
+use crate::services::service_traits::QueryEventStream;
 use async_trait::async_trait;
 use tonic::{Request, Response, Status};
 
@@ -38,4 +43,84 @@ pub trait QueryControllerTrait: Send + Sync {
         &self,
         request: Request<SendQueryRequest>,
     ) -> Result<Response<SendQueryResponse>, Status>;
+
+    /// Sends all queries of a project, or only the outdated ones, to be run on Reveaal.
+    /// After each query is run the result is stored in the contexts.
+    ///
+    /// Returns the response or error of every query, a failing query does not stop the others.
+    async fn send_queries(
+        &self,
+        request: Request<SendQueriesRequest>,
+    ) -> Result<Response<SendQueriesResponse>, Status>;
+
+    /// Subscribes to the query events of a project.
+    ///
+    /// Returns a stream that receives an event whenever a query of the project is started, finishes, fails or becomes outdated.
+    /// # Errors
+    /// Errors on missing access to the project.
+    async fn subscribe_query_events(
+        &self,
+        request: Request<SubscribeQueryEventsRequest>,
+    ) -> Result<Response<QueryEventStream>, Status>;
+
+    /// Submits a query to be run on Reveaal in the background.
+    ///
+    /// Returns the id of the query job, which can be used to poll its status and result.
+    /// # Errors
+    /// Errors on non existent query or missing access to the project.
+    async fn submit_query_job(
+        &self,
+        request: Request<SubmitQueryJobRequest>,
+    ) -> Result<Response<SubmitQueryJobResponse>, Status>;
+
+    /// Returns the status and, once done, the result of a query job.
+    /// # Errors
+    /// Errors on non existent query job or missing access to the project.
+    async fn get_query_job(
+        &self,
+        request: Request<GetQueryJobRequest>,
+    ) -> Result<Response<GetQueryJobResponse>, Status>;
+
+    /// Lists the query jobs of a project, newest first.
+    /// # Errors
+    /// Errors on missing access to the project.
+    async fn list_query_jobs(
+        &self,
+        request: Request<ListQueryJobsRequest>,
+    ) -> Result<Response<ListQueryJobsResponse>, Status>;
+
+    /// Cancels a queued or running query job, the result of a running job is discarded.
+    /// # Errors
+    /// Errors on non existent or finished query job, or if the user is neither the submitter nor an editor.
+    async fn cancel_query_job(
+        &self,
+        request: Request<CancelQueryJobRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Aborts the runs of a query that are in flight on Reveaal, including those of running query jobs.
+    /// The query is given an error result saying it was cancelled, in place of its previous result.
+    /// # Errors
+    /// Errors on non existent query, if the query is not running, or if the user is not an editor.
+    async fn cancel_query(
+        &self,
+        request: Request<CancelQueryRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Returns a page of the result history of a query, newest first, and the total number of results.
+    ///
+    /// Pages are zero-indexed, a page size of 0 gives the default page size.
+    /// # Errors
+    /// Errors on non existent query or missing access to the project.
+    async fn list_query_results(
+        &self,
+        request: Request<ListQueryResultsRequest>,
+    ) -> Result<Response<ListQueryResultsResponse>, Status>;
+
+    /// Returns the counterexample of a given query result, or of the query's most recent failed run.
+    /// # Errors
+    /// Errors on non existent query, missing access to the project or if there is no counterexample.
+    async fn get_counterexample(
+        &self,
+        request: Request<GetCounterexampleRequest>,
+    ) -> Result<Response<GetCounterexampleResponse>, Status>;
 }
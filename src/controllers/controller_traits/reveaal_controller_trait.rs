@@ -0,0 +1,13 @@
+use crate::api::server::protobuf::ecdar_backend_server::EcdarBackend;
+use crate::api::server::protobuf::GetBackendHealthResponse;
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait ReveaalControllerTrait: EcdarBackend {
+    /// Returns the address and health of each Reveaal backend.
+    async fn get_backend_health(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<GetBackendHealthResponse>, Status>;
+}
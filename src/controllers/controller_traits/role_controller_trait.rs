@@ -0,0 +1,38 @@
+use crate::api::server::protobuf::{
+    CreateRoleRequest, DeleteRoleRequest, ListRolesResponse, RoleInfo, UpdateRoleRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait RoleControllerTrait: Send + Sync {
+    /// Returns every role that access to a project can be granted with, along with its permissions.
+    /// # Errors
+    /// Errors if the roles cannot be read.
+    async fn list_roles(&self, request: Request<()>)
+        -> Result<Response<ListRolesResponse>, Status>;
+
+    /// Creates a role with a set of permissions.
+    /// # Errors
+    /// Errors if the user is not an admin, a role with the name exists or a permission is unknown.
+    async fn create_role(
+        &self,
+        request: Request<CreateRoleRequest>,
+    ) -> Result<Response<RoleInfo>, Status>;
+
+    /// Replaces the permissions of a role.
+    /// # Errors
+    /// Errors if the user is not an admin, the role is one of the seeded roles or a permission is unknown.
+    async fn update_role(
+        &self,
+        request: Request<UpdateRoleRequest>,
+    ) -> Result<Response<RoleInfo>, Status>;
+
+    /// Deletes a role.
+    /// # Errors
+    /// Errors if the user is not an admin, the role is one of the seeded roles or access is still granted with it.
+    async fn delete_role(
+        &self,
+        request: Request<DeleteRoleRequest>,
+    ) -> Result<Response<()>, Status>;
+}
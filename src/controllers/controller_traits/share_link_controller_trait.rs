@@ -0,0 +1,51 @@
+use crate::api::server::protobuf::{
+    CreateShareLinkRequest, GetProjectResponse, GetSharedProjectRequest, ListQueryResultsResponse,
+    ListShareLinksRequest, ListShareLinksResponse, ListSharedQueryResultsRequest,
+    RevokeShareLinkRequest, ShareLinkInfo,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait ShareLinkControllerTrait: Send + Sync {
+    /// Creates a share link to a project, which gives anyone with its token read-only access to the project.
+    /// The link never expires unless an expiry is given.
+    /// # Errors
+    /// Errors if the project does not exist, the user is not its owner, or the expiry is 0 seconds.
+    async fn create_share_link(
+        &self,
+        request: Request<CreateShareLinkRequest>,
+    ) -> Result<Response<ShareLinkInfo>, Status>;
+
+    /// Returns the share links to a project, including revoked and expired ones.
+    /// # Errors
+    /// Errors if the project does not exist or the user is not its owner.
+    async fn list_share_links(
+        &self,
+        request: Request<ListShareLinksRequest>,
+    ) -> Result<Response<ListShareLinksResponse>, Status>;
+
+    /// Revokes a share link, so its token no longer gives access to the project.
+    /// # Errors
+    /// Errors if the share link does not exist or the user is not the owner of its project.
+    async fn revoke_share_link(
+        &self,
+        request: Request<RevokeShareLinkRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Returns the project of a share link along with its queries. Needs no user.
+    /// # Errors
+    /// Errors if there is no usable share link with the token.
+    async fn get_shared_project(
+        &self,
+        request: Request<GetSharedProjectRequest>,
+    ) -> Result<Response<GetProjectResponse>, Status>;
+
+    /// Returns a page of the results of a query in the project of a share link. Needs no user.
+    /// # Errors
+    /// Errors if there is no usable share link with the token, or the query is not in its project.
+    async fn list_shared_query_results(
+        &self,
+        request: Request<ListSharedQueryResultsRequest>,
+    ) -> Result<Response<ListQueryResultsResponse>, Status>;
+}
@@ -0,0 +1,89 @@
+use crate::api::server::protobuf::{
+    CreateSimulationSessionRequest, DeleteSimulationSessionRequest, ExportSimulationTraceRequest,
+    ExportSimulationTraceResponse, GetSimulationSessionRequest, GetSimulationSessionResponse,
+    ListSimulationSessionsRequest, ListSimulationSessionsResponse, RenameSimulationSessionRequest,
+    ReplaySimulationTraceRequest, ReplaySimulationTraceResponse, SimulationSessionStep,
+    StartCounterexampleSimulationRequest, TakeSimulationSessionStepRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait SimulationControllerTrait: Send + Sync {
+    /// Starts a simulation of a project on Reveaal and saves it as a session of the user,
+    /// with the initial decision points as its first step.
+    /// # Errors
+    /// Errors on invalid rights, an empty component composition or if Reveaal fails to start the simulation.
+    async fn create_simulation_session(
+        &self,
+        request: Request<CreateSimulationSessionRequest>,
+    ) -> Result<Response<GetSimulationSessionResponse>, Status>;
+
+    /// Takes a step in a saved simulation session and appends it to the session's trace.
+    /// # Errors
+    /// Errors on non existent session, invalid rights, a decision that is not one of the
+    /// current decision points or if Reveaal fails to take the step.
+    async fn take_simulation_session_step(
+        &self,
+        request: Request<TakeSimulationSessionStepRequest>,
+    ) -> Result<Response<SimulationSessionStep>, Status>;
+
+    /// Returns a saved simulation session together with its trace, so it can be resumed.
+    /// # Errors
+    /// Errors on non existent session or invalid rights.
+    async fn get_simulation_session(
+        &self,
+        request: Request<GetSimulationSessionRequest>,
+    ) -> Result<Response<GetSimulationSessionResponse>, Status>;
+
+    /// Lists the simulation sessions the user has saved in a project, most recently used first.
+    /// # Errors
+    /// Errors on invalid rights.
+    async fn list_simulation_sessions(
+        &self,
+        request: Request<ListSimulationSessionsRequest>,
+    ) -> Result<Response<ListSimulationSessionsResponse>, Status>;
+
+    /// Renames a saved simulation session.
+    /// # Errors
+    /// Errors on non existent session, invalid rights or an empty name.
+    async fn rename_simulation_session(
+        &self,
+        request: Request<RenameSimulationSessionRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Deletes a saved simulation session along with its trace.
+    /// # Errors
+    /// Errors on non existent session or invalid rights.
+    async fn delete_simulation_session(
+        &self,
+        request: Request<DeleteSimulationSessionRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Exports the trace of a saved simulation session as portable JSON.
+    /// # Errors
+    /// Errors on non existent session or invalid rights.
+    async fn export_simulation_trace(
+        &self,
+        request: Request<ExportSimulationTraceRequest>,
+    ) -> Result<Response<ExportSimulationTraceResponse>, Status>;
+
+    /// Replays an exported trace against the current components of a project
+    /// and reports the first step where Reveaal's decision points differ from the trace.
+    /// # Errors
+    /// Errors on an invalid trace, invalid rights or if Reveaal cannot be reached.
+    async fn replay_simulation_trace(
+        &self,
+        request: Request<ReplaySimulationTraceRequest>,
+    ) -> Result<Response<ReplaySimulationTraceResponse>, Status>;
+
+    /// Starts a new simulation session at the initial state of a counterexample's system,
+    /// from which the counterexample's path can be followed.
+    /// # Errors
+    /// Errors on non existent counterexample, invalid rights, a counterexample without a system
+    /// or if Reveaal cannot be reached.
+    async fn start_counterexample_simulation(
+        &self,
+        request: Request<StartCounterexampleSimulationRequest>,
+    ) -> Result<Response<GetSimulationSessionResponse>, Status>;
+}
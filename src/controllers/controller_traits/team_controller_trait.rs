@@ -0,0 +1,56 @@
+use crate::api::server::protobuf::{
+    AddTeamMembersRequest, CreateTeamRequest, CreateTeamResponse, DeleteTeamRequest,
+    GetTeamRequest, GetTeamResponse, ListTeamsResponse, RemoveTeamMemberRequest,
+};
+use async_trait::async_trait;
+use tonic::{Request, Response, Status};
+
+#[async_trait]
+pub trait TeamControllerTrait: Send + Sync {
+    /// Creates a team owned by the user, who becomes its first member.
+    /// # Errors
+    /// Errors if the name is empty.
+    async fn create_team(
+        &self,
+        request: Request<CreateTeamRequest>,
+    ) -> Result<Response<CreateTeamResponse>, Status>;
+
+    /// Returns a team along with its members.
+    /// # Errors
+    /// Errors if the team does not exist or the user is not a member of it.
+    async fn get_team(
+        &self,
+        request: Request<GetTeamRequest>,
+    ) -> Result<Response<GetTeamResponse>, Status>;
+
+    /// Returns the teams the user is a member of.
+    /// # Errors
+    /// Errors if the teams cannot be read.
+    async fn list_teams(&self, request: Request<()>)
+        -> Result<Response<ListTeamsResponse>, Status>;
+
+    /// Deletes a team, revoking the access granted to it.
+    /// # Errors
+    /// Errors if the team does not exist or the user is not its owner.
+    async fn delete_team(
+        &self,
+        request: Request<DeleteTeamRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Adds users to a team by their usernames. Users who are already members are left as they are.
+    /// # Errors
+    /// Errors if the user is not the owner of the team, or any of the usernames does not belong to a user,
+    /// in which case none of the users are added.
+    async fn add_team_members(
+        &self,
+        request: Request<AddTeamMembersRequest>,
+    ) -> Result<Response<()>, Status>;
+
+    /// Removes a member from a team. The owner can remove anyone but themself, the other members only themselves.
+    /// # Errors
+    /// Errors if the user may not remove the member, or the member is not in the team.
+    async fn remove_team_member(
+        &self,
+        request: Request<RemoveTeamMemberRequest>,
+    ) -> Result<Response<()>, Status>;
+}
@@ -9,6 +9,7 @@ pub struct Model {
     pub project_id: i32,
     pub session_id: i32,
     pub latest_activity: DateTime,
+    pub expires_at: DateTime,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
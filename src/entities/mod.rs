@@ -3,9 +3,25 @@
 pub mod prelude;
 
 pub mod access;
+pub mod backend_audit;
+pub mod comment;
+pub mod component;
+pub mod counterexample;
 pub mod in_use;
+pub mod invitation;
 pub mod project;
+pub mod project_version;
 pub mod query;
+pub mod query_cache;
+pub mod query_job;
+pub mod query_result;
 pub mod role;
+pub mod sea_orm_active_enums;
 pub mod session;
+pub mod share_link;
+pub mod simulation_session;
+pub mod simulation_step;
+pub mod team;
+pub mod team_access;
+pub mod team_member;
 pub mod user;
@@ -8,18 +8,37 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
-    pub components_info: Json,
     pub owner_id: i32,
+    pub settings: Option<Json>,
+    pub timeout_secs: Option<i32>,
+    pub revision: i32,
+    pub collaborative: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::access::Entity")]
     Access,
+    #[sea_orm(has_many = "super::comment::Entity")]
+    Comment,
+    #[sea_orm(has_many = "super::component::Entity")]
+    Component,
     #[sea_orm(has_many = "super::in_use::Entity")]
     InUse,
+    #[sea_orm(has_many = "super::invitation::Entity")]
+    Invitation,
+    #[sea_orm(has_many = "super::project_version::Entity")]
+    ProjectVersion,
     #[sea_orm(has_many = "super::query::Entity")]
     Query,
+    #[sea_orm(has_many = "super::query_job::Entity")]
+    QueryJob,
+    #[sea_orm(has_many = "super::share_link::Entity")]
+    ShareLink,
+    #[sea_orm(has_many = "super::simulation_session::Entity")]
+    SimulationSession,
+    #[sea_orm(has_many = "super::team_access::Entity")]
+    TeamAccess,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::OwnerId",
@@ -36,18 +55,66 @@ impl Related<super::access::Entity> for Entity {
     }
 }
 
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl Related<super::component::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Component.def()
+    }
+}
+
 impl Related<super::in_use::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::InUse.def()
     }
 }
 
+impl Related<super::invitation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invitation.def()
+    }
+}
+
+impl Related<super::project_version::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectVersion.def()
+    }
+}
+
 impl Related<super::query::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Query.def()
     }
 }
 
+impl Related<super::query_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QueryJob.def()
+    }
+}
+
+impl Related<super::share_link::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ShareLink.def()
+    }
+}
+
+impl Related<super::simulation_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SimulationSession.def()
+    }
+}
+
+impl Related<super::team_access::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamAccess.def()
+    }
+}
+
 impl Related<super::user::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::User.def()
@@ -11,10 +11,16 @@ pub struct Model {
     pub result: Option<Json>,
     pub outdated: bool,
     pub project_id: i32,
+    pub settings: Option<Json>,
+    pub timeout_secs: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::comment::Entity")]
+    Comment,
+    #[sea_orm(has_many = "super::counterexample::Entity")]
+    Counterexample,
     #[sea_orm(
         belongs_to = "super::project::Entity",
         from = "Column::ProjectId",
@@ -23,6 +29,22 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Project,
+    #[sea_orm(has_many = "super::query_job::Entity")]
+    QueryJob,
+    #[sea_orm(has_many = "super::query_result::Entity")]
+    QueryResult,
+}
+
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl Related<super::counterexample::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Counterexample.def()
+    }
 }
 
 impl Related<super::project::Entity> for Entity {
@@ -31,4 +53,16 @@ impl Related<super::project::Entity> for Entity {
     }
 }
 
+impl Related<super::query_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QueryJob.def()
+    }
+}
+
+impl Related<super::query_result::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QueryResult.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -7,12 +7,17 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub name: String,
+    pub permissions: Json,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::access::Entity")]
     Access,
+    #[sea_orm(has_many = "super::invitation::Entity")]
+    Invitation,
+    #[sea_orm(has_many = "super::team_access::Entity")]
+    TeamAccess,
 }
 
 impl Related<super::access::Entity> for Entity {
@@ -21,4 +26,16 @@ impl Related<super::access::Entity> for Entity {
     }
 }
 
+impl Related<super::invitation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invitation.def()
+    }
+}
+
+impl Related<super::team_access::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamAccess.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
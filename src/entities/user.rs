@@ -18,10 +18,28 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::access::Entity")]
     Access,
+    #[sea_orm(has_many = "super::backend_audit::Entity")]
+    BackendAudit,
+    #[sea_orm(has_many = "super::comment::Entity")]
+    Comment,
+    #[sea_orm(has_many = "super::invitation::Entity")]
+    Invitation,
     #[sea_orm(has_many = "super::project::Entity")]
     Project,
+    #[sea_orm(has_many = "super::project_version::Entity")]
+    ProjectVersion,
+    #[sea_orm(has_many = "super::query_job::Entity")]
+    QueryJob,
+    #[sea_orm(has_many = "super::query_result::Entity")]
+    QueryResult,
     #[sea_orm(has_many = "super::session::Entity")]
     Session,
+    #[sea_orm(has_many = "super::simulation_session::Entity")]
+    SimulationSession,
+    #[sea_orm(has_many = "super::team::Entity")]
+    Team,
+    #[sea_orm(has_many = "super::team_member::Entity")]
+    TeamMember,
 }
 
 impl Related<super::access::Entity> for Entity {
@@ -30,16 +48,70 @@ impl Related<super::access::Entity> for Entity {
     }
 }
 
+impl Related<super::backend_audit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BackendAudit.def()
+    }
+}
+
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl Related<super::invitation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invitation.def()
+    }
+}
+
 impl Related<super::project::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Project.def()
     }
 }
 
+impl Related<super::project_version::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectVersion.def()
+    }
+}
+
+impl Related<super::query_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QueryJob.def()
+    }
+}
+
+impl Related<super::query_result::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QueryResult.def()
+    }
+}
+
 impl Related<super::session::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Session.def()
     }
 }
 
+impl Related<super::simulation_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SimulationSession.def()
+    }
+}
+
+impl Related<super::team::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Team.def()
+    }
+}
+
+impl Related<super::team_member::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamMember.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -19,21 +19,78 @@ use crate::contexts::context_traits::DatabaseContextTrait;
 use crate::controllers::controller_collection::ControllerCollection;
 use crate::controllers::controller_impls::*;
 use crate::services::service_collection::ServiceCollection;
-use crate::services::service_impls::{HashingService, ReveaalService};
+use crate::services::service_impls::{
+    HashingService, ProjectEditService, QueryEventService, QueryJobService, QueryRunService,
+    ReveaalService, ReveaalServiceConfig,
+};
+use crate::services::service_traits::{
+    QueryEventServiceTrait, QueryRunServiceTrait, ReveaalServiceTrait,
+};
 use api::server::start_grpc_server;
 use dotenv::dotenv;
+use log::LevelFilter;
 use sea_orm::{ConnectionTrait, Database, DbBackend};
 use std::env;
 use std::error::Error;
 use std::sync::Arc;
 
+const DEFAULT_QUERY_JOB_WORKERS: usize = 4;
+const DEFAULT_MAX_BACKEND_REQUESTS_PER_USER: usize = 4;
+const DEFAULT_MAX_CONCURRENT_QUERIES: usize = 4;
+const DEFAULT_LOCK_LEASE_SECS: u32 = 10 * 60;
+
 #[tokio::main]
 #[allow(clippy::expect_used)]
 async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
 
+    let log_level = match env::var("LOG_LEVEL") {
+        Ok(level) => level
+            .parse()
+            .expect("Expected LOG_LEVEL to be one of off, error, warn, info, debug or trace."),
+        Err(_) => LevelFilter::Info,
+    };
+    api::logger::init(log_level).expect("Expected no other logger to be installed.");
+
     let reveaal_addr = env::var("REVEAAL_ADDRESS").expect("Expected REVEAAL_ADDRESS to be set.");
+    let reveaal_addrs: Vec<&str> = reveaal_addr.split(',').map(str::trim).collect();
     let db_url = env::var("DATABASE_URL").expect("Expected DATABASE_URL to be set.");
+    let query_job_workers = match env::var("QUERY_JOB_WORKERS") {
+        Ok(workers) => workers
+            .parse()
+            .expect("Expected QUERY_JOB_WORKERS to be a number."),
+        Err(_) => DEFAULT_QUERY_JOB_WORKERS,
+    };
+    let max_backend_requests_per_user = match env::var("BACKEND_MAX_REQUESTS_PER_USER") {
+        Ok(max) => max
+            .parse()
+            .expect("Expected BACKEND_MAX_REQUESTS_PER_USER to be a number."),
+        Err(_) => DEFAULT_MAX_BACKEND_REQUESTS_PER_USER,
+    };
+    let max_concurrent_queries = match env::var("MAX_CONCURRENT_QUERIES") {
+        Ok(max) => max
+            .parse()
+            .expect("Expected MAX_CONCURRENT_QUERIES to be a number."),
+        Err(_) => DEFAULT_MAX_CONCURRENT_QUERIES,
+    };
+    let lock_lease_secs = match env::var("LOCK_LEASE_SECS") {
+        Ok(secs) => secs
+            .parse()
+            .expect("Expected LOCK_LEASE_SECS to be a number."),
+        Err(_) => DEFAULT_LOCK_LEASE_SECS,
+    };
+    let admin_ids: Vec<i32> = match env::var("ADMIN_USER_IDS") {
+        Ok(ids) => ids
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse()
+                    .expect("Expected ADMIN_USER_IDS to be a comma separated list of user ids.")
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
 
     let db = Database::connect(&db_url).await?;
     let db_context: Arc<dyn DatabaseContextTrait> = match db.get_database_backend() {
@@ -44,25 +101,87 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let contexts = ContextCollection {
         access_context: Arc::new(AccessContext::new(db_context.clone())),
+        backend_audit_context: Arc::new(BackendAuditContext::new(db_context.clone())),
+        comment_context: Arc::new(CommentContext::new(db_context.clone())),
+        component_context: Arc::new(ComponentContext::new(db_context.clone())),
+        counterexample_context: Arc::new(CounterexampleContext::new(db_context.clone())),
         in_use_context: Arc::new(InUseContext::new(db_context.clone())),
+        invitation_context: Arc::new(InvitationContext::new(db_context.clone())),
         project_context: Arc::new(ProjectContext::new(db_context.clone())),
+        project_version_context: Arc::new(ProjectVersionContext::new(db_context.clone())),
+        query_cache_context: Arc::new(QueryCacheContext::new(db_context.clone())),
         query_context: Arc::new(QueryContext::new(db_context.clone())),
+        query_job_context: Arc::new(QueryJobContext::new(db_context.clone())),
+        query_result_context: Arc::new(QueryResultContext::new(db_context.clone())),
+        role_context: Arc::new(RoleContext::new(db_context.clone())),
         session_context: Arc::new(SessionContext::new(db_context.clone())),
+        share_link_context: Arc::new(ShareLinkContext::new(db_context.clone())),
+        simulation_session_context: Arc::new(SimulationSessionContext::new(db_context.clone())),
+        simulation_step_context: Arc::new(SimulationStepContext::new(db_context.clone())),
+        team_access_context: Arc::new(TeamAccessContext::new(db_context.clone())),
+        team_context: Arc::new(TeamContext::new(db_context.clone())),
         user_context: Arc::new(UserContext::new(db_context.clone())),
     };
 
+    let reveaal_service: Arc<dyn ReveaalServiceTrait> = Arc::new(ReveaalService::new(
+        &reveaal_addrs,
+        ReveaalServiceConfig::from_env(),
+    )?);
+
+    let query_event_service: Arc<dyn QueryEventServiceTrait> = Arc::new(QueryEventService::new());
+    let query_run_service: Arc<dyn QueryRunServiceTrait> = Arc::new(QueryRunService::new());
+
+    let query_job_service = QueryJobService::new(
+        contexts.clone(),
+        reveaal_service.clone(),
+        query_event_service.clone(),
+        query_run_service.clone(),
+        query_job_workers,
+    );
+    query_job_service.requeue_unfinished(&contexts).await?;
+
     let services = ServiceCollection {
         hashing_service: Arc::new(HashingService),
-        reveaal_service: Arc::new(ReveaalService::new(&reveaal_addr)),
+        project_edit_service: Arc::new(ProjectEditService::new()),
+        query_event_service,
+        query_job_service: Arc::new(query_job_service),
+        query_run_service,
+        reveaal_service,
     };
 
     let controllers = ControllerCollection {
         access_controller: Arc::new(AccessController::new(contexts.clone())),
-        project_controller: Arc::new(ProjectController::new(contexts.clone())),
-        query_controller: Arc::new(QueryController::new(contexts.clone(), services.clone())),
+        collaboration_controller: Arc::new(CollaborationController::new(
+            contexts.clone(),
+            services.clone(),
+        )),
+        comment_controller: Arc::new(CommentController::new(contexts.clone())),
+        component_controller: Arc::new(ComponentController::new(
+            contexts.clone(),
+            services.clone(),
+        )),
+        invitation_controller: Arc::new(InvitationController::new(contexts.clone())),
+        lock_controller: Arc::new(LockController::new(contexts.clone(), lock_lease_secs)),
+        project_controller: Arc::new(ProjectController::new(contexts.clone(), services.clone())),
+        query_controller: Arc::new(QueryController::new(
+            contexts.clone(),
+            services.clone(),
+            max_concurrent_queries,
+        )),
+        role_controller: Arc::new(RoleController::new(contexts.clone(), admin_ids)),
         session_controller: Arc::new(SessionController::new(contexts.clone(), services.clone())),
+        share_link_controller: Arc::new(ShareLinkController::new(contexts.clone())),
+        simulation_controller: Arc::new(SimulationController::new(
+            contexts.clone(),
+            services.clone(),
+        )),
+        team_controller: Arc::new(TeamController::new(contexts.clone())),
         user_controller: Arc::new(UserController::new(contexts.clone(), services.clone())),
-        reveaal_controller: Arc::new(ReveaalController::new(services.clone())),
+        reveaal_controller: Arc::new(ReveaalController::new(
+            contexts.clone(),
+            services.clone(),
+            max_backend_requests_per_user,
+        )),
     };
 
     start_grpc_server(controllers)
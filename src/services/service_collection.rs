@@ -1,8 +1,15 @@
-use crate::services::service_traits::{HashingServiceTrait, ReveaalServiceTrait};
+use crate::services::service_traits::{
+    HashingServiceTrait, ProjectEditServiceTrait, QueryEventServiceTrait, QueryJobServiceTrait,
+    QueryRunServiceTrait, ReveaalServiceTrait,
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct ServiceCollection {
     pub(crate) hashing_service: Arc<dyn HashingServiceTrait>,
+    pub(crate) project_edit_service: Arc<dyn ProjectEditServiceTrait>,
+    pub(crate) query_event_service: Arc<dyn QueryEventServiceTrait>,
+    pub(crate) query_job_service: Arc<dyn QueryJobServiceTrait>,
+    pub(crate) query_run_service: Arc<dyn QueryRunServiceTrait>,
     pub(crate) reveaal_service: Arc<dyn ReveaalServiceTrait>,
 }
@@ -1,5 +1,14 @@
 pub mod hashing_service;
-mod reveaal_service;
+mod project_edit_service;
+mod query_event_service;
+pub(crate) mod query_job_service;
+mod query_run_service;
+pub(crate) mod query_runner;
+pub(crate) mod reveaal_service;
 
 pub use hashing_service::HashingService;
-pub use reveaal_service::ReveaalService;
+pub use project_edit_service::ProjectEditService;
+pub use query_event_service::QueryEventService;
+pub use query_job_service::QueryJobService;
+pub use query_run_service::QueryRunService;
+pub use reveaal_service::{ReveaalService, ReveaalServiceConfig};
@@ -0,0 +1,63 @@
+use crate::api::server::protobuf::ProjectEditEvent;
+use crate::services::service_traits::{ProjectEditServiceTrait, ProjectEditStream};
+use futures::stream;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::Status;
+
+/// The amount of edits a slow collaborator can fall behind before its stream is ended.
+const EDIT_BUFFER_SIZE: usize = 1024;
+
+pub struct ProjectEditService {
+    sender: broadcast::Sender<ProjectEditEvent>,
+}
+
+impl ProjectEditService {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EDIT_BUFFER_SIZE);
+        Self { sender }
+    }
+}
+
+impl Default for ProjectEditService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectEditServiceTrait for ProjectEditService {
+    fn publish(&self, event: ProjectEditEvent) {
+        // Sending only fails when no one is subscribed, in which case the edit can be dropped
+        let _ = self.sender.send(event);
+    }
+
+    fn subscribe(&self, project_id: i32) -> ProjectEditStream {
+        let receiver = self.sender.subscribe();
+
+        Box::pin(stream::unfold(Some(receiver), move |receiver| async move {
+            let mut receiver = receiver?;
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.project_id == project_id => {
+                        return Some((Ok(event), Some(receiver)))
+                    }
+                    Ok(_) => continue,
+                    // A collaborator that missed edits no longer knows the state of the project
+                    Err(RecvError::Lagged(_)) => {
+                        return Some((
+                            Err(Status::data_loss(
+                                "Fell too far behind the edits of the project, rejoin to continue",
+                            )),
+                            None,
+                        ))
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/services/project_edit_service.rs"]
+mod project_edit_service_tests;
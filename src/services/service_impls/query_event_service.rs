@@ -0,0 +1,52 @@
+use crate::api::server::protobuf::QueryEvent;
+use crate::services::service_traits::{QueryEventServiceTrait, QueryEventStream};
+use futures::stream;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+/// The amount of events a slow subscriber can fall behind before it starts missing events.
+const EVENT_BUFFER_SIZE: usize = 1024;
+
+pub struct QueryEventService {
+    sender: broadcast::Sender<QueryEvent>,
+}
+
+impl QueryEventService {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self { sender }
+    }
+}
+
+impl Default for QueryEventService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryEventServiceTrait for QueryEventService {
+    fn publish(&self, event: QueryEvent) {
+        // Sending only fails when no one is subscribed, in which case the event can be dropped
+        let _ = self.sender.send(event);
+    }
+
+    fn subscribe(&self, project_id: i32) -> QueryEventStream {
+        let receiver = self.sender.subscribe();
+
+        Box::pin(stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.project_id == project_id => {
+                        return Some((Ok(event), receiver))
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/services/query_event_service.rs"]
+mod query_event_service_tests;
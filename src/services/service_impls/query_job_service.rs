@@ -0,0 +1,228 @@
+use crate::api::query_settings::{resolve_query_settings, resolve_query_timeout};
+use crate::api::server::protobuf::QueryResponse;
+use crate::contexts::context_collection::ContextCollection;
+use crate::entities::query_job;
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use crate::services::service_impls::query_runner::QueryRunner;
+use crate::services::service_traits::{
+    QueryEventServiceTrait, QueryJobServiceTrait, QueryRunServiceTrait, ReveaalServiceTrait,
+};
+use sea_orm::DbErr;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tonic::{Code, Status};
+
+pub struct QueryJobService {
+    sender: mpsc::UnboundedSender<i32>,
+}
+
+impl QueryJobService {
+    /// Starts a pool of `workers` workers that run the enqueued query jobs on Reveaal.
+    pub fn new(
+        contexts: ContextCollection,
+        reveaal_service: Arc<dyn ReveaalServiceTrait>,
+        query_event_service: Arc<dyn QueryEventServiceTrait>,
+        query_run_service: Arc<dyn QueryRunServiceTrait>,
+        workers: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel::<i32>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for _ in 0..workers.max(1) {
+            let receiver = receiver.clone();
+            let contexts = contexts.clone();
+            let reveaal_service = reveaal_service.clone();
+            let query_event_service = query_event_service.clone();
+            let query_run_service = query_run_service.clone();
+
+            tokio::spawn(async move {
+                loop {
+                    let job_id = match receiver.lock().await.recv().await {
+                        Some(job_id) => job_id,
+                        None => break,
+                    };
+
+                    if let Err(err) = run_query_job(
+                        &contexts,
+                        &reveaal_service,
+                        &query_event_service,
+                        &query_run_service,
+                        job_id,
+                    )
+                    .await
+                    {
+                        log::error!("failed to run query job {}: {}", job_id, err.message());
+                    }
+                }
+            });
+        }
+
+        Self { sender }
+    }
+
+    /// Enqueues the jobs that were left queued or running when the server last stopped.
+    /// Running jobs are interrupted by a restart, so they are set back to queued.
+    pub async fn requeue_unfinished(&self, contexts: &ContextCollection) -> Result<(), DbErr> {
+        for job in contexts
+            .query_job_context
+            .get_all_by_status(QueryJobStatus::Running)
+            .await?
+        {
+            contexts
+                .query_job_context
+                .update(query_job::Model {
+                    status: QueryJobStatus::Queued,
+                    ..job
+                })
+                .await?;
+        }
+
+        for job in contexts
+            .query_job_context
+            .get_all_by_status(QueryJobStatus::Queued)
+            .await?
+        {
+            self.enqueue(job.id)
+                .map_err(|err| DbErr::Custom(err.message().to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QueryJobServiceTrait for QueryJobService {
+    fn enqueue(&self, job_id: i32) -> Result<(), Status> {
+        self.sender
+            .send(job_id)
+            .map_err(|_| Status::unavailable("query job workers have stopped"))
+    }
+}
+
+/// Runs a single query job on Reveaal and stores the outcome on the job.
+/// The query itself is run like any other query, so it is stored on the query and announced to subscribers.
+/// Jobs that are no longer queued, e.g. because they were cancelled, are skipped,
+/// and a job that is cancelled while running keeps its cancelled status.
+/// Jobs whose query is cancelled through `cancel_query` while running are stored as cancelled.
+/// # Errors
+/// Only errors if the database fails, a failing query is stored as a failed job.
+pub(crate) async fn run_query_job(
+    contexts: &ContextCollection,
+    reveaal_service: &Arc<dyn ReveaalServiceTrait>,
+    query_event_service: &Arc<dyn QueryEventServiceTrait>,
+    query_run_service: &Arc<dyn QueryRunServiceTrait>,
+    job_id: i32,
+) -> Result<(), Status> {
+    let job = match contexts
+        .query_job_context
+        .get_by_id(job_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+    {
+        Some(job) if job.status == QueryJobStatus::Queued => job,
+        _ => return Ok(()),
+    };
+
+    // Another worker or a cancellation may have changed the job since it was read
+    let job = query_job::Model {
+        status: QueryJobStatus::Running,
+        ..job
+    };
+    if !contexts
+        .query_job_context
+        .update_if_status(job.clone(), QueryJobStatus::Queued)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+    {
+        return Ok(());
+    }
+
+    let runner = QueryRunner {
+        contexts,
+        reveaal_service: reveaal_service.as_ref(),
+        query_event_service: query_event_service.as_ref(),
+        query_run_service: query_run_service.as_ref(),
+        job_id: Some(job.id),
+    };
+    let outcome = execute_query_job(&runner, &job).await;
+
+    let job = match outcome {
+        Ok(response) => query_job::Model {
+            status: QueryJobStatus::Done,
+            result: Some(serde_json::to_value(response.result).map_err(|err| {
+                Status::internal(format!(
+                    "error parsing query result, internal error: {}",
+                    err
+                ))
+            })?),
+            error: None,
+            ..job
+        },
+        Err(err) => query_job::Model {
+            status: match err.code() {
+                Code::Cancelled => QueryJobStatus::Cancelled,
+                _ => QueryJobStatus::Failed,
+            },
+            result: None,
+            error: Some(err.message().to_string()),
+            ..job
+        },
+    };
+
+    // A job that was cancelled while Reveaal was working on it stays cancelled
+    contexts
+        .query_job_context
+        .update_if_status(job, QueryJobStatus::Running)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+    Ok(())
+}
+
+/// Runs the query of a job with the settings and timeout of its query and project.
+async fn execute_query_job(
+    runner: &QueryRunner<'_>,
+    job: &query_job::Model,
+) -> Result<QueryResponse, Status> {
+    let query = runner
+        .contexts
+        .query_context
+        .get_by_id(job.query_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .ok_or_else(|| Status::new(Code::NotFound, "Query not found"))?;
+
+    let project = runner
+        .contexts
+        .project_context
+        .get_by_id(job.project_id)
+        .await
+        .map_err(|err| Status::new(Code::Internal, err.to_string()))?
+        .ok_or_else(|| Status::new(Code::NotFound, "Model not found"))?;
+
+    let settings = resolve_query_settings(project.settings.as_ref(), query.settings.as_ref(), None)
+        .map_err(|err| {
+            Status::internal(format!(
+                "error parsing query settings, internal error: {}",
+                err
+            ))
+        })?;
+    let timeout = resolve_query_timeout(project.timeout_secs, query.timeout_secs);
+    let (components_info, components_hash) = runner.components(project.id).await?;
+
+    let (response, _) = runner
+        .run(
+            job.user_id,
+            query,
+            components_info,
+            components_hash,
+            settings,
+            timeout,
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[cfg(test)]
+#[path = "../../tests/services/query_job_service.rs"]
+mod query_job_service_tests;
@@ -0,0 +1,119 @@
+use crate::services::service_traits::{QueryRun, QueryRunServiceTrait};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::watch;
+
+/// The runs of a single query that are in flight.
+struct Runs {
+    /// Tells apart runs started before and after the query was last cancelled
+    generation: u64,
+    count: usize,
+    cancel: watch::Sender<bool>,
+}
+
+#[derive(Default)]
+struct State {
+    next_generation: u64,
+    runs: HashMap<i32, Runs>,
+    /// The runs of query jobs in flight, with the generation of the run
+    jobs: HashMap<i32, (u64, watch::Sender<bool>)>,
+}
+
+pub struct QueryRunService {
+    // A std mutex, as runs are deregistered when dropped, where the lock cannot be awaited
+    state: Arc<Mutex<State>>,
+}
+
+impl QueryRunService {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+}
+
+impl Default for QueryRunService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryRunServiceTrait for QueryRunService {
+    fn start(&self, query_id: i32, job_id: Option<i32>) -> QueryRun {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let generation = state.next_generation;
+
+        let runs = state.runs.entry(query_id).or_insert_with(|| Runs {
+            generation,
+            count: 0,
+            cancel: watch::channel(false).0,
+        });
+        runs.count += 1;
+        let (runs_generation, cancelled) = (runs.generation, runs.cancel.subscribe());
+        state.next_generation += 1;
+
+        let job_cancelled = job_id.map(|job_id| {
+            let (cancel, cancelled) = watch::channel(false);
+            state.jobs.insert(job_id, (generation, cancel));
+            cancelled
+        });
+
+        let state = Arc::downgrade(&self.state);
+        let run = QueryRun::new(cancelled, move || {
+            let state = match state.upgrade() {
+                Some(state) => state,
+                None => return,
+            };
+            let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+
+            if let Some(runs) = state.runs.get_mut(&query_id) {
+                if runs.generation == runs_generation {
+                    runs.count -= 1;
+                    if runs.count == 0 {
+                        state.runs.remove(&query_id);
+                    }
+                }
+            }
+
+            if let Some(job_id) = job_id {
+                if matches!(state.jobs.get(&job_id), Some((job_generation, _)) if *job_generation == generation)
+                {
+                    state.jobs.remove(&job_id);
+                }
+            }
+        });
+
+        match job_cancelled {
+            Some(job_cancelled) => run.with_job_cancelled(job_cancelled),
+            None => run,
+        }
+    }
+
+    fn cancel(&self, query_id: i32) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match state.runs.remove(&query_id) {
+            Some(runs) => {
+                runs.cancel.send_replace(true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cancel_job(&self, job_id: i32) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match state.jobs.remove(&job_id) {
+            Some((_, cancel)) => {
+                cancel.send_replace(true);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../../tests/services/query_run_service.rs"]
+mod query_run_service_tests;
@@ -0,0 +1,293 @@
+use crate::api::counterexample::extract_counterexample;
+use crate::api::query_hash::{hash_components_info, hash_query};
+use crate::api::server::protobuf::query_event::Kind;
+use crate::api::server::protobuf::query_request::Settings;
+use crate::api::server::protobuf::query_response;
+use crate::api::server::protobuf::{ComponentsInfo, QueryEvent, QueryRequest, QueryResponse};
+use crate::contexts::context_collection::ContextCollection;
+use crate::entities::{query, query_cache, query_result};
+use crate::services::service_traits::{
+    interrupted_query_result, QueryEventServiceTrait, QueryRunServiceTrait, ReveaalServiceTrait,
+};
+use sea_orm::prelude::Json;
+use std::time::Duration;
+use tonic::{Code, Request, Status};
+
+/// Runs queries on Reveaal and stores their outcome.
+/// Both the query endpoints and the query job workers run queries through it,
+/// so every run goes through the same result cache, history and events.
+pub(crate) struct QueryRunner<'a> {
+    pub(crate) contexts: &'a ContextCollection,
+    pub(crate) reveaal_service: &'a dyn ReveaalServiceTrait,
+    pub(crate) query_event_service: &'a dyn QueryEventServiceTrait,
+    pub(crate) query_run_service: &'a dyn QueryRunServiceTrait,
+    /// The query job the runs are made for, whose cancellation stops only its own run
+    pub(crate) job_id: Option<i32>,
+}
+
+impl<'a> QueryRunner<'a> {
+    /// Returns the components of a project together with their hash.
+    pub(crate) async fn components(
+        &self,
+        project_id: i32,
+    ) -> Result<(ComponentsInfo, String), Status> {
+        let components_info = self
+            .contexts
+            .component_context
+            .get_components_info_by_project_id(project_id)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        let components_hash =
+            hash_components_info(&serde_json::to_value(&components_info).map_err(|err| {
+                Status::internal(format!(
+                    "error parsing components info, internal error: {}",
+                    err
+                ))
+            })?);
+        Ok((components_info, components_hash))
+    }
+
+    /// Runs a query on Reveaal with the given settings, stores the result on the query
+    /// and records the run in the query's result history.
+    /// Unchanged runs are served from the result cache, which is reported by the returned bool.
+    /// Subscribers of the project are told when the query starts, finishes, fails or is cancelled.
+    pub(crate) async fn run(
+        &self,
+        uid: i32,
+        query: query::Model,
+        components_info: ComponentsInfo,
+        components_hash: String,
+        settings: Settings,
+        timeout: Option<Duration>,
+    ) -> Result<(QueryResponse, bool), Status> {
+        let (query_id, project_id) = (query.id, query.project_id);
+
+        self.query_event_service.publish(QueryEvent {
+            query_id,
+            project_id,
+            kind: Kind::Started.into(),
+            ..Default::default()
+        });
+
+        let query_result = self
+            .run_on_reveaal(
+                uid,
+                query,
+                components_info,
+                components_hash,
+                settings,
+                timeout,
+            )
+            .await;
+
+        self.query_event_service.publish(match &query_result {
+            Ok((response, _)) => QueryEvent {
+                query_id,
+                project_id,
+                kind: Kind::Finished.into(),
+                response: Some(response.clone()),
+                ..Default::default()
+            },
+            Err(err) => QueryEvent {
+                query_id,
+                project_id,
+                kind: match err.code() {
+                    Code::Cancelled => Kind::Cancelled.into(),
+                    _ => Kind::Failed.into(),
+                },
+                error: Some(err.message().to_string()),
+                ..Default::default()
+            },
+        });
+
+        query_result
+    }
+
+    /// Returns the response of a query run together with whether it was served from the result cache.
+    /// Runs that are not cached are sent to Reveaal, and their result is cached unless it may change on a rerun.
+    /// Runs that time out or are cancelled through `cancel_query` or `cancel_query_job` are recorded with an error result,
+    /// so the query does not keep the result of an older run.
+    async fn run_on_reveaal(
+        &self,
+        uid: i32,
+        query: query::Model,
+        components_info: ComponentsInfo,
+        components_hash: String,
+        settings: Settings,
+        timeout: Option<Duration>,
+    ) -> Result<(QueryResponse, bool), Status> {
+        let settings_value = serde_json::to_value(&settings).map_err(|err| {
+            Status::internal(format!(
+                "error serializing query settings, internal error: {}",
+                err
+            ))
+        })?;
+
+        let cache_hash = hash_query(&query.string, &components_hash, &settings_value);
+        let cache_entry = self
+            .contexts
+            .query_cache_context
+            .get_by_hash(&cache_hash)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        let (query_result, result, cached) = match cache_entry {
+            Some(cache_entry) => {
+                let query_result = QueryResponse {
+                    query_id: query.id,
+                    info: Default::default(),
+                    result: Some(serde_json::from_value(cache_entry.result.clone()).map_err(
+                        |err| {
+                            Status::internal(format!(
+                                "error parsing cached query result, internal error: {}",
+                                err
+                            ))
+                        },
+                    )?),
+                };
+                (query_result, cache_entry.result, true)
+            }
+            None => {
+                // Construct query request to send to Reveaal
+                let mut query_request = Request::new(QueryRequest {
+                    user_id: uid,
+                    query_id: query.id,
+                    query: query.string.clone(),
+                    components_info: Some(components_info),
+                    settings: Some(settings),
+                });
+                if let Some(timeout) = timeout {
+                    query_request.set_timeout(timeout);
+                }
+
+                // Run query on Reveaal until it finishes, times out or is cancelled
+                let mut run = self.query_run_service.start(query.id, self.job_id);
+                let query_result = match run
+                    .run(self.reveaal_service.send_query(query_request), timeout)
+                    .await
+                {
+                    Ok(query_result) => query_result.into_inner(),
+                    Err(err) => {
+                        if let Some(result) = interrupted_query_result(&err) {
+                            let result = serde_json::to_value(result).map_err(|err| {
+                                Status::internal(format!(
+                                    "error parsing query result, internal error: {}",
+                                    err
+                                ))
+                            })?;
+                            self.store_run(
+                                uid,
+                                query,
+                                result,
+                                true,
+                                settings_value,
+                                components_hash,
+                            )
+                            .await?;
+                        }
+                        return Err(err);
+                    }
+                };
+
+                let response_result = query_result
+                    .result
+                    .clone()
+                    .ok_or(Status::internal("failed to get query result"))?; //TODO better error message ?
+
+                let result = serde_json::to_value(&response_result).map_err(|err| {
+                    Status::internal(format!(
+                        "error parsing query result, internal error: {}",
+                        err
+                    ))
+                })?;
+
+                if is_cacheable(&response_result) {
+                    // A failing cache write only costs a Reveaal round trip next time
+                    if let Err(err) = self
+                        .contexts
+                        .query_cache_context
+                        .create(query_cache::Model {
+                            id: Default::default(),
+                            hash: cache_hash,
+                            result: result.clone(),
+                            created_at: Default::default(),
+                        })
+                        .await
+                    {
+                        log::warn!("failed to cache query result: {}", err);
+                    }
+                }
+
+                (query_result, result, false)
+            }
+        };
+
+        self.store_run(uid, query, result, false, settings_value, components_hash)
+            .await?;
+
+        Ok((query_result, cached))
+    }
+
+    /// Stores the result of a run on the query and records the run in the query's result history.
+    /// The result is marked as outdated if the run was interrupted or the components changed during the run.
+    async fn store_run(
+        &self,
+        uid: i32,
+        query: query::Model,
+        result: Json,
+        outdated: bool,
+        settings: Json,
+        components_hash: String,
+    ) -> Result<(), Status> {
+        // The components may have changed while the query was running
+        let (_, current_components_hash) = self.components(query.project_id).await?;
+        let outdated = outdated || current_components_hash != components_hash;
+
+        // Only the result is written, so edits made to the query during the run are kept
+        let updated = self
+            .contexts
+            .query_context
+            .update_result(query.id, result.clone(), outdated)
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        if !updated {
+            return Err(Status::new(Code::NotFound, "Query not found"));
+        }
+
+        // Record the run in the query's result history
+        let query_result = self
+            .contexts
+            .query_result_context
+            .create(query_result::Model {
+                id: Default::default(),
+                query_id: query.id,
+                user_id: Some(uid),
+                result,
+                settings,
+                components_hash,
+                created_at: Default::default(),
+            })
+            .await
+            .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+
+        // Keep the counterexample of a failed run, so it can be inspected and simulated
+        if let Some(counterexample) = extract_counterexample(&query, &query_result) {
+            self.contexts
+                .counterexample_context
+                .create(counterexample)
+                .await
+                .map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Results that may change when the same query is run again are not cached,
+/// e.g. when Reveaal did not have the components or failed internally.
+fn is_cacheable(result: &query_response::Result) -> bool {
+    !matches!(
+        result,
+        query_response::Result::ComponentsNotInCache(_) | query_response::Result::Error(_)
+    )
+}
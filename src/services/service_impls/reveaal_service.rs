@@ -3,59 +3,397 @@ use crate::api::server::protobuf::{
     QueryRequest, QueryResponse, SimulationStartRequest, SimulationStepRequest,
     SimulationStepResponse, UserTokenResponse,
 };
-use crate::services::service_traits::ReveaalServiceTrait;
+use crate::services::service_traits::{ReveaalHealth, ReveaalServiceTrait};
 use async_trait::async_trait;
-use tonic::transport::Channel;
-use tonic::{Request, Response, Status};
+use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Mutex};
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Extensions, Request, Response, Status};
 
-pub struct ReveaalService {
+/// Timeouts and intervals of the connections to Reveaal.
+/// Requests themselves have no deadline here, each call sets its own, e.g. the timeout of the query it runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReveaalServiceConfig {
+    pub connect_timeout: Duration,
+    pub keep_alive_interval: Duration,
+    pub keep_alive_timeout: Duration,
+    pub health_check_interval: Duration,
+}
+
+impl Default for ReveaalServiceConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            keep_alive_interval: Duration::from_secs(30),
+            keep_alive_timeout: Duration::from_secs(10),
+            health_check_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReveaalServiceConfig {
+    /// Reads the config from the `REVEAAL_*_SECS` environment variables,
+    /// falling back to the defaults for the variables that are not set.
+    /// # Panics
+    /// Panics if a variable is set but is not a number.
+    pub fn from_env() -> Self {
+        let secs = |name: &str, default: Duration| match env::var(name) {
+            Ok(secs) => Duration::from_secs(
+                secs.parse()
+                    .unwrap_or_else(|_| panic!("Expected {} to be a number.", name)),
+            ),
+            Err(_) => default,
+        };
+        let default = Self::default();
+
+        Self {
+            connect_timeout: secs("REVEAAL_CONNECT_TIMEOUT_SECS", default.connect_timeout),
+            keep_alive_interval: secs(
+                "REVEAAL_KEEP_ALIVE_INTERVAL_SECS",
+                default.keep_alive_interval,
+            ),
+            keep_alive_timeout: secs(
+                "REVEAAL_KEEP_ALIVE_TIMEOUT_SECS",
+                default.keep_alive_timeout,
+            ),
+            health_check_interval: secs(
+                "REVEAAL_HEALTH_CHECK_INTERVAL_SECS",
+                default.health_check_interval,
+            ),
+        }
+    }
+}
+
+/// A single Reveaal instance that requests can be sent to.
+struct Backend {
     address: String,
+    channel: Channel,
+    health: watch::Sender<ReveaalHealth>,
+    outstanding: AtomicUsize,
+}
+
+impl Backend {
+    /// Whether the backend is in rotation, which it is unless its last probe or request failed.
+    fn is_available(&self) -> bool {
+        !matches!(*self.health.borrow(), ReveaalHealth::NotServing(_))
+    }
+
+    fn set_health(&self, health: ReveaalHealth) {
+        self.health.send_if_modified(|current| {
+            if *current == health {
+                return false;
+            }
+            match health {
+                ReveaalHealth::NotServing(_) => {
+                    log::warn!("Reveaal at '{}' is {}", self.address, health)
+                }
+                _ => log::info!("Reveaal at '{}' is {}", self.address, health),
+            }
+            *current = health;
+            true
+        });
+    }
+
+    /// Sends a request to the backend while counting it as outstanding.
+    /// The backend is taken out of rotation if it cannot be reached.
+    async fn call<T, R, F, Fut>(&self, request: Request<T>, call: &F) -> Result<Response<R>, Status>
+    where
+        F: Fn(EcdarBackendClient<Channel>, Request<T>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        self.outstanding.fetch_add(1, Ordering::SeqCst);
+        let _outstanding = Outstanding(&self.outstanding);
+
+        let res = call(EcdarBackendClient::new(self.channel.clone()), request).await;
+
+        if let Err(err) = &res {
+            if err.code() == Code::Unavailable {
+                self.set_health(ReveaalHealth::NotServing(err.message().to_string()));
+            }
+        }
+        res
+    }
+}
+
+/// Decrements the outstanding requests of a backend when dropped, so cancelled requests are not counted.
+struct Outstanding<'a>(&'a AtomicUsize);
+
+impl Drop for Outstanding<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// How long a simulation session stays pinned to its backend without taking a step.
+const SIMULATION_PIN_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// The backend a simulation session is pinned to, and when the session last used it.
+struct SimulationPin {
+    backend: usize,
+    last_used: Instant,
+}
+
+pub struct ReveaalService {
+    backends: Vec<Arc<Backend>>,
+    /// The pins of the simulation sessions, keyed on the session id, as Reveaal has no simulation ids.
+    simulations: Mutex<HashMap<i32, SimulationPin>>,
 }
 
 impl ReveaalService {
-    pub fn new(address: &str) -> Self {
-        Self {
-            address: address.to_string(),
+    /// Creates a service that spreads requests across the Reveaal instances at the given addresses.
+    /// Each instance gets its own channel, which connects on the first request and reconnects by itself when the connection is lost.
+    /// A background probe checks whether each instance can be reached over its channel every `health_check_interval`,
+    /// and puts instances that have failed back into rotation once they can.
+    /// # Errors
+    /// Errors if an address is not a valid uri.
+    pub fn new(
+        addresses: &[&str],
+        config: ReveaalServiceConfig,
+    ) -> Result<Self, tonic::transport::Error> {
+        let mut backends = Vec::with_capacity(addresses.len());
+
+        for address in addresses {
+            let endpoint = Endpoint::from_shared(address.to_string())?
+                .connect_timeout(config.connect_timeout)
+                .http2_keep_alive_interval(config.keep_alive_interval)
+                .keep_alive_timeout(config.keep_alive_timeout)
+                .keep_alive_while_idle(true);
+
+            let backend = Arc::new(Backend {
+                address: address.to_string(),
+                channel: endpoint.connect_lazy(),
+                health: watch::channel(ReveaalHealth::Unknown).0,
+                outstanding: AtomicUsize::new(0),
+            });
+
+            let weak_backend = Arc::downgrade(&backend);
+            let mut interval = tokio::time::interval(config.health_check_interval);
+            let timeout = config.connect_timeout;
+            tokio::spawn(async move {
+                loop {
+                    interval.tick().await;
+                    // The backend is only held while it is probed, so the probe stops once the service is dropped
+                    let channel = match weak_backend.upgrade() {
+                        Some(backend) => backend.channel.clone(),
+                        None => break,
+                    };
+                    let health = probe(channel, timeout).await;
+
+                    match weak_backend.upgrade() {
+                        Some(backend) => backend.set_health(health),
+                        // The service has been dropped
+                        None => break,
+                    }
+                }
+            });
+
+            backends.push(backend);
+        }
+
+        Ok(Self {
+            backends,
+            simulations: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Pins a simulation session to a backend, and drops the pins of sessions that have been idle for too long.
+    async fn pin_simulation(&self, simulation_session_id: i32, backend: usize) {
+        let mut simulations = self.simulations.lock().await;
+        simulations.retain(|_, pin| pin.last_used.elapsed() < SIMULATION_PIN_IDLE_TIMEOUT);
+        simulations.insert(
+            simulation_session_id,
+            SimulationPin {
+                backend,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    /// Returns the backend a simulation session is pinned to, unless the pin has expired.
+    async fn pinned_backend(&self, simulation_session_id: i32) -> Option<usize> {
+        let mut simulations = self.simulations.lock().await;
+        match simulations.get_mut(&simulation_session_id) {
+            Some(pin) if pin.last_used.elapsed() < SIMULATION_PIN_IDLE_TIMEOUT => {
+                pin.last_used = Instant::now();
+                Some(pin.backend)
+            }
+            Some(_) => {
+                simulations.remove(&simulation_session_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Returns the index of the backend with the fewest outstanding requests among the candidates.
+    fn least_outstanding(&self, candidates: impl Iterator<Item = usize>) -> Option<usize> {
+        candidates.min_by_key(|&index| self.backends[index].outstanding.load(Ordering::SeqCst))
+    }
+
+    /// Sends a request to the available backend with the fewest outstanding requests.
+    /// If that backend cannot be reached, the request is retried on the next one.
+    /// When no backend is available, the request is still tried once in case the backends have recovered since they were last probed.
+    ///
+    /// Returns the index of the backend that answered along with its response.
+    async fn call<T, R, F, Fut>(
+        &self,
+        request: Request<T>,
+        call: F,
+    ) -> Result<(usize, Response<R>), Status>
+    where
+        T: Clone,
+        F: Fn(EcdarBackendClient<Channel>, Request<T>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        let (metadata, _, message) = request.into_parts();
+        let mut tried = vec![false; self.backends.len()];
+        let mut last_err = None;
+
+        loop {
+            let untried = (0..self.backends.len()).filter(|&index| !tried[index]);
+            let index = self
+                .least_outstanding(
+                    untried
+                        .clone()
+                        .filter(|&index| self.backends[index].is_available()),
+                )
+                .or_else(|| match last_err {
+                    None => self.least_outstanding(untried),
+                    Some(_) => None,
+                });
+
+            let index = match index {
+                Some(index) => index,
+                None => {
+                    return Err(last_err.unwrap_or_else(|| {
+                        Status::unavailable("No Reveaal backends are configured")
+                    }))
+                }
+            };
+            tried[index] = true;
+
+            let request =
+                Request::from_parts(metadata.clone(), Extensions::default(), message.clone());
+            match self.backends[index].call(request, &call).await {
+                Err(err) if err.code() == Code::Unavailable => last_err = Some(err),
+                res => return res.map(|response| (index, response)),
+            }
         }
     }
+}
 
-    async fn get_connection(&self) -> Result<EcdarBackendClient<Channel>, Status> {
-        EcdarBackendClient::connect(self.address.clone())
-            .await
-            .map_err(|err| Status::internal(format!("{err}")))
+/// Checks whether Reveaal can be reached over the channel requests are sent on,
+/// so the probe shares the connection of the requests instead of opening one of its own.
+/// Reveaal is sent a standard gRPC health check, and any answer it gives counts as serving,
+/// including that it does not implement health checks.
+pub(crate) async fn probe(channel: Channel, timeout: Duration) -> ReveaalHealth {
+    let mut client = Grpc::new(channel);
+    if let Err(err) = client.ready().await {
+        return ReveaalHealth::NotServing(err.to_string());
+    }
+
+    let mut request = Request::new(());
+    request.set_timeout(timeout);
+    let res = client
+        .unary::<(), (), _>(
+            request,
+            PathAndQuery::from_static("/grpc.health.v1.Health/Check"),
+            ProstCodec::default(),
+        )
+        .await;
+
+    match res {
+        Err(err) if matches!(err.code(), Code::Unavailable | Code::DeadlineExceeded) => {
+            ReveaalHealth::NotServing(err.message().to_string())
+        }
+        _ => ReveaalHealth::Serving,
     }
 }
 
 #[async_trait]
 impl ReveaalServiceTrait for ReveaalService {
+    fn health(&self) -> Vec<(String, ReveaalHealth)> {
+        self.backends
+            .iter()
+            .map(|backend| (backend.address.clone(), backend.health.borrow().clone()))
+            .collect()
+    }
+
     async fn get_user_token(
         &self,
         request: Request<()>,
     ) -> Result<Response<UserTokenResponse>, Status> {
-        self.get_connection().await?.get_user_token(request).await
+        self.call(request, |mut client, request| async move {
+            client.get_user_token(request).await
+        })
+        .await
+        .map(|(_, response)| response)
     }
 
     async fn send_query(
         &self,
         request: Request<QueryRequest>,
     ) -> Result<Response<QueryResponse>, Status> {
-        self.get_connection().await?.send_query(request).await
+        self.call(request, |mut client, request| async move {
+            client.send_query(request).await
+        })
+        .await
+        .map(|(_, response)| response)
     }
 
+    /// Starts the simulation on the least busy backend.
+    /// A simulation of a session is pinned to that backend, so that its steps are taken on the same backend.
     async fn start_simulation(
         &self,
+        simulation_session_id: Option<i32>,
         request: Request<SimulationStartRequest>,
     ) -> Result<Response<SimulationStepResponse>, Status> {
-        self.get_connection().await?.start_simulation(request).await
+        let (index, response) = self
+            .call(request, |mut client, request| async move {
+                client.start_simulation(request).await
+            })
+            .await?;
+
+        if let Some(simulation_session_id) = simulation_session_id {
+            self.pin_simulation(simulation_session_id, index).await;
+        }
+        Ok(response)
     }
 
+    /// Takes the step on the backend that started the simulation of the session.
+    /// Steps of sessions that are not pinned are sent to the least busy backend, which the session is then pinned to.
     async fn take_simulation_step(
         &self,
+        simulation_session_id: Option<i32>,
         request: Request<SimulationStepRequest>,
     ) -> Result<Response<SimulationStepResponse>, Status> {
-        self.get_connection()
-            .await?
-            .take_simulation_step(request)
-            .await
+        let call = |mut client: EcdarBackendClient<Channel>, request| async move {
+            client.take_simulation_step(request).await
+        };
+
+        let Some(simulation_session_id) = simulation_session_id else {
+            return self.call(request, call).await.map(|(_, response)| response);
+        };
+
+        match self.pinned_backend(simulation_session_id).await {
+            Some(index) => self.backends[index].call(request, &call).await,
+            None => {
+                let (index, response) = self.call(request, call).await?;
+                self.pin_simulation(simulation_session_id, index).await;
+                Ok(response)
+            }
+        }
+    }
+
+    async fn end_simulation(&self, simulation_session_id: i32) {
+        self.simulations.lock().await.remove(&simulation_session_id);
     }
 }
@@ -1,5 +1,13 @@
 pub mod hashing_service_trait;
+pub mod project_edit_service_trait;
+pub mod query_event_service_trait;
+pub mod query_job_service_trait;
+pub mod query_run_service_trait;
 pub mod reveaal_service_trait;
 
 pub use hashing_service_trait::HashingServiceTrait;
-pub use reveaal_service_trait::ReveaalServiceTrait;
+pub use project_edit_service_trait::{ProjectEditServiceTrait, ProjectEditStream};
+pub use query_event_service_trait::{QueryEventServiceTrait, QueryEventStream};
+pub use query_job_service_trait::QueryJobServiceTrait;
+pub use query_run_service_trait::{interrupted_query_result, QueryRun, QueryRunServiceTrait};
+pub use reveaal_service_trait::{ReveaalHealth, ReveaalServiceTrait};
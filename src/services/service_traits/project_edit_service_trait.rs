@@ -0,0 +1,15 @@
+use crate::api::server::protobuf::ProjectEditEvent;
+use futures::Stream;
+use std::pin::Pin;
+use tonic::Status;
+
+/// A stream of the edits made to a single project.
+pub type ProjectEditStream = Pin<Box<dyn Stream<Item = Result<ProjectEditEvent, Status>> + Send>>;
+
+pub trait ProjectEditServiceTrait: Send + Sync {
+    /// Sends an edit to everyone collaborating on the project of the edit.
+    fn publish(&self, event: ProjectEditEvent);
+
+    /// Returns a stream of all edits published for the given project from now on.
+    fn subscribe(&self, project_id: i32) -> ProjectEditStream;
+}
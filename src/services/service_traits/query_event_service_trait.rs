@@ -0,0 +1,15 @@
+use crate::api::server::protobuf::QueryEvent;
+use futures::Stream;
+use std::pin::Pin;
+use tonic::Status;
+
+/// A stream of the query events of a single project.
+pub type QueryEventStream = Pin<Box<dyn Stream<Item = Result<QueryEvent, Status>> + Send>>;
+
+pub trait QueryEventServiceTrait: Send + Sync {
+    /// Sends an event to everyone subscribed to the project of the event.
+    fn publish(&self, event: QueryEvent);
+
+    /// Returns a stream of all events published for the given project from now on.
+    fn subscribe(&self, project_id: i32) -> QueryEventStream;
+}
@@ -0,0 +1,8 @@
+use tonic::Status;
+
+pub trait QueryJobServiceTrait: Send + Sync {
+    /// Puts a query job on the queue, it is run as soon as a worker is available.
+    /// # Errors
+    /// Errors if the workers have stopped.
+    fn enqueue(&self, job_id: i32) -> Result<(), Status>;
+}
@@ -0,0 +1,113 @@
+use crate::api::server::protobuf::query_response;
+use std::future::{pending, Future};
+use std::time::Duration;
+use tokio::sync::watch;
+use tonic::{Code, Status};
+
+/// A run of a query that is in flight on Reveaal.
+/// The run stays registered until it is dropped.
+pub struct QueryRun {
+    cancelled: watch::Receiver<bool>,
+    job_cancelled: Option<watch::Receiver<bool>>,
+    on_drop: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl QueryRun {
+    /// Creates a run that is cancelled once `cancelled` turns true, and calls `on_drop` when dropped.
+    pub fn new(cancelled: watch::Receiver<bool>, on_drop: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            cancelled,
+            job_cancelled: None,
+            on_drop: Some(Box::new(on_drop)),
+        }
+    }
+
+    /// Makes the run of a query job also be cancelled once `job_cancelled` turns true.
+    pub fn with_job_cancelled(mut self, job_cancelled: watch::Receiver<bool>) -> Self {
+        self.job_cancelled = Some(job_cancelled);
+        self
+    }
+
+    /// Waits until the run is cancelled, which may be never.
+    pub async fn cancelled(&mut self) {
+        match &mut self.job_cancelled {
+            Some(job_cancelled) => tokio::select! {
+                _ = wait_for_cancel(&mut self.cancelled) => {},
+                _ = wait_for_cancel(job_cancelled) => {},
+            },
+            None => wait_for_cancel(&mut self.cancelled).await,
+        }
+    }
+
+    /// Awaits a call to Reveaal until it finishes, the timeout elapses or the run is cancelled.
+    /// In the latter cases the call is dropped, which aborts the request to Reveaal.
+    /// # Errors
+    /// Errors with `DeadlineExceeded` if the timeout elapses, and `Cancelled` if the run is cancelled.
+    pub async fn run<T>(
+        &mut self,
+        call: impl Future<Output = Result<T, Status>>,
+        timeout: Option<Duration>,
+    ) -> Result<T, Status> {
+        let call = async {
+            match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, call).await.map_err(|_| {
+                    Status::deadline_exceeded(format!(
+                        "Query timed out after {} seconds",
+                        timeout.as_secs()
+                    ))
+                })?,
+                None => call.await,
+            }
+        };
+
+        // Cancellation is checked first, so a cancelled run never delivers a result
+        tokio::select! {
+            biased;
+            _ = self.cancelled() => Err(Status::cancelled("Query was cancelled")),
+            res = call => res,
+        }
+    }
+}
+
+/// Waits until `cancelled` turns true, which may be never.
+async fn wait_for_cancel(cancelled: &mut watch::Receiver<bool>) {
+    if cancelled.wait_for(|cancelled| *cancelled).await.is_err() {
+        // The run can no longer be cancelled
+        pending::<()>().await;
+    }
+}
+
+impl Drop for QueryRun {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop.take() {
+            on_drop();
+        }
+    }
+}
+
+/// Returns the result to record for a run that was stopped with the given status,
+/// or `None` if the run was not stopped by a timeout or cancellation.
+pub fn interrupted_query_result(status: &Status) -> Option<query_response::Result> {
+    match status.code() {
+        Code::Cancelled | Code::DeadlineExceeded => {
+            Some(query_response::Result::Error(query_response::Error {
+                error: status.message().to_string(),
+            }))
+        }
+        _ => None,
+    }
+}
+
+pub trait QueryRunServiceTrait: Send + Sync {
+    /// Registers a run of the query, which is cancelled along with the other runs of the query by `cancel`.
+    /// A run made for a query job is also cancelled on its own by `cancel_job`.
+    fn start(&self, query_id: i32, job_id: Option<i32>) -> QueryRun;
+
+    /// Cancels every run of the query that is in flight.
+    /// Returns whether any run was in flight.
+    fn cancel(&self, query_id: i32) -> bool;
+
+    /// Cancels the run of the query job, leaving the other runs of its query alone.
+    /// Returns whether the job's run was in flight.
+    fn cancel_job(&self, job_id: i32) -> bool;
+}
@@ -3,10 +3,33 @@ use crate::api::server::protobuf::{
     SimulationStepResponse, UserTokenResponse,
 };
 use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
 use tonic::{Request, Response, Status};
 
+/// The health of a Reveaal backend as last seen by its health probe or the requests sent to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReveaalHealth {
+    /// The backend has not been probed yet
+    Unknown,
+    Serving,
+    /// The backend could not be reached, with the reason why
+    NotServing(String),
+}
+
+impl Display for ReveaalHealth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReveaalHealth::Unknown => write!(f, "unknown"),
+            ReveaalHealth::Serving => write!(f, "serving"),
+            ReveaalHealth::NotServing(reason) => write!(f, "not serving: {}", reason),
+        }
+    }
+}
+
 #[async_trait]
 pub trait ReveaalServiceTrait: Send + Sync {
+    /// Returns the address and health of each Reveaal backend as last reported by its health probe or requests.
+    fn health(&self) -> Vec<(String, ReveaalHealth)>;
     async fn get_user_token(
         &self,
         request: Request<()>,
@@ -15,12 +38,18 @@ pub trait ReveaalServiceTrait: Send + Sync {
         &self,
         request: Request<QueryRequest>,
     ) -> Result<Response<QueryResponse>, Status>;
+    /// Starts a simulation, as part of the simulation session with the given id if there is one.
     async fn start_simulation(
         &self,
+        simulation_session_id: Option<i32>,
         request: Request<SimulationStartRequest>,
     ) -> Result<Response<SimulationStepResponse>, Status>;
+    /// Takes a step of a simulation, as part of the simulation session with the given id if there is one.
     async fn take_simulation_step(
         &self,
+        simulation_session_id: Option<i32>,
         request: Request<SimulationStepRequest>,
     ) -> Result<Response<SimulationStepResponse>, Status>;
+    /// Forgets about a simulation session that has ended.
+    async fn end_simulation(&self, simulation_session_id: i32);
 }
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod auth {
-    use crate::api::auth::{RequestExt, Token, TokenError, TokenType};
+    use crate::api::auth::{backend_interceptor, RequestExt, Token, TokenError, TokenType};
+    use chrono::{Duration, Utc};
     use std::{env, str::FromStr};
-    use tonic::{metadata::MetadataValue, Request};
+    use tonic::{metadata::MetadataValue, Code, Request};
 
     #[tokio::test]
     async fn request_token_trims_bearer() {
@@ -77,6 +78,31 @@ mod auth {
         assert_eq!(result_refresh.unwrap_err(), TokenError::InvalidToken);
     }
 
+    #[tokio::test]
+    async fn token_with_expiration_invitation_returns_tokendata() {
+        env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+
+        let expiration = Utc::now() + Duration::hours(1);
+        let token = Token::with_expiration(TokenType::InvitationToken, "1", expiration).unwrap();
+        let result = token.validate().unwrap();
+
+        assert_eq!(result.claims.sub, "1");
+    }
+
+    #[tokio::test]
+    async fn token_with_expiration_in_past_returns_err() {
+        env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+
+        let token = Token::with_expiration(
+            TokenType::InvitationToken,
+            "1",
+            Utc::now() - Duration::hours(1),
+        )
+        .unwrap();
+
+        assert_eq!(token.validate().unwrap_err(), TokenError::ExpiredSignature);
+    }
+
     #[tokio::test]
     async fn token_type_access_returns_access() {
         env::set_var("ACCESS_TOKEN_HS512_SECRET", "access_secret");
@@ -138,4 +164,69 @@ mod auth {
 
         assert!(result.is_err());
     }
+
+    fn backend_request(token: &str) -> Request<()> {
+        env::set_var("ACCESS_TOKEN_HS512_SECRET", "access_secret");
+        env::set_var("BACKEND_SERVICE_TOKEN", "service_secret");
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        request
+    }
+
+    #[tokio::test]
+    async fn backend_interceptor_access_token_sets_uid() {
+        env::set_var("ACCESS_TOKEN_HS512_SECRET", "access_secret");
+        let token = Token::access("1").unwrap();
+
+        let request = backend_interceptor(backend_request(token.as_str())).unwrap();
+
+        assert_eq!(request.uid().unwrap(), Some(1));
+        assert!(!request.is_service());
+    }
+
+    #[tokio::test]
+    async fn backend_interceptor_service_token_marks_service() {
+        let request = backend_interceptor(backend_request("service_secret")).unwrap();
+
+        assert!(request.is_service());
+        assert_eq!(request.uid().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn backend_interceptor_invalid_token_returns_err() {
+        let res = backend_interceptor(backend_request("service_secre"));
+
+        assert_eq!(res.unwrap_err().code(), Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn backend_interceptor_no_token_returns_err() {
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("uid", MetadataValue::from_static("1"));
+
+        let res = backend_interceptor(request);
+
+        assert_eq!(res.unwrap_err().code(), Code::Unauthenticated);
+    }
+
+    #[tokio::test]
+    async fn backend_interceptor_strips_spoofed_service_metadata() {
+        env::set_var("ACCESS_TOKEN_HS512_SECRET", "access_secret");
+        let token = Token::access("1").unwrap();
+        let mut request = backend_request(token.as_str());
+        request
+            .metadata_mut()
+            .insert("service", MetadataValue::from_static("true"));
+
+        let request = backend_interceptor(request).unwrap();
+
+        assert!(!request.is_service());
+        assert_eq!(request.uid().unwrap(), Some(1));
+    }
 }
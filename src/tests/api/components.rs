@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod components {
+    use crate::api::components::{
+        component_name, components_info, placeholder_component_name, references_any_component,
+    };
+    use crate::api::server::protobuf::component::Rep;
+    use crate::api::server::protobuf::Component;
+    use crate::entities::component;
+
+    fn json_component(json: &str) -> Component {
+        Component {
+            rep: Some(Rep::Json(json.to_owned())),
+        }
+    }
+
+    fn create_component(name: &str) -> component::Model {
+        component::Model {
+            id: Default::default(),
+            project_id: 1,
+            name: name.to_owned(),
+            component: serde_json::to_value(json_component(&format!("{{\"name\":\"{}\"}}", name)))
+                .unwrap(),
+            updated_at: Default::default(),
+        }
+    }
+
+    #[test]
+    fn component_name_returns_json_name() {
+        let component = json_component("{\"name\":\"Machine\",\"locations\":[]}");
+
+        assert_eq!(component_name(&component), Some("Machine".to_owned()));
+    }
+
+    #[test]
+    fn component_name_without_name_returns_none() {
+        let component = json_component("{\"locations\":[]}");
+
+        assert_eq!(component_name(&component), None);
+    }
+
+    #[test]
+    fn component_name_of_xml_component_returns_none() {
+        let component = Component {
+            rep: Some(Rep::Xml(
+                "<template><name>Machine</name></template>".to_owned(),
+            )),
+        };
+
+        assert_eq!(component_name(&component), None);
+    }
+
+    #[test]
+    fn placeholder_component_name_returns_first_free_name() {
+        let name =
+            placeholder_component_name(|name| name == "Component 1" || name == "Component 3");
+
+        assert_eq!(name, "Component 2");
+    }
+
+    #[test]
+    fn references_any_component_matches_identifier() {
+        assert!(references_any_component(
+            "refinement: Machine <= Researcher",
+            &["Researcher"]
+        ));
+    }
+
+    #[test]
+    fn references_any_component_ignores_longer_identifier() {
+        assert!(!references_any_component(
+            "consistency: Machine2 && Machine_old",
+            &["Machine"]
+        ));
+    }
+
+    #[test]
+    fn references_any_component_skips_leading_digits() {
+        assert!(references_any_component(
+            "consistency: 2Machine",
+            &["Machine"]
+        ));
+    }
+
+    #[test]
+    fn references_any_component_without_names_returns_false() {
+        assert!(!references_any_component("consistency: Machine", &[]));
+    }
+
+    #[test]
+    fn components_info_returns_components_in_order() {
+        let result = components_info(vec![create_component("A"), create_component("B")]).unwrap();
+
+        assert_eq!(
+            result.components,
+            vec![
+                json_component("{\"name\":\"A\"}"),
+                json_component("{\"name\":\"B\"}")
+            ]
+        );
+    }
+
+    #[test]
+    fn components_info_equal_components_give_equal_hash() {
+        let first = components_info(vec![create_component("A")]).unwrap();
+        let second = components_info(vec![create_component("A")]).unwrap();
+
+        assert_eq!(first.components_hash, second.components_hash);
+    }
+
+    #[test]
+    fn components_info_different_components_give_different_hash() {
+        let first = components_info(vec![create_component("A")]).unwrap();
+        let second = components_info(vec![create_component("B")]).unwrap();
+
+        assert_ne!(first.components_hash, second.components_hash);
+    }
+
+    #[test]
+    fn components_info_invalid_component_returns_err() {
+        let mut component = create_component("A");
+        component.component = serde_json::json!({ "rep": 1 });
+
+        assert!(components_info(vec![component]).is_err());
+    }
+}
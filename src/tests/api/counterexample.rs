@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod counterexample {
+    use crate::api::counterexample::{
+        extract_counterexample, reachability_system, simulation_composition,
+    };
+    use crate::api::server::protobuf::query_response::{
+        consistency_failure, refinement_failure, refinement_state_failure, ConsistencyFailure,
+        DeterminismFailure, ImplementationFailure, ModelFailure, ReachabilityFailure,
+        ReachabilityPath, RefinementFailure, RefinementStateFailure, Result, Success,
+    };
+    use crate::api::server::protobuf::{ActionSet, Decision, Path, State, StateAction};
+    use crate::entities::{query, query_result};
+    use serde_json::json;
+
+    fn state() -> State {
+        State {
+            location_tree: None,
+            zone: None,
+        }
+    }
+
+    fn decision(action: &str) -> Decision {
+        Decision {
+            source: Some(state()),
+            action: action.to_owned(),
+            edges: vec![],
+            destination: Some(state()),
+        }
+    }
+
+    fn query(string: &str) -> query::Model {
+        query::Model {
+            id: 1,
+            string: string.to_owned(),
+            result: None,
+            outdated: false,
+            project_id: 1,
+            settings: None,
+            timeout_secs: None,
+        }
+    }
+
+    fn query_result(result: Result) -> query_result::Model {
+        query_result::Model {
+            id: 2,
+            query_id: 1,
+            user_id: Some(1),
+            result: serde_json::to_value(result).unwrap(),
+            settings: json!({}),
+            components_hash: "e3b0c442".to_owned(),
+            created_at: Default::default(),
+        }
+    }
+
+    #[test]
+    fn extract_refinement_state_failure_returns_refinement_counterexample() {
+        let result = Result::RefinementFailure(RefinementFailure {
+            failure: Some(refinement_failure::Failure::RefinementStateFailure(
+                RefinementStateFailure {
+                    reason: refinement_state_failure::Reason::CannotMatch as i32,
+                    system: "A <= B".to_owned(),
+                    action_set: Some(ActionSet {
+                        actions: vec!["a".to_owned(), "b".to_owned()],
+                        is_input: true,
+                    }),
+                    state: Some(state()),
+                },
+            )),
+        });
+
+        let counterexample =
+            extract_counterexample(&query("refinement: A <= B"), &query_result(result)).unwrap();
+
+        assert_eq!(counterexample.query_id, 1);
+        assert_eq!(counterexample.query_result_id, 2);
+        assert_eq!(counterexample.kind, "REFINEMENT");
+        assert_eq!(counterexample.system, Some("A <= B".to_owned()));
+        assert_eq!(counterexample.reason, Some("CANNOT_MATCH".to_owned()));
+        assert_eq!(counterexample.actions, json!(["a", "b"]));
+        assert_eq!(
+            counterexample.state,
+            Some(serde_json::to_value(state()).unwrap())
+        );
+        assert_eq!(counterexample.path, json!([]));
+    }
+
+    #[test]
+    fn extract_consistency_implementation_failure_returns_implementation_counterexample() {
+        let result = Result::ConsistencyFailure(ConsistencyFailure {
+            failure: Some(consistency_failure::Failure::Implementation(
+                ImplementationFailure {
+                    system: "A".to_owned(),
+                    failure_state: Some(StateAction {
+                        state: Some(state()),
+                        action: "a".to_owned(),
+                    }),
+                },
+            )),
+        });
+
+        let counterexample =
+            extract_counterexample(&query("consistency: A"), &query_result(result)).unwrap();
+
+        assert_eq!(counterexample.kind, "IMPLEMENTATION");
+        assert_eq!(counterexample.system, Some("A".to_owned()));
+        assert_eq!(counterexample.reason, None);
+        assert_eq!(counterexample.actions, json!(["a"]));
+        assert!(counterexample.state.is_some());
+    }
+
+    #[test]
+    fn extract_determinism_failure_returns_determinism_counterexample() {
+        let result = Result::DeterminismFailure(DeterminismFailure {
+            system: "A".to_owned(),
+            failure_state: Some(StateAction {
+                state: Some(state()),
+                action: "a".to_owned(),
+            }),
+        });
+
+        let counterexample =
+            extract_counterexample(&query("determinism: A"), &query_result(result)).unwrap();
+
+        assert_eq!(counterexample.kind, "DETERMINISM");
+        assert_eq!(counterexample.actions, json!(["a"]));
+    }
+
+    #[test]
+    fn extract_reachability_path_returns_reachability_counterexample() {
+        let path = vec![decision("a"), decision("b")];
+        let result = Result::ReachabilityPath(ReachabilityPath {
+            path: Some(Path {
+                decisions: path.clone(),
+            }),
+        });
+
+        let counterexample = extract_counterexample(
+            &query("reachability: (A || B) -> [L1, L2]()"),
+            &query_result(result),
+        )
+        .unwrap();
+
+        assert_eq!(counterexample.kind, "REACHABILITY");
+        assert_eq!(counterexample.system, Some("(A || B)".to_owned()));
+        assert_eq!(counterexample.actions, json!(["a", "b"]));
+        assert_eq!(
+            counterexample.state,
+            Some(serde_json::to_value(state()).unwrap())
+        );
+        assert_eq!(counterexample.path, serde_json::to_value(path).unwrap());
+    }
+
+    #[test]
+    fn extract_success_returns_none() {
+        let result = Result::Success(Success {});
+
+        let counterexample =
+            extract_counterexample(&query("refinement: A <= B"), &query_result(result));
+
+        assert!(counterexample.is_none());
+    }
+
+    #[test]
+    fn extract_model_failure_returns_none() {
+        let result = Result::RefinementFailure(RefinementFailure {
+            failure: Some(refinement_failure::Failure::ModelFailure(ModelFailure {
+                reason: 0,
+                system: "A <= B".to_owned(),
+                action_failure: None,
+            })),
+        });
+
+        let counterexample =
+            extract_counterexample(&query("refinement: A <= B"), &query_result(result));
+
+        assert!(counterexample.is_none());
+    }
+
+    #[test]
+    fn extract_unreachable_returns_none() {
+        let result = Result::ReachabilityFailure(ReachabilityFailure {
+            reason: 0,
+            system: "A".to_owned(),
+        });
+
+        let counterexample =
+            extract_counterexample(&query("reachability: A -> [L1]()"), &query_result(result));
+
+        assert!(counterexample.is_none());
+    }
+
+    #[test]
+    fn extract_unparsable_result_returns_none() {
+        let mut query_result = query_result(Result::Success(Success {}));
+        query_result.result = json!({});
+
+        let counterexample = extract_counterexample(&query("refinement: A <= B"), &query_result);
+
+        assert!(counterexample.is_none());
+    }
+
+    #[test]
+    fn reachability_system_without_system_returns_none() {
+        assert_eq!(reachability_system("reachability:  -> [L1]()"), None);
+    }
+
+    #[test]
+    fn simulation_composition_of_refinement_returns_refining_side() {
+        let mut counterexample = extract_counterexample(
+            &query("determinism: A"),
+            &query_result(Result::DeterminismFailure(DeterminismFailure {
+                system: "A".to_owned(),
+                failure_state: None,
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            simulation_composition(&counterexample),
+            Some("A".to_owned())
+        );
+
+        counterexample.kind = "REFINEMENT".to_owned();
+        counterexample.system = Some("(A || B) <= C".to_owned());
+
+        assert_eq!(
+            simulation_composition(&counterexample),
+            Some("(A || B)".to_owned())
+        );
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod query_hash {
+    use crate::api::query_hash::{hash_components_info, hash_query};
+    use serde_json::json;
+
+    #[test]
+    fn hash_is_hex_encoded_sha256() {
+        let result = hash_components_info(&json!({}));
+
+        assert_eq!(
+            result,
+            "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        );
+    }
+
+    #[test]
+    fn hash_equal_components_info_returns_same_hash() {
+        let components_info = json!({ "components": [], "components_hash": 0 });
+        let reordered = json!({ "components_hash": 0, "components": [] });
+
+        assert_eq!(
+            hash_components_info(&components_info),
+            hash_components_info(&reordered)
+        );
+    }
+
+    #[test]
+    fn hash_different_components_info_returns_different_hash() {
+        let components_info = json!({ "components": [], "components_hash": 0 });
+        let changed = json!({ "components": [], "components_hash": 1 });
+
+        assert_ne!(
+            hash_components_info(&components_info),
+            hash_components_info(&changed)
+        );
+    }
+
+    #[test]
+    fn hash_query_equal_runs_returns_same_hash() {
+        let components_hash = hash_components_info(&json!({ "components": [] }));
+        let settings = json!({ "disable_clock_reduction": false });
+
+        assert_eq!(
+            hash_query("refinement: A <= B", &components_hash, &settings),
+            hash_query("refinement: A <= B", &components_hash, &settings)
+        );
+    }
+
+    #[test]
+    fn hash_query_different_query_returns_different_hash() {
+        let components_hash = hash_components_info(&json!({ "components": [] }));
+        let settings = json!({ "disable_clock_reduction": false });
+
+        assert_ne!(
+            hash_query("refinement: A <= B", &components_hash, &settings),
+            hash_query("refinement: B <= A", &components_hash, &settings)
+        );
+    }
+
+    #[test]
+    fn hash_query_different_settings_returns_different_hash() {
+        let components_hash = hash_components_info(&json!({ "components": [] }));
+
+        assert_ne!(
+            hash_query(
+                "consistency: A",
+                &components_hash,
+                &json!({ "disable_clock_reduction": false })
+            ),
+            hash_query(
+                "consistency: A",
+                &components_hash,
+                &json!({ "disable_clock_reduction": true })
+            )
+        );
+    }
+}
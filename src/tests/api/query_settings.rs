@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod query_settings {
+    use crate::api::query_settings::{
+        resolve_query_settings, resolve_query_timeout, update_timeout,
+    };
+    use crate::api::server::protobuf::query_request::Settings;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn resolve_no_settings_returns_default() {
+        let result = resolve_query_settings(None, None, None).unwrap();
+
+        assert_eq!(result, Settings::default());
+    }
+
+    #[test]
+    fn resolve_project_settings_returns_project_settings() {
+        let project_settings = json!({ "disable_clock_reduction": true });
+
+        let result = resolve_query_settings(Some(&project_settings), None, None).unwrap();
+
+        assert!(result.disable_clock_reduction);
+    }
+
+    #[test]
+    fn resolve_query_settings_overrides_project_settings() {
+        let project_settings = json!({ "disable_clock_reduction": true });
+        let query_settings = json!({ "disable_clock_reduction": false });
+
+        let result =
+            resolve_query_settings(Some(&project_settings), Some(&query_settings), None).unwrap();
+
+        assert!(!result.disable_clock_reduction);
+    }
+
+    #[test]
+    fn resolve_request_settings_overrides_stored_settings() {
+        let project_settings = json!({ "disable_clock_reduction": false });
+        let query_settings = json!({ "disable_clock_reduction": false });
+
+        let result = resolve_query_settings(
+            Some(&project_settings),
+            Some(&query_settings),
+            Some(Settings {
+                disable_clock_reduction: true,
+            }),
+        )
+        .unwrap();
+
+        assert!(result.disable_clock_reduction);
+    }
+
+    #[test]
+    fn resolve_missing_fields_are_defaulted() {
+        let query_settings = json!({});
+
+        let result = resolve_query_settings(None, Some(&query_settings), None).unwrap();
+
+        assert_eq!(result, Settings::default());
+    }
+
+    #[test]
+    fn resolve_malformed_settings_returns_err() {
+        let query_settings = json!({ "disable_clock_reduction": "yes" });
+
+        let result = resolve_query_settings(None, Some(&query_settings), None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn resolve_timeout_none_returns_none() {
+        assert_eq!(resolve_query_timeout(None, None), None);
+    }
+
+    #[test]
+    fn resolve_timeout_project_timeout_returns_project_timeout() {
+        assert_eq!(
+            resolve_query_timeout(Some(30), None),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn resolve_timeout_query_timeout_overrides_project_timeout() {
+        assert_eq!(
+            resolve_query_timeout(Some(30), Some(5)),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn update_timeout_none_keeps_stored_timeout() {
+        assert_eq!(update_timeout(Some(30), None), Ok(Some(30)));
+    }
+
+    #[test]
+    fn update_timeout_zero_removes_timeout() {
+        assert_eq!(update_timeout(Some(30), Some(0)), Ok(None));
+    }
+
+    #[test]
+    fn update_timeout_sets_timeout() {
+        assert_eq!(update_timeout(None, Some(60)), Ok(Some(60)));
+    }
+
+    #[test]
+    fn update_timeout_too_long_returns_err() {
+        assert!(update_timeout(None, Some(u32::MAX)).is_err());
+    }
+}
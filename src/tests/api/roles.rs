@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod roles {
+    use crate::api::roles::{
+        parse_permissions, permissions_from_json, permissions_to_json, Permission, Role,
+    };
+    use serde_json::json;
+    use std::str::FromStr;
+
+    /// Every permission alongside whether the Editor, Commenter and Reader roles have it.
+    const MATRIX: [(Permission, [bool; 3]); 7] = [
+        (Permission::ReadProject, [true, true, true]),
+        (Permission::EditProject, [true, false, false]),
+        (Permission::EditComponents, [true, false, false]),
+        (Permission::EditQueries, [true, false, false]),
+        (Permission::RunQuery, [true, true, true]),
+        (Permission::ManageAccess, [true, false, false]),
+        (Permission::Comment, [true, true, false]),
+    ];
+
+    #[test]
+    fn permissions_match_matrix() {
+        for (permission, expected) in MATRIX {
+            for (role, expected) in Role::ALL.into_iter().zip(expected) {
+                assert_eq!(
+                    role.permissions().contains(&permission),
+                    expected,
+                    "{} should {}be able to {}",
+                    role,
+                    if expected { "" } else { "not " },
+                    permission.description()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn permissions_are_covered_by_matrix() {
+        for role in Role::ALL {
+            for permission in role.permissions() {
+                assert!(MATRIX.iter().any(|(p, _)| p == permission));
+            }
+        }
+    }
+
+    #[test]
+    fn from_str_roundtrips() {
+        for role in Role::ALL {
+            assert_eq!(Role::from_str(role.as_str()), Ok(role));
+        }
+    }
+
+    #[test]
+    fn from_str_seeded_names_returns_ok() {
+        let cases = [
+            ("Editor", Role::Editor),
+            ("Commenter", Role::Commenter),
+            ("Reader", Role::Reader),
+        ];
+
+        for (name, role) in cases {
+            assert_eq!(Role::from_str(name), Ok(role));
+        }
+    }
+
+    #[test]
+    fn from_str_unknown_returns_err() {
+        for name in ["", "editor", "Owner", "Viewer"] {
+            assert!(Role::from_str(name).is_err());
+        }
+    }
+
+    #[test]
+    fn permission_from_str_roundtrips() {
+        for permission in Permission::ALL {
+            assert_eq!(Permission::from_str(permission.as_str()), Ok(permission));
+        }
+    }
+
+    #[test]
+    fn parse_permissions_orders_and_deduplicates() {
+        let result = parse_permissions(&["Comment", "ReadProject", "Comment"]).unwrap();
+
+        assert_eq!(result, vec![Permission::ReadProject, Permission::Comment]);
+    }
+
+    #[test]
+    fn parse_permissions_unknown_returns_err() {
+        assert!(parse_permissions(&["ReadProject", "Fly"]).is_err());
+    }
+
+    #[test]
+    fn permissions_json_roundtrips() {
+        let permissions = vec![Permission::ReadProject, Permission::RunQuery];
+
+        let json = permissions_to_json(&permissions);
+
+        assert_eq!(json, json!(["ReadProject", "RunQuery"]));
+        assert_eq!(permissions_from_json(&json).unwrap(), permissions);
+    }
+
+    #[test]
+    fn permissions_from_json_not_a_list_returns_err() {
+        assert!(permissions_from_json(&json!({ "ReadProject": true })).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod simulation_trace {
+    use crate::api::server::protobuf::Decision;
+    use crate::api::simulation_trace::{
+        same_decision_points, SimulationTrace, SimulationTraceStep, SIMULATION_TRACE_VERSION,
+    };
+
+    fn decision(action: &str) -> Decision {
+        Decision {
+            source: None,
+            action: action.to_owned(),
+            edges: vec![],
+            destination: None,
+        }
+    }
+
+    fn trace() -> SimulationTrace {
+        SimulationTrace::new(
+            "(A || B)".to_owned(),
+            vec![
+                SimulationTraceStep {
+                    chosen_decision: None,
+                    decision_points: vec![decision("a")],
+                },
+                SimulationTraceStep {
+                    chosen_decision: Some(decision("a")),
+                    decision_points: vec![decision("b")],
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_exported_trace_returns_trace() {
+        let exported = serde_json::to_string(&trace()).unwrap();
+
+        let result = SimulationTrace::parse(&exported).unwrap();
+
+        assert_eq!(result, trace());
+    }
+
+    #[test]
+    fn export_omits_missing_chosen_decision() {
+        let exported = serde_json::to_value(trace()).unwrap();
+
+        assert!(exported["steps"][0].get("chosen_decision").is_none());
+        assert_eq!(exported["version"], SIMULATION_TRACE_VERSION);
+    }
+
+    #[test]
+    fn parse_invalid_json_returns_err() {
+        assert!(SimulationTrace::parse("{").is_err());
+    }
+
+    #[test]
+    fn parse_unsupported_version_returns_err() {
+        let trace = SimulationTrace {
+            version: SIMULATION_TRACE_VERSION + 1,
+            ..trace()
+        };
+
+        let result = SimulationTrace::parse(&serde_json::to_string(&trace).unwrap());
+
+        assert!(result.unwrap_err().contains("version"));
+    }
+
+    #[test]
+    fn parse_no_steps_returns_err() {
+        let trace = SimulationTrace::new("(A || B)".to_owned(), vec![]);
+
+        let result = SimulationTrace::parse(&serde_json::to_string(&trace).unwrap());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_first_step_with_chosen_decision_returns_err() {
+        let mut trace = trace();
+        trace.steps[0].chosen_decision = Some(decision("a"));
+
+        let result = SimulationTrace::parse(&serde_json::to_string(&trace).unwrap());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_later_step_without_chosen_decision_returns_err() {
+        let mut trace = trace();
+        trace.steps[1].chosen_decision = None;
+
+        let result = SimulationTrace::parse(&serde_json::to_string(&trace).unwrap());
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Step 1 of the trace has no chosen decision"
+        );
+    }
+
+    #[test]
+    fn same_decision_points_ignores_order() {
+        let expected = vec![decision("a"), decision("b")];
+        let actual = vec![decision("b"), decision("a")];
+
+        assert!(same_decision_points(&expected, &actual));
+    }
+
+    #[test]
+    fn same_decision_points_different_points_returns_false() {
+        let expected = vec![decision("a"), decision("b")];
+        let actual = vec![decision("a"), decision("c")];
+
+        assert!(!same_decision_points(&expected, &actual));
+    }
+
+    #[test]
+    fn same_decision_points_missing_point_returns_false() {
+        let expected = vec![decision("a"), decision("b")];
+        let actual = vec![decision("a")];
+
+        assert!(!same_decision_points(&expected, &actual));
+    }
+}
@@ -1,11 +1,12 @@
 use crate::api::server::protobuf::AccessInfo;
-use crate::contexts::context_traits::{AccessContextTrait, EntityContextTrait};
+use crate::contexts::context_traits::{AccessContextTrait, EffectiveAccess, EntityContextTrait};
 use crate::tests::contexts::helpers::{
-    create_accesses, create_projects, create_users, get_reset_database_context,
+    create_accesses, create_projects, create_team_accesses, create_teams, create_users,
+    get_reset_database_context,
 };
 use crate::{
     contexts::context_impls::AccessContext,
-    entities::{access, project, user},
+    entities::{access, project, team, team_access, team_member, user},
     to_active_models,
 };
 use sea_orm::{entity::prelude::*, IntoActiveModel};
@@ -359,7 +360,7 @@ async fn get_by_uid_and_project_id_test() {
         .get_access_by_uid_and_project_id(user.id, project.id)
         .await;
 
-    assert_eq!(access.unwrap().unwrap(), expected_access);
+    assert_eq!(access.unwrap().unwrap(), expected_access.into());
 }
 
 #[tokio::test]
@@ -391,3 +392,144 @@ async fn get_access_by_project_id_test_returns_empty() {
 
     assert!(access.unwrap().is_empty());
 }
+
+#[tokio::test]
+async fn get_access_by_role_test_returns_ok() {
+    let (access_context, expected_access, _, _) = seed_db().await;
+
+    access::Entity::insert(expected_access.clone().into_active_model())
+        .exec(&access_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let access = access_context
+        .get_access_by_role(expected_access.role.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(access, vec![expected_access]);
+}
+
+#[tokio::test]
+async fn get_access_by_role_test_returns_empty() {
+    let (access_context, expected_access, _, _) = seed_db().await;
+
+    access::Entity::insert(expected_access.clone().into_active_model())
+        .exec(&access_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let access = access_context
+        .get_access_by_role("Editor".to_owned())
+        .await
+        .unwrap();
+
+    assert!(access.is_empty());
+}
+
+/// Seeds a second user and a team they are a member of, which is granted the role on the project.
+async fn seed_team_access(
+    access_context: &AccessContext,
+    project: &project::Model,
+    role: &str,
+) -> user::Model {
+    let member = create_users(2)[1].clone();
+    let team = create_teams(1, member.id)[0].clone();
+
+    user::Entity::insert(member.clone().into_active_model())
+        .exec(&access_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team::Entity::insert(team.clone().into_active_model())
+        .exec(&access_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team_member::Entity::insert(
+        team_member::Model {
+            team_id: team.id,
+            user_id: member.id,
+        }
+        .into_active_model(),
+    )
+    .exec(&access_context.db_context.get_connection())
+    .await
+    .unwrap();
+    team_access::Entity::insert(
+        team_access::Model {
+            role: role.to_owned(),
+            ..create_team_accesses(1, team.id, project.id)[0].clone()
+        }
+        .into_active_model(),
+    )
+    .exec(&access_context.db_context.get_connection())
+    .await
+    .unwrap();
+
+    member
+}
+
+#[tokio::test]
+async fn get_by_uid_and_project_id_team_access_test() {
+    let (access_context, _, _, project) = seed_db().await;
+
+    let member = seed_team_access(&access_context, &project, "Commenter").await;
+
+    let access = access_context
+        .get_access_by_uid_and_project_id(member.id, project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        access,
+        EffectiveAccess {
+            direct: None,
+            team_roles: vec!["Commenter".to_owned()],
+        }
+    );
+}
+
+#[tokio::test]
+async fn get_by_uid_and_project_id_direct_and_team_access_test() {
+    let (access_context, _, _, project) = seed_db().await;
+
+    let member = seed_team_access(&access_context, &project, "Editor").await;
+    let direct_access = access::Model {
+        id: 1,
+        role: "Reader".to_owned(),
+        project_id: project.id,
+        user_id: member.id,
+    };
+    access::Entity::insert(direct_access.clone().into_active_model())
+        .exec(&access_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let access = access_context
+        .get_access_by_uid_and_project_id(member.id, project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        access,
+        EffectiveAccess {
+            direct: Some(direct_access),
+            team_roles: vec!["Editor".to_owned()],
+        }
+    );
+}
+
+#[tokio::test]
+async fn get_by_uid_and_project_id_not_team_member_test() {
+    let (access_context, _, user, project) = seed_db().await;
+
+    seed_team_access(&access_context, &project, "Editor").await;
+
+    let access = access_context
+        .get_access_by_uid_and_project_id(user.id, project.id)
+        .await
+        .unwrap();
+
+    assert!(access.is_none());
+}
@@ -0,0 +1,233 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::BackendAuditContext,
+    contexts::context_traits::EntityContextTrait,
+    entities::{backend_audit, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (BackendAuditContext, backend_audit::Model, user::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let backend_audit_context = BackendAuditContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let backend_audit = create_backend_audits(1, user.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (backend_audit_context, backend_audit, user)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    let created_backend_audit = backend_audit_context
+        .create(backend_audit.clone())
+        .await
+        .unwrap();
+
+    let fetched_backend_audit = backend_audit::Entity::find_by_id(created_backend_audit.id)
+        .one(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        backend_audit,
+        backend_audit::Model {
+            created_at: backend_audit.created_at,
+            ..created_backend_audit.clone()
+        }
+    );
+    assert_eq!(fetched_backend_audit, created_backend_audit);
+}
+
+#[tokio::test]
+async fn create_sets_created_at_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    let created_backend_audit = backend_audit_context
+        .create(backend_audit.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(created_backend_audit.created_at, backend_audit.created_at);
+}
+
+#[tokio::test]
+async fn create_without_user_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    let created_backend_audit = backend_audit_context
+        .create(backend_audit::Model {
+            user_id: None,
+            ..backend_audit
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(created_backend_audit.user_id, None);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    backend_audit::Entity::insert(backend_audit.clone().into_active_model())
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_backend_audit = backend_audit_context
+        .get_by_id(backend_audit.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_backend_audit, backend_audit);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (backend_audit_context, _, _) = seed_db().await;
+
+    let fetched_backend_audit = backend_audit_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_backend_audit.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (backend_audit_context, _, user) = seed_db().await;
+
+    let backend_audits = create_backend_audits(3, user.id);
+
+    backend_audit::Entity::insert_many(to_active_models!(backend_audits.clone()))
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(backend_audit_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    backend_audit::Entity::insert(backend_audit.clone().into_active_model())
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_backend_audit = backend_audit::Model {
+        code: 14,
+        ..backend_audit.clone()
+    };
+
+    let updated_backend_audit = backend_audit_context
+        .update(new_backend_audit.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(updated_backend_audit, new_backend_audit);
+}
+
+#[tokio::test]
+async fn update_does_not_modify_method_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    backend_audit::Entity::insert(backend_audit.clone().into_active_model())
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_backend_audit = backend_audit::Model {
+        method: "StartSimulation".to_owned(),
+        ..backend_audit.clone()
+    };
+
+    let updated_backend_audit = backend_audit_context
+        .update(new_backend_audit)
+        .await
+        .unwrap();
+
+    assert_eq!(updated_backend_audit.method, backend_audit.method);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    let updated_backend_audit = backend_audit_context.update(backend_audit.clone()).await;
+
+    assert!(matches!(
+        updated_backend_audit.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (backend_audit_context, backend_audit, _) = seed_db().await;
+
+    backend_audit::Entity::insert(backend_audit.clone().into_active_model())
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_backend_audit = backend_audit_context
+        .delete(backend_audit.id)
+        .await
+        .unwrap();
+
+    let all_backend_audits = backend_audit::Entity::find()
+        .all(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(backend_audit, deleted_backend_audit);
+    assert!(all_backend_audits.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (backend_audit_context, _, _) = seed_db().await;
+
+    let deleted_backend_audit = backend_audit_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_backend_audit.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_user_sets_user_id_null_test() {
+    let (backend_audit_context, backend_audit, user) = seed_db().await;
+
+    backend_audit::Entity::insert(backend_audit.clone().into_active_model())
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    user::Entity::delete_by_id(user.id)
+        .exec(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_backend_audit = backend_audit::Entity::find_by_id(backend_audit.id)
+        .one(&backend_audit_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_backend_audit.user_id, None);
+}
@@ -0,0 +1,318 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::CommentContext,
+    contexts::context_traits::{CommentContextTrait, EntityContextTrait},
+    entities::{comment, component, project, query, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (CommentContext, comment::Model, project::Model, user::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let comment_context = CommentContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let comment = create_comments(1, project.id, user.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (comment_context, comment, project, user)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (comment_context, comment, _, _) = seed_db().await;
+
+    let created_comment = comment_context.create(comment.clone()).await.unwrap();
+
+    let fetched_comment = comment::Entity::find_by_id(created_comment.id)
+        .one(&comment_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        comment,
+        comment::Model {
+            created_at: comment.created_at,
+            ..created_comment.clone()
+        }
+    );
+    assert_eq!(fetched_comment, created_comment);
+}
+
+#[tokio::test]
+async fn create_sets_created_at_test() {
+    let (comment_context, comment, _, _) = seed_db().await;
+
+    let created_comment = comment_context.create(comment.clone()).await.unwrap();
+
+    assert_ne!(created_comment.created_at, comment.created_at);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (comment_context, comment, _, _) = seed_db().await;
+
+    comment::Entity::insert(comment.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_comment = comment_context
+        .get_by_id(comment.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_comment, comment);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (comment_context, _, _, _) = seed_db().await;
+
+    let fetched_comment = comment_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_comment.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (comment_context, _, project, user) = seed_db().await;
+
+    let comments = create_comments(3, project.id, user.id);
+
+    comment::Entity::insert_many(to_active_models!(comments.clone()))
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(comment_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_test() {
+    let (comment_context, _, project, user) = seed_db().await;
+
+    let other_project = project::Model {
+        id: project.id + 1,
+        name: "other project".to_owned(),
+        ..project.clone()
+    };
+    project::Entity::insert(other_project.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let mut comments = create_comments(3, project.id, user.id);
+    comments[1].project_id = other_project.id;
+
+    comment::Entity::insert_many(to_active_models!(comments.clone()))
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_comments = comment_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        fetched_comments,
+        vec![comments[0].clone(), comments[2].clone()]
+    );
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (comment_context, comment, _, _) = seed_db().await;
+
+    comment::Entity::insert(comment.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_comment = comment::Model {
+        text: "edited".to_owned(),
+        resolved: true,
+        edited_at: Some(Default::default()),
+        ..comment.clone()
+    };
+
+    let updated_comment = comment_context.update(new_comment.clone()).await.unwrap();
+
+    let fetched_comment = comment::Entity::find_by_id(comment.id)
+        .one(&comment_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(updated_comment, new_comment);
+    assert_eq!(fetched_comment, updated_comment);
+}
+
+#[tokio::test]
+async fn update_does_not_modify_anchor_test() {
+    let (comment_context, comment, _, user) = seed_db().await;
+
+    comment::Entity::insert(comment.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_comment = comment::Model {
+        user_id: Some(user.id + 1),
+        location_id: Some("L0".to_owned()),
+        ..comment.clone()
+    };
+
+    comment_context.update(new_comment).await.unwrap();
+
+    let fetched_comment = comment::Entity::find_by_id(comment.id)
+        .one(&comment_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_comment, comment);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (comment_context, comment, _, _) = seed_db().await;
+
+    let updated_comment = comment_context.update(comment).await;
+
+    assert!(matches!(
+        updated_comment.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (comment_context, comment, _, _) = seed_db().await;
+
+    comment::Entity::insert(comment.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_comment = comment_context.delete(comment.id).await.unwrap();
+
+    let all_comments = comment::Entity::find()
+        .all(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(comment, deleted_comment);
+    assert!(all_comments.is_empty());
+}
+
+#[tokio::test]
+async fn delete_cascade_replies_test() {
+    let (comment_context, _, project, user) = seed_db().await;
+
+    let mut comments = create_comments(2, project.id, user.id);
+    comments[1].parent_id = Some(comments[0].id);
+
+    comment::Entity::insert_many(to_active_models!(comments.clone()))
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    comment_context.delete(comments[0].id).await.unwrap();
+
+    assert!(comment_context.get_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn delete_cascade_project_test() {
+    let (comment_context, comment, project, _) = seed_db().await;
+
+    comment::Entity::insert(comment.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(comment_context.get_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn delete_anchor_keeps_thread_test() {
+    let (comment_context, comment, project, _) = seed_db().await;
+
+    let component = create_components(1, project.id)[0].clone();
+    let query = create_queries(1, project.id)[0].clone();
+
+    component::Entity::insert(component.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let comments = vec![
+        comment::Model {
+            component_id: Some(component.id),
+            ..comment.clone()
+        },
+        comment::Model {
+            id: comment.id + 1,
+            query_id: Some(query.id),
+            ..comment.clone()
+        },
+    ];
+
+    comment::Entity::insert_many(to_active_models!(comments))
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    component::Entity::delete_by_id(component.id)
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+    query::Entity::delete_by_id(query.id)
+        .exec(&comment_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_comments = comment_context.get_all().await.unwrap();
+
+    assert_eq!(fetched_comments.len(), 2);
+    assert!(fetched_comments
+        .iter()
+        .all(|comment| comment.component_id.is_none() && comment.query_id.is_none()));
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (comment_context, _, _, _) = seed_db().await;
+
+    let deleted_comment = comment_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_comment.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
@@ -0,0 +1,269 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::ComponentContext,
+    contexts::context_traits::{ComponentContextTrait, EntityContextTrait},
+    entities::{component, project, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (ComponentContext, component::Model, project::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let component_context = ComponentContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let component = create_components(1, project.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (component_context, component, project)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (component_context, component, _) = seed_db().await;
+
+    let created_component = component_context.create(component.clone()).await.unwrap();
+
+    let fetched_component = component::Entity::find_by_id(created_component.id)
+        .one(&component_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        component,
+        component::Model {
+            updated_at: component.updated_at,
+            ..created_component.clone()
+        }
+    );
+    assert_eq!(fetched_component, created_component);
+}
+
+#[tokio::test]
+async fn create_same_name_test() {
+    let (component_context, component, _) = seed_db().await;
+
+    component_context.create(component.clone()).await.unwrap();
+    let created_component = component_context.create(component.clone()).await;
+
+    assert!(matches!(
+        created_component.unwrap_err().sql_err(),
+        Some(SqlErr::UniqueConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (component_context, component, _) = seed_db().await;
+
+    component::Entity::insert(component.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_component = component_context
+        .get_by_id(component.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_component, component);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (component_context, _, _) = seed_db().await;
+
+    let fetched_component = component_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_component.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (component_context, _, project) = seed_db().await;
+
+    let components = create_components(3, project.id);
+
+    component::Entity::insert_many(to_active_models!(components.clone()))
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(component_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_test() {
+    let (component_context, _, project) = seed_db().await;
+
+    let other_project = project::Model {
+        id: project.id + 1,
+        name: "other project".to_owned(),
+        ..project.clone()
+    };
+    project::Entity::insert(other_project.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let mut components = create_components(2, project.id);
+    components.push(component::Model {
+        id: 3,
+        project_id: other_project.id,
+        ..components[0].clone()
+    });
+
+    component::Entity::insert_many(to_active_models!(components.clone()))
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_components = component_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched_components, components[..2]);
+}
+
+#[tokio::test]
+async fn get_components_info_by_project_id_test() {
+    let (component_context, _, project) = seed_db().await;
+
+    let components = create_components(2, project.id);
+
+    component::Entity::insert_many(to_active_models!(components.clone()))
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let components_info = component_context
+        .get_components_info_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(components_info.components.len(), 2);
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (component_context, component, _) = seed_db().await;
+
+    component::Entity::insert(component.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_component = component::Model {
+        name: "Machine".to_owned(),
+        ..component.clone()
+    };
+
+    let updated_component = component_context
+        .update(new_component.clone())
+        .await
+        .unwrap();
+
+    let fetched_component = component::Entity::find_by_id(component.id)
+        .one(&component_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(updated_component.name, "Machine");
+    assert_eq!(fetched_component, updated_component);
+}
+
+#[tokio::test]
+async fn update_does_not_modify_project_id_test() {
+    let (component_context, component, project) = seed_db().await;
+
+    component::Entity::insert(component.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_component = component::Model {
+        project_id: project.id + 1,
+        ..component.clone()
+    };
+
+    let updated_component = component_context.update(new_component).await.unwrap();
+
+    assert_eq!(updated_component.project_id, project.id);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (component_context, component, _) = seed_db().await;
+
+    let updated_component = component_context.update(component).await;
+
+    assert!(matches!(
+        updated_component.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (component_context, component, _) = seed_db().await;
+
+    component::Entity::insert(component.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_component = component_context.delete(component.id).await.unwrap();
+
+    let all_components = component::Entity::find()
+        .all(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(component, deleted_component);
+    assert_eq!(all_components.len(), 0);
+}
+
+#[tokio::test]
+async fn delete_cascade_project_test() {
+    let (component_context, component, project) = seed_db().await;
+
+    component::Entity::insert(component.clone().into_active_model())
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&component_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(component_context.get_all().await.unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (component_context, _, _) = seed_db().await;
+
+    let deleted_component = component_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_component.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
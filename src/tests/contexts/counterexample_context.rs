@@ -0,0 +1,242 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::CounterexampleContext,
+    contexts::context_traits::{CounterexampleContextTrait, EntityContextTrait},
+    entities::{counterexample, project, query, query_result, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (CounterexampleContext, counterexample::Model, query::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let counterexample_context = CounterexampleContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let query = create_queries(1, project.id)[0].clone();
+    let query_results = create_query_results(3, query.id, user.id);
+    let counterexample = create_counterexamples(1, query.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+    query_result::Entity::insert_many(to_active_models!(query_results))
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (counterexample_context, counterexample, query)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (counterexample_context, counterexample, _) = seed_db().await;
+
+    let created_counterexample = counterexample_context
+        .create(counterexample.clone())
+        .await
+        .unwrap();
+
+    let fetched_counterexample = counterexample::Entity::find_by_id(created_counterexample.id)
+        .one(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        counterexample,
+        counterexample::Model {
+            created_at: counterexample.created_at,
+            ..created_counterexample.clone()
+        }
+    );
+    assert_eq!(fetched_counterexample, created_counterexample);
+}
+
+#[tokio::test]
+async fn create_same_query_result_test() {
+    let (counterexample_context, counterexample, _) = seed_db().await;
+
+    counterexample_context
+        .create(counterexample.clone())
+        .await
+        .unwrap();
+    let created_counterexample = counterexample_context.create(counterexample.clone()).await;
+
+    assert!(matches!(
+        created_counterexample.unwrap_err().sql_err(),
+        Some(SqlErr::UniqueConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (counterexample_context, counterexample, _) = seed_db().await;
+
+    counterexample::Entity::insert(counterexample.clone().into_active_model())
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_counterexample = counterexample_context
+        .get_by_id(counterexample.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_counterexample, counterexample);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (counterexample_context, _, _) = seed_db().await;
+
+    let fetched_counterexample = counterexample_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_counterexample.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (counterexample_context, _, query) = seed_db().await;
+
+    let counterexamples = create_counterexamples(3, query.id);
+
+    counterexample::Entity::insert_many(to_active_models!(counterexamples.clone()))
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(counterexample_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_latest_by_query_id_test() {
+    let (counterexample_context, _, query) = seed_db().await;
+
+    let counterexamples = create_counterexamples(3, query.id);
+
+    counterexample::Entity::insert_many(to_active_models!(counterexamples.clone()))
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_counterexample = counterexample_context
+        .get_latest_by_query_id(query.id)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched_counterexample, Some(counterexamples[2].clone()));
+}
+
+#[tokio::test]
+async fn get_latest_by_query_id_none_test() {
+    let (counterexample_context, _, query) = seed_db().await;
+
+    let fetched_counterexample = counterexample_context
+        .get_latest_by_query_id(query.id)
+        .await
+        .unwrap();
+
+    assert!(fetched_counterexample.is_none());
+}
+
+#[tokio::test]
+async fn get_by_query_result_id_test() {
+    let (counterexample_context, _, query) = seed_db().await;
+
+    let counterexamples = create_counterexamples(3, query.id);
+
+    counterexample::Entity::insert_many(to_active_models!(counterexamples.clone()))
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_counterexample = counterexample_context
+        .get_by_query_result_id(2)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched_counterexample, Some(counterexamples[1].clone()));
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (counterexample_context, counterexample, _) = seed_db().await;
+
+    counterexample::Entity::insert(counterexample.clone().into_active_model())
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_counterexample = counterexample::Model {
+        reason: Some("CUT_PREDECESSOR".to_owned()),
+        ..counterexample.clone()
+    };
+
+    let updated_counterexample = counterexample_context
+        .update(new_counterexample.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(updated_counterexample, new_counterexample);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (counterexample_context, counterexample, _) = seed_db().await;
+
+    let updated_counterexample = counterexample_context.update(counterexample.clone()).await;
+
+    assert!(matches!(
+        updated_counterexample.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (counterexample_context, counterexample, _) = seed_db().await;
+
+    counterexample::Entity::insert(counterexample.clone().into_active_model())
+        .exec(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_counterexample = counterexample_context
+        .delete(counterexample.id)
+        .await
+        .unwrap();
+
+    let all_counterexamples = counterexample::Entity::find()
+        .all(&counterexample_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(counterexample, deleted_counterexample);
+    assert!(all_counterexamples.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (counterexample_context, _, _) = seed_db().await;
+
+    let deleted_counterexample = counterexample_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_counterexample.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
@@ -2,7 +2,13 @@
 
 use crate::contexts::context_impls::{PostgresDatabaseContext, SQLiteDatabaseContext};
 use crate::contexts::context_traits::DatabaseContextTrait;
-use crate::entities::{access, in_use, project, query, session, user};
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use crate::entities::{
+    access, backend_audit, comment, component, counterexample, in_use, invitation, project,
+    project_version, query, query_cache, query_job, query_result, session, share_link,
+    simulation_session, simulation_step, team, team_access, user,
+};
+use chrono::{Duration, Utc};
 use dotenv::dotenv;
 use sea_orm::{ConnectionTrait, Database, DbBackend};
 use std::env;
@@ -66,8 +72,50 @@ pub fn create_projects(amount: i32, user_id: i32) -> Vec<project::Model> {
     create_entities(amount, |i| project::Model {
         id: i + 1,
         name: format!("name {}", i),
-        components_info: "{}".to_owned().parse().unwrap(),
         owner_id: user_id,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    })
+}
+
+pub fn create_components(amount: i32, project_id: i32) -> Vec<component::Model> {
+    create_entities(amount, |i| component::Model {
+        id: i + 1,
+        project_id,
+        name: format!("Component{}", i),
+        component: serde_json::json!({
+            "rep": { "Json": format!("{{\"name\":\"Component{}\"}}", i) }
+        }),
+        updated_at: Default::default(),
+    })
+}
+
+pub fn create_comments(amount: i32, project_id: i32, user_id: i32) -> Vec<comment::Model> {
+    create_entities(amount, |i| comment::Model {
+        id: i + 1,
+        project_id,
+        parent_id: None,
+        user_id: Some(user_id),
+        component_id: None,
+        location_id: None,
+        edge_id: None,
+        query_id: None,
+        text: format!("comment{}", i),
+        resolved: false,
+        created_at: Default::default(),
+        edited_at: None,
+    })
+}
+
+pub fn create_project_versions(amount: i32, project_id: i32) -> Vec<project_version::Model> {
+    create_entities(amount, |i| project_version::Model {
+        id: i + 1,
+        project_id,
+        components_info: "{}".to_owned().parse().unwrap(),
+        user_id: None,
+        created_at: Default::default(),
     })
 }
 
@@ -80,6 +128,37 @@ pub fn create_accesses(amount: i32, user_id: i32, project_id: i32) -> Vec<access
     })
 }
 
+pub fn create_teams(amount: i32, owner_id: i32) -> Vec<team::Model> {
+    create_entities(amount, |i| team::Model {
+        id: i + 1,
+        name: format!("team {}", i),
+        owner_id,
+    })
+}
+
+pub fn create_team_accesses(amount: i32, team_id: i32, project_id: i32) -> Vec<team_access::Model> {
+    create_entities(amount, |i| team_access::Model {
+        id: i + 1,
+        role: "Reader".into(),
+        project_id: project_id + i,
+        team_id,
+    })
+}
+
+pub fn create_invitations(amount: i32, project_id: i32, user_id: i32) -> Vec<invitation::Model> {
+    create_entities(amount, |i| invitation::Model {
+        id: i + 1,
+        project_id,
+        role: "Reader".into(),
+        user_id: Some(user_id),
+        max_uses: 1,
+        uses: 0,
+        revoked: false,
+        expires_at: (Utc::now() + Duration::days(1)).naive_utc(),
+        created_at: Default::default(),
+    })
+}
+
 pub fn create_sessions(amount: i32, user_id: i32) -> Vec<session::Model> {
     create_entities(amount, |i| session::Model {
         id: i + 1,
@@ -90,11 +169,23 @@ pub fn create_sessions(amount: i32, user_id: i32) -> Vec<session::Model> {
     })
 }
 
+pub fn create_share_links(amount: i32, project_id: i32) -> Vec<share_link::Model> {
+    create_entities(amount, |i| share_link::Model {
+        id: i + 1,
+        project_id,
+        token: format!("test_share_token{}", i),
+        revoked: false,
+        expires_at: None,
+        created_at: Default::default(),
+    })
+}
+
 pub fn create_in_uses(amount: i32, project_id: i32, session_id: i32) -> Vec<in_use::Model> {
     create_entities(amount, |i| in_use::Model {
         project_id: project_id + i,
         session_id,
         latest_activity: Default::default(),
+        expires_at: Default::default(),
     })
 }
 
@@ -105,6 +196,103 @@ pub fn create_queries(amount: i32, project_id: i32) -> Vec<query::Model> {
         result: None,
         outdated: true,
         project_id,
+        settings: Default::default(),
+        timeout_secs: None,
+    })
+}
+
+pub fn create_backend_audits(amount: i32, user_id: i32) -> Vec<backend_audit::Model> {
+    create_entities(amount, |i| backend_audit::Model {
+        id: i + 1,
+        user_id: Some(user_id),
+        method: "SendQuery".to_owned(),
+        code: 0,
+        created_at: Default::default(),
+    })
+}
+
+/// Creates counterexamples for a query, the n'th counterexample belonging to the query result with id n
+pub fn create_counterexamples(amount: i32, query_id: i32) -> Vec<counterexample::Model> {
+    create_entities(amount, |i| counterexample::Model {
+        id: i + 1,
+        query_id,
+        query_result_id: i + 1,
+        kind: "REFINEMENT".to_owned(),
+        system: Some("A <= B".to_owned()),
+        reason: None,
+        actions: "[]".to_owned().parse().unwrap(),
+        state: None,
+        path: "[]".to_owned().parse().unwrap(),
+        created_at: Default::default(),
+    })
+}
+
+pub fn create_query_caches(amount: i32) -> Vec<query_cache::Model> {
+    create_entities(amount, |i| query_cache::Model {
+        id: i + 1,
+        hash: format!("hash{}", i),
+        result: "{}".to_owned().parse().unwrap(),
+        created_at: Utc::now().naive_utc(),
+    })
+}
+
+pub fn create_query_jobs(
+    amount: i32,
+    query_id: i32,
+    project_id: i32,
+    user_id: i32,
+) -> Vec<query_job::Model> {
+    create_entities(amount, |i| query_job::Model {
+        id: i + 1,
+        query_id,
+        project_id,
+        user_id,
+        status: QueryJobStatus::Queued,
+        result: None,
+        error: None,
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    })
+}
+
+pub fn create_query_results(amount: i32, query_id: i32, user_id: i32) -> Vec<query_result::Model> {
+    create_entities(amount, |i| query_result::Model {
+        id: i + 1,
+        query_id,
+        user_id: Some(user_id),
+        result: "{}".to_owned().parse().unwrap(),
+        settings: "{}".to_owned().parse().unwrap(),
+        components_hash: "e3b0c442".to_owned(),
+        created_at: Default::default(),
+    })
+}
+
+pub fn create_simulation_sessions(
+    amount: i32,
+    project_id: i32,
+    user_id: i32,
+) -> Vec<simulation_session::Model> {
+    create_entities(amount, |i| simulation_session::Model {
+        id: i + 1,
+        project_id,
+        user_id,
+        name: format!("simulation {}", i),
+        component_composition: "(A || B)".to_owned(),
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    })
+}
+
+pub fn create_simulation_steps(
+    amount: i32,
+    simulation_session_id: i32,
+) -> Vec<simulation_step::Model> {
+    create_entities(amount, |i| simulation_step::Model {
+        id: i + 1,
+        simulation_session_id,
+        chosen_decision: None,
+        decision_points: "[]".to_owned().parse().unwrap(),
+        created_at: Default::default(),
     })
 }
 
@@ -1,7 +1,7 @@
 use crate::tests::contexts::helpers::*;
 use crate::{
     contexts::context_impls::InUseContext,
-    contexts::context_traits::EntityContextTrait,
+    contexts::context_traits::{EntityContextTrait, InUseContextTrait},
     entities::{in_use, project, session, user},
     to_active_models,
 };
@@ -269,3 +269,210 @@ async fn delete_non_existing_id_test() {
         DbErr::RecordNotFound(_)
     ))
 }
+
+/// Inserts a second session for the user of the seeded session.
+async fn seed_other_session(in_use_context: &InUseContext, user: &user::Model) -> session::Model {
+    let other_session = create_sessions(2, user.id)[1].clone();
+
+    session::Entity::insert(other_session.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    other_session
+}
+
+async fn fetch_in_use(in_use_context: &InUseContext, project_id: i32) -> in_use::Model {
+    in_use::Entity::find_by_id(project_id)
+        .one(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn acquire_released_test() {
+    let (in_use_context, in_use, _, _, user) = seed_db().await;
+
+    in_use::Entity::insert(in_use.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let other_session = seed_other_session(&in_use_context, &user).await;
+    let expires_at = Utc::now().naive_utc().add(Duration::minutes(10));
+
+    let acquired = in_use_context
+        .acquire(in_use.project_id, other_session.id, expires_at)
+        .await
+        .unwrap();
+
+    let fetched_in_use = fetch_in_use(&in_use_context, in_use.project_id).await;
+
+    assert!(acquired);
+    assert_eq!(fetched_in_use.session_id, other_session.id);
+    assert_eq!(fetched_in_use.expires_at, expires_at);
+}
+
+#[tokio::test]
+async fn acquire_held_by_other_session_test() {
+    let (in_use_context, in_use, _, _, user) = seed_db().await;
+
+    let in_use = in_use::Model {
+        expires_at: Utc::now().naive_utc().add(Duration::minutes(10)),
+        ..in_use
+    };
+
+    in_use::Entity::insert(in_use.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let other_session = seed_other_session(&in_use_context, &user).await;
+
+    let acquired = in_use_context
+        .acquire(
+            in_use.project_id,
+            other_session.id,
+            Utc::now().naive_utc().add(Duration::minutes(10)),
+        )
+        .await
+        .unwrap();
+
+    assert!(!acquired);
+    assert_eq!(
+        fetch_in_use(&in_use_context, in_use.project_id).await,
+        in_use
+    );
+}
+
+#[tokio::test]
+async fn acquire_without_row_test() {
+    let (in_use_context, in_use, session, _, _) = seed_db().await;
+
+    let acquired = in_use_context
+        .acquire(
+            in_use.project_id,
+            session.id,
+            Utc::now().naive_utc().add(Duration::minutes(10)),
+        )
+        .await
+        .unwrap();
+
+    assert!(acquired);
+    assert_eq!(
+        fetch_in_use(&in_use_context, in_use.project_id)
+            .await
+            .session_id,
+        session.id
+    );
+}
+
+#[tokio::test]
+async fn renew_test() {
+    let (in_use_context, in_use, session, _, _) = seed_db().await;
+
+    let in_use = in_use::Model {
+        expires_at: Utc::now().naive_utc().add(Duration::minutes(1)),
+        ..in_use
+    };
+
+    in_use::Entity::insert(in_use.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let expires_at = Utc::now().naive_utc().add(Duration::minutes(10));
+
+    let renewed = in_use_context
+        .renew(in_use.project_id, session.id, expires_at)
+        .await
+        .unwrap();
+
+    assert!(renewed);
+    assert_eq!(
+        fetch_in_use(&in_use_context, in_use.project_id)
+            .await
+            .expires_at,
+        expires_at
+    );
+}
+
+#[tokio::test]
+async fn renew_expired_test() {
+    let (in_use_context, in_use, session, _, _) = seed_db().await;
+
+    in_use::Entity::insert(in_use.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let renewed = in_use_context
+        .renew(
+            in_use.project_id,
+            session.id,
+            Utc::now().naive_utc().add(Duration::minutes(10)),
+        )
+        .await
+        .unwrap();
+
+    assert!(!renewed);
+}
+
+#[tokio::test]
+async fn release_test() {
+    let (in_use_context, in_use, session, _, _) = seed_db().await;
+
+    let in_use = in_use::Model {
+        expires_at: Utc::now().naive_utc().add(Duration::minutes(10)),
+        ..in_use
+    };
+
+    in_use::Entity::insert(in_use.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let released = in_use_context
+        .release(in_use.project_id, Some(session.id))
+        .await
+        .unwrap();
+
+    assert!(released);
+    assert!(
+        fetch_in_use(&in_use_context, in_use.project_id)
+            .await
+            .expires_at
+            <= Utc::now().naive_utc()
+    );
+}
+
+#[tokio::test]
+async fn release_by_other_session_test() {
+    let (in_use_context, in_use, _, _, user) = seed_db().await;
+
+    let in_use = in_use::Model {
+        expires_at: Utc::now().naive_utc().add(Duration::minutes(10)),
+        ..in_use
+    };
+
+    in_use::Entity::insert(in_use.clone().into_active_model())
+        .exec(&in_use_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let other_session = seed_other_session(&in_use_context, &user).await;
+
+    let released = in_use_context
+        .release(in_use.project_id, Some(other_session.id))
+        .await
+        .unwrap();
+
+    let forced = in_use_context
+        .release(in_use.project_id, None)
+        .await
+        .unwrap();
+
+    assert!(!released);
+    assert!(forced);
+}
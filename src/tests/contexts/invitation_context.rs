@@ -0,0 +1,362 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::InvitationContext,
+    contexts::context_traits::{EntityContextTrait, InvitationContextTrait},
+    entities::{invitation, project, user},
+    to_active_models,
+};
+use chrono::{Duration, Utc};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (InvitationContext, invitation::Model, project::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let invitation_context = InvitationContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let projects = create_projects(2, user.id);
+    let invitation = create_invitations(1, projects[0].id, user.id)[0].clone();
+
+    user::Entity::insert(user.into_active_model())
+        .exec(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert_many(to_active_models!(projects.clone()))
+        .exec(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (invitation_context, invitation, projects[0].clone())
+}
+
+async fn insert_invitation(invitation_context: &InvitationContext, invitation: &invitation::Model) {
+    invitation::Entity::insert(invitation.clone().into_active_model())
+        .exec(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap();
+}
+
+async fn fetch_invitation(invitation_context: &InvitationContext, id: i32) -> invitation::Model {
+    invitation::Entity::find_by_id(id)
+        .one(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    let created_invitation = invitation_context.create(invitation.clone()).await.unwrap();
+
+    let fetched_invitation = fetch_invitation(&invitation_context, created_invitation.id).await;
+
+    assert_eq!(created_invitation.project_id, invitation.project_id);
+    assert_eq!(created_invitation.max_uses, invitation.max_uses);
+    assert_eq!(fetched_invitation, created_invitation);
+}
+
+#[tokio::test]
+async fn create_resets_uses_and_creation_time_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    let created_invitation = invitation_context
+        .create(invitation::Model {
+            uses: 3,
+            ..invitation
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(created_invitation.uses, 0);
+    assert!(created_invitation.created_at > Utc::now().naive_utc() - Duration::minutes(1));
+}
+
+#[tokio::test]
+async fn create_non_existing_role_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    let created_invitation = invitation_context
+        .create(invitation::Model {
+            role: "Owner".into(),
+            ..invitation
+        })
+        .await;
+
+    assert!(matches!(
+        created_invitation.unwrap_err().sql_err(),
+        Some(SqlErr::ForeignKeyConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(&invitation_context, &invitation).await;
+
+    let fetched_invitation = invitation_context
+        .get_by_id(invitation.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_invitation, invitation);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (invitation_context, _, _) = seed_db().await;
+
+    let fetched_invitation = invitation_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_invitation.is_none());
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_test() {
+    let (invitation_context, invitation, project) = seed_db().await;
+
+    let invitations = vec![
+        invitation.clone(),
+        invitation::Model {
+            id: 2,
+            ..invitation.clone()
+        },
+        invitation::Model {
+            id: 3,
+            project_id: project.id + 1,
+            ..invitation
+        },
+    ];
+    invitation::Entity::insert_many(to_active_models!(invitations.clone()))
+        .exec(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_invitations = invitation_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched_invitations, invitations[..2].to_vec());
+}
+
+#[tokio::test]
+async fn update_revoked_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(&invitation_context, &invitation).await;
+
+    let updated_invitation = invitation_context
+        .update(invitation::Model {
+            revoked: true,
+            ..invitation.clone()
+        })
+        .await
+        .unwrap();
+
+    let fetched_invitation = fetch_invitation(&invitation_context, invitation.id).await;
+
+    assert!(updated_invitation.revoked);
+    assert_eq!(fetched_invitation, updated_invitation);
+}
+
+#[tokio::test]
+async fn update_only_changes_revoked_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(&invitation_context, &invitation).await;
+
+    let updated_invitation = invitation_context
+        .update(invitation::Model {
+            role: "Editor".into(),
+            max_uses: 10,
+            uses: 5,
+            ..invitation.clone()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(updated_invitation, invitation);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    let updated_invitation = invitation_context.update(invitation).await;
+
+    assert!(matches!(
+        updated_invitation.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(&invitation_context, &invitation).await;
+
+    let deleted_invitation = invitation_context.delete(invitation.id).await.unwrap();
+
+    let all_invitations = invitation::Entity::find()
+        .all(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(deleted_invitation, invitation);
+    assert!(all_invitations.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (invitation_context, _, _) = seed_db().await;
+
+    let deleted_invitation = invitation_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_invitation.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_project_cascades_test() {
+    let (invitation_context, invitation, project) = seed_db().await;
+
+    insert_invitation(&invitation_context, &invitation).await;
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&invitation_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_invitation = invitation_context.get_by_id(invitation.id).await.unwrap();
+
+    assert!(fetched_invitation.is_none());
+}
+
+#[tokio::test]
+async fn redeem_counts_use_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(&invitation_context, &invitation).await;
+
+    let redeemed = invitation_context.redeem(invitation.id).await.unwrap();
+
+    let fetched_invitation = fetch_invitation(&invitation_context, invitation.id).await;
+
+    assert!(redeemed);
+    assert_eq!(fetched_invitation.uses, 1);
+}
+
+#[tokio::test]
+async fn redeem_used_up_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(
+        &invitation_context,
+        &invitation::Model {
+            max_uses: 2,
+            ..invitation.clone()
+        },
+    )
+    .await;
+
+    assert!(invitation_context.redeem(invitation.id).await.unwrap());
+    assert!(invitation_context.redeem(invitation.id).await.unwrap());
+    assert!(!invitation_context.redeem(invitation.id).await.unwrap());
+
+    let fetched_invitation = fetch_invitation(&invitation_context, invitation.id).await;
+
+    assert_eq!(fetched_invitation.uses, 2);
+}
+
+#[tokio::test]
+async fn redeem_revoked_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(
+        &invitation_context,
+        &invitation::Model {
+            revoked: true,
+            ..invitation.clone()
+        },
+    )
+    .await;
+
+    let redeemed = invitation_context.redeem(invitation.id).await.unwrap();
+
+    assert!(!redeemed);
+}
+
+#[tokio::test]
+async fn redeem_expired_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    insert_invitation(
+        &invitation_context,
+        &invitation::Model {
+            expires_at: (Utc::now() - Duration::minutes(1)).naive_utc(),
+            ..invitation.clone()
+        },
+    )
+    .await;
+
+    let redeemed = invitation_context.redeem(invitation.id).await.unwrap();
+
+    assert!(!redeemed);
+}
+
+#[tokio::test]
+async fn redeem_non_existing_id_test() {
+    let (invitation_context, _, _) = seed_db().await;
+
+    let redeemed = invitation_context.redeem(1).await.unwrap();
+
+    assert!(!redeemed);
+}
+
+#[tokio::test]
+async fn get_redeemable_by_role_test() {
+    let (invitation_context, invitation, _) = seed_db().await;
+
+    let invitations = vec![
+        invitation.clone(),
+        invitation::Model {
+            id: 2,
+            revoked: true,
+            ..invitation.clone()
+        },
+        invitation::Model {
+            id: 3,
+            expires_at: (Utc::now() - Duration::minutes(1)).naive_utc(),
+            ..invitation.clone()
+        },
+        invitation::Model {
+            id: 4,
+            uses: 1,
+            ..invitation.clone()
+        },
+        invitation::Model {
+            id: 5,
+            role: "Editor".to_owned(),
+            ..invitation.clone()
+        },
+    ];
+    for invitation in &invitations {
+        insert_invitation(&invitation_context, invitation).await;
+    }
+
+    let redeemable_invitations = invitation_context
+        .get_redeemable_by_role(invitation.role.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(redeemable_invitations, vec![invitation]);
+}
@@ -1,8 +1,9 @@
+use crate::api::server::protobuf::ProjectInfo;
 use crate::tests::contexts::helpers::*;
 use crate::{
     contexts::context_impls::ProjectContext,
-    contexts::context_traits::EntityContextTrait,
-    entities::{access, in_use, project, query, session, user},
+    contexts::context_traits::{EntityContextTrait, ProjectContextTrait},
+    entities::{access, in_use, project, query, session, team, team_access, team_member, user},
     to_active_models,
 };
 use sea_orm::error::DbErr;
@@ -174,31 +175,6 @@ async fn update_modifies_name_test() {
     assert_ne!(project, new_project);
 }
 
-#[tokio::test]
-async fn update_modifies_components_info_test() {
-    let (project_context, project, _) = seed_db().await;
-
-    let project = project::Model {
-        components_info: "{\"a\":1}".to_owned().parse().unwrap(),
-        ..project.clone()
-    };
-
-    project::Entity::insert(project.clone().into_active_model())
-        .exec(&project_context.db_context.get_connection())
-        .await
-        .unwrap();
-
-    let new_project = project::Model {
-        components_info: "{\"a\":2}".to_owned().parse().unwrap(),
-        ..project.clone()
-    };
-
-    let updated_project = project_context.update(new_project.clone()).await.unwrap();
-
-    assert_ne!(project, updated_project);
-    assert_ne!(project, new_project);
-}
-
 #[tokio::test]
 async fn update_does_not_modify_id_test() {
     let (project_context, project, _) = seed_db().await;
@@ -237,37 +213,6 @@ async fn update_does_not_modify_owner_id_test() {
     assert_eq!(project, res);
 }
 
-#[tokio::test]
-async fn update_check_query_outdated_test() {
-    let (project_context, project, _) = seed_db().await;
-
-    let mut query = create_queries(1, project.id)[0].clone();
-
-    query.outdated = false;
-
-    project::Entity::insert(project.clone().into_active_model())
-        .exec(&project_context.db_context.get_connection())
-        .await
-        .unwrap();
-
-    query::Entity::insert(query.clone().into_active_model())
-        .exec(&project_context.db_context.get_connection())
-        .await
-        .unwrap();
-
-    let new_project = project::Model { ..project };
-
-    let updated_project = project_context.update(new_project.clone()).await.unwrap();
-
-    let fetched_query = query::Entity::find_by_id(updated_project.id)
-        .one(&project_context.db_context.get_connection())
-        .await
-        .unwrap()
-        .unwrap();
-
-    assert!(fetched_query.outdated);
-}
-
 #[tokio::test]
 async fn update_non_existing_id_test() {
     let (project_context, project, _) = seed_db().await;
@@ -407,3 +352,281 @@ async fn delete_non_existing_id_test() {
         DbErr::RecordNotFound(_)
     ));
 }
+
+#[tokio::test]
+async fn update_does_not_modify_revision_test() {
+    let (project_context, project, _) = seed_db().await;
+
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_project = project::Model {
+        revision: project.revision + 1,
+        collaborative: false,
+        ..project.clone()
+    };
+
+    let updated_project = project_context.update(new_project).await.unwrap();
+
+    assert_eq!(updated_project.revision, project.revision);
+}
+
+#[tokio::test]
+async fn advance_revision_test() {
+    let (project_context, project, _) = seed_db().await;
+
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let advanced = project_context
+        .advance_revision(project.id, Some(project.revision))
+        .await
+        .unwrap();
+
+    let fetched_project = project_context
+        .get_by_id(project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(advanced);
+    assert_eq!(fetched_project.revision, project.revision + 1);
+}
+
+#[tokio::test]
+async fn advance_revision_stale_revision_test() {
+    let (project_context, project, _) = seed_db().await;
+
+    let project = project::Model {
+        revision: 3,
+        collaborative: false,
+        ..project
+    };
+
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let advanced = project_context
+        .advance_revision(project.id, Some(2))
+        .await
+        .unwrap();
+
+    let fetched_project = project_context
+        .get_by_id(project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!advanced);
+    assert_eq!(fetched_project.revision, 3);
+}
+
+#[tokio::test]
+async fn advance_revision_without_expected_revision_test() {
+    let (project_context, project, _) = seed_db().await;
+
+    let project = project::Model {
+        revision: 3,
+        collaborative: false,
+        ..project
+    };
+
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let advanced = project_context
+        .advance_revision(project.id, None)
+        .await
+        .unwrap();
+
+    let fetched_project = project_context
+        .get_by_id(project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(advanced);
+    assert_eq!(fetched_project.revision, 4);
+}
+
+#[tokio::test]
+async fn update_at_revision_test() {
+    let (project_context, project, _) = seed_db().await;
+
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let updated = project_context
+        .update_at_revision(
+            project::Model {
+                name: "new_name".to_owned(),
+                ..project.clone()
+            },
+            project.revision,
+        )
+        .await
+        .unwrap();
+
+    let fetched_project = project_context
+        .get_by_id(project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(updated);
+    assert_eq!(fetched_project.name, "new_name");
+    assert_eq!(fetched_project.revision, project.revision + 1);
+}
+
+#[tokio::test]
+async fn update_at_revision_stale_revision_test() {
+    let (project_context, project, _) = seed_db().await;
+
+    let project = project::Model {
+        revision: 3,
+        collaborative: false,
+        ..project
+    };
+
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let updated = project_context
+        .update_at_revision(
+            project::Model {
+                name: "new_name".to_owned(),
+                ..project.clone()
+            },
+            2,
+        )
+        .await
+        .unwrap();
+
+    let fetched_project = project_context
+        .get_by_id(project.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!updated);
+    assert_eq!(fetched_project, project);
+}
+
+#[tokio::test]
+async fn advance_revision_non_existing_id_test() {
+    let (project_context, _, _) = seed_db().await;
+
+    let advanced = project_context.advance_revision(1, None).await.unwrap();
+
+    assert!(!advanced);
+}
+
+#[tokio::test]
+async fn get_project_info_by_uid_includes_team_projects_test() {
+    let (project_context, _, user) = seed_db().await;
+
+    let projects = create_projects(2, user.id);
+    let team = create_teams(1, user.id)[0].clone();
+    let access = create_accesses(1, user.id, projects[0].id)[0].clone();
+    let team_accesses = vec![
+        team_access::Model {
+            role: "Editor".to_owned(),
+            ..create_team_accesses(2, team.id, projects[0].id)[0].clone()
+        },
+        team_access::Model {
+            role: "Commenter".to_owned(),
+            ..create_team_accesses(2, team.id, projects[0].id)[1].clone()
+        },
+    ];
+
+    project::Entity::insert_many(to_active_models!(projects.clone()))
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+    access::Entity::insert(access.into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team::Entity::insert(team.clone().into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team_member::Entity::insert(
+        team_member::Model {
+            team_id: team.id,
+            user_id: user.id,
+        }
+        .into_active_model(),
+    )
+    .exec(&project_context.db_context.get_connection())
+    .await
+    .unwrap();
+    team_access::Entity::insert_many(to_active_models!(team_accesses))
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let mut project_infos = project_context
+        .get_project_info_by_uid(user.id)
+        .await
+        .unwrap();
+    project_infos.sort_by_key(|info| info.project_id);
+
+    assert_eq!(
+        project_infos,
+        vec![
+            ProjectInfo {
+                project_id: projects[0].id,
+                project_name: projects[0].name.clone(),
+                project_owner_id: user.id,
+                user_role_on_project: "Reader".to_owned(),
+            },
+            ProjectInfo {
+                project_id: projects[1].id,
+                project_name: projects[1].name.clone(),
+                project_owner_id: user.id,
+                user_role_on_project: "Commenter".to_owned(),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn get_project_info_by_uid_excludes_projects_of_other_teams_test() {
+    let (project_context, project, user) = seed_db().await;
+
+    let team = create_teams(1, user.id)[0].clone();
+    let team_access = create_team_accesses(1, team.id, project.id)[0].clone();
+
+    project::Entity::insert(project.into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team::Entity::insert(team.into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team_access::Entity::insert(team_access.into_active_model())
+        .exec(&project_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let project_infos = project_context
+        .get_project_info_by_uid(user.id)
+        .await
+        .unwrap();
+
+    assert!(project_infos.is_empty());
+}
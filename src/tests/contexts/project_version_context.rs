@@ -0,0 +1,280 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::ProjectVersionContext,
+    contexts::context_traits::{EntityContextTrait, ProjectVersionContextTrait},
+    entities::{project, project_version, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (
+    ProjectVersionContext,
+    project_version::Model,
+    project::Model,
+    user::Model,
+) {
+    let db_context = get_reset_database_context().await;
+
+    let project_version_context = ProjectVersionContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let project_version = project_version::Model {
+        user_id: Some(user.id),
+        ..create_project_versions(1, project.id)[0].clone()
+    };
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (project_version_context, project_version, project, user)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    let created_project_version = project_version_context
+        .create(project_version.clone())
+        .await
+        .unwrap();
+
+    let fetched_project_version = project_version::Entity::find_by_id(created_project_version.id)
+        .one(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        project_version,
+        project_version::Model {
+            created_at: project_version.created_at,
+            ..created_project_version.clone()
+        }
+    );
+    assert_eq!(fetched_project_version, created_project_version);
+}
+
+#[tokio::test]
+async fn create_sets_created_at_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    let created_project_version = project_version_context
+        .create(project_version.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(
+        created_project_version.created_at,
+        project_version.created_at
+    );
+}
+
+#[tokio::test]
+async fn create_auto_increment_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    let created_project_version1 = project_version_context
+        .create(project_version.clone())
+        .await
+        .unwrap();
+    let created_project_version2 = project_version_context
+        .create(project_version.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(created_project_version1.id, created_project_version2.id);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    project_version::Entity::insert(project_version.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_project_version = project_version_context
+        .get_by_id(project_version.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(project_version, fetched_project_version);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (project_version_context, _, _, _) = seed_db().await;
+
+    let fetched_project_version = project_version_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_project_version.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (project_version_context, _, project, _) = seed_db().await;
+
+    let project_versions = create_project_versions(3, project.id);
+
+    project_version::Entity::insert_many(to_active_models!(project_versions.clone()))
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(project_version_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_newest_first_test() {
+    let (project_version_context, project_version, project, _) = seed_db().await;
+
+    let first = project_version_context
+        .create(project_version.clone())
+        .await
+        .unwrap();
+    let second = project_version_context
+        .create(project_version.clone())
+        .await
+        .unwrap();
+
+    let project_versions = project_version_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(project_versions, vec![second, first]);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_other_project_test() {
+    let (project_version_context, project_version, project, user) = seed_db().await;
+
+    let other_project = project::Model {
+        id: project.id + 1,
+        name: "other project".to_string(),
+        ..project.clone()
+    };
+
+    project::Entity::insert(other_project.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    project_version_context
+        .create(project_version::Model {
+            project_id: other_project.id,
+            user_id: Some(user.id),
+            ..project_version.clone()
+        })
+        .await
+        .unwrap();
+
+    let project_versions = project_version_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert!(project_versions.is_empty());
+}
+
+#[tokio::test]
+async fn update_modifies_components_info_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    project_version::Entity::insert(project_version.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_project_version = project_version::Model {
+        components_info: "{\"a\":1}".to_owned().parse().unwrap(),
+        ..project_version.clone()
+    };
+
+    let updated_project_version = project_version_context
+        .update(new_project_version.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(new_project_version, updated_project_version);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    let updated_project_version = project_version_context.update(project_version).await;
+
+    assert!(matches!(
+        updated_project_version.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (project_version_context, project_version, _, _) = seed_db().await;
+
+    project_version::Entity::insert(project_version.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_project_version = project_version_context
+        .delete(project_version.id)
+        .await
+        .unwrap();
+
+    let all_project_versions = project_version::Entity::find()
+        .all(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(project_version, deleted_project_version);
+    assert!(all_project_versions.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (project_version_context, _, _, _) = seed_db().await;
+
+    let deleted_project_version = project_version_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_project_version.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_project_cascades_test() {
+    let (project_version_context, project_version, project, _) = seed_db().await;
+
+    project_version::Entity::insert(project_version.clone().into_active_model())
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let all_project_versions = project_version::Entity::find()
+        .all(&project_version_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(all_project_versions.is_empty());
+}
@@ -0,0 +1,261 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::QueryCacheContext,
+    contexts::context_traits::{EntityContextTrait, QueryCacheContextTrait},
+    entities::query_cache,
+    to_active_models,
+};
+use chrono::{Duration, Utc};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (QueryCacheContext, query_cache::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let query_cache_context = QueryCacheContext::new(db_context);
+
+    let query_cache = create_query_caches(1)[0].clone();
+
+    (query_cache_context, query_cache)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    let created_query_cache = query_cache_context
+        .create(query_cache.clone())
+        .await
+        .unwrap();
+
+    let fetched_query_cache = query_cache::Entity::find_by_id(created_query_cache.id)
+        .one(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        query_cache,
+        query_cache::Model {
+            created_at: query_cache.created_at,
+            ..created_query_cache.clone()
+        }
+    );
+    assert_eq!(fetched_query_cache, created_query_cache);
+}
+
+#[tokio::test]
+async fn create_duplicate_hash_returns_err() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    query_cache_context
+        .create(query_cache.clone())
+        .await
+        .unwrap();
+    let duplicate = query_cache_context.create(query_cache.clone()).await;
+
+    assert!(duplicate.is_err());
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    query_cache::Entity::insert(query_cache.clone().into_active_model())
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_query_cache = query_cache_context
+        .get_by_id(query_cache.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_cache, query_cache);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (query_cache_context, _) = seed_db().await;
+
+    let fetched_query_cache = query_cache_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_query_cache.is_none());
+}
+
+#[tokio::test]
+async fn get_by_hash_test() {
+    let (query_cache_context, _) = seed_db().await;
+
+    let query_caches = create_query_caches(3);
+
+    query_cache::Entity::insert_many(to_active_models!(query_caches.clone()))
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_query_cache = query_cache_context
+        .get_by_hash(&query_caches[1].hash)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_cache, query_caches[1]);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_hash_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    query_cache::Entity::insert(query_cache.clone().into_active_model())
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_query_cache = query_cache_context.get_by_hash("other").await.unwrap();
+
+    assert!(fetched_query_cache.is_none());
+}
+
+#[tokio::test]
+async fn get_by_expired_hash_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    let query_cache = query_cache::Model {
+        created_at: (Utc::now() - Duration::days(30)).naive_utc(),
+        ..query_cache
+    };
+
+    query_cache::Entity::insert(query_cache.clone().into_active_model())
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_query_cache = query_cache_context
+        .get_by_hash(&query_cache.hash)
+        .await
+        .unwrap();
+
+    assert!(fetched_query_cache.is_none());
+}
+
+#[tokio::test]
+async fn create_evicts_expired_test() {
+    let (query_cache_context, _) = seed_db().await;
+
+    let mut query_caches = create_query_caches(2);
+    query_caches[0].created_at = (Utc::now() - Duration::days(30)).naive_utc();
+
+    query_cache::Entity::insert(query_caches[0].clone().into_active_model())
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    // An expired entry does not keep its hash from being cached again
+    let recreated_query_cache = query_cache_context
+        .create(query_caches[0].clone())
+        .await
+        .unwrap();
+    query_cache_context
+        .create(query_caches[1].clone())
+        .await
+        .unwrap();
+
+    let all_query_caches = query_cache::Entity::find()
+        .all(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(all_query_caches.len(), 2);
+    assert!(all_query_caches.contains(&recreated_query_cache));
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (query_cache_context, _) = seed_db().await;
+
+    let query_caches = create_query_caches(3);
+
+    query_cache::Entity::insert_many(to_active_models!(query_caches.clone()))
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(query_cache_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn update_does_not_modify_hash_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    query_cache::Entity::insert(query_cache.clone().into_active_model())
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_query_cache = query_cache::Model {
+        hash: "other".to_owned(),
+        result: "[]".to_owned().parse().unwrap(),
+        ..query_cache.clone()
+    };
+
+    query_cache_context.update(new_query_cache).await.unwrap();
+
+    let fetched_query_cache = query_cache::Entity::find_by_id(query_cache.id)
+        .one(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_cache.hash, query_cache.hash);
+    assert_eq!(
+        fetched_query_cache.result,
+        "[]".to_owned().parse::<Json>().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    let updated_query_cache = query_cache_context.update(query_cache.clone()).await;
+
+    assert!(matches!(
+        updated_query_cache.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (query_cache_context, query_cache) = seed_db().await;
+
+    query_cache::Entity::insert(query_cache.clone().into_active_model())
+        .exec(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_query_cache = query_cache_context.delete(query_cache.id).await.unwrap();
+
+    let all_query_caches = query_cache::Entity::find()
+        .all(&query_cache_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(query_cache, deleted_query_cache);
+    assert!(all_query_caches.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (query_cache_context, _) = seed_db().await;
+
+    let deleted_query_cache = query_cache_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_query_cache.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
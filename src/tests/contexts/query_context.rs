@@ -3,11 +3,11 @@ use crate::tests::contexts::helpers::{
 };
 use crate::{
     contexts::context_impls::QueryContext,
-    contexts::context_traits::EntityContextTrait,
+    contexts::context_traits::{EntityContextTrait, QueryContextTrait},
     entities::{project, query, user},
     to_active_models,
 };
-use sea_orm::{entity::prelude::*, IntoActiveModel};
+use sea_orm::{entity::prelude::*, IntoActiveModel, QueryOrder};
 
 async fn seed_db() -> (QueryContext, query::Model, project::Model) {
     let db_context = get_reset_database_context().await;
@@ -282,6 +282,208 @@ async fn update_non_existing_id_test() {
     ));
 }
 
+#[tokio::test]
+async fn update_result_test() {
+    let (query_context, query, _) = seed_db().await;
+
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&query_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let result: Json = "{}".to_owned().parse().unwrap();
+
+    let updated = query_context
+        .update_result(query.id, result.clone(), false)
+        .await
+        .unwrap();
+
+    let fetched_query = query::Entity::find_by_id(query.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(updated);
+    assert_eq!(
+        fetched_query,
+        query::Model {
+            result: Some(result),
+            outdated: false,
+            ..query
+        }
+    );
+}
+
+#[tokio::test]
+async fn update_result_keeps_concurrent_edits_test() {
+    let (query_context, query, _) = seed_db().await;
+
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&query_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    // The query is edited while its run is in flight
+    let edited_query = query_context
+        .update(query::Model {
+            string: "edited".to_string(),
+            ..query.clone()
+        })
+        .await
+        .unwrap();
+
+    query_context
+        .update_result(query.id, "{}".to_owned().parse().unwrap(), true)
+        .await
+        .unwrap();
+
+    let fetched_query = query::Entity::find_by_id(query.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query.string, edited_query.string);
+    assert!(fetched_query.outdated);
+}
+
+#[tokio::test]
+async fn update_result_non_existing_id_test() {
+    let (query_context, query, _) = seed_db().await;
+
+    let updated = query_context
+        .update_result(query.id, "{}".to_owned().parse().unwrap(), false)
+        .await
+        .unwrap();
+
+    assert!(!updated);
+}
+
+#[tokio::test]
+async fn mark_outdated_test() {
+    let (query_context, _, project) = seed_db().await;
+
+    let queries: Vec<query::Model> = create_queries(3, project.id)
+        .into_iter()
+        .map(|query| query::Model {
+            outdated: false,
+            ..query
+        })
+        .collect();
+
+    query::Entity::insert_many(to_active_models!(queries.clone()))
+        .exec(&query_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    query_context
+        .mark_outdated(vec![queries[0].id, queries[2].id])
+        .await
+        .unwrap();
+
+    let outdated: Vec<bool> = query::Entity::find()
+        .order_by_asc(query::Column::Id)
+        .all(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|query| query.outdated)
+        .collect();
+
+    assert_eq!(outdated, vec![true, false, true]);
+}
+
+#[tokio::test]
+async fn update_at_revision_test() {
+    let (query_context, query, project) = seed_db().await;
+
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&query_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_query = query::Model {
+        string: "updated".to_string(),
+        ..query.clone()
+    };
+
+    let updated = query_context
+        .update_at_revision(new_query.clone(), project.revision)
+        .await
+        .unwrap();
+
+    let fetched_query = query::Entity::find_by_id(query.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+    let fetched_project = project::Entity::find_by_id(project.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(updated);
+    assert_eq!(fetched_query, new_query);
+    assert_eq!(fetched_project.revision, project.revision + 1);
+}
+
+#[tokio::test]
+async fn update_at_revision_stale_revision_test() {
+    let (query_context, query, project) = seed_db().await;
+
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&query_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let updated = query_context
+        .update_at_revision(
+            query::Model {
+                string: "updated".to_string(),
+                ..query.clone()
+            },
+            project.revision + 1,
+        )
+        .await
+        .unwrap();
+
+    let fetched_query = query::Entity::find_by_id(query.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+    let fetched_project = project::Entity::find_by_id(project.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!updated);
+    assert_eq!(fetched_query, query);
+    assert_eq!(fetched_project.revision, project.revision);
+}
+
+#[tokio::test]
+async fn update_at_revision_failed_update_keeps_revision_test() {
+    let (query_context, query, project) = seed_db().await;
+
+    // The query does not exist, so the update fails after the revision has been moved
+    let res = query_context
+        .update_at_revision(query, project.revision)
+        .await;
+
+    let fetched_project = project::Entity::find_by_id(project.id)
+        .one(&query_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(res.is_err());
+    assert_eq!(fetched_project.revision, project.revision);
+}
+
 #[tokio::test]
 async fn delete_test() {
     let (query_context, query, _) = seed_db().await;
@@ -0,0 +1,372 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::QueryJobContext,
+    contexts::context_traits::{EntityContextTrait, QueryJobContextTrait},
+    entities::sea_orm_active_enums::QueryJobStatus,
+    entities::{project, query, query_job, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (
+    QueryJobContext,
+    query_job::Model,
+    query::Model,
+    project::Model,
+) {
+    let db_context = get_reset_database_context().await;
+
+    let query_job_context = QueryJobContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let query = create_queries(1, project.id)[0].clone();
+    let query_job = create_query_jobs(1, query.id, project.id, user.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (query_job_context, query_job, query, project)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    let created_query_job = query_job_context.create(query_job.clone()).await.unwrap();
+
+    let fetched_query_job = query_job::Entity::find_by_id(created_query_job.id)
+        .one(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        query_job,
+        query_job::Model {
+            created_at: query_job.created_at,
+            updated_at: query_job.updated_at,
+            ..created_query_job.clone()
+        }
+    );
+    assert_eq!(fetched_query_job, created_query_job);
+}
+
+#[tokio::test]
+async fn create_sets_timestamps_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    let created_query_job = query_job_context.create(query_job.clone()).await.unwrap();
+
+    assert_ne!(created_query_job.created_at, query_job.created_at);
+    assert_eq!(created_query_job.created_at, created_query_job.updated_at);
+}
+
+#[tokio::test]
+async fn create_auto_increment_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    let created_query_job1 = query_job_context.create(query_job.clone()).await.unwrap();
+    let created_query_job2 = query_job_context.create(query_job.clone()).await.unwrap();
+
+    assert_ne!(created_query_job1.id, created_query_job2.id);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_query_job = query_job_context
+        .get_by_id(query_job.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_job, query_job);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (query_job_context, _, _, _) = seed_db().await;
+
+    let fetched_query_job = query_job_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_query_job.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (query_job_context, _, query, project) = seed_db().await;
+
+    let query_jobs = create_query_jobs(3, query.id, project.id, project.owner_id);
+
+    query_job::Entity::insert_many(to_active_models!(query_jobs.clone()))
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(query_job_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_newest_first_test() {
+    let (query_job_context, _, query, project) = seed_db().await;
+
+    let query_jobs = create_query_jobs(3, query.id, project.id, project.owner_id);
+
+    query_job::Entity::insert_many(to_active_models!(query_jobs.clone()))
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let ids: Vec<i32> = query_job_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|query_job| query_job.id)
+        .collect();
+
+    assert_eq!(ids, vec![3, 2, 1]);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_other_project_test() {
+    let (query_job_context, query_job, _, project) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let query_jobs = query_job_context
+        .get_all_by_project_id(project.id + 1)
+        .await
+        .unwrap();
+
+    assert!(query_jobs.is_empty());
+}
+
+#[tokio::test]
+async fn get_all_by_status_test() {
+    let (query_job_context, _, query, project) = seed_db().await;
+
+    let mut query_jobs = create_query_jobs(3, query.id, project.id, project.owner_id);
+    query_jobs[1].status = QueryJobStatus::Running;
+
+    query_job::Entity::insert_many(to_active_models!(query_jobs.clone()))
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let ids: Vec<i32> = query_job_context
+        .get_all_by_status(QueryJobStatus::Queued)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|query_job| query_job.id)
+        .collect();
+
+    assert_eq!(ids, vec![1, 3]);
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_query_job = query_job::Model {
+        status: QueryJobStatus::Done,
+        result: Some("{}".to_owned().parse().unwrap()),
+        ..query_job.clone()
+    };
+
+    let updated_query_job = query_job_context
+        .update(new_query_job.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(updated_query_job.status, QueryJobStatus::Done);
+    assert_eq!(updated_query_job.result, new_query_job.result);
+    assert_ne!(updated_query_job.updated_at, query_job.updated_at);
+}
+
+#[tokio::test]
+async fn update_does_not_modify_query_id_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_query_job = query_job::Model {
+        query_id: query_job.query_id + 1,
+        ..query_job.clone()
+    };
+
+    let updated_query_job = query_job_context.update(new_query_job).await.unwrap();
+
+    let fetched_query_job = query_job::Entity::find_by_id(query_job.id)
+        .one(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_job.query_id, query_job.query_id);
+    assert_eq!(fetched_query_job.updated_at, updated_query_job.updated_at);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    let updated_query_job = query_job_context.update(query_job.clone()).await;
+
+    assert!(matches!(
+        updated_query_job.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn update_if_status_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let updated = query_job_context
+        .update_if_status(
+            query_job::Model {
+                status: QueryJobStatus::Cancelled,
+                error: Some("cancelled".to_string()),
+                ..query_job.clone()
+            },
+            query_job.status.clone(),
+        )
+        .await
+        .unwrap();
+
+    let fetched_query_job = query_job::Entity::find_by_id(query_job.id)
+        .one(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(updated);
+    assert_eq!(fetched_query_job.status, QueryJobStatus::Cancelled);
+    assert_eq!(fetched_query_job.error, Some("cancelled".to_string()));
+}
+
+#[tokio::test]
+async fn update_if_status_other_status_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    let query_job = query_job::Model {
+        status: QueryJobStatus::Done,
+        ..query_job
+    };
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let updated = query_job_context
+        .update_if_status(
+            query_job::Model {
+                status: QueryJobStatus::Cancelled,
+                ..query_job.clone()
+            },
+            QueryJobStatus::Running,
+        )
+        .await
+        .unwrap();
+
+    let fetched_query_job = query_job::Entity::find_by_id(query_job.id)
+        .one(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!updated);
+    assert_eq!(fetched_query_job, query_job);
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (query_job_context, query_job, _, _) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_query_job = query_job_context.delete(query_job.id).await.unwrap();
+
+    let all_query_jobs = query_job::Entity::find()
+        .all(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(query_job, deleted_query_job);
+    assert!(all_query_jobs.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (query_job_context, _, _, _) = seed_db().await;
+
+    let deleted_query_job = query_job_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_query_job.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_query_cascade_test() {
+    let (query_job_context, query_job, query, _) = seed_db().await;
+
+    query_job::Entity::insert(query_job.clone().into_active_model())
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    query::Entity::delete_by_id(query.id)
+        .exec(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let all_query_jobs = query_job::Entity::find()
+        .all(&query_job_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(all_query_jobs.is_empty());
+}
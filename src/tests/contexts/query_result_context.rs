@@ -0,0 +1,314 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::QueryResultContext,
+    contexts::context_traits::{EntityContextTrait, QueryResultContextTrait},
+    entities::{project, query, query_result, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (
+    QueryResultContext,
+    query_result::Model,
+    query::Model,
+    user::Model,
+) {
+    let db_context = get_reset_database_context().await;
+
+    let query_result_context = QueryResultContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let query = create_queries(1, project.id)[0].clone();
+    let query_result = create_query_results(1, query.id, user.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+    query::Entity::insert(query.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (query_result_context, query_result, query, user)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    let created_query_result = query_result_context
+        .create(query_result.clone())
+        .await
+        .unwrap();
+
+    let fetched_query_result = query_result::Entity::find_by_id(created_query_result.id)
+        .one(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        query_result,
+        query_result::Model {
+            created_at: query_result.created_at,
+            ..created_query_result.clone()
+        }
+    );
+    assert_eq!(fetched_query_result, created_query_result);
+}
+
+#[tokio::test]
+async fn create_sets_created_at_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    let created_query_result = query_result_context
+        .create(query_result.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(created_query_result.created_at, query_result.created_at);
+}
+
+#[tokio::test]
+async fn create_auto_increment_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    let created_query_result1 = query_result_context
+        .create(query_result.clone())
+        .await
+        .unwrap();
+    let created_query_result2 = query_result_context
+        .create(query_result.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(created_query_result1.id, created_query_result2.id);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    query_result::Entity::insert(query_result.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_query_result = query_result_context
+        .get_by_id(query_result.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_result, query_result);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (query_result_context, _, _, _) = seed_db().await;
+
+    let fetched_query_result = query_result_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_query_result.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (query_result_context, _, query, user) = seed_db().await;
+
+    let query_results = create_query_results(3, query.id, user.id);
+
+    query_result::Entity::insert_many(to_active_models!(query_results.clone()))
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(query_result_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_page_by_query_id_newest_first_test() {
+    let (query_result_context, _, query, user) = seed_db().await;
+
+    let query_results = create_query_results(5, query.id, user.id);
+
+    query_result::Entity::insert_many(to_active_models!(query_results.clone()))
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let (page, total) = query_result_context
+        .get_page_by_query_id(query.id, 0, 2)
+        .await
+        .unwrap();
+    let ids: Vec<i32> = page
+        .into_iter()
+        .map(|query_result| query_result.id)
+        .collect();
+
+    assert_eq!(ids, vec![5, 4]);
+    assert_eq!(total, 5);
+}
+
+#[tokio::test]
+async fn get_page_by_query_id_last_page_test() {
+    let (query_result_context, _, query, user) = seed_db().await;
+
+    let query_results = create_query_results(5, query.id, user.id);
+
+    query_result::Entity::insert_many(to_active_models!(query_results.clone()))
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let (page, total) = query_result_context
+        .get_page_by_query_id(query.id, 2, 2)
+        .await
+        .unwrap();
+    let ids: Vec<i32> = page
+        .into_iter()
+        .map(|query_result| query_result.id)
+        .collect();
+
+    assert_eq!(ids, vec![1]);
+    assert_eq!(total, 5);
+}
+
+#[tokio::test]
+async fn get_page_by_query_id_other_query_test() {
+    let (query_result_context, query_result, query, _) = seed_db().await;
+
+    query_result::Entity::insert(query_result.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let (page, total) = query_result_context
+        .get_page_by_query_id(query.id + 1, 0, 10)
+        .await
+        .unwrap();
+
+    assert!(page.is_empty());
+    assert_eq!(total, 0);
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    query_result::Entity::insert(query_result.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_query_result = query_result::Model {
+        components_hash: "updated".to_owned(),
+        ..query_result.clone()
+    };
+
+    let updated_query_result = query_result_context
+        .update(new_query_result.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(updated_query_result, new_query_result);
+}
+
+#[tokio::test]
+async fn update_does_not_modify_query_id_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    query_result::Entity::insert(query_result.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_query_result = query_result::Model {
+        query_id: query_result.query_id + 1,
+        ..query_result.clone()
+    };
+
+    query_result_context.update(new_query_result).await.unwrap();
+
+    let fetched_query_result = query_result::Entity::find_by_id(query_result.id)
+        .one(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_query_result.query_id, query_result.query_id);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    let updated_query_result = query_result_context.update(query_result.clone()).await;
+
+    assert!(matches!(
+        updated_query_result.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (query_result_context, query_result, _, _) = seed_db().await;
+
+    query_result::Entity::insert(query_result.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_query_result = query_result_context.delete(query_result.id).await.unwrap();
+
+    let all_query_results = query_result::Entity::find()
+        .all(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(query_result, deleted_query_result);
+    assert!(all_query_results.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (query_result_context, _, _, _) = seed_db().await;
+
+    let deleted_query_result = query_result_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_query_result.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_query_cascade_test() {
+    let (query_result_context, query_result, query, _) = seed_db().await;
+
+    query_result::Entity::insert(query_result.clone().into_active_model())
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    query::Entity::delete_by_id(query.id)
+        .exec(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let all_query_results = query_result::Entity::find()
+        .all(&query_result_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(all_query_results.is_empty());
+}
@@ -0,0 +1,224 @@
+use crate::api::roles::{permissions_from_json, Role};
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::RoleContext,
+    contexts::context_traits::RoleContextTrait,
+    entities::{access, project, role, user},
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use serde_json::json;
+use std::matches;
+
+async fn seed_db() -> (RoleContext, role::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let role_context = RoleContext::new(db_context);
+
+    let role = role::Model {
+        name: "Verifier".to_owned(),
+        permissions: json!(["ReadProject", "RunQuery"]),
+    };
+
+    (role_context, role)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (role_context, role) = seed_db().await;
+
+    let created_role = role_context.create(role.clone()).await.unwrap();
+
+    let fetched_role = role::Entity::find_by_id(created_role.name.clone())
+        .one(&role_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(role, created_role);
+    assert_eq!(fetched_role, created_role);
+}
+
+#[tokio::test]
+async fn create_existing_name_returns_err() {
+    let (role_context, _) = seed_db().await;
+
+    let res = role_context
+        .create(role::Model {
+            name: "Editor".to_owned(),
+            permissions: json!([]),
+        })
+        .await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn seeded_roles_have_their_permissions() {
+    let (role_context, _) = seed_db().await;
+
+    for role in Role::ALL {
+        let seeded_role = role_context
+            .get_by_name(role.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            permissions_from_json(&seeded_role.permissions).unwrap(),
+            role.permissions()
+        );
+    }
+}
+
+#[tokio::test]
+async fn get_by_name_test() {
+    let (role_context, role) = seed_db().await;
+
+    role::Entity::insert(role.clone().into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_role = role_context
+        .get_by_name(role.name.clone())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_role, role);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_name_test() {
+    let (role_context, role) = seed_db().await;
+
+    let fetched_role = role_context.get_by_name(role.name).await.unwrap();
+
+    assert!(fetched_role.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (role_context, role) = seed_db().await;
+
+    role::Entity::insert(role.clone().into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let names: Vec<String> = role_context
+        .get_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|role| role.name)
+        .collect();
+
+    assert_eq!(names, vec!["Commenter", "Editor", "Reader", "Verifier"]);
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (role_context, role) = seed_db().await;
+
+    role::Entity::insert(role.clone().into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_role = role::Model {
+        permissions: json!(["ReadProject", "RunQuery", "Comment"]),
+        ..role
+    };
+
+    let updated_role = role_context.update(new_role.clone()).await.unwrap();
+
+    let fetched_role = role::Entity::find_by_id(new_role.name.clone())
+        .one(&role_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(new_role, updated_role);
+    assert_eq!(updated_role, fetched_role);
+}
+
+#[tokio::test]
+async fn update_non_existing_name_test() {
+    let (role_context, role) = seed_db().await;
+
+    let updated_role = role_context.update(role).await;
+
+    assert!(matches!(updated_role.unwrap_err(), DbErr::RecordNotUpdated));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (role_context, role) = seed_db().await;
+
+    role::Entity::insert(role.clone().into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_role = role_context.delete(role.name.clone()).await.unwrap();
+
+    let all_roles = role::Entity::find()
+        .all(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(role, deleted_role);
+    assert!(!all_roles.contains(&role));
+}
+
+#[tokio::test]
+async fn delete_cascade_access_test() {
+    let (role_context, role) = seed_db().await;
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let access = access::Model {
+        role: role.name.clone(),
+        ..create_accesses(1, user.id, project.id)[0].clone()
+    };
+
+    user::Entity::insert(user.into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+    role::Entity::insert(role.clone().into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+    access::Entity::insert(access.into_active_model())
+        .exec(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    role_context.delete(role.name).await.unwrap();
+
+    let all_accesses = access::Entity::find()
+        .all(&role_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(all_accesses.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_name_test() {
+    let (role_context, role) = seed_db().await;
+
+    let deleted_role = role_context.delete(role.name).await;
+
+    assert!(matches!(
+        deleted_role.unwrap_err(),
+        sea_orm::error::DbErr::RecordNotFound(_)
+    ));
+}
@@ -0,0 +1,270 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::ShareLinkContext,
+    contexts::context_traits::{EntityContextTrait, ShareLinkContextTrait},
+    entities::{project, share_link, user},
+    to_active_models,
+};
+use chrono::{Duration, Utc};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (ShareLinkContext, share_link::Model, project::Model) {
+    let db_context = get_reset_database_context().await;
+
+    let share_link_context = ShareLinkContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let projects = create_projects(2, user.id);
+    let share_link = create_share_links(1, projects[0].id)[0].clone();
+
+    user::Entity::insert(user.into_active_model())
+        .exec(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert_many(to_active_models!(projects.clone()))
+        .exec(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (share_link_context, share_link, projects[0].clone())
+}
+
+async fn insert_share_link(share_link_context: &ShareLinkContext, share_link: &share_link::Model) {
+    share_link::Entity::insert(share_link.clone().into_active_model())
+        .exec(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    let created_share_link = share_link_context.create(share_link.clone()).await.unwrap();
+
+    let fetched_share_link = share_link::Entity::find_by_id(created_share_link.id)
+        .one(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(created_share_link.token, share_link.token);
+    assert!(created_share_link.created_at > Utc::now().naive_utc() - Duration::minutes(1));
+    assert_eq!(fetched_share_link, created_share_link);
+}
+
+#[tokio::test]
+async fn create_with_expiry_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    let expires_at = Some(Utc::now().naive_utc() + Duration::days(1));
+
+    let created_share_link = share_link_context
+        .create(share_link::Model {
+            expires_at,
+            ..share_link
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(created_share_link.expires_at, expires_at);
+}
+
+#[tokio::test]
+async fn create_duplicate_token_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let created_share_link = share_link_context.create(share_link).await;
+
+    assert!(matches!(
+        created_share_link.unwrap_err().sql_err(),
+        Some(SqlErr::UniqueConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let fetched_share_link = share_link_context
+        .get_by_id(share_link.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_share_link, share_link);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (share_link_context, _, _) = seed_db().await;
+
+    let fetched_share_link = share_link_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_share_link.is_none());
+}
+
+#[tokio::test]
+async fn get_by_token_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let fetched_share_link = share_link_context
+        .get_by_token(share_link.token.clone())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_share_link, share_link);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_token_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let fetched_share_link = share_link_context
+        .get_by_token("unknown_token".to_owned())
+        .await
+        .unwrap();
+
+    assert!(fetched_share_link.is_none());
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_test() {
+    let (share_link_context, share_link, project) = seed_db().await;
+
+    let share_links = vec![
+        share_link.clone(),
+        share_link::Model {
+            id: 2,
+            token: "second_token".to_owned(),
+            ..share_link.clone()
+        },
+        share_link::Model {
+            id: 3,
+            project_id: project.id + 1,
+            token: "third_token".to_owned(),
+            ..share_link
+        },
+    ];
+    share_link::Entity::insert_many(to_active_models!(share_links.clone()))
+        .exec(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_share_links = share_link_context
+        .get_all_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched_share_links, share_links[..2].to_vec());
+}
+
+#[tokio::test]
+async fn update_revoked_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let updated_share_link = share_link_context
+        .update(share_link::Model {
+            revoked: true,
+            ..share_link.clone()
+        })
+        .await
+        .unwrap();
+
+    let fetched_share_link = share_link_context
+        .get_by_id(share_link.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(updated_share_link.revoked);
+    assert_eq!(fetched_share_link, updated_share_link);
+}
+
+#[tokio::test]
+async fn update_only_changes_revoked_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let updated_share_link = share_link_context
+        .update(share_link::Model {
+            token: "new_token".to_owned(),
+            expires_at: Some(Utc::now().naive_utc()),
+            ..share_link.clone()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(updated_share_link, share_link);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    let updated_share_link = share_link_context.update(share_link).await;
+
+    assert!(matches!(
+        updated_share_link.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (share_link_context, share_link, _) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    let deleted_share_link = share_link_context.delete(share_link.id).await.unwrap();
+
+    let all_share_links = share_link::Entity::find()
+        .all(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(deleted_share_link, share_link);
+    assert!(all_share_links.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (share_link_context, _, _) = seed_db().await;
+
+    let deleted_share_link = share_link_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_share_link.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_project_cascades_test() {
+    let (share_link_context, share_link, project) = seed_db().await;
+
+    insert_share_link(&share_link_context, &share_link).await;
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&share_link_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_share_link = share_link_context.get_by_id(share_link.id).await.unwrap();
+
+    assert!(fetched_share_link.is_none());
+}
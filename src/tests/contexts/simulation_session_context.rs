@@ -0,0 +1,354 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::SimulationSessionContext,
+    contexts::context_traits::{EntityContextTrait, SimulationSessionContextTrait},
+    entities::{project, simulation_session, simulation_step, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (
+    SimulationSessionContext,
+    simulation_session::Model,
+    project::Model,
+) {
+    let db_context = get_reset_database_context().await;
+
+    let simulation_session_context = SimulationSessionContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let simulation_session = create_simulation_sessions(1, project.id, user.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (simulation_session_context, simulation_session, project)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    let created_simulation_session = simulation_session_context
+        .create(simulation_session.clone())
+        .await
+        .unwrap();
+
+    let fetched_simulation_session =
+        simulation_session::Entity::find_by_id(created_simulation_session.id)
+            .one(&simulation_session_context.db_context.get_connection())
+            .await
+            .unwrap()
+            .unwrap();
+
+    assert_eq!(
+        simulation_session,
+        simulation_session::Model {
+            created_at: simulation_session.created_at,
+            updated_at: simulation_session.updated_at,
+            ..created_simulation_session.clone()
+        }
+    );
+    assert_eq!(fetched_simulation_session, created_simulation_session);
+}
+
+#[tokio::test]
+async fn create_sets_timestamps_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    let created_simulation_session = simulation_session_context
+        .create(simulation_session.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(
+        created_simulation_session.created_at,
+        simulation_session.created_at
+    );
+    assert_eq!(
+        created_simulation_session.created_at,
+        created_simulation_session.updated_at
+    );
+}
+
+#[tokio::test]
+async fn create_auto_increment_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    let created_simulation_session1 = simulation_session_context
+        .create(simulation_session.clone())
+        .await
+        .unwrap();
+    let created_simulation_session2 = simulation_session_context
+        .create(simulation_session.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(
+        created_simulation_session1.id,
+        created_simulation_session2.id
+    );
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_simulation_session = simulation_session_context
+        .get_by_id(simulation_session.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_simulation_session, simulation_session);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (simulation_session_context, _, _) = seed_db().await;
+
+    let fetched_simulation_session = simulation_session_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_simulation_session.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (simulation_session_context, _, project) = seed_db().await;
+
+    let simulation_sessions = create_simulation_sessions(3, project.id, project.owner_id);
+
+    simulation_session::Entity::insert_many(to_active_models!(simulation_sessions.clone()))
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(simulation_session_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_and_user_id_test() {
+    let (simulation_session_context, _, project) = seed_db().await;
+
+    let other_user = create_users(2)[1].clone();
+    user::Entity::insert(other_user.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let mut simulation_sessions = create_simulation_sessions(3, project.id, project.owner_id);
+    simulation_sessions[1].user_id = other_user.id;
+
+    simulation_session::Entity::insert_many(to_active_models!(simulation_sessions.clone()))
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let ids: Vec<i32> = simulation_session_context
+        .get_all_by_project_id_and_user_id(project.id, project.owner_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|simulation_session| simulation_session.id)
+        .collect();
+
+    assert_eq!(ids, vec![3, 1]);
+}
+
+#[tokio::test]
+async fn get_all_by_project_id_and_user_id_recently_updated_first_test() {
+    let (simulation_session_context, _, project) = seed_db().await;
+
+    let simulation_sessions = create_simulation_sessions(2, project.id, project.owner_id);
+
+    simulation_session::Entity::insert_many(to_active_models!(simulation_sessions.clone()))
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    simulation_session_context
+        .update(simulation_sessions[0].clone())
+        .await
+        .unwrap();
+
+    let ids: Vec<i32> = simulation_session_context
+        .get_all_by_project_id_and_user_id(project.id, project.owner_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|simulation_session| simulation_session.id)
+        .collect();
+
+    assert_eq!(ids, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_simulation_session = simulation_session::Model {
+        name: "renamed".to_owned(),
+        ..simulation_session.clone()
+    };
+
+    let updated_simulation_session = simulation_session_context
+        .update(new_simulation_session.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(updated_simulation_session.name, "renamed");
+    assert_ne!(
+        updated_simulation_session.updated_at,
+        simulation_session.updated_at
+    );
+}
+
+#[tokio::test]
+async fn update_does_not_modify_component_composition_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_simulation_session = simulation_session::Model {
+        component_composition: "(C && D)".to_owned(),
+        ..simulation_session.clone()
+    };
+
+    simulation_session_context
+        .update(new_simulation_session)
+        .await
+        .unwrap();
+
+    let fetched_simulation_session = simulation_session::Entity::find_by_id(simulation_session.id)
+        .one(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        fetched_simulation_session.component_composition,
+        simulation_session.component_composition
+    );
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    let updated_simulation_session = simulation_session_context
+        .update(simulation_session.clone())
+        .await;
+
+    assert!(matches!(
+        updated_simulation_session.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_simulation_session = simulation_session_context
+        .delete(simulation_session.id)
+        .await
+        .unwrap();
+
+    let all_simulation_sessions = simulation_session::Entity::find()
+        .all(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(simulation_session, deleted_simulation_session);
+    assert!(all_simulation_sessions.is_empty());
+}
+
+#[tokio::test]
+async fn delete_cascade_simulation_step_test() {
+    let (simulation_session_context, simulation_session, _) = seed_db().await;
+
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+    simulation_step::Entity::insert_many(to_active_models!(create_simulation_steps(
+        2,
+        simulation_session.id
+    )))
+    .exec(&simulation_session_context.db_context.get_connection())
+    .await
+    .unwrap();
+
+    simulation_session_context
+        .delete(simulation_session.id)
+        .await
+        .unwrap();
+
+    let all_simulation_steps = simulation_step::Entity::find()
+        .all(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(all_simulation_steps.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (simulation_session_context, _, _) = seed_db().await;
+
+    let deleted_simulation_session = simulation_session_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_simulation_session.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_project_cascade_test() {
+    let (simulation_session_context, simulation_session, project) = seed_db().await;
+
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    project::Entity::delete_by_id(project.id)
+        .exec(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let all_simulation_sessions = simulation_session::Entity::find()
+        .all(&simulation_session_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert!(all_simulation_sessions.is_empty());
+}
@@ -0,0 +1,231 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::SimulationStepContext,
+    contexts::context_traits::{EntityContextTrait, SimulationStepContextTrait},
+    entities::{project, simulation_session, simulation_step, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (
+    SimulationStepContext,
+    simulation_step::Model,
+    simulation_session::Model,
+) {
+    let db_context = get_reset_database_context().await;
+
+    let simulation_step_context = SimulationStepContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let simulation_session = create_simulation_sessions(1, project.id, user.id)[0].clone();
+    let simulation_step = create_simulation_steps(1, simulation_session.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+    simulation_session::Entity::insert(simulation_session.clone().into_active_model())
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (simulation_step_context, simulation_step, simulation_session)
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (simulation_step_context, simulation_step, _) = seed_db().await;
+
+    let created_simulation_step = simulation_step_context
+        .create(simulation_step.clone())
+        .await
+        .unwrap();
+
+    let fetched_simulation_step = simulation_step::Entity::find_by_id(created_simulation_step.id)
+        .one(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        simulation_step,
+        simulation_step::Model {
+            created_at: simulation_step.created_at,
+            ..created_simulation_step.clone()
+        }
+    );
+    assert_eq!(fetched_simulation_step, created_simulation_step);
+}
+
+#[tokio::test]
+async fn create_auto_increment_test() {
+    let (simulation_step_context, simulation_step, _) = seed_db().await;
+
+    let created_simulation_step1 = simulation_step_context
+        .create(simulation_step.clone())
+        .await
+        .unwrap();
+    let created_simulation_step2 = simulation_step_context
+        .create(simulation_step.clone())
+        .await
+        .unwrap();
+
+    assert_ne!(created_simulation_step1.id, created_simulation_step2.id);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (simulation_step_context, simulation_step, _) = seed_db().await;
+
+    simulation_step::Entity::insert(simulation_step.clone().into_active_model())
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_simulation_step = simulation_step_context
+        .get_by_id(simulation_step.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_simulation_step, simulation_step);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (simulation_step_context, _, _) = seed_db().await;
+
+    let fetched_simulation_step = simulation_step_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_simulation_step.is_none());
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let (simulation_step_context, _, simulation_session) = seed_db().await;
+
+    let simulation_steps = create_simulation_steps(3, simulation_session.id);
+
+    simulation_step::Entity::insert_many(to_active_models!(simulation_steps.clone()))
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(simulation_step_context.get_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn get_all_by_simulation_session_id_in_order_test() {
+    let (simulation_step_context, _, simulation_session) = seed_db().await;
+
+    let simulation_steps = create_simulation_steps(3, simulation_session.id);
+
+    simulation_step::Entity::insert_many(to_active_models!(simulation_steps.clone()))
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_simulation_steps = simulation_step_context
+        .get_all_by_simulation_session_id(simulation_session.id)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched_simulation_steps, simulation_steps);
+}
+
+#[tokio::test]
+async fn get_all_by_simulation_session_id_other_session_test() {
+    let (simulation_step_context, _, simulation_session) = seed_db().await;
+
+    let simulation_steps = create_simulation_steps(3, simulation_session.id);
+
+    simulation_step::Entity::insert_many(to_active_models!(simulation_steps.clone()))
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_simulation_steps = simulation_step_context
+        .get_all_by_simulation_session_id(simulation_session.id + 1)
+        .await
+        .unwrap();
+
+    assert!(fetched_simulation_steps.is_empty());
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (simulation_step_context, simulation_step, _) = seed_db().await;
+
+    simulation_step::Entity::insert(simulation_step.clone().into_active_model())
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let new_simulation_step = simulation_step::Model {
+        decision_points: "[{}]".to_owned().parse().unwrap(),
+        ..simulation_step.clone()
+    };
+
+    let updated_simulation_step = simulation_step_context
+        .update(new_simulation_step.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(updated_simulation_step, new_simulation_step);
+}
+
+#[tokio::test]
+async fn update_non_existing_id_test() {
+    let (simulation_step_context, simulation_step, _) = seed_db().await;
+
+    let updated_simulation_step = simulation_step_context
+        .update(simulation_step.clone())
+        .await;
+
+    assert!(matches!(
+        updated_simulation_step.unwrap_err(),
+        DbErr::RecordNotUpdated
+    ));
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (simulation_step_context, simulation_step, _) = seed_db().await;
+
+    simulation_step::Entity::insert(simulation_step.clone().into_active_model())
+        .exec(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let deleted_simulation_step = simulation_step_context
+        .delete(simulation_step.id)
+        .await
+        .unwrap();
+
+    let all_simulation_steps = simulation_step::Entity::find()
+        .all(&simulation_step_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(simulation_step, deleted_simulation_step);
+    assert!(all_simulation_steps.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (simulation_step_context, _, _) = seed_db().await;
+
+    let deleted_simulation_step = simulation_step_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_simulation_step.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
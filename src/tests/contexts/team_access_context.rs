@@ -0,0 +1,251 @@
+use crate::api::server::protobuf::TeamAccessInfo;
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::TeamAccessContext,
+    contexts::context_traits::{EntityContextTrait, TeamAccessContextTrait},
+    entities::{project, team, team_access, user},
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (
+    TeamAccessContext,
+    team_access::Model,
+    team::Model,
+    project::Model,
+) {
+    let db_context = get_reset_database_context().await;
+
+    let team_access_context = TeamAccessContext::new(db_context);
+
+    let user = create_users(1)[0].clone();
+    let project = create_projects(1, user.id)[0].clone();
+    let team = create_teams(1, user.id)[0].clone();
+    let team_access = create_team_accesses(1, team.id, project.id)[0].clone();
+
+    user::Entity::insert(user.clone().into_active_model())
+        .exec(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap();
+    project::Entity::insert(project.clone().into_active_model())
+        .exec(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap();
+    team::Entity::insert(team.clone().into_active_model())
+        .exec(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (team_access_context, team_access, team, project)
+}
+
+async fn insert_team_access(
+    team_access_context: &TeamAccessContext,
+    team_access: &team_access::Model,
+) {
+    team_access::Entity::insert(team_access.clone().into_active_model())
+        .exec(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    let created_team_access = team_access_context
+        .create(team_access.clone())
+        .await
+        .unwrap();
+
+    let fetched_team_access = team_access::Entity::find_by_id(created_team_access.id)
+        .one(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(team_access, created_team_access);
+    assert_eq!(fetched_team_access, created_team_access);
+}
+
+#[tokio::test]
+async fn create_check_unique_pair_project_id_team_id_test() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    team_access_context
+        .create(team_access.clone())
+        .await
+        .unwrap();
+    let created_team_access = team_access_context.create(team_access.clone()).await;
+
+    assert!(matches!(
+        created_team_access.unwrap_err().sql_err(),
+        Some(SqlErr::UniqueConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn create_invalid_role_test() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    let created_team_access = team_access_context
+        .create(team_access::Model {
+            role: "abc".into(),
+            ..team_access
+        })
+        .await;
+
+    assert!(matches!(
+        created_team_access.unwrap_err().sql_err(),
+        Some(SqlErr::ForeignKeyConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    let fetched_team_access = team_access_context
+        .get_by_id(team_access.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(fetched_team_access, team_access);
+}
+
+#[tokio::test]
+async fn update_modifies_only_role_test() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    let updated_team_access = team_access_context
+        .update(team_access::Model {
+            role: "Editor".into(),
+            project_id: team_access.project_id + 1,
+            team_id: team_access.team_id + 1,
+            ..team_access.clone()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        updated_team_access,
+        team_access::Model {
+            role: "Editor".into(),
+            ..team_access
+        }
+    );
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    let deleted_team_access = team_access_context.delete(team_access.id).await.unwrap();
+
+    let all_team_accesses = team_access::Entity::find()
+        .all(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(deleted_team_access, team_access);
+    assert!(all_team_accesses.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (team_access_context, _, _, _) = seed_db().await;
+
+    let deleted_team_access = team_access_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_team_access.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn delete_team_deletes_team_access_test() {
+    let (team_access_context, team_access, team, _) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    team::Entity::delete_by_id(team.id)
+        .exec(&team_access_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    let fetched_team_access = team_access_context.get_by_id(team_access.id).await.unwrap();
+
+    assert!(fetched_team_access.is_none());
+}
+
+#[tokio::test]
+async fn get_team_access_by_project_id_test_returns_ok() {
+    let (team_access_context, team_access, team, project) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    let team_access_info_list = team_access_context
+        .get_team_access_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        team_access_info_list,
+        vec![TeamAccessInfo {
+            id: team_access.id,
+            role: team_access.role,
+            project_id: project.id,
+            team_id: team.id,
+            team_name: team.name,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn get_team_access_by_project_id_test_returns_empty() {
+    let (team_access_context, _, _, project) = seed_db().await;
+
+    let team_access_info_list = team_access_context
+        .get_team_access_by_project_id(project.id)
+        .await
+        .unwrap();
+
+    assert!(team_access_info_list.is_empty());
+}
+
+#[tokio::test]
+async fn get_team_access_by_role_test_returns_ok() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    let team_accesses = team_access_context
+        .get_team_access_by_role(team_access.role.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(team_accesses, vec![team_access]);
+}
+
+#[tokio::test]
+async fn get_team_access_by_role_test_returns_empty() {
+    let (team_access_context, team_access, _, _) = seed_db().await;
+
+    insert_team_access(&team_access_context, &team_access).await;
+
+    let team_accesses = team_access_context
+        .get_team_access_by_role("Editor".to_owned())
+        .await
+        .unwrap();
+
+    assert!(team_accesses.is_empty());
+}
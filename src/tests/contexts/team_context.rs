@@ -0,0 +1,261 @@
+use crate::tests::contexts::helpers::*;
+use crate::{
+    contexts::context_impls::TeamContext,
+    contexts::context_traits::{EntityContextTrait, TeamContextTrait},
+    entities::{team, team_member, user},
+    to_active_models,
+};
+use sea_orm::error::DbErr;
+use sea_orm::{entity::prelude::*, IntoActiveModel};
+use std::matches;
+
+async fn seed_db() -> (TeamContext, team::Model, Vec<user::Model>) {
+    let db_context = get_reset_database_context().await;
+
+    let team_context = TeamContext::new(db_context);
+
+    let users = create_users(3);
+    let team = create_teams(1, users[0].id)[0].clone();
+
+    user::Entity::insert_many(to_active_models!(users.clone()))
+        .exec(&team_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    (team_context, team, users)
+}
+
+async fn insert_team(team_context: &TeamContext, team: &team::Model) {
+    team::Entity::insert(team.clone().into_active_model())
+        .exec(&team_context.db_context.get_connection())
+        .await
+        .unwrap();
+}
+
+async fn insert_member(team_context: &TeamContext, team_id: i32, user_id: i32) {
+    team_member::Entity::insert(team_member::Model { team_id, user_id }.into_active_model())
+        .exec(&team_context.db_context.get_connection())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn create_test() {
+    let (team_context, team, _) = seed_db().await;
+
+    let created_team = team_context.create(team.clone()).await.unwrap();
+
+    let fetched_team = team::Entity::find_by_id(created_team.id)
+        .one(&team_context.db_context.get_connection())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(team, created_team);
+    assert_eq!(fetched_team, created_team);
+}
+
+#[tokio::test]
+async fn create_non_existing_owner_test() {
+    let (team_context, team, _) = seed_db().await;
+
+    let created_team = team_context
+        .create(team::Model {
+            owner_id: 4,
+            ..team
+        })
+        .await;
+
+    assert!(matches!(
+        created_team.unwrap_err().sql_err(),
+        Some(SqlErr::ForeignKeyConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let (team_context, team, _) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+
+    let fetched_team = team_context.get_by_id(team.id).await.unwrap().unwrap();
+
+    assert_eq!(fetched_team, team);
+}
+
+#[tokio::test]
+async fn get_by_non_existing_id_test() {
+    let (team_context, _, _) = seed_db().await;
+
+    let fetched_team = team_context.get_by_id(1).await.unwrap();
+
+    assert!(fetched_team.is_none());
+}
+
+#[tokio::test]
+async fn update_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+
+    let updated_team = team_context
+        .update(team::Model {
+            name: "renamed".to_owned(),
+            owner_id: users[1].id,
+            ..team.clone()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(updated_team.name, "renamed");
+    assert_eq!(updated_team.owner_id, team.owner_id);
+}
+
+#[tokio::test]
+async fn delete_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+    insert_member(&team_context, team.id, users[0].id).await;
+
+    let deleted_team = team_context.delete(team.id).await.unwrap();
+
+    let all_members = team_member::Entity::find()
+        .all(&team_context.db_context.get_connection())
+        .await
+        .unwrap();
+
+    assert_eq!(deleted_team, team);
+    assert!(all_members.is_empty());
+}
+
+#[tokio::test]
+async fn delete_non_existing_id_test() {
+    let (team_context, _, _) = seed_db().await;
+
+    let deleted_team = team_context.delete(1).await;
+
+    assert!(matches!(
+        deleted_team.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
+
+#[tokio::test]
+async fn get_all_by_uid_returns_teams_of_member_test() {
+    let (team_context, _, users) = seed_db().await;
+
+    let teams = create_teams(3, users[0].id);
+    for team in &teams {
+        insert_team(&team_context, team).await;
+    }
+    insert_member(&team_context, teams[0].id, users[1].id).await;
+    insert_member(&team_context, teams[2].id, users[1].id).await;
+    insert_member(&team_context, teams[1].id, users[2].id).await;
+
+    let fetched_teams = team_context.get_all_by_uid(users[1].id).await.unwrap();
+
+    assert_eq!(fetched_teams, vec![teams[0].clone(), teams[2].clone()]);
+}
+
+#[tokio::test]
+async fn get_all_by_uid_not_member_returns_empty_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+
+    let fetched_teams = team_context.get_all_by_uid(users[1].id).await.unwrap();
+
+    assert!(fetched_teams.is_empty());
+}
+
+#[tokio::test]
+async fn add_members_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+
+    team_context
+        .add_members(team.id, vec![users[2].id, users[0].id])
+        .await
+        .unwrap();
+
+    let members = team_context.get_members(team.id).await.unwrap();
+
+    assert_eq!(
+        members,
+        vec![
+            team_member::Model {
+                team_id: team.id,
+                user_id: users[0].id
+            },
+            team_member::Model {
+                team_id: team.id,
+                user_id: users[2].id
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn add_members_empty_test() {
+    let (team_context, team, _) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+
+    team_context.add_members(team.id, vec![]).await.unwrap();
+
+    assert!(team_context.get_members(team.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn add_members_existing_member_adds_none_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+    insert_member(&team_context, team.id, users[0].id).await;
+
+    let res = team_context
+        .add_members(team.id, vec![users[1].id, users[0].id])
+        .await;
+
+    assert!(res.is_err());
+    assert_eq!(team_context.get_members(team.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn remove_member_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+    insert_member(&team_context, team.id, users[0].id).await;
+    insert_member(&team_context, team.id, users[1].id).await;
+
+    let removed_member = team_context
+        .remove_member(team.id, users[1].id)
+        .await
+        .unwrap();
+
+    assert_eq!(removed_member.user_id, users[1].id);
+    assert_eq!(
+        team_context.get_members(team.id).await.unwrap(),
+        vec![team_member::Model {
+            team_id: team.id,
+            user_id: users[0].id
+        }]
+    );
+}
+
+#[tokio::test]
+async fn remove_non_member_test() {
+    let (team_context, team, users) = seed_db().await;
+
+    insert_team(&team_context, &team).await;
+
+    let removed_member = team_context.remove_member(team.id, users[1].id).await;
+
+    assert!(matches!(
+        removed_member.unwrap_err(),
+        DbErr::RecordNotFound(_)
+    ));
+}
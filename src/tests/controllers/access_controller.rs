@@ -1,14 +1,17 @@
 use crate::api::server::protobuf::create_access_request::User;
 use crate::api::server::protobuf::{
-    AccessInfo, CreateAccessRequest, DeleteAccessRequest, ListAccessInfoRequest,
-    UpdateAccessRequest,
+    AccessInfo, CreateAccessRequest, CreateTeamAccessRequest, DeleteAccessRequest,
+    DeleteTeamAccessRequest, ListAccessInfoRequest, TeamAccessInfo, UpdateAccessRequest,
+    UpdateTeamAccessRequest,
 };
+use crate::contexts::context_traits::EffectiveAccess;
 use crate::controllers::controller_impls::AccessController;
 use crate::controllers::controller_traits::AccessControllerTrait;
-use crate::entities::{access, project, user};
-use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts};
+use crate::entities::{access, project, role, team, team_access, user};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
 use mockall::predicate;
 use sea_orm::DbErr;
+use serde_json::json;
 use std::str::FromStr;
 use tonic::{metadata, Code, Request};
 
@@ -34,12 +37,15 @@ async fn create_invalid_access_returns_err() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: Default::default(),
-                role: "Editor".to_owned(),
-                user_id: 1,
-                project_id: 1,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: Default::default(),
+                    role: "Editor".to_owned(),
+                    user_id: 1,
+                    project_id: 1,
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -90,12 +96,15 @@ async fn create_access_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: Default::default(),
-                role: "Editor".to_string(),
-                user_id: 1,
-                project_id: 1,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: Default::default(),
+                    role: "Editor".to_string(),
+                    user_id: 1,
+                    project_id: 1,
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -136,6 +145,219 @@ async fn create_access_returns_ok() {
     assert!(res.is_ok());
 }
 
+#[tokio::test]
+async fn create_access_unknown_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: Default::default(),
+                    role: "Editor".to_string(),
+                    user_id: 1,
+                    project_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Owner".to_owned()))
+        .returning(|_| Ok(None));
+
+    mock_contexts.access_context_mock.expect_create().never();
+
+    let mut request = Request::new(CreateAccessRequest {
+        role: "Owner".to_string(),
+        project_id: 1,
+        user: Some(User::UserId(2)),
+    });
+
+    request.metadata_mut().insert(
+        "uid",
+        tonic::metadata::MetadataValue::from_str("1").unwrap(),
+    );
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let access_logic = AccessController::new(contexts);
+
+    let res = access_logic.create_access(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_access_reader_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: Default::default(),
+                    role: "Reader".to_string(),
+                    user_id: 1,
+                    project_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts.access_context_mock.expect_create().never();
+
+    let mut request = Request::new(CreateAccessRequest {
+        role: "Reader".to_string(),
+        project_id: 1,
+        user: Some(User::UserId(2)),
+    });
+
+    request.metadata_mut().insert(
+        "uid",
+        tonic::metadata::MetadataValue::from_str("1").unwrap(),
+    );
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let access_logic = AccessController::new(contexts);
+
+    let res = access_logic.create_access(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_access_reader_with_team_editor_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 2,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(EffectiveAccess {
+                direct: Some(access::Model {
+                    id: 1,
+                    role: "Reader".to_string(),
+                    user_id: 1,
+                    project_id: 1,
+                }),
+                team_roles: vec!["Editor".to_string()],
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_create()
+        .with(predicate::eq(access.clone()))
+        .times(1)
+        .returning(move |_| Ok(access.clone()));
+
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(2))
+        .returning(move |_| {
+            Ok(Some(user::Model {
+                id: 2,
+                email: Default::default(),
+                username: "test".to_string(),
+                password: "test".to_string(),
+            }))
+        });
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let access_logic = AccessController::new(contexts);
+
+    let res = access_logic
+        .create_access(with_uid(CreateAccessRequest {
+            role: "Reader".to_string(),
+            project_id: 1,
+            user: Some(User::UserId(2)),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn create_access_role_beyond_granter_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_manager_access(&mut mock_contexts, &[]);
+
+    mock_contexts.access_context_mock.expect_create().never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_access(with_uid(CreateAccessRequest {
+            role: "Reader".to_string(),
+            project_id: 1,
+            user: Some(User::UserId(2)),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_access_role_within_granter_team_roles_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 2,
+    };
+
+    expect_manager_access(&mut mock_contexts, &["Commenter"]);
+
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(2))
+        .returning(move |_| {
+            Ok(Some(user::Model {
+                id: 2,
+                email: Default::default(),
+                username: "test".to_string(),
+                password: "test".to_string(),
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_create()
+        .with(predicate::eq(access.clone()))
+        .times(1)
+        .returning(move |_| Ok(access.clone()));
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_access(with_uid(CreateAccessRequest {
+            role: "Reader".to_string(),
+            project_id: 1,
+            user: Some(User::UserId(2)),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
 #[tokio::test]
 async fn update_invalid_access_returns_err() {
     let mut mock_contexts = get_mock_contexts();
@@ -171,12 +393,15 @@ async fn update_invalid_access_returns_err() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                role: "Editor".to_string(),
-                project_id: 1,
-                user_id: 1,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -188,7 +413,10 @@ async fn update_invalid_access_returns_err() {
                 id: 1,
                 name: "test".to_string(),
                 owner_id: 1,
-                components_info: Default::default(),
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -245,12 +473,15 @@ async fn update_access_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                role: "Editor".to_string(),
-                project_id: 1,
-                user_id: 1,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -262,7 +493,10 @@ async fn update_access_returns_ok() {
                 id: 1,
                 name: "test".to_string(),
                 owner_id: 1,
-                components_info: Default::default(),
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -286,6 +520,97 @@ async fn update_access_returns_ok() {
     assert!(res.is_ok());
 }
 
+#[tokio::test]
+async fn update_access_unknown_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(2))
+        .returning(move |_| {
+            Ok(Some(access::Model {
+                id: 2,
+                role: "Reader".to_string(),
+                project_id: 1,
+                user_id: 2,
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Owner".to_owned()))
+        .returning(|_| Ok(None));
+
+    mock_contexts.access_context_mock.expect_update().never();
+
+    let mut request = Request::new(UpdateAccessRequest {
+        id: 2,
+        role: "Owner".to_string(),
+    });
+
+    request.metadata_mut().insert(
+        "uid",
+        tonic::metadata::MetadataValue::from_str("1").unwrap(),
+    );
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let access_logic = AccessController::new(contexts);
+
+    let res = access_logic.update_access(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn update_access_role_beyond_granter_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(2))
+        .returning(move |_| {
+            Ok(Some(access::Model {
+                id: 2,
+                role: "Reader".to_string(),
+                project_id: 1,
+                user_id: 2,
+            }))
+        });
+
+    expect_manager_access(&mut mock_contexts, &[]);
+
+    mock_contexts.access_context_mock.expect_update().never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .update_access(with_uid(UpdateAccessRequest {
+            id: 2,
+            role: "Editor".to_string(),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
 #[tokio::test]
 async fn delete_invalid_access_returns_err() {
     let mut mock_contexts = get_mock_contexts();
@@ -314,12 +639,15 @@ async fn delete_invalid_access_returns_err() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                role: "Editor".to_string(),
-                project_id: 1,
-                user_id: 1,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -331,7 +659,10 @@ async fn delete_invalid_access_returns_err() {
                 id: 1,
                 name: "test".to_string(),
                 owner_id: 1,
-                components_info: Default::default(),
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -385,12 +716,15 @@ async fn delete_access_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                role: "Editor".to_string(),
-                project_id: 1,
-                user_id: 1,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -402,7 +736,10 @@ async fn delete_access_returns_ok() {
                 id: 1,
                 name: "test".to_string(),
                 owner_id: 1,
-                components_info: Default::default(),
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -443,12 +780,15 @@ async fn list_access_info_returns_ok() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                role: "Editor".to_string(),
-                project_id: Default::default(),
-                user_id: Default::default(),
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: Default::default(),
+                    user_id: Default::default(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -456,6 +796,11 @@ async fn list_access_info_returns_ok() {
         .expect_get_access_by_project_id()
         .returning(move |_| Ok(vec![access.clone()]));
 
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_project_id()
+        .returning(move |_| Ok(vec![]));
+
     let contexts = disguise_context_mocks(mock_contexts);
     let access_logic = AccessController::new(contexts);
 
@@ -486,10 +831,15 @@ async fn list_access_info_returns_not_found() {
         .expect_get_access_by_project_id()
         .returning(move |_| Ok(vec![]));
 
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_project_id()
+        .returning(move |_| Ok(vec![]));
+
     mock_contexts
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     let contexts = disguise_context_mocks(mock_contexts);
     let access_logic = AccessController::new(contexts);
@@ -521,3 +871,410 @@ async fn list_access_info_returns_no_permission() {
 
     assert_eq!(res.code(), Code::PermissionDenied);
 }
+
+#[tokio::test]
+async fn list_access_info_team_access_only_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let mut request = Request::new(ListAccessInfoRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    expect_team_access_role(&mut mock_contexts, "Reader");
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_project_id()
+        .returning(move |_| Ok(vec![]));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_project_id()
+        .returning(move |_| {
+            Ok(vec![TeamAccessInfo {
+                id: 1,
+                role: "Reader".to_owned(),
+                project_id: 1,
+                team_id: 1,
+                team_name: "team".to_owned(),
+            }])
+        });
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let access_logic = AccessController::new(contexts);
+
+    let res = access_logic.list_access_info(request).await.unwrap();
+
+    assert!(res.get_ref().access_info_list.is_empty());
+    assert_eq!(res.get_ref().team_access_info_list.len(), 1);
+}
+
+/// Expects the user with id 1 to have the role on the project with id 1, as it would through a team.
+fn expect_team_access_role(mock_contexts: &mut MockContexts, role: &str) {
+    let role = role.to_owned();
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(EffectiveAccess {
+                direct: None,
+                team_roles: vec![role.clone()],
+            }))
+        });
+}
+
+fn team_access_model() -> team_access::Model {
+    team_access::Model {
+        id: 1,
+        role: "Reader".to_owned(),
+        project_id: 1,
+        team_id: 1,
+    }
+}
+
+/// Expects the user with id 1 to be a `Manager` on the project with id 1, besides any roles through teams.
+/// `Manager` is a custom role that may manage access, but may not run queries or edit anything.
+fn expect_manager_access(mock_contexts: &mut MockContexts, team_roles: &[&str]) {
+    let team_roles: Vec<String> = team_roles.iter().map(|role| role.to_string()).collect();
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(EffectiveAccess {
+                direct: Some(access::Model {
+                    id: 1,
+                    role: "Manager".to_owned(),
+                    project_id: 1,
+                    user_id: 1,
+                }),
+                team_roles: team_roles.clone(),
+            }))
+        });
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Manager".to_owned()))
+        .returning(|name| {
+            Ok(Some(role::Model {
+                name,
+                permissions: json!(["ReadProject", "ManageAccess"]),
+            }))
+        });
+}
+
+fn with_uid<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    request
+}
+
+#[tokio::test]
+async fn create_team_access_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .team_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(Some(team::Model {
+                id: 1,
+                name: "team".to_owned(),
+                owner_id: 2,
+            }))
+        });
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_project_id()
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_create()
+        .with(predicate::eq(team_access::Model {
+            id: Default::default(),
+            ..team_access_model()
+        }))
+        .times(1)
+        .returning(|_| Ok(team_access_model()));
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_team_access(with_uid(CreateTeamAccessRequest {
+            role: "Reader".to_owned(),
+            project_id: 1,
+            team_id: 1,
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn create_team_access_already_granted_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .team_context_mock
+        .expect_get_by_id()
+        .returning(|_| {
+            Ok(Some(team::Model {
+                id: 1,
+                name: "team".to_owned(),
+                owner_id: 2,
+            }))
+        });
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_project_id()
+        .returning(|_| {
+            Ok(vec![TeamAccessInfo {
+                id: 1,
+                role: "Reader".to_owned(),
+                project_id: 1,
+                team_id: 1,
+                team_name: "team".to_owned(),
+            }])
+        });
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_create()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_team_access(with_uid(CreateTeamAccessRequest {
+            role: "Editor".to_owned(),
+            project_id: 1,
+            team_id: 1,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::AlreadyExists);
+}
+
+#[tokio::test]
+async fn create_team_access_non_existing_team_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .team_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_create()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_team_access(with_uid(CreateTeamAccessRequest {
+            role: "Reader".to_owned(),
+            project_id: 1,
+            team_id: 1,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn create_team_access_reader_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Reader");
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_create()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_team_access(with_uid(CreateTeamAccessRequest {
+            role: "Reader".to_owned(),
+            project_id: 1,
+            team_id: 1,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_team_access_role_beyond_granter_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_manager_access(&mut mock_contexts, &[]);
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_create()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .create_team_access(with_uid(CreateTeamAccessRequest {
+            role: "Commenter".to_owned(),
+            project_id: 1,
+            team_id: 1,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn update_team_access_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(team_access_model())));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_update()
+        .with(predicate::eq(team_access::Model {
+            role: "Commenter".to_owned(),
+            ..team_access_model()
+        }))
+        .times(1)
+        .returning(Ok);
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .update_team_access(with_uid(UpdateTeamAccessRequest {
+            id: 1,
+            role: "Commenter".to_owned(),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn update_team_access_unknown_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(Some(team_access_model())));
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_update()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .update_team_access(with_uid(UpdateTeamAccessRequest {
+            id: 1,
+            role: "Owner".to_owned(),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn update_team_access_role_beyond_granter_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_manager_access(&mut mock_contexts, &[]);
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(Some(team_access_model())));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_update()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .update_team_access(with_uid(UpdateTeamAccessRequest {
+            id: 1,
+            role: "Editor".to_owned(),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn delete_team_access_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team_access_role(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(team_access_model())));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(team_access_model()));
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .delete_team_access(with_uid(DeleteTeamAccessRequest { id: 1 }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_non_existing_team_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_delete()
+        .never();
+
+    let res = AccessController::new(disguise_context_mocks(mock_contexts))
+        .delete_team_access(with_uid(DeleteTeamAccessRequest { id: 1 }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
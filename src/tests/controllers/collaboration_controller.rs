@@ -0,0 +1,328 @@
+use crate::api::server::protobuf::{
+    component::Rep, project_edit_event::Change, project_edit_operation::Change as Operation,
+    Component, CreateComponentRequest, DeleteComponentRequest, ProjectEditEvent,
+    ProjectEditOperation,
+};
+use crate::controllers::controller_impls::CollaborationController;
+use crate::controllers::controller_traits::{
+    CollaborationControllerTrait, ProjectEditOperationStream,
+};
+use crate::entities::{access, component, project};
+use crate::tests::controllers::helpers::{
+    disguise_context_mocks, disguise_service_mocks, get_mock_contexts, get_mock_services,
+    MockContexts, MockServices,
+};
+use futures::{stream, StreamExt};
+use mockall::predicate;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tonic::{metadata, Code, Request};
+
+fn json_component(json: &str) -> Component {
+    Component {
+        rep: Some(Rep::Json(json.to_owned())),
+    }
+}
+
+fn create_component(project_id: i32) -> component::Model {
+    component::Model {
+        id: 1,
+        project_id,
+        name: "A".to_owned(),
+        component: serde_json::to_value(json_component("{\"name\":\"A\"}")).unwrap(),
+        updated_at: Default::default(),
+    }
+}
+
+fn expect_project(mock_contexts: &mut MockContexts, collaborative: bool) {
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: "project".to_owned(),
+                owner_id: 1,
+                settings: None,
+                timeout_secs: None,
+                revision: 5,
+                collaborative,
+            }))
+        });
+}
+
+fn expect_access(mock_contexts: &mut MockContexts, role: &str) {
+    let role = role.to_owned();
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: role.clone(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+}
+
+/// Expects the project to be joined, and returns the edits that end up being published.
+fn expect_join(
+    mock_contexts: &mut MockContexts,
+    mock_services: &mut MockServices,
+) -> Arc<Mutex<Vec<ProjectEditEvent>>> {
+    expect_project(mock_contexts, true);
+    expect_access(mock_contexts, "Editor");
+
+    mock_services
+        .project_edit_service_mock
+        .expect_subscribe()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Box::pin(stream::empty()));
+
+    let published = Arc::new(Mutex::new(vec![]));
+    let publish_target = published.clone();
+    mock_services
+        .project_edit_service_mock
+        .expect_publish()
+        .returning(move |event| publish_target.lock().unwrap().push(event));
+
+    published
+}
+
+/// Expects the project to move to a new revision and version, without any queries to outdate.
+fn expect_components_changed(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Default::default()));
+
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .withf(|project_id, revision| *project_id == 1 && revision.is_some())
+        .returning(|_, _| Ok(true));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .returning(Ok);
+}
+
+fn controller(mock_contexts: MockContexts, mock_services: MockServices) -> CollaborationController {
+    CollaborationController::new(
+        disguise_context_mocks(mock_contexts),
+        disguise_service_mocks(mock_services),
+    )
+}
+
+fn operations(operations: Vec<ProjectEditOperation>) -> Request<ProjectEditOperationStream> {
+    let operations: ProjectEditOperationStream =
+        Box::pin(stream::iter(operations.into_iter().map(Ok)));
+    let mut request = Request::new(operations);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    request
+}
+
+fn join_operation() -> ProjectEditOperation {
+    ProjectEditOperation {
+        project_id: 1,
+        client_op_id: "join".to_owned(),
+        change: None,
+    }
+}
+
+#[tokio::test]
+async fn collaborate_on_project_publishes_applied_operations() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let published = expect_join(&mut mock_contexts, &mut mock_services);
+    expect_components_changed(&mut mock_contexts);
+
+    mock_contexts
+        .component_context_mock
+        .expect_create()
+        .withf(|component| component.project_id == 1 && component.name == "A")
+        .times(1)
+        .returning(|_| Ok(create_component(1)));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(create_component(1))));
+
+    mock_contexts
+        .component_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(create_component(1)));
+
+    let res = controller(mock_contexts, mock_services)
+        .collaborate_on_project(operations(vec![
+            join_operation(),
+            ProjectEditOperation {
+                project_id: 1,
+                client_op_id: "create".to_owned(),
+                change: Some(Operation::CreateComponent(CreateComponentRequest {
+                    project_id: 1,
+                    revision: 5,
+                    component: Some(json_component("{\"name\":\"A\"}")),
+                })),
+            },
+            ProjectEditOperation {
+                project_id: 1,
+                client_op_id: "delete".to_owned(),
+                change: Some(Operation::DeleteComponent(DeleteComponentRequest {
+                    id: 1,
+                    revision: 6,
+                })),
+            },
+        ]))
+        .await
+        .unwrap();
+
+    let rejections: Vec<_> = res.into_inner().collect().await;
+    let published = published.lock().unwrap();
+
+    assert!(rejections.is_empty());
+    assert_eq!(published.len(), 2);
+    assert_eq!(published[0].client_op_id, "create");
+    assert_eq!(published[0].revision, 6);
+    assert!(matches!(
+        &published[0].change,
+        Some(Change::ComponentCreated(component)) if component.name == "A"
+    ));
+    assert_eq!(published[1].client_op_id, "delete");
+    assert_eq!(published[1].revision, 7);
+    assert_eq!(published[1].change, Some(Change::ComponentDeleted(1)));
+}
+
+#[tokio::test]
+async fn collaborate_on_project_rejects_component_of_other_project() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let published = expect_join(&mut mock_contexts, &mut mock_services);
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(create_component(2))));
+
+    mock_contexts.component_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts, mock_services)
+        .collaborate_on_project(operations(vec![ProjectEditOperation {
+            project_id: 1,
+            client_op_id: "delete".to_owned(),
+            change: Some(Operation::DeleteComponent(DeleteComponentRequest {
+                id: 1,
+                revision: 0,
+            })),
+        }]))
+        .await
+        .unwrap();
+
+    let rejections: Vec<_> = res.into_inner().collect().await;
+
+    assert_eq!(rejections.len(), 1);
+    let rejection = rejections[0].as_ref().unwrap();
+    assert_eq!(rejection.client_op_id, "delete");
+    assert!(rejection.error.is_some());
+    assert!(published.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn collaborate_on_project_drops_lock_when_last_collaborator_leaves() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_join(&mut mock_contexts, &mut mock_services);
+
+    let controller = controller(mock_contexts, mock_services);
+
+    let res = controller
+        .collaborate_on_project(operations(vec![join_operation()]))
+        .await
+        .unwrap();
+
+    let rejections: Vec<_> = res.into_inner().collect().await;
+    assert!(rejections.is_empty());
+
+    // The collaborator leaves once its operations end, which happens in the background
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while !controller.project_locks.lock().unwrap().is_empty() {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn collaborate_on_project_not_collaborative_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_project(&mut mock_contexts, false);
+    expect_access(&mut mock_contexts, "Editor");
+
+    mock_services
+        .project_edit_service_mock
+        .expect_subscribe()
+        .never();
+
+    let res = controller(mock_contexts, mock_services)
+        .collaborate_on_project(operations(vec![join_operation()]))
+        .await;
+
+    assert_eq!(res.err().unwrap().code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn collaborate_on_project_viewer_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts, true);
+    expect_access(&mut mock_contexts, "Reader");
+
+    let res = controller(mock_contexts, get_mock_services())
+        .collaborate_on_project(operations(vec![join_operation()]))
+        .await;
+
+    assert_eq!(res.err().unwrap().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn collaborate_on_project_without_operations_returns_err() {
+    let res = controller(get_mock_contexts(), get_mock_services())
+        .collaborate_on_project(operations(vec![]))
+        .await;
+
+    assert_eq!(res.err().unwrap().code(), Code::InvalidArgument);
+}
@@ -0,0 +1,647 @@
+use crate::api::server::protobuf::{
+    CreateCommentRequest, DeleteCommentRequest, ListCommentsRequest, ReplyToCommentRequest,
+    ResolveCommentRequest, UpdateCommentRequest,
+};
+use crate::controllers::controller_impls::CommentController;
+use crate::controllers::controller_traits::CommentControllerTrait;
+use crate::entities::{access, comment, component, project, query, user};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+fn controller(mock_contexts: MockContexts) -> CommentController {
+    CommentController::new(disguise_context_mocks(mock_contexts))
+}
+
+fn with_uid<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    request
+}
+
+fn expect_access(mock_contexts: &mut MockContexts, role: &str) {
+    let role = role.to_owned();
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: role.clone(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+}
+
+fn expect_usernames(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_ids()
+        .returning(|ids| {
+            Ok(ids
+                .into_iter()
+                .map(|id| user::Model {
+                    id,
+                    email: format!("user{}@example.com", id),
+                    username: format!("user{}", id),
+                    password: "password".to_owned(),
+                })
+                .collect())
+        });
+}
+
+fn comment_model(id: i32, parent_id: Option<i32>, user_id: i32) -> comment::Model {
+    comment::Model {
+        id,
+        project_id: 1,
+        parent_id,
+        user_id: Some(user_id),
+        component_id: None,
+        location_id: None,
+        edge_id: None,
+        query_id: None,
+        text: format!("comment{}", id),
+        resolved: false,
+        created_at: Default::default(),
+        edited_at: None,
+    }
+}
+
+fn expect_comment(mock_contexts: &mut MockContexts, comment: comment::Model) {
+    mock_contexts
+        .comment_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(comment.id))
+        .returning(move |_| Ok(Some(comment.clone())));
+}
+
+fn create_request() -> CreateCommentRequest {
+    CreateCommentRequest {
+        project_id: 1,
+        text: "Why is this location urgent?".to_owned(),
+        component_id: None,
+        location_id: None,
+        edge_id: None,
+        query_id: None,
+    }
+}
+
+#[tokio::test]
+async fn create_comment_commenter_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Commenter");
+    expect_usernames(&mut mock_contexts);
+
+    mock_contexts
+        .comment_context_mock
+        .expect_create()
+        .withf(|comment| {
+            comment.project_id == 1
+                && comment.parent_id.is_none()
+                && comment.user_id == Some(1)
+                && comment.text == "Why is this location urgent?"
+        })
+        .returning(|comment| Ok(comment::Model { id: 1, ..comment }));
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(create_request()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.id, 1);
+    assert_eq!(res.username, Some("user1".to_owned()));
+}
+
+#[tokio::test]
+async fn create_comment_reader_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+
+    mock_contexts.comment_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(create_request()))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_comment_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(create_request()))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_comment_empty_text_returns_err() {
+    let mock_contexts = get_mock_contexts();
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(CreateCommentRequest {
+            text: "  \n".to_owned(),
+            ..create_request()
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_comment_on_location_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_usernames(&mut mock_contexts);
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(2))
+        .returning(|_| {
+            Ok(Some(component::Model {
+                id: 2,
+                project_id: 1,
+                name: "Machine".to_owned(),
+                component: json!({}),
+                updated_at: Default::default(),
+            }))
+        });
+
+    mock_contexts
+        .comment_context_mock
+        .expect_create()
+        .withf(|comment| {
+            comment.component_id == Some(2) && comment.location_id == Some("L0".to_owned())
+        })
+        .returning(|comment| Ok(comment::Model { id: 1, ..comment }));
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(CreateCommentRequest {
+            component_id: Some(2),
+            location_id: Some("L0".to_owned()),
+            ..create_request()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.location_id, Some("L0".to_owned()));
+}
+
+#[tokio::test]
+async fn create_comment_location_without_component_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(CreateCommentRequest {
+            location_id: Some("L0".to_owned()),
+            ..create_request()
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_comment_component_and_query_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(CreateCommentRequest {
+            component_id: Some(2),
+            query_id: Some(3),
+            ..create_request()
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_comment_query_in_other_project_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(3))
+        .returning(|_| {
+            Ok(Some(query::Model {
+                id: 3,
+                string: "refinement: A <= B".to_owned(),
+                result: None,
+                outdated: true,
+                project_id: 2,
+                settings: None,
+                timeout_secs: None,
+            }))
+        });
+
+    mock_contexts.comment_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .create_comment(with_uid(CreateCommentRequest {
+            query_id: Some(3),
+            ..create_request()
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn reply_to_comment_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Commenter");
+    expect_usernames(&mut mock_contexts);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 2));
+
+    mock_contexts
+        .comment_context_mock
+        .expect_create()
+        .withf(|comment| comment.parent_id == Some(1) && comment.user_id == Some(1))
+        .returning(|comment| Ok(comment::Model { id: 2, ..comment }));
+
+    let res = controller(mock_contexts)
+        .reply_to_comment(with_uid(ReplyToCommentRequest {
+            comment_id: 1,
+            text: "It is not".to_owned(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.parent_id, Some(1));
+}
+
+#[tokio::test]
+async fn reply_to_reply_attaches_to_thread_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_usernames(&mut mock_contexts);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 2));
+    expect_comment(&mut mock_contexts, comment_model(2, Some(1), 2));
+
+    mock_contexts
+        .comment_context_mock
+        .expect_create()
+        .withf(|comment| comment.parent_id == Some(1))
+        .returning(|comment| Ok(comment::Model { id: 3, ..comment }));
+
+    let res = controller(mock_contexts)
+        .reply_to_comment(with_uid(ReplyToCommentRequest {
+            comment_id: 2,
+            text: "Agreed".to_owned(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.parent_id, Some(1));
+}
+
+#[tokio::test]
+async fn reply_to_non_existing_comment_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .comment_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .reply_to_comment(with_uid(ReplyToCommentRequest {
+            comment_id: 1,
+            text: "Hello".to_owned(),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn resolve_reply_resolves_thread_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Commenter");
+    expect_usernames(&mut mock_contexts);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 2));
+    expect_comment(&mut mock_contexts, comment_model(2, Some(1), 2));
+
+    mock_contexts
+        .comment_context_mock
+        .expect_update()
+        .withf(|comment| comment.id == 1 && comment.resolved)
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .resolve_comment(with_uid(ResolveCommentRequest {
+            comment_id: 2,
+            resolved: true,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.id, 1);
+    assert!(res.resolved);
+}
+
+#[tokio::test]
+async fn resolve_comment_reader_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+    expect_comment(&mut mock_contexts, comment_model(1, None, 2));
+
+    mock_contexts.comment_context_mock.expect_update().never();
+
+    let res = controller(mock_contexts)
+        .resolve_comment(with_uid(ResolveCommentRequest {
+            comment_id: 1,
+            resolved: true,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn update_own_comment_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Commenter");
+    expect_usernames(&mut mock_contexts);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 1));
+
+    mock_contexts
+        .comment_context_mock
+        .expect_update()
+        .withf(|comment| comment.text == "edited" && comment.edited_at.is_some())
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .update_comment(with_uid(UpdateCommentRequest {
+            id: 1,
+            text: "edited".to_owned(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.text, "edited");
+    assert!(res.edited_at.is_some());
+}
+
+#[tokio::test]
+async fn update_other_users_comment_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_comment(&mut mock_contexts, comment_model(1, None, 2));
+
+    mock_contexts.comment_context_mock.expect_update().never();
+
+    let res = controller(mock_contexts)
+        .update_comment(with_uid(UpdateCommentRequest {
+            id: 1,
+            text: "edited".to_owned(),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+fn expect_project(mock_contexts: &mut MockContexts, owner_id: i32) {
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: "project".to_owned(),
+                owner_id,
+                settings: None,
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+}
+
+#[tokio::test]
+async fn delete_own_comment_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Commenter");
+    expect_project(&mut mock_contexts, 2);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 1));
+
+    mock_contexts
+        .comment_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(comment_model(1, None, 1)));
+
+    let res = controller(mock_contexts)
+        .delete_comment(with_uid(DeleteCommentRequest { id: 1 }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_other_users_comment_as_owner_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts, 1);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 2));
+
+    mock_contexts
+        .comment_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(comment_model(1, None, 2)));
+
+    let res = controller(mock_contexts)
+        .delete_comment(with_uid(DeleteCommentRequest { id: 1 }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_other_users_comment_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts, 2);
+    expect_comment(&mut mock_contexts, comment_model(1, None, 3));
+
+    mock_contexts.comment_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts)
+        .delete_comment(with_uid(DeleteCommentRequest { id: 1 }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn list_comments_groups_threads_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+    expect_usernames(&mut mock_contexts);
+
+    mock_contexts
+        .comment_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                comment_model(1, None, 1),
+                comment_model(2, None, 2),
+                comment_model(3, Some(1), 2),
+                comment_model(4, Some(1), 1),
+            ])
+        });
+
+    let res = controller(mock_contexts)
+        .list_comments(with_uid(ListCommentsRequest {
+            project_id: 1,
+            include_resolved: false,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.threads.len(), 2);
+    assert_eq!(res.threads[0].comment.as_ref().unwrap().id, 1);
+    assert_eq!(
+        res.threads[0]
+            .replies
+            .iter()
+            .map(|reply| reply.id)
+            .collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(res.threads[0].replies[0].username, Some("user2".to_owned()));
+    assert!(res.threads[1].replies.is_empty());
+}
+
+#[tokio::test]
+async fn list_comments_hides_resolved_threads_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+    expect_usernames(&mut mock_contexts);
+
+    mock_contexts
+        .comment_context_mock
+        .expect_get_all_by_project_id()
+        .returning(|_| {
+            Ok(vec![
+                comment::Model {
+                    resolved: true,
+                    ..comment_model(1, None, 1)
+                },
+                comment_model(2, None, 1),
+            ])
+        });
+
+    let res = controller(mock_contexts)
+        .list_comments(with_uid(ListCommentsRequest {
+            project_id: 1,
+            include_resolved: false,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.threads.len(), 1);
+    assert_eq!(res.threads[0].comment.as_ref().unwrap().id, 2);
+}
+
+#[tokio::test]
+async fn list_comments_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let res = controller(mock_contexts)
+        .list_comments(with_uid(ListCommentsRequest {
+            project_id: 1,
+            include_resolved: true,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn list_comments_internal_error_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+
+    mock_contexts
+        .comment_context_mock
+        .expect_get_all_by_project_id()
+        .returning(|_| Err(DbErr::RecordNotInserted));
+
+    let res = controller(mock_contexts)
+        .list_comments(with_uid(ListCommentsRequest {
+            project_id: 1,
+            include_resolved: true,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::Internal);
+}
@@ -0,0 +1,739 @@
+use crate::api::auth::TokenType;
+use crate::api::server::protobuf::{
+    component::Rep, query_event::Kind, Component, CreateComponentRequest, DeleteComponentRequest,
+    GetComponentRequest, UpdateComponentRequest,
+};
+use crate::controllers::controller_impls::ComponentController;
+use crate::controllers::controller_traits::ComponentControllerTrait;
+use crate::entities::{access, component, in_use, project, query, role, session};
+use crate::tests::controllers::helpers::{
+    disguise_context_mocks, disguise_service_mocks, get_mock_contexts, get_mock_services,
+    MockContexts, MockServices,
+};
+use chrono::{Duration, Utc};
+use mockall::predicate;
+use serde_json::json;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+fn json_component(json: &str) -> Component {
+    Component {
+        rep: Some(Rep::Json(json.to_owned())),
+    }
+}
+
+fn create_component() -> component::Model {
+    component::Model {
+        id: 1,
+        project_id: 1,
+        name: "A".to_owned(),
+        component: serde_json::to_value(json_component("{\"name\":\"A\"}")).unwrap(),
+        updated_at: Default::default(),
+    }
+}
+
+fn create_query(outdated: bool) -> query::Model {
+    query::Model {
+        id: 1,
+        string: "consistency: A".to_owned(),
+        result: None,
+        outdated,
+        project_id: 1,
+        settings: Default::default(),
+        timeout_secs: None,
+    }
+}
+
+fn expect_access(mock_contexts: &mut MockContexts, role: &str) {
+    let role = role.to_owned();
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: role.clone(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+}
+
+/// Expects the custom role "Verifier", which can read the project and run queries but not edit it.
+fn expect_verifier_role(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Verifier".to_owned()))
+        .returning(|name| {
+            Ok(Some(role::Model {
+                name,
+                permissions: json!(["ReadProject", "RunQuery"]),
+            }))
+        });
+}
+
+/// Expects the project to be locked by the given session, while the request is made from session 1.
+fn expect_lock(mock_contexts: &mut MockContexts, session_id: i32) {
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: "project".to_owned(),
+                owner_id: 1,
+                settings: None,
+                timeout_secs: None,
+                revision: 3,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_owned()),
+        )
+        .returning(|_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_owned(),
+                access_token: "access_token".to_owned(),
+                updated_at: Default::default(),
+                user_id: 1,
+            }))
+        });
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                project_id: 1,
+                session_id,
+                latest_activity: Default::default(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+            }))
+        });
+}
+
+fn expect_component(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .component_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(create_component())));
+}
+
+/// Expects the project to move to a new revision and version.
+/// If `outdates_query` is set, the change touches component A, so the query mentioning it goes from up to date to outdated.
+fn expect_components_changed(
+    mock_contexts: &mut MockContexts,
+    mock_services: &mut MockServices,
+    outdates_query: bool,
+) {
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .times(2)
+        .returning(|_| Ok(vec![create_query(false)]));
+
+    mock_contexts
+        .query_context_mock
+        .expect_mark_outdated()
+        .with(predicate::eq(vec![1]))
+        .times(usize::from(outdates_query))
+        .returning(|_| Ok(()));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(move |_| Ok(vec![create_query(outdates_query)]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Default::default()));
+
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .with(predicate::eq(1), predicate::eq(Some(0)))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .withf(|project_version| {
+            project_version.project_id == 1 && project_version.user_id == Some(1)
+        })
+        .times(1)
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .withf(|event| {
+            event.query_id == 1 && event.project_id == 1 && event.kind == i32::from(Kind::Outdated)
+        })
+        .times(usize::from(outdates_query))
+        .returning(|_| ());
+}
+
+fn controller(mock_contexts: MockContexts, mock_services: MockServices) -> ComponentController {
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    ComponentController::new(contexts, services)
+}
+
+fn with_uid<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+    request
+}
+
+#[tokio::test]
+async fn create_component_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_components_changed(&mut mock_contexts, &mut mock_services, true);
+
+    mock_contexts
+        .component_context_mock
+        .expect_create()
+        .withf(|component| component.project_id == 1 && component.name == "A")
+        .times(1)
+        .returning(|_| Ok(create_component()));
+
+    let res = controller(mock_contexts, mock_services)
+        .create_component(with_uid(CreateComponentRequest {
+            project_id: 1,
+            revision: 0,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap().get_ref().id, 1);
+}
+
+#[tokio::test]
+async fn create_component_without_name_uses_placeholder_name() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_components_changed(&mut mock_contexts, &mut mock_services, false);
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![component::Model {
+                name: "Component 1".to_owned(),
+                ..create_component()
+            }])
+        });
+
+    mock_contexts
+        .component_context_mock
+        .expect_create()
+        .withf(|component| component.name == "Component 2")
+        .times(1)
+        .returning(|component| Ok(component::Model { id: 2, ..component }));
+
+    let res = controller(mock_contexts, mock_services)
+        .create_component(with_uid(CreateComponentRequest {
+            project_id: 1,
+            revision: 0,
+            component: Some(Component {
+                rep: Some(Rep::Xml("<nta></nta>".to_owned())),
+            }),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap().get_ref().id, 2);
+}
+
+#[tokio::test]
+async fn create_component_viewer_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+
+    mock_contexts.component_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .create_component(with_uid(CreateComponentRequest {
+            project_id: 1,
+            revision: 0,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_component_custom_role_without_permission_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Verifier");
+    expect_verifier_role(&mut mock_contexts);
+
+    mock_contexts.component_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .create_component(with_uid(CreateComponentRequest {
+            project_id: 1,
+            revision: 0,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn get_component_custom_role_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Verifier");
+    expect_verifier_role(&mut mock_contexts);
+    expect_component(&mut mock_contexts);
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_component(with_uid(GetComponentRequest { id: 1 }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn create_component_locked_by_other_session_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 2);
+
+    mock_contexts.component_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .create_component(with_uid(CreateComponentRequest {
+            project_id: 1,
+            revision: 3,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn get_component_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+    expect_component(&mut mock_contexts);
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_component(with_uid(GetComponentRequest { id: 1 }))
+        .await
+        .unwrap();
+
+    let component = res.get_ref().component.clone().unwrap();
+    assert_eq!(component.name, "A");
+    assert_eq!(
+        component.component,
+        Some(json_component("{\"name\":\"A\"}"))
+    );
+}
+
+#[tokio::test]
+async fn get_component_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_component(&mut mock_contexts);
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_component(with_uid(GetComponentRequest { id: 1 }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn get_component_not_found_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_component(with_uid(GetComponentRequest { id: 1 }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn update_component_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_component(&mut mock_contexts);
+    expect_components_changed(&mut mock_contexts, &mut mock_services, true);
+
+    let new_component = json_component("{\"name\":\"A\",\"locations\":[]}");
+    let new_component_json = serde_json::to_value(new_component.clone()).unwrap();
+
+    mock_contexts
+        .component_context_mock
+        .expect_update()
+        .withf(move |component| {
+            component.id == 1
+                && component.project_id == 1
+                && component.component == new_component_json
+        })
+        .times(1)
+        .returning(Ok);
+
+    let res = controller(mock_contexts, mock_services)
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 0,
+            component: Some(new_component),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn update_component_without_name_keeps_stored_name() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_component(&mut mock_contexts);
+    expect_components_changed(&mut mock_contexts, &mut mock_services, true);
+
+    mock_contexts
+        .component_context_mock
+        .expect_update()
+        .withf(|component| component.id == 1 && component.name == "A")
+        .times(1)
+        .returning(Ok);
+
+    let res = controller(mock_contexts, mock_services)
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 0,
+            component: Some(Component {
+                rep: Some(Rep::Xml("<nta></nta>".to_owned())),
+            }),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn update_component_rename_outdates_queries_of_old_and_new_name() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_component(&mut mock_contexts);
+
+    let queries: Vec<query::Model> = ["consistency: A", "consistency: B", "consistency: C"]
+        .into_iter()
+        .zip(1..)
+        .map(|(string, id)| query::Model {
+            id,
+            string: string.to_owned(),
+            ..create_query(false)
+        })
+        .collect();
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(queries.clone()));
+
+    mock_contexts
+        .query_context_mock
+        .expect_mark_outdated()
+        .with(predicate::eq(vec![1, 2]))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(|_| Ok(Default::default()));
+
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .component_context_mock
+        .expect_update()
+        .withf(|component| component.name == "B")
+        .times(1)
+        .returning(Ok);
+
+    // The mocked queries stay up to date, so no events are published
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .never();
+
+    let res = controller(mock_contexts, mock_services)
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 0,
+            component: Some(json_component("{\"name\":\"B\"}")),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn update_component_unchanged_keeps_queries_up_to_date() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_component(&mut mock_contexts);
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(vec![create_query(false)]));
+
+    mock_contexts
+        .query_context_mock
+        .expect_mark_outdated()
+        .never();
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(|_| Ok(Default::default()));
+
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .component_context_mock
+        .expect_update()
+        .times(1)
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .never();
+
+    let res = controller(mock_contexts, mock_services)
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 0,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn update_component_stale_revision_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_component(&mut mock_contexts);
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .returning(|_| Ok(vec![create_query(false)]));
+
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .with(predicate::eq(1), predicate::eq(Some(2)))
+        .returning(|_, _| Ok(false));
+
+    mock_contexts.component_context_mock.expect_update().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 2,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    let status = res.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert_eq!(status.metadata().get("revision").unwrap(), "3");
+}
+
+#[tokio::test]
+async fn update_component_viewer_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+    expect_component(&mut mock_contexts);
+
+    mock_contexts.component_context_mock.expect_update().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 0,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn update_component_locked_by_other_session_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_component(&mut mock_contexts);
+    expect_lock(&mut mock_contexts, 2);
+
+    mock_contexts.component_context_mock.expect_update().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .update_component(with_uid(UpdateComponentRequest {
+            id: 1,
+            revision: 3,
+            component: Some(json_component("{\"name\":\"A\"}")),
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn delete_component_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_lock(&mut mock_contexts, 1);
+    expect_component(&mut mock_contexts);
+    expect_components_changed(&mut mock_contexts, &mut mock_services, true);
+
+    mock_contexts
+        .component_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(create_component()));
+
+    let res = controller(mock_contexts, mock_services)
+        .delete_component(with_uid(DeleteComponentRequest { id: 1, revision: 0 }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_component_not_found_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    mock_contexts.component_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .delete_component(with_uid(DeleteComponentRequest { id: 1, revision: 0 }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn delete_component_locked_by_other_session_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_component(&mut mock_contexts);
+    expect_lock(&mut mock_contexts, 2);
+
+    mock_contexts.component_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts, get_mock_services())
+        .delete_component(with_uid(DeleteComponentRequest { id: 1, revision: 3 }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+}
@@ -2,18 +2,27 @@
 
 use crate::api::auth::TokenType;
 use crate::api::server::protobuf::AccessInfo;
+use crate::api::server::protobuf::ComponentsInfo;
 use crate::api::server::protobuf::ProjectInfo;
+use crate::api::server::protobuf::TeamAccessInfo;
 use crate::api::server::protobuf::{
-    QueryRequest, QueryResponse, SimulationStartRequest, SimulationStepRequest,
-    SimulationStepResponse, UserTokenResponse,
+    ProjectEditEvent, QueryEvent, QueryRequest, QueryResponse, SimulationStartRequest,
+    SimulationStepRequest, SimulationStepResponse, UserTokenResponse,
 };
 use crate::contexts::context_collection::ContextCollection;
 use crate::contexts::context_traits::*;
-use crate::entities::{access, in_use, project, query, session, user};
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use crate::entities::{
+    access, backend_audit, comment, component, counterexample, in_use, invitation, project,
+    project_version, query, query_cache, query_job, query_result, role, session, share_link,
+    simulation_session, simulation_step, team, team_access, team_member, user,
+};
 use crate::services::service_collection::ServiceCollection;
 use crate::services::service_traits::*;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use mockall::mock;
+use sea_orm::prelude::Json;
 use sea_orm::DbErr;
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
@@ -21,10 +30,25 @@ use tonic::{Request, Response, Status};
 pub fn get_mock_contexts() -> MockContexts {
     MockContexts {
         access_context_mock: MockAccessContext::new(),
+        backend_audit_context_mock: MockBackendAuditContext::new(),
+        comment_context_mock: MockCommentContext::new(),
+        component_context_mock: MockComponentContext::new(),
+        counterexample_context_mock: MockCounterexampleContext::new(),
         in_use_context_mock: MockInUseContext::new(),
+        invitation_context_mock: MockInvitationContext::new(),
         project_context_mock: MockProjectContext::new(),
+        project_version_context_mock: MockProjectVersionContext::new(),
+        query_cache_context_mock: MockQueryCacheContext::new(),
         query_context_mock: MockQueryContext::new(),
+        query_job_context_mock: MockQueryJobContext::new(),
+        query_result_context_mock: MockQueryResultContext::new(),
+        role_context_mock: MockRoleContext::new(),
         session_context_mock: MockSessionContext::new(),
+        share_link_context_mock: MockShareLinkContext::new(),
+        simulation_session_context_mock: MockSimulationSessionContext::new(),
+        simulation_step_context_mock: MockSimulationStepContext::new(),
+        team_access_context_mock: MockTeamAccessContext::new(),
+        team_context_mock: MockTeamContext::new(),
         user_context_mock: MockUserContext::new(),
     }
 }
@@ -32,6 +56,10 @@ pub fn get_mock_contexts() -> MockContexts {
 pub fn get_mock_services() -> MockServices {
     MockServices {
         hashing_service_mock: MockHashingService::new(),
+        project_edit_service_mock: MockProjectEditService::new(),
+        query_event_service_mock: MockQueryEventService::new(),
+        query_job_service_mock: MockQueryJobService::new(),
+        query_run_service_mock: MockQueryRunService::new(),
         reveaal_service_mock: MockReveaalService::new(),
     }
 }
@@ -39,10 +67,25 @@ pub fn get_mock_services() -> MockServices {
 pub fn disguise_context_mocks(mock_services: MockContexts) -> ContextCollection {
     ContextCollection {
         access_context: Arc::new(mock_services.access_context_mock),
+        backend_audit_context: Arc::new(mock_services.backend_audit_context_mock),
+        comment_context: Arc::new(mock_services.comment_context_mock),
+        component_context: Arc::new(mock_services.component_context_mock),
+        counterexample_context: Arc::new(mock_services.counterexample_context_mock),
         in_use_context: Arc::new(mock_services.in_use_context_mock),
+        invitation_context: Arc::new(mock_services.invitation_context_mock),
         project_context: Arc::new(mock_services.project_context_mock),
+        project_version_context: Arc::new(mock_services.project_version_context_mock),
+        query_cache_context: Arc::new(mock_services.query_cache_context_mock),
         query_context: Arc::new(mock_services.query_context_mock),
+        query_job_context: Arc::new(mock_services.query_job_context_mock),
+        query_result_context: Arc::new(mock_services.query_result_context_mock),
+        role_context: Arc::new(mock_services.role_context_mock),
         session_context: Arc::new(mock_services.session_context_mock),
+        share_link_context: Arc::new(mock_services.share_link_context_mock),
+        simulation_session_context: Arc::new(mock_services.simulation_session_context_mock),
+        simulation_step_context: Arc::new(mock_services.simulation_step_context_mock),
+        team_access_context: Arc::new(mock_services.team_access_context_mock),
+        team_context: Arc::new(mock_services.team_context_mock),
         user_context: Arc::new(mock_services.user_context_mock),
     }
 }
@@ -50,21 +93,44 @@ pub fn disguise_context_mocks(mock_services: MockContexts) -> ContextCollection
 pub fn disguise_service_mocks(mock_services: MockServices) -> ServiceCollection {
     ServiceCollection {
         hashing_service: Arc::new(mock_services.hashing_service_mock),
+        project_edit_service: Arc::new(mock_services.project_edit_service_mock),
+        query_event_service: Arc::new(mock_services.query_event_service_mock),
+        query_job_service: Arc::new(mock_services.query_job_service_mock),
+        query_run_service: Arc::new(mock_services.query_run_service_mock),
         reveaal_service: Arc::new(mock_services.reveaal_service_mock),
     }
 }
 
 pub struct MockContexts {
     pub(crate) access_context_mock: MockAccessContext,
+    pub(crate) backend_audit_context_mock: MockBackendAuditContext,
+    pub(crate) comment_context_mock: MockCommentContext,
+    pub(crate) component_context_mock: MockComponentContext,
+    pub(crate) counterexample_context_mock: MockCounterexampleContext,
     pub(crate) in_use_context_mock: MockInUseContext,
+    pub(crate) invitation_context_mock: MockInvitationContext,
     pub(crate) project_context_mock: MockProjectContext,
+    pub(crate) project_version_context_mock: MockProjectVersionContext,
+    pub(crate) query_cache_context_mock: MockQueryCacheContext,
     pub(crate) query_context_mock: MockQueryContext,
+    pub(crate) query_job_context_mock: MockQueryJobContext,
+    pub(crate) query_result_context_mock: MockQueryResultContext,
+    pub(crate) role_context_mock: MockRoleContext,
     pub(crate) session_context_mock: MockSessionContext,
+    pub(crate) share_link_context_mock: MockShareLinkContext,
+    pub(crate) simulation_session_context_mock: MockSimulationSessionContext,
+    pub(crate) simulation_step_context_mock: MockSimulationStepContext,
+    pub(crate) team_access_context_mock: MockTeamAccessContext,
+    pub(crate) team_context_mock: MockTeamContext,
     pub(crate) user_context_mock: MockUserContext,
 }
 
 pub struct MockServices {
     pub(crate) hashing_service_mock: MockHashingService,
+    pub(crate) project_edit_service_mock: MockProjectEditService,
+    pub(crate) query_event_service_mock: MockQueryEventService,
+    pub(crate) query_job_service_mock: MockQueryJobService,
+    pub(crate) query_run_service_mock: MockQueryRunService,
     pub(crate) reveaal_service_mock: MockReveaalService,
 }
 
@@ -84,12 +150,77 @@ mock! {
             &self,
             uid: i32,
             project_id: i32,
-        ) -> Result<Option<access::Model>, DbErr>;
+        ) -> Result<Option<EffectiveAccess>, DbErr>;
 
         async fn get_access_by_project_id(
             &self,
             project_id: i32,
         ) -> Result<Vec<AccessInfo>, DbErr>;
+
+        async fn get_access_by_role(&self, role: String) -> Result<Vec<access::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub BackendAuditContext {}
+    #[async_trait]
+    impl EntityContextTrait<backend_audit::Model> for BackendAuditContext {
+        async fn create(&self, entity: backend_audit::Model) -> Result<backend_audit::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<backend_audit::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<backend_audit::Model>, DbErr>;
+        async fn update(&self, entity: backend_audit::Model) -> Result<backend_audit::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<backend_audit::Model, DbErr>;
+    }
+    impl BackendAuditContextTrait for BackendAuditContext {}
+}
+
+mock! {
+    pub CommentContext {}
+    #[async_trait]
+    impl EntityContextTrait<comment::Model> for CommentContext {
+        async fn create(&self, entity: comment::Model) -> Result<comment::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<comment::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<comment::Model>, DbErr>;
+        async fn update(&self, entity: comment::Model) -> Result<comment::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<comment::Model, DbErr>;
+    }
+    #[async_trait]
+    impl CommentContextTrait for CommentContext {
+        async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<comment::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub ComponentContext {}
+    #[async_trait]
+    impl EntityContextTrait<component::Model> for ComponentContext {
+        async fn create(&self, entity: component::Model) -> Result<component::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<component::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<component::Model>, DbErr>;
+        async fn update(&self, entity: component::Model) -> Result<component::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<component::Model, DbErr>;
+    }
+    #[async_trait]
+    impl ComponentContextTrait for ComponentContext {
+        async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<component::Model>, DbErr>;
+        async fn get_components_info_by_project_id(&self, project_id: i32) -> Result<ComponentsInfo, DbErr>;
+    }
+}
+
+mock! {
+    pub CounterexampleContext {}
+    #[async_trait]
+    impl EntityContextTrait<counterexample::Model> for CounterexampleContext {
+        async fn create(&self, entity: counterexample::Model) -> Result<counterexample::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<counterexample::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<counterexample::Model>, DbErr>;
+        async fn update(&self, entity: counterexample::Model) -> Result<counterexample::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<counterexample::Model, DbErr>;
+    }
+    #[async_trait]
+    impl CounterexampleContextTrait for CounterexampleContext {
+        async fn get_latest_by_query_id(&self, query_id: i32) -> Result<Option<counterexample::Model>, DbErr>;
+        async fn get_by_query_result_id(&self, query_result_id: i32) -> Result<Option<counterexample::Model>, DbErr>;
     }
 }
 
@@ -104,7 +235,29 @@ mock! {
         async fn delete(&self, entity_id: i32) -> Result<in_use::Model, DbErr>;
     }
     #[async_trait]
-    impl InUseContextTrait for InUseContext {}
+    impl InUseContextTrait for InUseContext {
+        async fn acquire(&self, project_id: i32, session_id: i32, expires_at: NaiveDateTime) -> Result<bool, DbErr>;
+        async fn renew(&self, project_id: i32, session_id: i32, expires_at: NaiveDateTime) -> Result<bool, DbErr>;
+        async fn release(&self, project_id: i32, session_id: Option<i32>) -> Result<bool, DbErr>;
+    }
+}
+
+mock! {
+    pub InvitationContext {}
+    #[async_trait]
+    impl EntityContextTrait<invitation::Model> for InvitationContext {
+        async fn create(&self, entity: invitation::Model) -> Result<invitation::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<invitation::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<invitation::Model>, DbErr>;
+        async fn update(&self, entity: invitation::Model) -> Result<invitation::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<invitation::Model, DbErr>;
+    }
+    #[async_trait]
+    impl InvitationContextTrait for InvitationContext {
+        async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<invitation::Model>, DbErr>;
+        async fn redeem(&self, id: i32) -> Result<bool, DbErr>;
+        async fn get_redeemable_by_role(&self, role: String) -> Result<Vec<invitation::Model>, DbErr>;
+    }
 }
 
 mock! {
@@ -120,6 +273,24 @@ mock! {
     #[async_trait]
     impl ProjectContextTrait for ProjectContext {
         async fn get_project_info_by_uid(&self, uid: i32) -> Result<Vec<ProjectInfo>, DbErr>;
+        async fn advance_revision(&self, project_id: i32, expected_revision: Option<i32>) -> Result<bool, DbErr>;
+        async fn update_at_revision(&self, entity: project::Model, expected_revision: i32) -> Result<bool, DbErr>;
+    }
+}
+
+mock! {
+    pub ProjectVersionContext {}
+    #[async_trait]
+    impl EntityContextTrait<project_version::Model> for ProjectVersionContext {
+        async fn create(&self, entity: project_version::Model) -> Result<project_version::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<project_version::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<project_version::Model>, DbErr>;
+        async fn update(&self, entity: project_version::Model) -> Result<project_version::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<project_version::Model, DbErr>;
+    }
+    #[async_trait]
+    impl ProjectVersionContextTrait for ProjectVersionContext {
+        async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<project_version::Model>, DbErr>;
     }
 }
 
@@ -136,6 +307,71 @@ mock! {
     #[async_trait]
     impl QueryContextTrait for QueryContext {
         async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<query::Model>, DbErr>;
+        async fn update_result(&self, id: i32, result: Json, outdated: bool) -> Result<bool, DbErr>;
+        async fn mark_outdated(&self, ids: Vec<i32>) -> Result<(), DbErr>;
+        async fn update_at_revision(&self, entity: query::Model, expected_revision: i32) -> Result<bool, DbErr>;
+    }
+}
+
+mock! {
+    pub QueryCacheContext {}
+    #[async_trait]
+    impl EntityContextTrait<query_cache::Model> for QueryCacheContext {
+        async fn create(&self, entity: query_cache::Model) -> Result<query_cache::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<query_cache::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<query_cache::Model>, DbErr>;
+        async fn update(&self, entity: query_cache::Model) -> Result<query_cache::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<query_cache::Model, DbErr>;
+    }
+    #[async_trait]
+    impl QueryCacheContextTrait for QueryCacheContext {
+        async fn get_by_hash(&self, hash: &str) -> Result<Option<query_cache::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub QueryJobContext {}
+    #[async_trait]
+    impl EntityContextTrait<query_job::Model> for QueryJobContext {
+        async fn create(&self, entity: query_job::Model) -> Result<query_job::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<query_job::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<query_job::Model>, DbErr>;
+        async fn update(&self, entity: query_job::Model) -> Result<query_job::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<query_job::Model, DbErr>;
+    }
+    #[async_trait]
+    impl QueryJobContextTrait for QueryJobContext {
+        async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<query_job::Model>, DbErr>;
+        async fn get_all_by_status(&self, status: QueryJobStatus) -> Result<Vec<query_job::Model>, DbErr>;
+        async fn update_if_status(&self, entity: query_job::Model, expected: QueryJobStatus) -> Result<bool, DbErr>;
+    }
+}
+
+mock! {
+    pub QueryResultContext {}
+    #[async_trait]
+    impl EntityContextTrait<query_result::Model> for QueryResultContext {
+        async fn create(&self, entity: query_result::Model) -> Result<query_result::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<query_result::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<query_result::Model>, DbErr>;
+        async fn update(&self, entity: query_result::Model) -> Result<query_result::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<query_result::Model, DbErr>;
+    }
+    #[async_trait]
+    impl QueryResultContextTrait for QueryResultContext {
+        async fn get_page_by_query_id(&self, query_id: i32, page: u64, page_size: u64) -> Result<(Vec<query_result::Model>, u64), DbErr>;
+    }
+}
+
+mock! {
+    pub RoleContext {}
+    #[async_trait]
+    impl RoleContextTrait for RoleContext {
+        async fn create(&self, entity: role::Model) -> Result<role::Model, DbErr>;
+        async fn get_by_name(&self, name: String) -> Result<Option<role::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<role::Model>, DbErr>;
+        async fn update(&self, entity: role::Model) -> Result<role::Model, DbErr>;
+        async fn delete(&self, name: String) -> Result<role::Model, DbErr>;
     }
 }
 
@@ -156,6 +392,91 @@ mock! {
     }
 }
 
+mock! {
+    pub ShareLinkContext {}
+    #[async_trait]
+    impl EntityContextTrait<share_link::Model> for ShareLinkContext {
+        async fn create(&self, entity: share_link::Model) -> Result<share_link::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<share_link::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<share_link::Model>, DbErr>;
+        async fn update(&self, entity: share_link::Model) -> Result<share_link::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<share_link::Model, DbErr>;
+    }
+    #[async_trait]
+    impl ShareLinkContextTrait for ShareLinkContext {
+        async fn get_by_token(&self, token: String) -> Result<Option<share_link::Model>, DbErr>;
+        async fn get_all_by_project_id(&self, project_id: i32) -> Result<Vec<share_link::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub SimulationSessionContext {}
+    #[async_trait]
+    impl EntityContextTrait<simulation_session::Model> for SimulationSessionContext {
+        async fn create(&self, entity: simulation_session::Model) -> Result<simulation_session::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<simulation_session::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<simulation_session::Model>, DbErr>;
+        async fn update(&self, entity: simulation_session::Model) -> Result<simulation_session::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<simulation_session::Model, DbErr>;
+    }
+    #[async_trait]
+    impl SimulationSessionContextTrait for SimulationSessionContext {
+        async fn get_all_by_project_id_and_user_id(&self, project_id: i32, user_id: i32) -> Result<Vec<simulation_session::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub SimulationStepContext {}
+    #[async_trait]
+    impl EntityContextTrait<simulation_step::Model> for SimulationStepContext {
+        async fn create(&self, entity: simulation_step::Model) -> Result<simulation_step::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<simulation_step::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<simulation_step::Model>, DbErr>;
+        async fn update(&self, entity: simulation_step::Model) -> Result<simulation_step::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<simulation_step::Model, DbErr>;
+    }
+    #[async_trait]
+    impl SimulationStepContextTrait for SimulationStepContext {
+        async fn get_all_by_simulation_session_id(&self, simulation_session_id: i32) -> Result<Vec<simulation_step::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub TeamAccessContext {}
+    #[async_trait]
+    impl EntityContextTrait<team_access::Model> for TeamAccessContext {
+        async fn create(&self, entity: team_access::Model) -> Result<team_access::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<team_access::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<team_access::Model>, DbErr>;
+        async fn update(&self, entity: team_access::Model) -> Result<team_access::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<team_access::Model, DbErr>;
+    }
+    #[async_trait]
+    impl TeamAccessContextTrait for TeamAccessContext {
+        async fn get_team_access_by_project_id(&self, project_id: i32) -> Result<Vec<TeamAccessInfo>, DbErr>;
+        async fn get_team_access_by_role(&self, role: String) -> Result<Vec<team_access::Model>, DbErr>;
+    }
+}
+
+mock! {
+    pub TeamContext {}
+    #[async_trait]
+    impl EntityContextTrait<team::Model> for TeamContext {
+        async fn create(&self, entity: team::Model) -> Result<team::Model, DbErr>;
+        async fn get_by_id(&self, entity_id: i32) -> Result<Option<team::Model>, DbErr>;
+        async fn get_all(&self) -> Result<Vec<team::Model>, DbErr>;
+        async fn update(&self, entity: team::Model) -> Result<team::Model, DbErr>;
+        async fn delete(&self, entity_id: i32) -> Result<team::Model, DbErr>;
+    }
+    #[async_trait]
+    impl TeamContextTrait for TeamContext {
+        async fn get_all_by_uid(&self, uid: i32) -> Result<Vec<team::Model>, DbErr>;
+        async fn get_members(&self, team_id: i32) -> Result<Vec<team_member::Model>, DbErr>;
+        async fn add_members(&self, team_id: i32, user_ids: Vec<i32>) -> Result<(), DbErr>;
+        async fn remove_member(&self, team_id: i32, user_id: i32) -> Result<team_member::Model, DbErr>;
+    }
+}
+
 mock! {
     pub UserContext {}
     #[async_trait]
@@ -178,10 +499,44 @@ mock! {
     pub ReveaalService{}
     #[async_trait]
     impl ReveaalServiceTrait for ReveaalService {
+        fn health(&self) -> Vec<(String, ReveaalHealth)>;
         async fn get_user_token(&self,request: Request<()>) -> Result<Response<UserTokenResponse>, Status>;
         async fn send_query(&self,request: Request<QueryRequest>) -> Result<Response<QueryResponse>, Status>;
-        async fn start_simulation(&self, request: Request<SimulationStartRequest>) -> Result<Response<SimulationStepResponse>, Status>;
-        async fn take_simulation_step(&self, request: Request<SimulationStepRequest>) -> Result<Response<SimulationStepResponse>, Status>;
+        async fn start_simulation(&self, simulation_session_id: Option<i32>, request: Request<SimulationStartRequest>) -> Result<Response<SimulationStepResponse>, Status>;
+        async fn take_simulation_step(&self, simulation_session_id: Option<i32>, request: Request<SimulationStepRequest>) -> Result<Response<SimulationStepResponse>, Status>;
+        async fn end_simulation(&self, simulation_session_id: i32);
+    }
+}
+
+mock! {
+    pub ProjectEditService {}
+    impl ProjectEditServiceTrait for ProjectEditService {
+        fn publish(&self, event: ProjectEditEvent);
+        fn subscribe(&self, project_id: i32) -> ProjectEditStream;
+    }
+}
+
+mock! {
+    pub QueryEventService {}
+    impl QueryEventServiceTrait for QueryEventService {
+        fn publish(&self, event: QueryEvent);
+        fn subscribe(&self, project_id: i32) -> QueryEventStream;
+    }
+}
+
+mock! {
+    pub QueryJobService {}
+    impl QueryJobServiceTrait for QueryJobService {
+        fn enqueue(&self, job_id: i32) -> Result<(), Status>;
+    }
+}
+
+mock! {
+    pub QueryRunService {}
+    impl QueryRunServiceTrait for QueryRunService {
+        fn start(&self, query_id: i32, job_id: Option<i32>) -> QueryRun;
+        fn cancel(&self, query_id: i32) -> bool;
+        fn cancel_job(&self, job_id: i32) -> bool;
     }
 }
 
@@ -0,0 +1,757 @@
+use crate::api::auth::{Token, TokenType};
+use crate::api::server::protobuf::{
+    AcceptInvitationRequest, CreateInvitationRequest, ListInvitationsRequest,
+    RevokeInvitationRequest,
+};
+use crate::contexts::context_traits::EffectiveAccess;
+use crate::controllers::controller_impls::InvitationController;
+use crate::controllers::controller_traits::InvitationControllerTrait;
+use crate::entities::{access, invitation, role};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
+use chrono::{Duration, NaiveDateTime, Utc};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+use std::env;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+fn controller(mock_contexts: MockContexts) -> InvitationController {
+    InvitationController::new(disguise_context_mocks(mock_contexts))
+}
+
+fn with_uid<T>(message: T, uid: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str(uid).unwrap());
+    request
+}
+
+fn in_a_day() -> NaiveDateTime {
+    (Utc::now() + Duration::days(1)).naive_utc()
+}
+
+/// An invitation to the project with id 1 as a reader, created by the user with id 1.
+fn invitation_model() -> invitation::Model {
+    invitation::Model {
+        id: 1,
+        project_id: 1,
+        role: "Reader".to_owned(),
+        user_id: Some(1),
+        max_uses: 1,
+        uses: 0,
+        revoked: false,
+        expires_at: in_a_day(),
+        created_at: Utc::now().naive_utc(),
+    }
+}
+
+fn invitation_token(id: i32) -> String {
+    env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+    Token::with_expiration(
+        TokenType::InvitationToken,
+        &id.to_string(),
+        Utc::now() + Duration::days(1),
+    )
+    .unwrap()
+    .to_string()
+}
+
+fn expect_access(mock_contexts: &mut MockContexts, uid: i32, role: &'static str) {
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(uid), predicate::eq(1))
+        .returning(move |user_id, project_id| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: role.to_owned(),
+                    project_id,
+                    user_id,
+                }
+                .into(),
+            ))
+        });
+}
+
+fn expect_no_access(mock_contexts: &mut MockContexts, uid: i32) {
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(uid), predicate::eq(1))
+        .returning(|_, _| Ok(None));
+}
+
+fn expect_invitation(mock_contexts: &mut MockContexts, invitation: invitation::Model) {
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(invitation.id))
+        .returning(move |_| Ok(Some(invitation.clone())));
+}
+
+#[tokio::test]
+async fn create_invitation_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, 1, "Editor");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_create()
+        .withf(|invitation| {
+            invitation.project_id == 1
+                && invitation.role == "Reader"
+                && invitation.user_id == Some(1)
+                && invitation.max_uses == 1
+                && !invitation.revoked
+        })
+        .returning(|invitation| {
+            Ok(invitation::Model {
+                id: 1,
+                ..invitation
+            })
+        });
+
+    env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+
+    let res = controller(mock_contexts)
+        .create_invitation(with_uid(
+            CreateInvitationRequest {
+                project_id: 1,
+                role: "Reader".to_owned(),
+                max_uses: None,
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let info = res.invitation.unwrap();
+    let claims = Token::from_str(TokenType::InvitationToken, &res.token)
+        .validate()
+        .unwrap()
+        .claims;
+
+    assert_eq!(info.id, 1);
+    assert_eq!(info.max_uses, 1);
+    assert_eq!(claims.sub, "1");
+}
+
+#[tokio::test]
+async fn create_invitation_with_uses_and_expiry_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, 1, "Editor");
+
+    let expected_expiry = Utc::now() + Duration::hours(1);
+    mock_contexts
+        .invitation_context_mock
+        .expect_create()
+        .withf(move |invitation| {
+            invitation.max_uses == 5
+                && (invitation.expires_at.and_utc() - expected_expiry)
+                    .num_seconds()
+                    .abs()
+                    < 60
+        })
+        .returning(|invitation| {
+            Ok(invitation::Model {
+                id: 1,
+                ..invitation
+            })
+        });
+
+    env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+
+    let res = controller(mock_contexts)
+        .create_invitation(with_uid(
+            CreateInvitationRequest {
+                project_id: 1,
+                role: "Reader".to_owned(),
+                max_uses: Some(5),
+                expires_in_secs: Some(60 * 60),
+            },
+            "1",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn create_invitation_without_manage_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, 1, "Commenter");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_invitation(with_uid(
+            CreateInvitationRequest {
+                project_id: 1,
+                role: "Reader".to_owned(),
+                max_uses: None,
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_invitation_unknown_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, 1, "Editor");
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Owner".to_owned()))
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_invitation(with_uid(
+            CreateInvitationRequest {
+                project_id: 1,
+                role: "Owner".to_owned(),
+                max_uses: None,
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_invitation_role_beyond_granter_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    // A custom role that may manage access, but may not run queries
+    expect_access(&mut mock_contexts, 1, "Manager");
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Manager".to_owned()))
+        .returning(|name| {
+            Ok(Some(role::Model {
+                name,
+                permissions: json!(["ReadProject", "ManageAccess"]),
+            }))
+        });
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_invitation(with_uid(
+            CreateInvitationRequest {
+                project_id: 1,
+                role: "Reader".to_owned(),
+                max_uses: None,
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_invitation_zero_uses_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_invitation(with_uid(
+            CreateInvitationRequest {
+                project_id: 1,
+                role: "Reader".to_owned(),
+                max_uses: Some(0),
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_invitation_expiry_out_of_range_returns_err() {
+    for expires_in_secs in [0, 31 * 24 * 60 * 60] {
+        let mut mock_contexts = get_mock_contexts();
+
+        mock_contexts
+            .invitation_context_mock
+            .expect_create()
+            .never();
+
+        let res = controller(mock_contexts)
+            .create_invitation(with_uid(
+                CreateInvitationRequest {
+                    project_id: 1,
+                    role: "Reader".to_owned(),
+                    max_uses: None,
+                    expires_in_secs: Some(expires_in_secs),
+                },
+                "1",
+            ))
+            .await
+            .unwrap_err();
+
+        assert_eq!(res.code(), Code::InvalidArgument);
+    }
+}
+
+#[tokio::test]
+async fn list_invitations_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, 1, "Editor");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                invitation_model(),
+                invitation::Model {
+                    id: 2,
+                    revoked: true,
+                    ..invitation_model()
+                },
+            ])
+        });
+
+    let res = controller(mock_contexts)
+        .list_invitations(with_uid(ListInvitationsRequest { project_id: 1 }, "1"))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.invitations.len(), 2);
+    assert!(res.invitations[1].revoked);
+}
+
+#[tokio::test]
+async fn list_invitations_without_manage_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, 1, "Reader");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_all_by_project_id()
+        .never();
+
+    let res = controller(mock_contexts)
+        .list_invitations(with_uid(ListInvitationsRequest { project_id: 1 }, "1"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn revoke_invitation_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+    expect_access(&mut mock_contexts, 1, "Editor");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_update()
+        .withf(|invitation| invitation.id == 1 && invitation.revoked)
+        .times(1)
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .revoke_invitation(with_uid(RevokeInvitationRequest { id: 1 }, "1"))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn revoke_non_existing_invitation_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .revoke_invitation(with_uid(RevokeInvitationRequest { id: 1 }, "1"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn revoke_invitation_without_manage_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+    expect_access(&mut mock_contexts, 2, "Reader");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_update()
+        .never();
+
+    let res = controller(mock_contexts)
+        .revoke_invitation(with_uid(RevokeInvitationRequest { id: 1 }, "2"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn accept_invitation_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+    expect_no_access(&mut mock_contexts, 2);
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_redeem()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(true));
+
+    mock_contexts
+        .access_context_mock
+        .expect_create()
+        .with(predicate::eq(access::Model {
+            id: Default::default(),
+            role: "Reader".to_owned(),
+            project_id: 1,
+            user_id: 2,
+        }))
+        .times(1)
+        .returning(|access| Ok(access::Model { id: 2, ..access }));
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: invitation_token(1),
+            },
+            "2",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.project_id, 1);
+    assert_eq!(res.role, "Reader");
+}
+
+#[tokio::test]
+async fn accept_invitation_with_team_access_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(2), predicate::eq(1))
+        .returning(|_, _| {
+            Ok(Some(EffectiveAccess {
+                direct: None,
+                team_roles: vec!["Reader".to_owned()],
+            }))
+        });
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_redeem()
+        .returning(|_| Ok(true));
+
+    mock_contexts
+        .access_context_mock
+        .expect_create()
+        .times(1)
+        .returning(|access| Ok(access::Model { id: 2, ..access }));
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: invitation_token(1),
+            },
+            "2",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn accept_invitation_invalid_token_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_by_id()
+        .never();
+    mock_contexts.access_context_mock.expect_create().never();
+
+    env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: "invalid_token".to_owned(),
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn accept_invitation_access_token_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_by_id()
+        .never();
+
+    env::set_var("INVITATION_TOKEN_HS512_SECRET", "invitation_secret");
+    env::set_var("ACCESS_TOKEN_HS512_SECRET", "access_secret");
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: Token::access("1").unwrap().to_string(),
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn accept_non_existing_invitation_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: invitation_token(1),
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn accept_unusable_invitation_returns_err() {
+    let unusable_invitations = [
+        invitation::Model {
+            revoked: true,
+            ..invitation_model()
+        },
+        invitation::Model {
+            expires_at: (Utc::now() - Duration::minutes(1)).naive_utc(),
+            ..invitation_model()
+        },
+        invitation::Model {
+            uses: 1,
+            ..invitation_model()
+        },
+    ];
+
+    for invitation in unusable_invitations {
+        let mut mock_contexts = get_mock_contexts();
+
+        expect_invitation(&mut mock_contexts, invitation);
+
+        mock_contexts
+            .invitation_context_mock
+            .expect_redeem()
+            .never();
+        mock_contexts.access_context_mock.expect_create().never();
+
+        let res = controller(mock_contexts)
+            .accept_invitation(with_uid(
+                AcceptInvitationRequest {
+                    token: invitation_token(1),
+                },
+                "2",
+            ))
+            .await
+            .unwrap_err();
+
+        assert_eq!(res.code(), Code::FailedPrecondition);
+    }
+}
+
+#[tokio::test]
+async fn accept_invitation_with_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+    expect_access(&mut mock_contexts, 2, "Reader");
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_redeem()
+        .never();
+    mock_contexts.access_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: invitation_token(1),
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::AlreadyExists);
+}
+
+#[tokio::test]
+async fn accept_invitation_used_up_concurrently_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+    expect_no_access(&mut mock_contexts, 2);
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_redeem()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(false));
+
+    mock_contexts
+        .access_context_mock
+        .expect_create()
+        .times(1)
+        .returning(|access| Ok(access::Model { id: 2, ..access }));
+    mock_contexts
+        .access_context_mock
+        .expect_delete()
+        .with(predicate::eq(2))
+        .times(1)
+        .returning(|id| {
+            Ok(access::Model {
+                id,
+                role: "Reader".to_owned(),
+                project_id: 1,
+                user_id: 2,
+            })
+        });
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: invitation_token(1),
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn accept_invitation_redeem_error_removes_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_invitation(&mut mock_contexts, invitation_model());
+    expect_no_access(&mut mock_contexts, 2);
+
+    mock_contexts
+        .access_context_mock
+        .expect_create()
+        .times(1)
+        .returning(|access| Ok(access::Model { id: 2, ..access }));
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_redeem()
+        .with(predicate::eq(1))
+        .returning(|_| Err(DbErr::Custom("connection lost".to_owned())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_delete()
+        .with(predicate::eq(2))
+        .times(1)
+        .returning(|id| {
+            Ok(access::Model {
+                id,
+                role: "Reader".to_owned(),
+                project_id: 1,
+                user_id: 2,
+            })
+        });
+
+    let res = controller(mock_contexts)
+        .accept_invitation(with_uid(
+            AcceptInvitationRequest {
+                token: invitation_token(1),
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::Internal);
+}
@@ -0,0 +1,385 @@
+use crate::api::auth::TokenType;
+use crate::api::server::protobuf::{
+    AcquireLockRequest, ForceReleaseRequest, HeartbeatRequest, ReleaseLockRequest,
+};
+use crate::controllers::controller_impls::LockController;
+use crate::controllers::controller_traits::LockControllerTrait;
+use crate::entities::{access, in_use, project, session, user};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
+use chrono::{Duration, Utc};
+use mockall::predicate;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+const LEASE_SECS: u32 = 600;
+
+fn controller(mock_contexts: MockContexts) -> LockController {
+    LockController::new(disguise_context_mocks(mock_contexts), LEASE_SECS)
+}
+
+fn with_token<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+    request
+}
+
+fn expect_access(mock_contexts: &mut MockContexts, role: &str) {
+    let role = role.to_owned();
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: role.clone(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+}
+
+fn expect_session(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_owned()),
+        )
+        .returning(|_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_owned(),
+                access_token: "access_token".to_owned(),
+                updated_at: Default::default(),
+                user_id: 1,
+            }))
+        });
+}
+
+/// Expects the lock on project 1 to be looked up, held by session `session_id` of user `session_id`
+/// for `lease_secs` more seconds.
+fn expect_lock_info(mock_contexts: &mut MockContexts, session_id: i32, lease_secs: i64) {
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                project_id: 1,
+                session_id,
+                latest_activity: Default::default(),
+                expires_at: Utc::now().naive_utc() + Duration::seconds(lease_secs),
+            }))
+        });
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(session_id))
+        .returning(move |_| {
+            Ok(Some(session::Model {
+                id: session_id,
+                refresh_token: "refresh_token".to_owned(),
+                access_token: "access_token".to_owned(),
+                updated_at: Default::default(),
+                user_id: session_id,
+            }))
+        });
+
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(session_id))
+        .returning(move |_| {
+            Ok(Some(user::Model {
+                id: session_id,
+                email: format!("user{}@example.com", session_id),
+                username: format!("user{}", session_id),
+                password: "password".to_owned(),
+            }))
+        });
+}
+
+#[tokio::test]
+async fn acquire_lock_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_session(&mut mock_contexts);
+    expect_lock_info(&mut mock_contexts, 1, LEASE_SECS.into());
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_acquire()
+        .withf(|project_id, session_id, expires_at| {
+            *project_id == 1
+                && *session_id == 1
+                && *expires_at > Utc::now().naive_utc() + Duration::seconds(LEASE_SECS as i64 - 10)
+        })
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    let res = controller(mock_contexts)
+        .acquire_lock(with_token(AcquireLockRequest {
+            project_id: 1,
+            lease_secs: None,
+        }))
+        .await
+        .unwrap();
+
+    let lock_info = res.get_ref();
+    assert!(lock_info.locked);
+    assert_eq!(lock_info.user_id, Some(1));
+    assert_eq!(lock_info.username, Some("user1".to_owned()));
+}
+
+#[tokio::test]
+async fn acquire_lock_held_by_other_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_session(&mut mock_contexts);
+    expect_lock_info(&mut mock_contexts, 2, 60);
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_acquire()
+        .returning(|_, _, _| Ok(false));
+
+    let res = controller(mock_contexts)
+        .acquire_lock(with_token(AcquireLockRequest {
+            project_id: 1,
+            lease_secs: None,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+    assert!(res.message().contains("user2"));
+}
+
+#[tokio::test]
+async fn acquire_lock_viewer_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+
+    mock_contexts.in_use_context_mock.expect_acquire().never();
+
+    let res = controller(mock_contexts)
+        .acquire_lock(with_token(AcquireLockRequest {
+            project_id: 1,
+            lease_secs: None,
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn acquire_lock_invalid_lease_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+
+    mock_contexts.in_use_context_mock.expect_acquire().never();
+
+    let lock_controller = controller(mock_contexts);
+
+    for lease_secs in [0, 24 * 60 * 60] {
+        let res = lock_controller
+            .acquire_lock(with_token(AcquireLockRequest {
+                project_id: 1,
+                lease_secs: Some(lease_secs),
+            }))
+            .await;
+
+        assert_eq!(res.unwrap_err().code(), Code::InvalidArgument);
+    }
+}
+
+#[tokio::test]
+async fn heartbeat_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_session(&mut mock_contexts);
+    expect_lock_info(&mut mock_contexts, 1, 30);
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_renew()
+        .withf(|project_id, session_id, expires_at| {
+            *project_id == 1
+                && *session_id == 1
+                && *expires_at > Utc::now().naive_utc() + Duration::seconds(20)
+                && *expires_at <= Utc::now().naive_utc() + Duration::seconds(30)
+        })
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    let res = controller(mock_contexts)
+        .heartbeat(with_token(HeartbeatRequest {
+            project_id: 1,
+            lease_secs: Some(30),
+        }))
+        .await;
+
+    assert!(res.unwrap().get_ref().locked);
+}
+
+#[tokio::test]
+async fn heartbeat_not_holder_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Editor");
+    expect_session(&mut mock_contexts);
+    expect_lock_info(&mut mock_contexts, 2, 60);
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_renew()
+        .returning(|_, _, _| Ok(false));
+
+    let res = controller(mock_contexts)
+        .heartbeat(with_token(HeartbeatRequest {
+            project_id: 1,
+            lease_secs: None,
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn heartbeat_revoked_access_releases_lock() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts, "Reader");
+    expect_session(&mut mock_contexts);
+
+    mock_contexts.in_use_context_mock.expect_renew().never();
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_release()
+        .with(predicate::eq(1), predicate::eq(Some(1)))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    let res = controller(mock_contexts)
+        .heartbeat(with_token(HeartbeatRequest {
+            project_id: 1,
+            lease_secs: None,
+        }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn release_lock_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_session(&mut mock_contexts);
+    expect_lock_info(&mut mock_contexts, 1, 0);
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_release()
+        .with(predicate::eq(1), predicate::eq(Some(1)))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    let res = controller(mock_contexts)
+        .release_lock(with_token(ReleaseLockRequest { project_id: 1 }))
+        .await
+        .unwrap();
+
+    assert!(!res.get_ref().locked);
+    assert_eq!(res.get_ref().user_id, None);
+}
+
+#[tokio::test]
+async fn release_lock_not_holder_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_session(&mut mock_contexts);
+    expect_lock_info(&mut mock_contexts, 2, 60);
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_release()
+        .returning(|_, _| Ok(false));
+
+    let res = controller(mock_contexts)
+        .release_lock(with_token(ReleaseLockRequest { project_id: 1 }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+}
+
+fn expect_project(mock_contexts: &mut MockContexts, owner_id: i32) {
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: "project".to_owned(),
+                owner_id,
+                settings: None,
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+}
+
+#[tokio::test]
+async fn force_release_owner_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts, 1);
+    expect_lock_info(&mut mock_contexts, 2, 0);
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_release()
+        .with(predicate::eq(1), predicate::eq(None))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    let res = controller(mock_contexts)
+        .force_release(with_token(ForceReleaseRequest { project_id: 1 }))
+        .await;
+
+    assert!(!res.unwrap().get_ref().locked);
+}
+
+#[tokio::test]
+async fn force_release_not_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts, 2);
+
+    mock_contexts.in_use_context_mock.expect_release().never();
+
+    let res = controller(mock_contexts)
+        .force_release(with_token(ForceReleaseRequest { project_id: 1 }))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
@@ -1,18 +1,22 @@
 use crate::controllers::controller_impls::ProjectController;
 use crate::controllers::controller_traits::ProjectControllerTrait;
-use crate::tests::controllers::helpers::disguise_context_mocks;
+use crate::tests::controllers::helpers::{
+    disguise_context_mocks, disguise_service_mocks, get_mock_services,
+};
 use crate::{
     api::{
         auth::TokenType,
         server::protobuf::{
-            component::Rep, Component, ComponentsInfo, CreateProjectRequest, DeleteProjectRequest,
-            GetProjectRequest, ProjectInfo, UpdateProjectRequest,
+            component::Rep, query_event::Kind, Component, ComponentsInfo, CreateProjectRequest,
+            DeleteProjectRequest, GetProjectRequest, GetProjectVersionRequest,
+            ListProjectVersionsRequest, ProjectInfo, RestoreProjectVersionRequest,
+            UpdateProjectRequest,
         },
     },
-    entities::{access, in_use, project, query, session},
+    entities::{access, component, in_use, project, project_version, query, session},
     tests::controllers::helpers::get_mock_contexts,
 };
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use mockall::predicate;
 use sea_orm::DbErr;
 use std::str::FromStr;
@@ -32,8 +36,11 @@ async fn create_project_returns_ok() {
     let project = project::Model {
         id: Default::default(),
         name: Default::default(),
-        components_info: serde_json::to_value(components_info.clone()).unwrap(),
         owner_id: uid,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let access = access::Model {
@@ -55,6 +62,7 @@ async fn create_project_returns_ok() {
         project_id: project.id,
         session_id: session.id,
         latest_activity: Default::default(),
+        expires_at: Default::default(),
     };
 
     mock_contexts
@@ -98,8 +106,25 @@ async fn create_project_returns_ok() {
         metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
     );
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_all_by_project_id()
+        .returning(move |_| Ok(vec![]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.create_project(request).await;
 
@@ -115,8 +140,11 @@ async fn create_project_existing_name_returns_err() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: uid,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     mock_contexts
@@ -135,7 +163,8 @@ async fn create_project_existing_name_returns_err() {
         .insert("uid", uid.to_string().parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.create_project(request).await;
 
@@ -149,8 +178,11 @@ async fn get_project_user_has_access_returns_ok() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 4,
+        collaborative: false,
     };
 
     let access = access::Model {
@@ -164,6 +196,7 @@ async fn get_project_user_has_access_returns_ok() {
         project_id: Default::default(),
         session_id: 0,
         latest_activity: Utc::now().naive_utc(),
+        expires_at: Utc::now().naive_utc() + Duration::minutes(10),
     };
 
     let queries: Vec<query::Model> = vec![];
@@ -172,7 +205,7 @@ async fn get_project_user_has_access_returns_ok() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(0), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .project_context_mock
@@ -186,6 +219,11 @@ async fn get_project_user_has_access_returns_ok() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(in_use.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .query_context_mock
         .expect_get_all_by_project_id()
@@ -197,11 +235,12 @@ async fn get_project_user_has_access_returns_ok() {
     request.metadata_mut().insert("uid", "0".parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.get_project(request).await;
 
-    assert!(res.is_ok());
+    assert_eq!(res.unwrap().get_ref().project.as_ref().unwrap().revision, 4);
 }
 
 #[tokio::test]
@@ -216,8 +255,11 @@ async fn delete_not_owner_returns_err() {
             Ok(Some(project::Model {
                 id: 1,
                 name: Default::default(),
-                components_info: Default::default(),
                 owner_id: 2,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -228,7 +270,8 @@ async fn delete_not_owner_returns_err() {
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.delete_project(request).await.unwrap_err();
 
@@ -252,7 +295,8 @@ async fn delete_invalid_project_returns_err() {
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.delete_project(request).await.unwrap_err();
 
@@ -271,8 +315,11 @@ async fn delete_project_returns_ok() {
             Ok(Some(project::Model {
                 id: 1,
                 name: Default::default(),
-                components_info: Default::default(),
                 owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -284,8 +331,11 @@ async fn delete_project_returns_ok() {
             Ok(project::Model {
                 id: 1,
                 name: Default::default(),
-                components_info: Default::default(),
                 owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             })
         });
 
@@ -296,7 +346,8 @@ async fn delete_project_returns_ok() {
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.delete_project(request).await;
 
@@ -310,14 +361,18 @@ async fn get_project_user_has_no_access_returns_err() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let in_use = in_use::Model {
         project_id: Default::default(),
         session_id: 0,
         latest_activity: Default::default(),
+        expires_at: Default::default(),
     };
 
     let queries: Vec<query::Model> = vec![];
@@ -340,6 +395,11 @@ async fn get_project_user_has_no_access_returns_err() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(in_use.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .query_context_mock
         .expect_get_all_by_project_id()
@@ -351,7 +411,8 @@ async fn get_project_user_has_no_access_returns_err() {
     request.metadata_mut().insert("uid", "0".parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.get_project(request).await.unwrap_err();
 
@@ -365,8 +426,11 @@ async fn get_project_is_in_use_is_true() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let access = access::Model {
@@ -380,6 +444,7 @@ async fn get_project_is_in_use_is_true() {
         project_id: Default::default(),
         session_id: 0,
         latest_activity: Utc::now().naive_utc(),
+        expires_at: Utc::now().naive_utc() + Duration::minutes(10),
     };
 
     let queries: Vec<query::Model> = vec![];
@@ -388,7 +453,7 @@ async fn get_project_is_in_use_is_true() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(0), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .project_context_mock
@@ -402,6 +467,11 @@ async fn get_project_is_in_use_is_true() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(in_use.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .query_context_mock
         .expect_get_all_by_project_id()
@@ -413,7 +483,8 @@ async fn get_project_is_in_use_is_true() {
     request.metadata_mut().insert("uid", "0".parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.get_project(request).await;
 
@@ -427,8 +498,11 @@ async fn get_project_is_in_use_is_false() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let access = access::Model {
@@ -442,12 +516,7 @@ async fn get_project_is_in_use_is_false() {
         project_id: 0,
         session_id: 0,
         latest_activity: Default::default(),
-    };
-
-    let updated_in_use = in_use::Model {
-        project_id: 0,
-        session_id: 1,
-        latest_activity: Default::default(),
+        expires_at: Default::default(),
     };
 
     let session = session::Model {
@@ -464,7 +533,7 @@ async fn get_project_is_in_use_is_false() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(0), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .project_context_mock
@@ -487,16 +556,18 @@ async fn get_project_is_in_use_is_false() {
         )
         .returning(move |_, _| Ok(Some(session.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .query_context_mock
         .expect_get_all_by_project_id()
         .with(predicate::eq(0))
         .returning(move |_| Ok(queries.clone()));
 
-    mock_contexts
-        .in_use_context_mock
-        .expect_update()
-        .returning(move |_| Ok(updated_in_use.clone()));
+    mock_contexts.in_use_context_mock.expect_update().never();
 
     let mut request = Request::new(GetProjectRequest { id: 0 });
 
@@ -506,7 +577,8 @@ async fn get_project_is_in_use_is_false() {
     request.metadata_mut().insert("uid", "0".parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.get_project(request).await;
 
@@ -520,8 +592,11 @@ async fn get_project_project_has_no_queries_queries_are_empty() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let access = access::Model {
@@ -535,6 +610,7 @@ async fn get_project_project_has_no_queries_queries_are_empty() {
         project_id: Default::default(),
         session_id: 0,
         latest_activity: Utc::now().naive_utc(),
+        expires_at: Utc::now().naive_utc() + Duration::minutes(10),
     };
 
     let queries: Vec<query::Model> = vec![];
@@ -543,7 +619,7 @@ async fn get_project_project_has_no_queries_queries_are_empty() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(0), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .project_context_mock
@@ -557,6 +633,11 @@ async fn get_project_project_has_no_queries_queries_are_empty() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(in_use.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .query_context_mock
         .expect_get_all_by_project_id()
@@ -568,7 +649,8 @@ async fn get_project_project_has_no_queries_queries_are_empty() {
     request.metadata_mut().insert("uid", "0".parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.get_project(request).await;
 
@@ -582,8 +664,11 @@ async fn get_project_query_has_no_result_query_is_empty() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let access = access::Model {
@@ -597,6 +682,7 @@ async fn get_project_query_has_no_result_query_is_empty() {
         project_id: Default::default(),
         session_id: 0,
         latest_activity: Utc::now().naive_utc(),
+        expires_at: Utc::now().naive_utc() + Duration::minutes(10),
     };
 
     let query = query::Model {
@@ -605,6 +691,8 @@ async fn get_project_query_has_no_result_query_is_empty() {
         string: "query".to_owned(),
         result: None,
         outdated: false,
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let queries: Vec<query::Model> = vec![query];
@@ -613,7 +701,7 @@ async fn get_project_query_has_no_result_query_is_empty() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(0), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .project_context_mock
@@ -627,6 +715,11 @@ async fn get_project_query_has_no_result_query_is_empty() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(in_use.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .query_context_mock
         .expect_get_all_by_project_id()
@@ -638,7 +731,8 @@ async fn get_project_query_has_no_result_query_is_empty() {
     request.metadata_mut().insert("uid", "0".parse().unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.get_project(request).await;
 
@@ -669,7 +763,8 @@ async fn list_projects_info_returns_ok() {
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic
         .list_projects_info(list_projects_info_request)
@@ -695,7 +790,8 @@ async fn list_projects_info_returns_err() {
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic
         .list_projects_info(list_projects_info_request)
@@ -717,6 +813,10 @@ async fn update_name_returns_ok() {
         name: Some(new_project_name.clone()),
         components_info: None,
         owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
     });
 
     update_project_request.metadata_mut().insert(
@@ -737,8 +837,11 @@ async fn update_name_returns_ok() {
             Ok(Some(project::Model {
                 id: project_id,
                 name: "old_name".to_owned(),
-                components_info: Default::default(),
                 owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -747,12 +850,15 @@ async fn update_name_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(project_id))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                user_id,
-                project_id,
-                role: "Editor".to_string(),
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -774,15 +880,10 @@ async fn update_name_returns_ok() {
 
     mock_contexts
         .project_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(project::Model {
-                id: project_id,
-                name: new_project_name.clone(),
-                components_info: Default::default(),
-                owner_id: user_id,
-            })
-        });
+        .expect_update_at_revision()
+        .withf(|project, revision| project.name == "new_name" && *revision == 0)
+        .times(1)
+        .returning(|_, _| Ok(true));
 
     mock_contexts
         .in_use_context_mock
@@ -792,22 +893,13 @@ async fn update_name_returns_ok() {
                 project_id,
                 session_id: 1,
                 latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
             }))
         });
 
-    mock_contexts
-        .in_use_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(in_use::Model {
-                project_id: 1,
-                session_id: 1,
-                latest_activity: Utc::now().naive_utc(),
-            })
-        });
-
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(update_project_request).await;
 
@@ -815,24 +907,22 @@ async fn update_name_returns_ok() {
 }
 
 #[tokio::test]
-async fn update_components_info_returns_ok() {
+async fn update_collaborative_without_lock_returns_ok() {
     let mut mock_contexts = get_mock_contexts();
 
     let user_id = 1;
     let project_id = 1;
-    let components_info_non_json = ComponentsInfo {
-        components: vec![Component {
-            rep: Some(Rep::Json("a".to_owned())),
-        }],
-        components_hash: 1234456,
-    };
-    let components_info = serde_json::to_value(components_info_non_json.clone()).unwrap();
+    let new_project_name = "new_name".to_string();
 
     let mut update_project_request = Request::new(UpdateProjectRequest {
         id: project_id,
-        name: None,
-        components_info: Some(components_info_non_json.clone()),
+        name: Some(new_project_name.clone()),
+        components_info: None,
         owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
     });
 
     update_project_request.metadata_mut().insert(
@@ -852,9 +942,12 @@ async fn update_components_info_returns_ok() {
         .returning(move |_| {
             Ok(Some(project::Model {
                 id: project_id,
-                name: Default::default(),
-                components_info: Default::default(),
+                name: "old_name".to_owned(),
                 owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: true,
             }))
         });
 
@@ -863,67 +956,29 @@ async fn update_components_info_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(project_id))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                user_id,
-                project_id,
-                role: "Editor".to_string(),
-            }))
-        });
-
-    mock_contexts
-        .session_context_mock
-        .expect_get_by_token()
-        .with(
-            predicate::eq(TokenType::AccessToken),
-            predicate::eq("access_token".to_string()),
-        )
-        .returning(move |_, _| {
-            Ok(Some(session::Model {
-                id: 1,
-                refresh_token: "refresh_token".to_string(),
-                access_token: "access_token".to_string(),
-                updated_at: Default::default(),
-                user_id,
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
         .project_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(project::Model {
-                id: project_id,
-                name: Default::default(),
-                components_info: components_info.clone(),
-                owner_id: user_id,
-            })
-        });
-
-    mock_contexts
-        .in_use_context_mock
-        .expect_get_by_id()
-        .returning(move |_| {
-            Ok(Some(in_use::Model {
-                project_id,
-                session_id: 1,
-                latest_activity: Utc::now().naive_utc(),
-            }))
-        });
+        .expect_update_at_revision()
+        .withf(|project, revision| project.collaborative && *revision == 0)
+        .times(1)
+        .returning(|_, _| Ok(true));
 
-    mock_contexts
-        .in_use_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(in_use::Model {
-                project_id: 1,
-                session_id: 1,
-                latest_activity: Utc::now().naive_utc(),
-            })
-        });
+    mock_contexts.in_use_context_mock.expect_get_by_id().never();
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(update_project_request).await;
 
@@ -931,18 +986,21 @@ async fn update_components_info_returns_ok() {
 }
 
 #[tokio::test]
-async fn update_owner_id_returns_ok() {
+async fn update_stale_revision_returns_err() {
     let mut mock_contexts = get_mock_contexts();
 
     let user_id = 1;
     let project_id = 1;
-    let new_owner_id = 2;
 
     let mut update_project_request = Request::new(UpdateProjectRequest {
         id: project_id,
-        name: None,
+        name: Some("new_name".to_string()),
         components_info: None,
-        owner_id: Some(new_owner_id),
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 2,
+        collaborative: None,
     });
 
     update_project_request.metadata_mut().insert(
@@ -962,9 +1020,12 @@ async fn update_owner_id_returns_ok() {
         .returning(move |_| {
             Ok(Some(project::Model {
                 id: project_id,
-                name: Default::default(),
-                components_info: Default::default(),
+                name: "old_name".to_owned(),
                 owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 3,
+                collaborative: false,
             }))
         });
 
@@ -973,12 +1034,15 @@ async fn update_owner_id_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(project_id))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                user_id,
-                project_id,
-                role: "Editor".to_string(),
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -998,18 +1062,6 @@ async fn update_owner_id_returns_ok() {
             }))
         });
 
-    mock_contexts
-        .project_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(project::Model {
-                id: project_id,
-                name: Default::default(),
-                components_info: Default::default(),
-                owner_id: new_owner_id,
-            })
-        });
-
     mock_contexts
         .in_use_context_mock
         .expect_get_by_id()
@@ -1018,49 +1070,51 @@ async fn update_owner_id_returns_ok() {
                 project_id,
                 session_id: 1,
                 latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
             }))
         });
 
     mock_contexts
-        .in_use_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(in_use::Model {
-                project_id: 1,
-                session_id: 1,
-                latest_activity: Utc::now().naive_utc(),
-            })
-        });
+        .project_context_mock
+        .expect_update_at_revision()
+        .withf(|_, revision| *revision == 2)
+        .times(1)
+        .returning(|_, _| Ok(false));
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(update_project_request).await;
 
-    assert!(res.is_ok());
+    let status = res.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert_eq!(status.metadata().get("revision").unwrap(), "3");
 }
 
 #[tokio::test]
-async fn update_returns_ok() {
+async fn update_components_info_returns_ok() {
     let mut mock_contexts = get_mock_contexts();
 
     let user_id = 1;
     let project_id = 1;
-    let new_project_name = "new_name".to_string();
-    let new_components_info_non_json = ComponentsInfo {
+    let components_info_non_json = ComponentsInfo {
         components: vec![Component {
-            rep: Some(Rep::Json("a".to_owned())),
+            rep: Some(Rep::Json("{\"name\":\"A\"}".to_owned())),
         }],
         components_hash: 1234456,
     };
-    let new_components_info = serde_json::to_value(new_components_info_non_json.clone()).unwrap();
-    let new_owner_id = 2;
+    let component = serde_json::to_value(components_info_non_json.components[0].clone()).unwrap();
 
     let mut update_project_request = Request::new(UpdateProjectRequest {
         id: project_id,
-        name: Some(new_project_name.clone()),
-        components_info: Some(new_components_info_non_json.clone()),
-        owner_id: Some(new_owner_id),
+        name: None,
+        components_info: Some(components_info_non_json.clone()),
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
     });
 
     update_project_request.metadata_mut().insert(
@@ -1080,9 +1134,12 @@ async fn update_returns_ok() {
         .returning(move |_| {
             Ok(Some(project::Model {
                 id: project_id,
-                name: "old_name".to_owned(),
-                components_info: serde_json::to_value("{\"old_components\":1}").unwrap(),
+                name: Default::default(),
                 owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -1091,12 +1148,15 @@ async fn update_returns_ok() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(project_id))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                user_id,
-                project_id,
-                role: "Editor".to_string(),
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -1118,15 +1178,10 @@ async fn update_returns_ok() {
 
     mock_contexts
         .project_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(project::Model {
-                id: project_id,
-                name: new_project_name.clone(),
-                components_info: new_components_info.clone(),
-                owner_id: new_owner_id,
-            })
-        });
+        .expect_update_at_revision()
+        .withf(|_, revision| *revision == 0)
+        .times(1)
+        .returning(|_, _| Ok(true));
 
     mock_contexts
         .in_use_context_mock
@@ -1136,22 +1191,47 @@ async fn update_returns_ok() {
                 project_id,
                 session_id: 1,
                 latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
             }))
         });
 
     mock_contexts
-        .in_use_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(in_use::Model {
-                project_id: 1,
-                session_id: 1,
-                latest_activity: Utc::now().naive_utc(),
-            })
-        });
+        .component_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(vec![]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_create()
+        .withf(move |new_component| {
+            new_component.project_id == project_id
+                && new_component.name == "A"
+                && new_component.component == component
+        })
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(components_info_non_json.clone()));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .returning(move |_| Ok(vec![]));
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(update_project_request).await;
 
@@ -1159,33 +1239,82 @@ async fn update_returns_ok() {
 }
 
 #[tokio::test]
-async fn update_owner_not_owner_returns_err() {
+async fn update_components_info_without_names_keeps_stored_names() {
     let mut mock_contexts = get_mock_contexts();
 
+    let user_id = 1;
+    let project_id = 1;
+    let components_info_non_json = ComponentsInfo {
+        components: vec![
+            Component {
+                rep: Some(Rep::Xml("<template></template>".to_owned())),
+            },
+            Component {
+                rep: Some(Rep::Json("{\"locations\":[]}".to_owned())),
+            },
+        ],
+        components_hash: 1234456,
+    };
+    let stored_component = component::Model {
+        id: 1,
+        project_id,
+        name: "Component 1".to_owned(),
+        component: serde_json::to_value(components_info_non_json.components[0].clone()).unwrap(),
+        updated_at: Default::default(),
+    };
+    let component = serde_json::to_value(components_info_non_json.components[1].clone()).unwrap();
+
+    let mut update_project_request = Request::new(UpdateProjectRequest {
+        id: project_id,
+        name: None,
+        components_info: Some(components_info_non_json.clone()),
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    update_project_request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+
+    update_project_request.metadata_mut().insert(
+        "uid",
+        metadata::MetadataValue::from_str(user_id.to_string().as_str()).unwrap(),
+    );
+
     mock_contexts
         .project_context_mock
         .expect_get_by_id()
-        .with(predicate::eq(1))
+        .with(predicate::eq(project_id))
         .returning(move |_| {
             Ok(Some(project::Model {
-                id: 1,
+                id: project_id,
                 name: Default::default(),
-                components_info: Default::default(),
-                owner_id: 2,
+                owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
     mock_contexts
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
-        .with(predicate::eq(1), predicate::eq(1))
+        .with(predicate::eq(1), predicate::eq(project_id))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                user_id: 1,
-                project_id: 1,
-                role: "Editor".to_owned(),
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -1201,85 +1330,135 @@ async fn update_owner_not_owner_returns_err() {
                 refresh_token: "refresh_token".to_string(),
                 access_token: "access_token".to_string(),
                 updated_at: Default::default(),
-                user_id: 1,
+                user_id,
             }))
         });
 
+    mock_contexts
+        .project_context_mock
+        .expect_update_at_revision()
+        .withf(|_, revision| *revision == 0)
+        .times(1)
+        .returning(|_, _| Ok(true));
+
     mock_contexts
         .in_use_context_mock
         .expect_get_by_id()
-        .with(predicate::eq(1))
         .returning(move |_| {
             Ok(Some(in_use::Model {
+                project_id,
                 session_id: 1,
-                latest_activity: Default::default(),
-                project_id: 1,
+                latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
             }))
         });
 
     mock_contexts
-        .in_use_context_mock
-        .expect_update()
-        .returning(move |_| {
-            Ok(in_use::Model {
-                session_id: 1,
-                latest_activity: Default::default(),
-                project_id: 1,
-            })
-        });
+        .component_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(vec![stored_component.clone()]));
 
-    let mut request = Request::new(UpdateProjectRequest {
-        id: 1,
-        name: None,
-        components_info: None,
-        owner_id: Some(1),
-    });
+    // The stored component is unchanged, so it keeps its name and is not written
+    mock_contexts.component_context_mock.expect_update().never();
+    mock_contexts.component_context_mock.expect_delete().never();
 
-    request
-        .metadata_mut()
-        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    mock_contexts
+        .component_context_mock
+        .expect_create()
+        .withf(move |new_component| {
+            new_component.project_id == project_id
+                && new_component.name == "Component 2"
+                && new_component.component == component
+        })
+        .times(1)
+        .returning(Ok);
 
-    request.metadata_mut().insert(
-        "authorization",
-        metadata::MetadataValue::from_str("access_token").unwrap(),
-    );
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(components_info_non_json.clone()));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .returning(move |_| Ok(vec![]));
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
-    let res = project_logic.update_project(request).await.unwrap_err();
+    let res = project_logic.update_project(update_project_request).await;
 
-    assert_eq!(res.code(), Code::PermissionDenied);
+    assert!(res.is_ok());
 }
 
 #[tokio::test]
-async fn update_no_in_use_returns_err() {
+async fn update_owner_id_returns_ok() {
     let mut mock_contexts = get_mock_contexts();
 
+    let user_id = 1;
+    let project_id = 1;
+    let new_owner_id = 2;
+
+    let mut update_project_request = Request::new(UpdateProjectRequest {
+        id: project_id,
+        name: None,
+        components_info: None,
+        owner_id: Some(new_owner_id),
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    update_project_request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+
+    update_project_request.metadata_mut().insert(
+        "uid",
+        metadata::MetadataValue::from_str(user_id.to_string().as_str()).unwrap(),
+    );
+
     mock_contexts
         .project_context_mock
         .expect_get_by_id()
-        .with(predicate::eq(1))
+        .with(predicate::eq(project_id))
         .returning(move |_| {
             Ok(Some(project::Model {
-                id: 1,
+                id: project_id,
                 name: Default::default(),
-                components_info: Default::default(),
-                owner_id: 1,
+                owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
     mock_contexts
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
-        .with(predicate::eq(1), predicate::eq(1))
+        .with(predicate::eq(1), predicate::eq(project_id))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
-                user_id: 1,
-                project_id: 1,
-                role: "Editor".to_owned(),
-            }))
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
         });
 
     mock_contexts
@@ -1295,90 +1474,195 @@ async fn update_no_in_use_returns_err() {
                 refresh_token: "refresh_token".to_string(),
                 access_token: "access_token".to_string(),
                 updated_at: Default::default(),
-                user_id: 1,
+                user_id,
             }))
         });
 
+    mock_contexts
+        .project_context_mock
+        .expect_update_at_revision()
+        .withf(|_, revision| *revision == 0)
+        .times(1)
+        .returning(|_, _| Ok(true));
+
     mock_contexts
         .in_use_context_mock
         .expect_get_by_id()
-        .with(predicate::eq(1))
         .returning(move |_| {
             Ok(Some(in_use::Model {
-                session_id: 2,
+                project_id,
+                session_id: 1,
                 latest_activity: Utc::now().naive_utc(),
-                project_id: 1,
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
             }))
         });
 
-    let mut request = Request::new(UpdateProjectRequest {
-        id: 1,
-        name: None,
-        components_info: None,
-        owner_id: None,
-    });
-
-    request
-        .metadata_mut()
-        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
-
-    request.metadata_mut().insert(
-        "authorization",
-        metadata::MetadataValue::from_str("access_token").unwrap(),
-    );
-
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
-    let res = project_logic.update_project(request).await.unwrap_err();
+    let res = project_logic.update_project(update_project_request).await;
 
-    assert_eq!(res.code(), Code::FailedPrecondition);
+    assert!(res.is_ok());
 }
 
 #[tokio::test]
-async fn update_no_access_returns_err() {
+async fn update_returns_ok() {
     let mut mock_contexts = get_mock_contexts();
 
+    let user_id = 1;
+    let project_id = 1;
+    let new_project_name = "new_name".to_string();
+    let new_components_info_non_json = ComponentsInfo {
+        components: vec![Component {
+            rep: Some(Rep::Json("{\"name\":\"A\"}".to_owned())),
+        }],
+        components_hash: 1234456,
+    };
+    let new_component =
+        serde_json::to_value(new_components_info_non_json.components[0].clone()).unwrap();
+    let new_owner_id = 2;
+
+    let mut update_project_request = Request::new(UpdateProjectRequest {
+        id: project_id,
+        name: Some(new_project_name.clone()),
+        components_info: Some(new_components_info_non_json.clone()),
+        owner_id: Some(new_owner_id),
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    update_project_request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+
+    update_project_request.metadata_mut().insert(
+        "uid",
+        metadata::MetadataValue::from_str(user_id.to_string().as_str()).unwrap(),
+    );
+
     mock_contexts
         .project_context_mock
         .expect_get_by_id()
-        .with(predicate::eq(1))
+        .with(predicate::eq(project_id))
         .returning(move |_| {
             Ok(Some(project::Model {
-                id: 1,
-                name: Default::default(),
-                components_info: Default::default(),
-                owner_id: 1,
+                id: project_id,
+                name: "old_name".to_owned(),
+                owner_id: user_id,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
     mock_contexts
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
-        .with(predicate::eq(1), predicate::eq(1))
-        .returning(move |_, _| Ok(None));
+        .with(predicate::eq(1), predicate::eq(project_id))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id,
+                    project_id,
+                    role: "Editor".to_string(),
+                }
+                .into(),
+            ))
+        });
 
-    let mut request = Request::new(UpdateProjectRequest {
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_string()),
+        )
+        .returning(move |_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_string(),
+                access_token: "access_token".to_string(),
+                updated_at: Default::default(),
+                user_id,
+            }))
+        });
+
+    mock_contexts
+        .project_context_mock
+        .expect_update_at_revision()
+        .withf(|_, revision| *revision == 0)
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                project_id,
+                session_id: 1,
+                latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+            }))
+        });
+
+    let old_component = component::Model {
         id: 1,
-        name: None,
-        components_info: None,
-        owner_id: None,
-    });
+        project_id,
+        name: "A".to_owned(),
+        component: "{\"old_component\":1}".parse().unwrap(),
+        updated_at: Default::default(),
+    };
 
-    request
-        .metadata_mut()
-        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    mock_contexts
+        .component_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(vec![old_component.clone()]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_update()
+        .withf(move |updated_component| {
+            updated_component.id == 1 && updated_component.component == new_component
+        })
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(new_components_info_non_json.clone()));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .returning(move |_| Ok(vec![]));
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
-    let res = project_logic.update_project(request).await.unwrap_err();
+    let res = project_logic.update_project(update_project_request).await;
 
-    assert_eq!(res.code(), Code::PermissionDenied);
+    assert!(res.is_ok());
 }
 
 #[tokio::test]
-async fn update_incorrect_role_returns_err() {
+async fn update_owner_not_owner_returns_err() {
     let mut mock_contexts = get_mock_contexts();
 
     mock_contexts
@@ -1389,8 +1673,11 @@ async fn update_incorrect_role_returns_err() {
             Ok(Some(project::Model {
                 id: 1,
                 name: Default::default(),
-                components_info: Default::default(),
-                owner_id: 1,
+                owner_id: 2,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -1399,11 +1686,44 @@ async fn update_incorrect_role_returns_err() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id: 1,
+                    project_id: 1,
+                    role: "Editor".to_owned(),
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_string()),
+        )
+        .returning(move |_, _| {
+            Ok(Some(session::Model {
                 id: 1,
+                refresh_token: "refresh_token".to_string(),
+                access_token: "access_token".to_string(),
+                updated_at: Default::default(),
                 user_id: 1,
+            }))
+        });
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                session_id: 1,
+                latest_activity: Default::default(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
                 project_id: 1,
-                role: "Viewer".to_owned(),
             }))
         });
 
@@ -1411,15 +1731,25 @@ async fn update_incorrect_role_returns_err() {
         id: 1,
         name: None,
         components_info: None,
-        owner_id: None,
+        owner_id: Some(1),
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
     });
 
     request
         .metadata_mut()
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("access_token").unwrap(),
+    );
+
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(request).await.unwrap_err();
 
@@ -1427,7 +1757,7 @@ async fn update_incorrect_role_returns_err() {
 }
 
 #[tokio::test]
-async fn update_no_session_returns_err() {
+async fn update_no_in_use_returns_err() {
     let mut mock_contexts = get_mock_contexts();
 
     mock_contexts
@@ -1438,8 +1768,11 @@ async fn update_no_session_returns_err() {
             Ok(Some(project::Model {
                 id: 1,
                 name: Default::default(),
-                components_info: Default::default(),
                 owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
             }))
         });
 
@@ -1448,14 +1781,317 @@ async fn update_no_session_returns_err() {
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
         .returning(move |_, _| {
-            Ok(Some(access::Model {
-                id: 1,
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id: 1,
+                    project_id: 1,
+                    role: "Editor".to_owned(),
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_string()),
+        )
+        .returning(move |_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_string(),
+                access_token: "access_token".to_string(),
+                updated_at: Default::default(),
+                user_id: 1,
+            }))
+        });
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                session_id: 2,
+                latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+                project_id: 1,
+            }))
+        });
+
+    let mut request = Request::new(UpdateProjectRequest {
+        id: 1,
+        name: None,
+        components_info: None,
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("access_token").unwrap(),
+    );
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.update_project(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn update_expired_lock_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: Default::default(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id: 1,
+                    project_id: 1,
+                    role: "Editor".to_owned(),
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_string()),
+        )
+        .returning(move |_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_string(),
+                access_token: "access_token".to_string(),
+                updated_at: Default::default(),
                 user_id: 1,
+            }))
+        });
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                session_id: 1,
+                latest_activity: Utc::now().naive_utc() - Duration::minutes(20),
+                expires_at: Utc::now().naive_utc() - Duration::minutes(10),
                 project_id: 1,
-                role: "Editor".to_owned(),
             }))
         });
 
+    let mut request = Request::new(UpdateProjectRequest {
+        id: 1,
+        name: None,
+        components_info: None,
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("access_token").unwrap(),
+    );
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.update_project(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn update_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: Default::default(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(None));
+
+    let mut request = Request::new(UpdateProjectRequest {
+        id: 1,
+        name: None,
+        components_info: None,
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.update_project(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn update_incorrect_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: Default::default(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id: 1,
+                    project_id: 1,
+                    role: "Reader".to_owned(),
+                }
+                .into(),
+            ))
+        });
+
+    let mut request = Request::new(UpdateProjectRequest {
+        id: 1,
+        name: None,
+        components_info: None,
+        owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.update_project(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn update_no_session_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: Default::default(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    user_id: 1,
+                    project_id: 1,
+                    role: "Editor".to_owned(),
+                }
+                .into(),
+            ))
+        });
+
     mock_contexts
         .session_context_mock
         .expect_get_by_token()
@@ -1470,6 +2106,10 @@ async fn update_no_session_returns_err() {
         name: None,
         components_info: None,
         owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
     });
 
     request
@@ -1482,7 +2122,8 @@ async fn update_no_session_returns_err() {
     );
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(request).await.unwrap_err();
 
@@ -1504,6 +2145,10 @@ async fn update_no_project_returns_err() {
         name: None,
         components_info: None,
         owner_id: None,
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+        collaborative: None,
     });
 
     request
@@ -1511,9 +2156,549 @@ async fn update_no_project_returns_err() {
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
     let contexts = disguise_context_mocks(mock_contexts);
-    let project_logic = ProjectController::new(contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
 
     let res = project_logic.update_project(request).await.unwrap_err();
 
     assert_eq!(res.code(), Code::NotFound);
 }
+
+#[tokio::test]
+async fn list_project_versions_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let project_versions = vec![
+        project_version::Model {
+            id: 2,
+            project_id: 1,
+            components_info: Default::default(),
+            user_id: Some(1),
+            created_at: Default::default(),
+        },
+        project_version::Model {
+            id: 1,
+            project_id: 1,
+            components_info: Default::default(),
+            user_id: None,
+            created_at: Default::default(),
+        },
+    ];
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Reader".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(project_versions.clone()));
+
+    let mut request = Request::new(ListProjectVersionsRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.list_project_versions(request).await.unwrap();
+
+    let ids: Vec<i32> = res
+        .get_ref()
+        .project_versions
+        .iter()
+        .map(|project_version| project_version.id)
+        .collect();
+
+    assert_eq!(ids, vec![2, 1]);
+}
+
+#[tokio::test]
+async fn list_project_versions_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(None));
+
+    let mut request = Request::new(ListProjectVersionsRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic
+        .list_project_versions(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn get_project_version_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let components_info = ComponentsInfo {
+        components: vec![Component {
+            rep: Some(Rep::Json("a".to_owned())),
+        }],
+        components_hash: 1234456,
+    };
+
+    let project_version = project_version::Model {
+        id: 1,
+        project_id: 1,
+        components_info: serde_json::to_value(components_info.clone()).unwrap(),
+        user_id: Some(1),
+        created_at: Default::default(),
+    };
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(project_version.clone())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Reader".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    let mut request = Request::new(GetProjectVersionRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.get_project_version(request).await.unwrap();
+
+    assert_eq!(res.get_ref().components_info, Some(components_info));
+}
+
+#[tokio::test]
+async fn get_project_version_not_found_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(None));
+
+    let mut request = Request::new(GetProjectVersionRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic
+        .get_project_version(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn restore_project_version_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let user_id = 1;
+    let project_id = 1;
+    let restored_components_info_non_json = ComponentsInfo {
+        components: vec![Component {
+            rep: Some(Rep::Json("{\"name\":\"A\"}".to_owned())),
+        }],
+        components_hash: 0,
+    };
+    let restored_components_info =
+        serde_json::to_value(restored_components_info_non_json.clone()).unwrap();
+    let restored_component =
+        serde_json::to_value(restored_components_info_non_json.components[0].clone()).unwrap();
+
+    let project_version = project_version::Model {
+        id: 1,
+        project_id,
+        components_info: restored_components_info.clone(),
+        user_id: Some(2),
+        created_at: Default::default(),
+    };
+
+    let project = project::Model {
+        id: project_id,
+        name: "project".to_string(),
+        owner_id: user_id,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    let component = component::Model {
+        id: 1,
+        project_id,
+        name: "A".to_owned(),
+        component: "{\"old_component\":1}".parse().unwrap(),
+        updated_at: Default::default(),
+    };
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(project_version.clone())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(user_id), predicate::eq(project_id))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id,
+                    user_id,
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .with(
+            predicate::eq(TokenType::AccessToken),
+            predicate::eq("access_token".to_string()),
+        )
+        .returning(move |_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_string(),
+                access_token: "access_token".to_string(),
+                updated_at: Default::default(),
+                user_id,
+            }))
+        });
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                project_id,
+                session_id: 1,
+                latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+            }))
+        });
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(vec![component.clone()]));
+
+    mock_contexts
+        .component_context_mock
+        .expect_update()
+        .withf(move |updated_component| {
+            updated_component.id == 1 && updated_component.component == restored_component
+        })
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(project_id))
+        .returning(move |_| Ok(restored_components_info_non_json.clone()));
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_create()
+        .withf(move |project_version| {
+            project_version.project_id == project_id
+                && project_version.user_id == Some(user_id)
+                && project_version.components_info == restored_components_info
+        })
+        .times(1)
+        .returning(Ok);
+
+    let mut request = Request::new(RestoreProjectVersionRequest { id: 1 });
+
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let query = query::Model {
+        id: 1,
+        string: "consistency: A".to_string(),
+        result: None,
+        outdated: false,
+        project_id,
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(project_id))
+        .times(2)
+        .returning(move |_| Ok(vec![query.clone()]));
+
+    // The query mentions the restored component, so it is marked as outdated
+    mock_contexts
+        .query_context_mock
+        .expect_mark_outdated()
+        .with(predicate::eq(vec![1]))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(project_id))
+        .times(1)
+        .returning(move |_| {
+            Ok(vec![query::Model {
+                id: 1,
+                string: "consistency: A".to_string(),
+                result: None,
+                outdated: true,
+                project_id,
+                settings: Default::default(),
+                timeout_secs: None,
+            }])
+        });
+
+    let mut mock_services = get_mock_services();
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .withf(move |event| {
+            event.query_id == 1
+                && event.project_id == project_id
+                && event.kind == i32::from(Kind::Outdated)
+        })
+        .times(1)
+        .returning(|_| ());
+
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .with(predicate::eq(project_id), predicate::eq(None))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic.restore_project_version(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn restore_project_version_incorrect_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project_version::Model {
+                id: 1,
+                project_id: 1,
+                components_info: Default::default(),
+                user_id: None,
+                created_at: Default::default(),
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Reader".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    let mut request = Request::new(RestoreProjectVersionRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic
+        .restore_project_version(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn restore_project_version_in_use_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_version_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project_version::Model {
+                id: 1,
+                project_id: 1,
+                components_info: Default::default(),
+                user_id: None,
+                created_at: Default::default(),
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Editor".to_string(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: "project".to_string(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .session_context_mock
+        .expect_get_by_token()
+        .returning(move |_, _| {
+            Ok(Some(session::Model {
+                id: 1,
+                refresh_token: "refresh_token".to_string(),
+                access_token: "access_token".to_string(),
+                updated_at: Default::default(),
+                user_id: 1,
+            }))
+        });
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .returning(move |_| {
+            Ok(Some(in_use::Model {
+                project_id: 1,
+                session_id: 2,
+                latest_activity: Utc::now().naive_utc(),
+                expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+            }))
+        });
+
+    let mut request = Request::new(RestoreProjectVersionRequest { id: 1 });
+
+    request.metadata_mut().insert(
+        "authorization",
+        metadata::MetadataValue::from_str("Bearer access_token").unwrap(),
+    );
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let project_logic = ProjectController::new(contexts, services);
+
+    let res = project_logic
+        .restore_project_version(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
@@ -1,17 +1,33 @@
+use crate::api::query_hash::hash_components_info;
+use crate::api::server::protobuf::counterexample::Kind as CounterexampleKind;
+use crate::api::server::protobuf::query_event::Kind;
+use crate::api::server::protobuf::query_request::Settings;
 use crate::api::server::protobuf::query_response::{self, Result};
+use crate::api::server::protobuf::send_queries_response::query_result::Outcome;
 use crate::api::server::protobuf::{
-    CreateQueryRequest, DeleteQueryRequest, QueryResponse, SendQueryRequest, UpdateQueryRequest,
+    CancelQueryJobRequest, CancelQueryRequest, ComponentsInfo, CreateQueryRequest,
+    DeleteQueryRequest, GetCounterexampleRequest, GetCounterexampleResponse, GetQueryJobRequest,
+    ListQueryJobsRequest, ListQueryResultsRequest, QueryEvent, QueryResponse, SendQueriesRequest,
+    SendQueryRequest, StateAction, SubmitQueryJobRequest, SubscribeQueryEventsRequest,
+    UpdateQueryRequest,
 };
 use crate::controllers::controller_impls::QueryController;
 use crate::controllers::controller_traits::QueryControllerTrait;
-use crate::entities::{access, project, query};
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use crate::entities::{
+    access, counterexample, project, query, query_cache, query_job, query_result,
+};
+use crate::services::service_traits::QueryRun;
 use crate::tests::controllers::helpers::{
     disguise_context_mocks, disguise_service_mocks, get_mock_contexts, get_mock_services,
+    MockContexts, MockServices,
 };
+use futures::StreamExt;
 use mockall::predicate;
 use sea_orm::DbErr;
 use std::str::FromStr;
-use tonic::{metadata, Code, Request, Response};
+use tokio::sync::watch;
+use tonic::{metadata, Code, Request, Response, Status};
 
 #[tokio::test]
 async fn create_invalid_query_returns_err() {
@@ -24,6 +40,8 @@ async fn create_invalid_query_returns_err() {
         result: Default::default(),
         project_id: 1,
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let access = access::Model {
@@ -37,7 +55,7 @@ async fn create_invalid_query_returns_err() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -48,6 +66,8 @@ async fn create_invalid_query_returns_err() {
     let mut request = Request::new(CreateQueryRequest {
         string: "".to_string(),
         project_id: 1,
+        settings: None,
+        timeout_secs: None,
     });
 
     request
@@ -56,7 +76,7 @@ async fn create_invalid_query_returns_err() {
 
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.create_query(request).await.unwrap_err();
 
@@ -74,6 +94,8 @@ async fn create_query_returns_ok() {
         result: Default::default(),
         project_id: 1,
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let access = access::Model {
@@ -87,7 +109,7 @@ async fn create_query_returns_ok() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -98,15 +120,24 @@ async fn create_query_returns_ok() {
     let mut request = Request::new(CreateQueryRequest {
         string: "".to_string(),
         project_id: 1,
+        settings: None,
+        timeout_secs: None,
     });
 
     request
         .metadata_mut()
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .with(predicate::eq(1), predicate::eq(None))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.create_query(request).await;
 
@@ -124,6 +155,8 @@ async fn update_invalid_query_returns_err() {
         result: None,
         project_id: Default::default(),
         outdated: true,
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let query = query::Model {
@@ -148,26 +181,36 @@ async fn update_invalid_query_returns_err() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
-        .expect_update()
-        .with(predicate::eq(query.clone()))
-        .returning(move |_| Err(DbErr::RecordNotUpdated));
+        .expect_update_at_revision()
+        .with(predicate::eq(query.clone()), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Err(DbErr::RecordNotUpdated));
 
     let mut request = Request::new(UpdateQueryRequest {
         id: 1,
         string: "updated".to_string(),
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
     });
 
     request
         .metadata_mut()
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
+    // The revision only moves along with the update
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .never();
+
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.update_query(request).await.unwrap_err();
 
@@ -185,6 +228,8 @@ async fn update_query_returns_ok() {
         result: None,
         project_id: Default::default(),
         outdated: true,
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let query = query::Model {
@@ -203,7 +248,7 @@ async fn update_query_returns_ok() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -213,13 +258,167 @@ async fn update_query_returns_ok() {
 
     mock_contexts
         .query_context_mock
-        .expect_update()
-        .with(predicate::eq(query.clone()))
-        .returning(move |_| Ok(query.clone()));
+        .expect_update_at_revision()
+        .with(predicate::eq(query.clone()), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    let mut request = Request::new(UpdateQueryRequest {
+        id: 1,
+        string: "updated".to_string(),
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.update_query(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn update_query_stale_revision_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let old_query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: None,
+        project_id: Default::default(),
+        outdated: true,
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(old_query.clone())));
+
+    let mut request = Request::new(UpdateQueryRequest {
+        id: 1,
+        string: "updated".to_string(),
+        settings: None,
+        timeout_secs: None,
+        revision: 2,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_at_revision()
+        .with(predicate::always(), predicate::eq(2))
+        .times(1)
+        .returning(|_, _| Ok(false));
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 0,
+                name: "project".to_string(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 3,
+                collaborative: false,
+            }))
+        });
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.update_query(request).await;
+
+    let status = res.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert_eq!(status.metadata().get("revision").unwrap(), "3");
+}
+
+#[tokio::test]
+async fn update_query_with_settings_stores_settings() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let old_query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: None,
+        project_id: Default::default(),
+        outdated: true,
+        settings: None,
+        timeout_secs: None,
+    };
+
+    let query = query::Model {
+        string: "updated".to_string(),
+        settings: Some(serde_json::json!({ "disable_clock_reduction": true })),
+        ..old_query.clone()
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(old_query.clone())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_at_revision()
+        .with(predicate::eq(query.clone()), predicate::eq(0))
+        .times(1)
+        .returning(|_, _| Ok(true));
 
     let mut request = Request::new(UpdateQueryRequest {
         id: 1,
         string: "updated".to_string(),
+        settings: Some(Settings {
+            disable_clock_reduction: true,
+        }),
+        timeout_secs: None,
+        revision: 0,
     });
 
     request
@@ -228,7 +427,7 @@ async fn update_query_returns_ok() {
 
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.update_query(request).await;
 
@@ -253,13 +452,15 @@ async fn delete_invalid_query_returns_err() {
         result: Default::default(),
         project_id: Default::default(),
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     mock_contexts
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -281,7 +482,7 @@ async fn delete_invalid_query_returns_err() {
 
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.delete_query(request).await.unwrap_err();
 
@@ -299,6 +500,8 @@ async fn delete_query_returns_ok() {
         result: Default::default(),
         project_id: Default::default(),
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let query_clone = query.clone();
@@ -320,7 +523,7 @@ async fn delete_query_returns_ok() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -334,9 +537,16 @@ async fn delete_query_returns_ok() {
         .metadata_mut()
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
+    mock_contexts
+        .project_context_mock
+        .expect_advance_revision()
+        .with(predicate::eq(0), predicate::eq(None))
+        .times(1)
+        .returning(|_, _| Ok(true));
+
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.delete_query(request).await;
 
@@ -354,11 +564,13 @@ async fn create_query_invalid_role_returns_err() {
         result: Default::default(),
         project_id: Default::default(),
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let access = access::Model {
         id: Default::default(),
-        role: "Viewer".to_string(),
+        role: "Reader".to_string(),
         project_id: Default::default(),
         user_id: 1,
     };
@@ -367,7 +579,7 @@ async fn create_query_invalid_role_returns_err() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(1))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -378,6 +590,8 @@ async fn create_query_invalid_role_returns_err() {
     let mut request = Request::new(CreateQueryRequest {
         string: "".to_string(),
         project_id: 1,
+        settings: None,
+        timeout_secs: None,
     });
 
     request
@@ -386,7 +600,7 @@ async fn create_query_invalid_role_returns_err() {
 
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.create_query(request).await.unwrap_err();
 
@@ -404,13 +618,15 @@ async fn delete_query_invalid_role_returns_err() {
         result: Default::default(),
         project_id: Default::default(),
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let query_clone = query.clone();
 
     let access = access::Model {
         id: Default::default(),
-        role: "Viewer".to_string(),
+        role: "Reader".to_string(),
         project_id: Default::default(),
         user_id: 1,
     };
@@ -425,7 +641,7 @@ async fn delete_query_invalid_role_returns_err() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -441,7 +657,7 @@ async fn delete_query_invalid_role_returns_err() {
 
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.delete_query(request).await.unwrap_err();
 
@@ -459,16 +675,13 @@ async fn update_query_invalid_role_returns_err() {
         result: None,
         project_id: Default::default(),
         outdated: true,
-    };
-
-    let query = query::Model {
-        string: "updated".to_string(),
-        ..old_query.clone()
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let access = access::Model {
         id: Default::default(),
-        role: "Viewer".to_string(),
+        role: "Reader".to_string(),
         project_id: Default::default(),
         user_id: 1,
     };
@@ -477,7 +690,7 @@ async fn update_query_invalid_role_returns_err() {
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -487,13 +700,15 @@ async fn update_query_invalid_role_returns_err() {
 
     mock_contexts
         .query_context_mock
-        .expect_update()
-        .with(predicate::eq(query.clone()))
-        .returning(move |_| Ok(query.clone()));
+        .expect_update_at_revision()
+        .never();
 
     let mut request = Request::new(UpdateQueryRequest {
         id: 1,
         string: "updated".to_string(),
+        settings: None,
+        timeout_secs: None,
+        revision: 0,
     });
 
     request
@@ -502,7 +717,7 @@ async fn update_query_invalid_role_returns_err() {
 
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.update_query(request).await.unwrap_err();
 
@@ -520,6 +735,8 @@ async fn send_query_returns_ok() {
         result: Default::default(),
         project_id: Default::default(),
         outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
     };
 
     let access = access::Model {
@@ -532,8 +749,11 @@ async fn send_query_returns_ok() {
     let project = project::Model {
         id: Default::default(),
         name: "project".to_string(),
-        components_info: Default::default(),
         owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
     };
 
     let query_response = QueryResponse {
@@ -542,10 +762,7 @@ async fn send_query_returns_ok() {
         result: Some(Result::Success(query_response::Success {})),
     };
 
-    let updated_query = query::Model {
-        result: Some(serde_json::to_value(query_response.clone().result).unwrap()),
-        ..query.clone()
-    };
+    let query_result = serde_json::to_value(query_response.clone().result).unwrap();
 
     mock_contexts
         .project_context_mock
@@ -553,11 +770,17 @@ async fn send_query_returns_ok() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(project.clone())));
 
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Default::default()));
+
     mock_contexts
         .access_context_mock
         .expect_get_access_by_uid_and_project_id()
         .with(predicate::eq(1), predicate::eq(0))
-        .returning(move |_, _| Ok(Some(access.clone())));
+        .returning(move |_, _| Ok(Some(access.clone().into())));
 
     mock_contexts
         .query_context_mock
@@ -565,6 +788,11 @@ async fn send_query_returns_ok() {
         .with(predicate::eq(0))
         .returning(move |_| Ok(Some(query.clone())));
 
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
     mock_services
         .reveaal_service_mock
         .expect_send_query()
@@ -572,24 +800,2484 @@ async fn send_query_returns_ok() {
 
     mock_contexts
         .query_context_mock
-        .expect_update()
-        .with(predicate::eq(updated_query.clone()))
-        .returning(move |_| Ok(updated_query.clone()));
+        .expect_update_result()
+        .with(
+            predicate::eq(0),
+            predicate::eq(query_result),
+            predicate::eq(false),
+        )
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    let components_hash =
+        hash_components_info(&serde_json::to_value(ComponentsInfo::default()).unwrap());
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .withf(move |query_result| {
+            query_result.query_id == 0
+                && query_result.user_id == Some(1)
+                && query_result.components_hash == components_hash
+        })
+        .times(1)
+        .returning(Ok);
 
     let mut request = Request::new(SendQueryRequest {
         id: Default::default(),
         project_id: Default::default(),
+        settings: None,
     });
 
     request
         .metadata_mut()
         .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
 
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .withf(|event| event.kind == i32::from(Kind::Started))
+        .times(1)
+        .returning(|_| ());
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .withf(|event| event.kind == i32::from(Kind::Finished) && event.response.is_some())
+        .times(1)
+        .returning(|_| ());
+
     let contexts = disguise_context_mocks(mock_contexts);
     let services = disguise_service_mocks(mock_services);
-    let query_logic = QueryController::new(contexts, services);
+    let query_logic = QueryController::new(contexts, services, 4);
 
     let res = query_logic.send_query(request).await;
 
     assert!(res.is_ok());
 }
+
+#[tokio::test]
+async fn send_query_of_other_project_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: Default::default(),
+        name: "project".to_string(),
+        owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    // The query belongs to a project the user has no access to
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(query::Model {
+                id: 1,
+                string: "".to_string(),
+                result: None,
+                outdated: true,
+                project_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+            }))
+        });
+
+    let mut request = Request::new(SendQueryRequest {
+        id: 1,
+        project_id: Default::default(),
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn send_query_components_changed_during_run_marks_result_outdated() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: Default::default(),
+        string: "".to_string(),
+        result: Default::default(),
+        project_id: Default::default(),
+        outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: Default::default(),
+        name: "project".to_string(),
+        owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    // The components are edited after the query was sent to Reveaal
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(0))
+        .times(1)
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(0))
+        .returning(move |_| {
+            Ok(ComponentsInfo {
+                components: vec![],
+                components_hash: 1,
+            })
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| {
+            Ok(Response::new(QueryResponse {
+                query_id: Default::default(),
+                info: Default::default(),
+                result: Some(Result::Success(query_response::Success {})),
+            }))
+        });
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(|_, _, outdated| *outdated)
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: Default::default(),
+        project_id: Default::default(),
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_deleted_during_run_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: Default::default(),
+        string: "".to_string(),
+        result: Default::default(),
+        project_id: Default::default(),
+        outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: Default::default(),
+        name: "project".to_string(),
+        owner_id: 0,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| {
+            Ok(Response::new(QueryResponse {
+                query_id: Default::default(),
+                info: Default::default(),
+                result: Some(Result::Success(query_response::Success {})),
+            }))
+        });
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .returning(|_, _, _| Ok(false));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .never();
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: Default::default(),
+        project_id: Default::default(),
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn send_query_forwards_stored_query_settings() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: Default::default(),
+        string: "".to_string(),
+        result: Default::default(),
+        project_id: Default::default(),
+        outdated: Default::default(),
+        settings: Some(serde_json::json!({ "disable_clock_reduction": true })),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: Default::default(),
+        name: "project".to_string(),
+        owner_id: 0,
+        settings: Some(serde_json::json!({ "disable_clock_reduction": false })),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    let query_response = QueryResponse {
+        query_id: Default::default(),
+        info: Default::default(),
+        result: Some(Result::Success(query_response::Success {})),
+    };
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .withf(|request| {
+            request.get_ref().settings
+                == Some(Settings {
+                    disable_clock_reduction: true,
+                })
+        })
+        .times(1)
+        .returning(move |_| Ok(Response::new(query_response.clone())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: Default::default(),
+        project_id: Default::default(),
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_request_settings_override_stored_settings() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: Default::default(),
+        string: "".to_string(),
+        result: Default::default(),
+        project_id: Default::default(),
+        outdated: Default::default(),
+        settings: Some(serde_json::json!({ "disable_clock_reduction": false })),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: Default::default(),
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: Default::default(),
+        name: "project".to_string(),
+        owner_id: 0,
+        settings: Some(serde_json::json!({ "disable_clock_reduction": false })),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    let query_response = QueryResponse {
+        query_id: Default::default(),
+        info: Default::default(),
+        result: Some(Result::Success(query_response::Success {})),
+    };
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(0))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(0))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .withf(|request| {
+            request.get_ref().settings
+                == Some(Settings {
+                    disable_clock_reduction: true,
+                })
+        })
+        .times(1)
+        .returning(move |_| Ok(Response::new(query_response.clone())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: Default::default(),
+        project_id: Default::default(),
+        settings: Some(Settings {
+            disable_clock_reduction: true,
+        }),
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await;
+
+    assert!(res.is_ok());
+}
+
+/// Sets up a project with a single query that the given user has editor access to.
+fn expect_send_query_lookups(mock_contexts: &mut MockContexts) {
+    let query = query::Model {
+        id: 1,
+        string: "consistency: A".to_string(),
+        result: Default::default(),
+        project_id: 1,
+        outdated: true,
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: 1,
+        name: "project".to_string(),
+        owner_id: 1,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query.clone())));
+}
+
+#[tokio::test]
+async fn send_query_cached_result_skips_reveaal() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_send_query_lookups(&mut mock_contexts);
+
+    let result = serde_json::to_value(Result::Success(query_response::Success {})).unwrap();
+    let cache_entry = query_cache::Model {
+        id: 1,
+        hash: "hash".to_string(),
+        result: result.clone(),
+        created_at: Default::default(),
+    };
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(move |_| Ok(Some(cache_entry.clone())));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .times(0);
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .times(0);
+
+    let query_result = result.clone();
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(move |_, result, outdated| *result == query_result && !outdated)
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .withf(move |query_result| query_result.result == result)
+        .times(1)
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: 1,
+        project_id: 1,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await.unwrap().into_inner();
+
+    assert!(res.cached);
+    assert_eq!(
+        res.response.unwrap().result,
+        Some(Result::Success(query_response::Success {}))
+    );
+}
+
+#[tokio::test]
+async fn send_query_uncached_result_is_cached() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_send_query_lookups(&mut mock_contexts);
+
+    let query_response = QueryResponse {
+        query_id: 1,
+        info: Default::default(),
+        result: Some(Result::Success(query_response::Success {})),
+    };
+    let result = serde_json::to_value(query_response.clone().result).unwrap();
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .times(1)
+        .returning(move |_| Ok(Response::new(query_response.clone())));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .withf(move |cache_entry| cache_entry.result == result)
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: 1,
+        project_id: 1,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await.unwrap().into_inner();
+
+    assert!(!res.cached);
+}
+
+#[tokio::test]
+async fn send_query_failed_result_stores_counterexample() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_send_query_lookups(&mut mock_contexts);
+
+    let query_response = QueryResponse {
+        query_id: 1,
+        info: Default::default(),
+        result: Some(Result::DeterminismFailure(
+            query_response::DeterminismFailure {
+                system: "A".to_string(),
+                failure_state: Some(StateAction {
+                    state: None,
+                    action: "a".to_string(),
+                }),
+            },
+        )),
+    };
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| Ok(Response::new(query_response.clone())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .returning(|query_result| {
+            Ok(query_result::Model {
+                id: 3,
+                ..query_result
+            })
+        });
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_create()
+        .withf(|counterexample| {
+            counterexample.query_id == 1
+                && counterexample.query_result_id == 3
+                && counterexample.kind == "DETERMINISM"
+                && counterexample.actions == serde_json::json!(["a"])
+        })
+        .times(1)
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: 1,
+        project_id: 1,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_error_result_is_not_cached() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_send_query_lookups(&mut mock_contexts);
+
+    let query_response = QueryResponse {
+        query_id: 1,
+        info: Default::default(),
+        result: Some(Result::Error(query_response::Error {
+            error: "internal error".to_string(),
+        })),
+    };
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| Ok(Response::new(query_response.clone())));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .times(0);
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: 1,
+        project_id: 1,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await.unwrap().into_inner();
+
+    assert!(!res.cached);
+}
+
+#[tokio::test]
+async fn send_queries_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let queries = vec![
+        query::Model {
+            id: 1,
+            string: "consistency: A".to_string(),
+            result: Default::default(),
+            project_id: 1,
+            outdated: true,
+            settings: Default::default(),
+            timeout_secs: None,
+        },
+        query::Model {
+            id: 2,
+            string: "consistency: B".to_string(),
+            result: Default::default(),
+            project_id: 1,
+            outdated: true,
+            settings: Default::default(),
+            timeout_secs: None,
+        },
+    ];
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: 1,
+        name: "project".to_string(),
+        owner_id: 1,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    let query_response = QueryResponse {
+        query_id: 1,
+        info: Default::default(),
+        result: Some(Result::Success(query_response::Success {})),
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(queries.clone()));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |request| match request.get_ref().query_id {
+            1 => Ok(Response::new(query_response.clone())),
+            _ => Err(Status::internal("reveaal failed")),
+        });
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(|id, _, outdated| *id == 1 && !outdated)
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .withf(|query_result| query_result.query_id == 1)
+        .times(1)
+        .returning(Ok);
+
+    let mut request = Request::new(SendQueriesRequest {
+        project_id: 1,
+        only_outdated: false,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .times(4)
+        .returning(|_| ());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_queries(request).await.unwrap();
+    let results = &res.get_ref().results;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].query_id, 1);
+    assert!(matches!(results[0].outcome, Some(Outcome::Response(_))));
+    assert_eq!(results[1].query_id, 2);
+    assert_eq!(
+        results[1].outcome,
+        Some(Outcome::Error("reveaal failed".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn send_queries_only_outdated_skips_up_to_date_queries() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let queries = vec![
+        query::Model {
+            id: 1,
+            string: "consistency: A".to_string(),
+            result: Default::default(),
+            project_id: 1,
+            outdated: false,
+            settings: Default::default(),
+            timeout_secs: None,
+        },
+        query::Model {
+            id: 2,
+            string: "consistency: B".to_string(),
+            result: Default::default(),
+            project_id: 1,
+            outdated: true,
+            settings: Default::default(),
+            timeout_secs: None,
+        },
+    ];
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    let project = project::Model {
+        id: 1,
+        name: "project".to_string(),
+        owner_id: 1,
+        settings: Default::default(),
+        timeout_secs: None,
+        revision: 0,
+        collaborative: false,
+    };
+
+    let query_response = QueryResponse {
+        query_id: 2,
+        info: Default::default(),
+        result: Some(Result::Success(query_response::Success {})),
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(project.clone())));
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(queries.clone()));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(false).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .withf(|request| request.get_ref().query_id == 2)
+        .times(1)
+        .returning(move |_| Ok(Response::new(query_response.clone())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(|id, _, _| *id == 2)
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .withf(|query_result| query_result.query_id == 2)
+        .times(1)
+        .returning(Ok);
+
+    let mut request = Request::new(SendQueriesRequest {
+        project_id: 1,
+        only_outdated: true,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .times(2)
+        .returning(|_| ());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_queries(request).await.unwrap();
+
+    assert_eq!(res.get_ref().results.len(), 1);
+    assert_eq!(res.get_ref().results[0].query_id, 2);
+}
+
+#[tokio::test]
+async fn send_queries_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(None));
+
+    let mut request = Request::new(SendQueriesRequest {
+        project_id: 1,
+        only_outdated: false,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_queries(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn subscribe_query_events_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_services
+        .query_event_service_mock
+        .expect_subscribe()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Box::pin(futures::stream::empty()));
+
+    let mut request = Request::new(SubscribeQueryEventsRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.subscribe_query_events(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn subscribe_query_events_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(None));
+
+    let mut request = Request::new(SubscribeQueryEventsRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.subscribe_query_events(request).await;
+
+    assert_eq!(res.err().unwrap().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn subscribe_query_events_revoked_access_ends_stream() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    // Access is revoked after the first event was passed on
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .times(2)
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(None));
+
+    mock_services
+        .query_event_service_mock
+        .expect_subscribe()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| {
+            Box::pin(futures::stream::iter((1..=3).map(|query_id| {
+                Ok(QueryEvent {
+                    query_id,
+                    project_id: 1,
+                    ..Default::default()
+                })
+            })))
+        });
+
+    let mut request = Request::new(SubscribeQueryEventsRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let events: Vec<_> = query_logic
+        .subscribe_query_events(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].as_ref().unwrap().query_id, 1);
+    assert_eq!(
+        events[1].as_ref().unwrap_err().code(),
+        Code::PermissionDenied
+    );
+}
+
+fn create_query_job(status: QueryJobStatus) -> query_job::Model {
+    query_job::Model {
+        id: 1,
+        query_id: 1,
+        project_id: 1,
+        user_id: 1,
+        status,
+        result: None,
+        error: None,
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn submit_query_job_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: Default::default(),
+        project_id: 1,
+        outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_create()
+        .withf(|query_job| {
+            query_job.query_id == 1
+                && query_job.user_id == 1
+                && query_job.status == QueryJobStatus::Queued
+        })
+        .returning(move |_| Ok(create_query_job(QueryJobStatus::Queued)));
+
+    mock_services
+        .query_job_service_mock
+        .expect_enqueue()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let mut request = Request::new(SubmitQueryJobRequest {
+        query_id: 1,
+        project_id: 1,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.submit_query_job(request).await.unwrap();
+
+    assert_eq!(res.get_ref().id, 1);
+}
+
+#[tokio::test]
+async fn submit_query_job_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(None));
+
+    let mut request = Request::new(SubmitQueryJobRequest {
+        query_id: 1,
+        project_id: 1,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.submit_query_job(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn submit_query_job_query_in_other_project_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: Default::default(),
+        project_id: 2,
+        outdated: Default::default(),
+        settings: Default::default(),
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    let mut request = Request::new(SubmitQueryJobRequest {
+        query_id: 1,
+        project_id: 1,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.submit_query_job(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn get_query_job_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let query_job = query_job::Model {
+        result: Some("{\"Success\":{}}".parse().unwrap()),
+        ..create_query_job(QueryJobStatus::Done)
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query_job.clone())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    let mut request = Request::new(GetQueryJobRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.get_query_job(request).await.unwrap();
+    let query_job = res.get_ref().query_job.clone().unwrap();
+
+    assert_eq!(
+        query_job.status,
+        i32::from(crate::api::server::protobuf::QueryJobStatus::Done)
+    );
+    assert_eq!(query_job.result, Some("{\"Success\":{}}".to_string()));
+}
+
+#[tokio::test]
+async fn get_query_job_not_found_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(None));
+
+    let mut request = Request::new(GetQueryJobRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.get_query_job(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn list_query_jobs_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(vec![
+                create_query_job(QueryJobStatus::Running),
+                create_query_job(QueryJobStatus::Done),
+            ])
+        });
+
+    let mut request = Request::new(ListQueryJobsRequest { project_id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.list_query_jobs(request).await.unwrap();
+
+    assert_eq!(res.get_ref().query_jobs.len(), 2);
+}
+
+#[tokio::test]
+async fn cancel_query_job_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Queued))));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(create_query_job(QueryJobStatus::Cancelled)),
+            predicate::eq(QueryJobStatus::Queued),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    let mut request = Request::new(CancelQueryJobRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.cancel_query_job(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn cancel_query_job_running_cancels_only_job_run() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Running))));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(create_query_job(QueryJobStatus::Cancelled)),
+            predicate::eq(QueryJobStatus::Queued),
+        )
+        .times(1)
+        .returning(|_, _| Ok(false));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(create_query_job(QueryJobStatus::Cancelled)),
+            predicate::eq(QueryJobStatus::Running),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    mock_services
+        .query_run_service_mock
+        .expect_cancel_job()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| true);
+
+    // The runs of the query made by others are left alone
+    mock_services
+        .query_run_service_mock
+        .expect_cancel()
+        .times(0);
+
+    let mut request = Request::new(CancelQueryJobRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.cancel_query_job(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn cancel_query_job_finished_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Editor".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Running))));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    // The job finished after it was read
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .times(2)
+        .returning(|_, _| Ok(false));
+
+    let mut request = Request::new(CancelQueryJobRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.cancel_query_job(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn cancel_query_job_of_other_user_invalid_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 2,
+    };
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Running))));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(2), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+
+    let mut request = Request::new(CancelQueryJobRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("2").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.cancel_query_job(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+/// Sets up a query with id 1 in project 1 that the user has the given role on.
+fn expect_query_with_role(mock_contexts: &mut MockContexts, role: &str) {
+    let access = access::Model {
+        id: Default::default(),
+        role: role.to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(query::Model {
+                id: 1,
+                string: "".to_string(),
+                result: None,
+                outdated: true,
+                project_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+            }))
+        });
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+}
+
+async fn cancel_query(
+    mock_contexts: MockContexts,
+    mock_services: MockServices,
+) -> std::result::Result<Response<()>, Status> {
+    let mut request = Request::new(CancelQueryRequest { id: 1 });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    query_logic.cancel_query(request).await
+}
+
+#[tokio::test]
+async fn cancel_query_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_query_with_role(&mut mock_contexts, "Editor");
+
+    mock_services
+        .query_run_service_mock
+        .expect_cancel()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| true);
+
+    let res = cancel_query(mock_contexts, mock_services).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn cancel_query_not_running_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_query_with_role(&mut mock_contexts, "Editor");
+
+    mock_services
+        .query_run_service_mock
+        .expect_cancel()
+        .returning(|_| false);
+
+    let res = cancel_query(mock_contexts, mock_services).await;
+
+    assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn cancel_query_invalid_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_query_with_role(&mut mock_contexts, "Reader");
+
+    mock_services
+        .query_run_service_mock
+        .expect_cancel()
+        .times(0);
+
+    let res = cancel_query(mock_contexts, mock_services).await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn cancel_query_non_existing_query_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    let res = cancel_query(mock_contexts, mock_services).await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn send_query_cancelled_run_records_cancellation() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_send_query_lookups(&mut mock_contexts);
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(|_| Ok(None));
+
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .returning(|_, _| QueryRun::new(watch::channel(true).1, || {}));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(|_| Err(Status::unavailable("should not be awaited")));
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(|_, result, outdated| *outdated && result.to_string().contains("cancelled"))
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .times(0);
+
+    let kind = i32::from(Kind::Cancelled);
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .withf(move |event| event.kind == kind)
+        .times(1)
+        .returning(|_| ());
+    mock_services
+        .query_event_service_mock
+        .expect_publish()
+        .returning(|_| ());
+
+    let mut request = Request::new(SendQueryRequest {
+        id: 1,
+        project_id: 1,
+        settings: None,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.send_query(request).await;
+
+    assert_eq!(res.unwrap_err().code(), Code::Cancelled);
+}
+
+fn create_query_result(id: i32) -> query_result::Model {
+    query_result::Model {
+        id,
+        query_id: 1,
+        user_id: Some(1),
+        result: serde_json::json!({ "Success": {} }),
+        settings: serde_json::json!({ "disable_clock_reduction": true }),
+        components_hash: "e3b0c442".to_string(),
+        created_at: Default::default(),
+    }
+}
+
+fn expect_query_and_access(mock_contexts: &mut MockContexts) {
+    let query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: None,
+        outdated: false,
+        project_id: 1,
+        settings: None,
+        timeout_secs: None,
+    };
+
+    let access = access::Model {
+        id: Default::default(),
+        role: "Reader".to_string(),
+        project_id: 1,
+        user_id: 1,
+    };
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| Ok(Some(access.clone().into())));
+}
+
+#[tokio::test]
+async fn list_query_results_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_get_page_by_query_id()
+        .with(predicate::eq(1), predicate::eq(1), predicate::eq(2))
+        .returning(|_, _, _| Ok((vec![create_query_result(2), create_query_result(1)], 5)));
+
+    let mut request = Request::new(ListQueryResultsRequest {
+        query_id: 1,
+        page: 1,
+        page_size: 2,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.list_query_results(request).await.unwrap();
+    let response = res.get_ref();
+
+    assert_eq!(response.total, 5);
+    assert_eq!(
+        response
+            .query_results
+            .iter()
+            .map(|query_result| query_result.id)
+            .collect::<Vec<i32>>(),
+        vec![2, 1]
+    );
+    assert_eq!(
+        response.query_results[0].settings,
+        Some(Settings {
+            disable_clock_reduction: true,
+        })
+    );
+}
+
+#[tokio::test]
+async fn list_query_results_no_page_size_uses_default() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_get_page_by_query_id()
+        .with(predicate::eq(1), predicate::eq(0), predicate::eq(20))
+        .times(1)
+        .returning(|_, _, _| Ok((vec![], 0)));
+
+    let mut request = Request::new(ListQueryResultsRequest {
+        query_id: 1,
+        page: 0,
+        page_size: 0,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.list_query_results(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn list_query_results_large_page_size_is_capped() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_get_page_by_query_id()
+        .with(predicate::eq(1), predicate::eq(0), predicate::eq(100))
+        .times(1)
+        .returning(|_, _, _| Ok((vec![], 0)));
+
+    let mut request = Request::new(ListQueryResultsRequest {
+        query_id: 1,
+        page: 0,
+        page_size: 10_000,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.list_query_results(request).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn list_query_results_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    let query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: None,
+        outdated: false,
+        project_id: 1,
+        settings: None,
+        timeout_secs: None,
+    };
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(|_, _| Ok(None));
+
+    let mut request = Request::new(ListQueryResultsRequest {
+        query_id: 1,
+        page: 0,
+        page_size: 0,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.list_query_results(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn list_query_results_non_existing_query_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(None));
+
+    let mut request = Request::new(ListQueryResultsRequest {
+        query_id: 1,
+        page: 0,
+        page_size: 0,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    let res = query_logic.list_query_results(request).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+fn create_counterexample(query_result_id: i32) -> counterexample::Model {
+    counterexample::Model {
+        id: query_result_id,
+        query_id: 1,
+        query_result_id,
+        kind: "REFINEMENT".to_string(),
+        system: Some("A <= B".to_string()),
+        reason: Some("CANNOT_MATCH".to_string()),
+        actions: serde_json::json!(["a"]),
+        state: None,
+        path: serde_json::json!([]),
+        created_at: Default::default(),
+    }
+}
+
+async fn get_counterexample(
+    mock_contexts: MockContexts,
+    query_result_id: Option<i32>,
+) -> std::result::Result<Response<GetCounterexampleResponse>, Status> {
+    let mut request = Request::new(GetCounterexampleRequest {
+        query_id: 1,
+        query_result_id,
+    });
+
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(get_mock_services());
+    let query_logic = QueryController::new(contexts, services, 4);
+
+    query_logic.get_counterexample(request).await
+}
+
+#[tokio::test]
+async fn get_counterexample_returns_latest() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_get_latest_by_query_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(create_counterexample(2))));
+
+    let res = get_counterexample(mock_contexts, None).await.unwrap();
+    let counterexample = res.into_inner().counterexample.unwrap();
+
+    assert_eq!(counterexample.query_result_id, 2);
+    assert_eq!(counterexample.kind(), CounterexampleKind::Refinement);
+    assert_eq!(counterexample.system, Some("A <= B".to_string()));
+    assert_eq!(counterexample.actions, vec!["a".to_string()]);
+}
+
+#[tokio::test]
+async fn get_counterexample_by_query_result_id_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_get_by_query_result_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(create_counterexample(1))));
+
+    let res = get_counterexample(mock_contexts, Some(1)).await.unwrap();
+
+    assert_eq!(res.into_inner().counterexample.unwrap().query_result_id, 1);
+}
+
+#[tokio::test]
+async fn get_counterexample_of_other_query_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_get_by_query_result_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(Some(counterexample::Model {
+                query_id: 2,
+                ..create_counterexample(1)
+            }))
+        });
+
+    let res = get_counterexample(mock_contexts, Some(1))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn get_counterexample_without_counterexample_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_query_and_access(&mut mock_contexts);
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_get_latest_by_query_id()
+        .returning(|_| Ok(None));
+
+    let res = get_counterexample(mock_contexts, None).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn get_counterexample_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    let query = query::Model {
+        id: 1,
+        string: "".to_string(),
+        result: None,
+        outdated: false,
+        project_id: 1,
+        settings: None,
+        timeout_secs: None,
+    };
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(query.clone())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let res = get_counterexample(mock_contexts, None).await.unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
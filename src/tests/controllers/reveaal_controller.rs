@@ -0,0 +1,436 @@
+use crate::api::server::protobuf::backend_health::Status as BackendStatus;
+use crate::api::server::protobuf::ecdar_backend_server::EcdarBackend;
+use crate::api::server::protobuf::{
+    QueryRequest, QueryResponse, SimulationInfo, SimulationStartRequest, SimulationStepResponse,
+};
+use crate::controllers::controller_impls::ReveaalController;
+use crate::controllers::controller_traits::ReveaalControllerTrait;
+use crate::entities::backend_audit;
+use crate::services::service_traits::ReveaalHealth;
+use crate::tests::controllers::helpers::{
+    disguise_context_mocks, disguise_service_mocks, get_mock_contexts, get_mock_services,
+};
+use mockall::predicate;
+use sea_orm::DbErr;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request, Response, Status};
+
+fn user_request<T>(message: T, uid: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str(uid).unwrap());
+    request
+}
+
+fn service_request<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("service", metadata::MetadataValue::from_static("true"));
+    request
+}
+
+#[tokio::test]
+async fn get_backend_health_serving_returns_serving() {
+    let mut mock_services = get_mock_services();
+
+    mock_services
+        .reveaal_service_mock
+        .expect_health()
+        .returning(|| vec![("http://reveaal:4000".to_string(), ReveaalHealth::Serving)]);
+
+    let contexts = disguise_context_mocks(get_mock_contexts());
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .get_backend_health(Request::new(()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.backends.len(), 1);
+    assert_eq!(res.backends[0].address, "http://reveaal:4000");
+    assert_eq!(res.backends[0].status(), BackendStatus::Serving);
+    assert_eq!(res.backends[0].error, None);
+}
+
+#[tokio::test]
+async fn get_backend_health_not_serving_returns_reason() {
+    let mut mock_services = get_mock_services();
+
+    mock_services
+        .reveaal_service_mock
+        .expect_health()
+        .returning(|| {
+            vec![(
+                "http://reveaal:4000".to_string(),
+                ReveaalHealth::NotServing("connection refused".to_string()),
+            )]
+        });
+
+    let contexts = disguise_context_mocks(get_mock_contexts());
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .get_backend_health(Request::new(()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.backends[0].status(), BackendStatus::NotServing);
+    assert_eq!(
+        res.backends[0].error,
+        Some("connection refused".to_string())
+    );
+}
+
+#[tokio::test]
+async fn get_backend_health_multiple_backends_returns_all() {
+    let mut mock_services = get_mock_services();
+
+    mock_services
+        .reveaal_service_mock
+        .expect_health()
+        .returning(|| {
+            vec![
+                ("http://reveaal1:4000".to_string(), ReveaalHealth::Unknown),
+                ("http://reveaal2:4000".to_string(), ReveaalHealth::Serving),
+            ]
+        });
+
+    let contexts = disguise_context_mocks(get_mock_contexts());
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .get_backend_health(Request::new(()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.backends.len(), 2);
+    assert_eq!(res.backends[0].status(), BackendStatus::Unknown);
+    assert_eq!(res.backends[1].address, "http://reveaal2:4000");
+    assert_eq!(res.backends[1].status(), BackendStatus::Serving);
+}
+
+#[tokio::test]
+async fn send_query_user_overrides_user_id() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .withf(|request| request.get_ref().user_id == 1)
+        .returning(|_| Ok(Response::new(QueryResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .send_query(user_request(
+            QueryRequest {
+                user_id: 2,
+                ..Default::default()
+            },
+            "1",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_service_keeps_user_id() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .with(predicate::function(|audit: &backend_audit::Model| {
+            audit.user_id.is_none()
+        }))
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .withf(|request| request.get_ref().user_id == 2)
+        .returning(|_| Ok(Response::new(QueryResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .send_query(service_request(QueryRequest {
+            user_id: 2,
+            ..Default::default()
+        }))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_no_caller_returns_err() {
+    let contexts = disguise_context_mocks(get_mock_contexts());
+    let services = disguise_service_mocks(get_mock_services());
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .send_query(Request::new(QueryRequest::default()))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::Internal);
+}
+
+#[tokio::test]
+async fn send_query_records_audit() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .with(predicate::eq(backend_audit::Model {
+            id: Default::default(),
+            user_id: Some(1),
+            method: "SendQuery".to_string(),
+            code: Code::Ok as i32,
+            created_at: Default::default(),
+        }))
+        .times(1)
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(|_| Ok(Response::new(QueryResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .send_query(user_request(QueryRequest::default(), "1"))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_reveaal_error_records_error_code() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .with(predicate::function(|audit: &backend_audit::Model| {
+            audit.code == Code::Unavailable as i32
+        }))
+        .times(1)
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(|_| Err(Status::unavailable("")));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .send_query(user_request(QueryRequest::default(), "1"))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::Unavailable);
+}
+
+#[tokio::test]
+async fn send_query_audit_fails_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .returning(|_| Err(DbErr::RecordNotInserted));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(|_| Ok(Response::new(QueryResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .send_query(user_request(QueryRequest::default(), "1"))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_over_limit_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .with(predicate::function(|audit: &backend_audit::Model| {
+            audit.code == Code::ResourceExhausted as i32
+        }))
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .never();
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 0);
+
+    let res = reveaal_logic
+        .send_query(user_request(QueryRequest::default(), "1"))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::ResourceExhausted);
+}
+
+#[tokio::test]
+async fn send_query_finished_requests_do_not_count_towards_limit() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .times(3)
+        .returning(|_| Ok(Response::new(QueryResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 1);
+
+    for _ in 0..3 {
+        let res = reveaal_logic
+            .send_query(user_request(QueryRequest::default(), "1"))
+            .await;
+
+        assert!(res.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn send_query_service_is_not_limited() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(|_| Ok(Response::new(QueryResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 0);
+
+    let res = reveaal_logic
+        .send_query(service_request(QueryRequest::default()))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn get_user_token_user_returns_uid() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_get_user_token()
+        .never();
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .get_user_token(user_request((), "7"))
+        .await
+        .unwrap();
+
+    assert_eq!(res.into_inner().user_id, 7);
+}
+
+#[tokio::test]
+async fn start_simulation_user_overrides_user_id() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .backend_audit_context_mock
+        .expect_create()
+        .with(predicate::function(|audit: &backend_audit::Model| {
+            audit.method == "StartSimulation"
+        }))
+        .returning(Ok);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .withf(|_, request| request.get_ref().simulation_info.as_ref().unwrap().user_id == 1)
+        .returning(|_, _| Ok(Response::new(SimulationStepResponse::default())));
+
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    let reveaal_logic = ReveaalController::new(contexts, services, 4);
+
+    let res = reveaal_logic
+        .start_simulation(user_request(
+            SimulationStartRequest {
+                simulation_info: Some(SimulationInfo {
+                    user_id: 2,
+                    ..Default::default()
+                }),
+            },
+            "1",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
@@ -0,0 +1,503 @@
+use crate::api::server::protobuf::{CreateRoleRequest, DeleteRoleRequest, UpdateRoleRequest};
+use crate::controllers::controller_impls::RoleController;
+use crate::controllers::controller_traits::RoleControllerTrait;
+use crate::entities::{access, invitation, role, team_access};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
+use mockall::predicate;
+use sea_orm::DbErr;
+use serde_json::json;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+/// The user with id 1 is an admin, everyone else is not.
+fn controller(mock_contexts: MockContexts) -> RoleController {
+    RoleController::new(disguise_context_mocks(mock_contexts), vec![1])
+}
+
+fn with_uid<T>(message: T, uid: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str(uid).unwrap());
+    request
+}
+
+fn verifier() -> role::Model {
+    role::Model {
+        name: "Verifier".to_owned(),
+        permissions: json!(["ReadProject", "RunQuery"]),
+    }
+}
+
+#[tokio::test]
+async fn list_roles_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_all()
+        .returning(|| {
+            Ok(vec![
+                role::Model {
+                    name: "Editor".to_owned(),
+                    permissions: json!([]),
+                },
+                verifier(),
+            ])
+        });
+
+    let res = controller(mock_contexts)
+        .list_roles(with_uid((), "2"))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.roles.len(), 2);
+    assert!(res.roles[0].built_in);
+    assert!(res.roles[0]
+        .permissions
+        .contains(&"ManageAccess".to_owned()));
+    assert!(!res.roles[1].built_in);
+    assert_eq!(res.roles[1].permissions, vec!["ReadProject", "RunQuery"]);
+}
+
+#[tokio::test]
+async fn list_roles_invalid_permissions_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_all()
+        .returning(|| {
+            Ok(vec![role::Model {
+                name: "Verifier".to_owned(),
+                permissions: json!(["Fly"]),
+            }])
+        });
+
+    let res = controller(mock_contexts)
+        .list_roles(with_uid((), "2"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::Internal);
+}
+
+#[tokio::test]
+async fn create_role_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Verifier".to_owned()))
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .role_context_mock
+        .expect_create()
+        .with(predicate::eq(verifier()))
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .create_role(with_uid(
+            CreateRoleRequest {
+                name: " Verifier ".to_owned(),
+                permissions: vec![
+                    "RunQuery".to_owned(),
+                    "ReadProject".to_owned(),
+                    "RunQuery".to_owned(),
+                ],
+            },
+            "1",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.name, "Verifier");
+    assert_eq!(res.permissions, vec!["ReadProject", "RunQuery"]);
+    assert!(!res.built_in);
+}
+
+#[tokio::test]
+async fn create_role_not_admin_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts.role_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .create_role(with_uid(
+            CreateRoleRequest {
+                name: "Verifier".to_owned(),
+                permissions: vec!["RunQuery".to_owned()],
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_role_unknown_permission_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts.role_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .create_role(with_uid(
+            CreateRoleRequest {
+                name: "Verifier".to_owned(),
+                permissions: vec!["RunQueries".to_owned()],
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_role_existing_name_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(Some(verifier())));
+
+    mock_contexts.role_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .create_role(with_uid(
+            CreateRoleRequest {
+                name: "Verifier".to_owned(),
+                permissions: vec![],
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::AlreadyExists);
+}
+
+#[tokio::test]
+async fn create_role_empty_name_returns_err() {
+    let res = controller(get_mock_contexts())
+        .create_role(with_uid(
+            CreateRoleRequest {
+                name: "  ".to_owned(),
+                permissions: vec![],
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn update_role_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .with(predicate::eq("Verifier".to_owned()))
+        .returning(|_| Ok(Some(verifier())));
+
+    mock_contexts
+        .role_context_mock
+        .expect_update()
+        .with(predicate::eq(role::Model {
+            permissions: json!(["ReadProject", "RunQuery", "Comment"]),
+            ..verifier()
+        }))
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .update_role(with_uid(
+            UpdateRoleRequest {
+                name: "Verifier".to_owned(),
+                permissions: vec![
+                    "Comment".to_owned(),
+                    "ReadProject".to_owned(),
+                    "RunQuery".to_owned(),
+                ],
+            },
+            "1",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.permissions, vec!["ReadProject", "RunQuery", "Comment"]);
+}
+
+#[tokio::test]
+async fn update_seeded_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts.role_context_mock.expect_update().never();
+
+    let res = controller(mock_contexts)
+        .update_role(with_uid(
+            UpdateRoleRequest {
+                name: "Reader".to_owned(),
+                permissions: vec!["EditComponents".to_owned()],
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn update_non_existing_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .update_role(with_uid(
+            UpdateRoleRequest {
+                name: "Verifier".to_owned(),
+                permissions: vec![],
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn delete_role_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(Some(verifier())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_role()
+        .with(predicate::eq("Verifier".to_owned()))
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_role()
+        .with(predicate::eq("Verifier".to_owned()))
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_redeemable_by_role()
+        .with(predicate::eq("Verifier".to_owned()))
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .role_context_mock
+        .expect_delete()
+        .with(predicate::eq("Verifier".to_owned()))
+        .times(1)
+        .returning(|_| Ok(verifier()));
+
+    let res = controller(mock_contexts)
+        .delete_role(with_uid(
+            DeleteRoleRequest {
+                name: "Verifier".to_owned(),
+            },
+            "1",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_role_in_use_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(Some(verifier())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_role()
+        .returning(|role| {
+            Ok(vec![access::Model {
+                id: 1,
+                role,
+                project_id: 1,
+                user_id: 2,
+            }])
+        });
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_role()
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts.role_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts)
+        .delete_role(with_uid(
+            DeleteRoleRequest {
+                name: "Verifier".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn delete_role_granted_to_team_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(Some(verifier())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_role()
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_role()
+        .returning(|role| {
+            Ok(vec![team_access::Model {
+                id: 1,
+                role,
+                project_id: 1,
+                team_id: 1,
+            }])
+        });
+
+    mock_contexts.role_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts)
+        .delete_role(with_uid(
+            DeleteRoleRequest {
+                name: "Verifier".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn delete_role_offered_by_open_invitation_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Ok(Some(verifier())));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_role()
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .team_access_context_mock
+        .expect_get_team_access_by_role()
+        .returning(|_| Ok(vec![]));
+
+    mock_contexts
+        .invitation_context_mock
+        .expect_get_redeemable_by_role()
+        .returning(|role| {
+            Ok(vec![invitation::Model {
+                id: 1,
+                project_id: 1,
+                role,
+                user_id: Some(1),
+                max_uses: 1,
+                uses: 0,
+                revoked: false,
+                expires_at: Default::default(),
+                created_at: Default::default(),
+            }])
+        });
+
+    mock_contexts.role_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts)
+        .delete_role(with_uid(
+            DeleteRoleRequest {
+                name: "Verifier".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn delete_seeded_role_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts.role_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts)
+        .delete_role(with_uid(
+            DeleteRoleRequest {
+                name: "Editor".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn delete_role_internal_error_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .role_context_mock
+        .expect_get_by_name()
+        .returning(|_| Err(DbErr::RecordNotInserted));
+
+    let res = controller(mock_contexts)
+        .delete_role(with_uid(
+            DeleteRoleRequest {
+                name: "Verifier".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::Internal);
+}
@@ -0,0 +1,594 @@
+use crate::api::server::protobuf::{
+    CreateShareLinkRequest, GetSharedProjectRequest, ListShareLinksRequest,
+    ListSharedQueryResultsRequest, RevokeShareLinkRequest,
+};
+use crate::controllers::controller_impls::ShareLinkController;
+use crate::controllers::controller_traits::ShareLinkControllerTrait;
+use crate::entities::{in_use, project, query, query_result, share_link};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
+use chrono::{Duration, Utc};
+use mockall::predicate;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+fn controller(mock_contexts: MockContexts) -> ShareLinkController {
+    ShareLinkController::new(disguise_context_mocks(mock_contexts))
+}
+
+fn with_uid<T>(message: T, uid: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str(uid).unwrap());
+    request
+}
+
+/// A share link to the project with id 1 that never expires.
+fn share_link_model() -> share_link::Model {
+    share_link::Model {
+        id: 1,
+        project_id: 1,
+        token: "share_token".to_owned(),
+        revoked: false,
+        expires_at: None,
+        created_at: Utc::now().naive_utc(),
+    }
+}
+
+/// Expects the project with id 1, owned by the user with id 1.
+fn expect_project(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|id| {
+            Ok(Some(project::Model {
+                id,
+                name: "project".to_owned(),
+                owner_id: 1,
+                settings: None,
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+}
+
+fn expect_share_link(mock_contexts: &mut MockContexts, share_link: share_link::Model) {
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_by_token()
+        .with(predicate::eq(share_link.token.clone()))
+        .returning(move |_| Ok(Some(share_link.clone())));
+}
+
+fn expect_query(mock_contexts: &mut MockContexts, project_id: i32) {
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |id| {
+            Ok(Some(query::Model {
+                id,
+                string: "refinement: A <= B".to_owned(),
+                result: None,
+                outdated: false,
+                project_id,
+                settings: None,
+                timeout_secs: None,
+            }))
+        });
+}
+
+#[tokio::test]
+async fn create_share_link_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_create()
+        .withf(|share_link| {
+            share_link.project_id == 1
+                && share_link.token.len() == 32
+                && !share_link.revoked
+                && share_link.expires_at.is_none()
+        })
+        .returning(|share_link| {
+            Ok(share_link::Model {
+                id: 1,
+                ..share_link
+            })
+        });
+
+    let res = controller(mock_contexts)
+        .create_share_link(with_uid(
+            CreateShareLinkRequest {
+                project_id: 1,
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.id, 1);
+    assert!(res.expires_at.is_none());
+}
+
+#[tokio::test]
+async fn create_share_link_tokens_differ() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    let controller = controller(mock_contexts);
+    let mut tokens = vec![];
+    for _ in 0..2 {
+        let res = controller
+            .create_share_link(with_uid(
+                CreateShareLinkRequest {
+                    project_id: 1,
+                    expires_in_secs: None,
+                },
+                "1",
+            ))
+            .await
+            .unwrap();
+        tokens.push(res.into_inner().token);
+    }
+
+    assert_ne!(tokens[0], tokens[1]);
+}
+
+#[tokio::test]
+async fn create_share_link_with_expiry_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    let expected_expiry = Utc::now() + Duration::hours(1);
+    mock_contexts
+        .share_link_context_mock
+        .expect_create()
+        .withf(move |share_link| {
+            share_link.expires_at.is_some_and(|expires_at| {
+                (expires_at.and_utc() - expected_expiry).num_seconds().abs() < 60
+            })
+        })
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .create_share_link(with_uid(
+            CreateShareLinkRequest {
+                project_id: 1,
+                expires_in_secs: Some(60 * 60),
+            },
+            "1",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(res.expires_at.is_some());
+}
+
+#[tokio::test]
+async fn create_share_link_zero_expiry_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_share_link(with_uid(
+            CreateShareLinkRequest {
+                project_id: 1,
+                expires_in_secs: Some(0),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_share_link_too_long_expiry_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_share_link(with_uid(
+            CreateShareLinkRequest {
+                project_id: 1,
+                expires_in_secs: Some(31 * 24 * 60 * 60),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_share_link_not_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_create()
+        .never();
+
+    let res = controller(mock_contexts)
+        .create_share_link(with_uid(
+            CreateShareLinkRequest {
+                project_id: 1,
+                expires_in_secs: None,
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_share_link_non_existing_project_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .create_share_link(with_uid(
+            CreateShareLinkRequest {
+                project_id: 1,
+                expires_in_secs: None,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn list_share_links_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                share_link_model(),
+                share_link::Model {
+                    id: 2,
+                    token: "other_token".to_owned(),
+                    revoked: true,
+                    ..share_link_model()
+                },
+            ])
+        });
+
+    let res = controller(mock_contexts)
+        .list_share_links(with_uid(ListShareLinksRequest { project_id: 1 }, "1"))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.share_links.len(), 2);
+    assert!(res.share_links[1].revoked);
+}
+
+#[tokio::test]
+async fn list_share_links_not_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_all_by_project_id()
+        .never();
+
+    let res = controller(mock_contexts)
+        .list_share_links(with_uid(ListShareLinksRequest { project_id: 1 }, "2"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn revoke_share_link_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(share_link_model())));
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_update()
+        .withf(|share_link| share_link.id == 1 && share_link.revoked)
+        .times(1)
+        .returning(Ok);
+
+    let res = controller(mock_contexts)
+        .revoke_share_link(with_uid(RevokeShareLinkRequest { id: 1 }, "1"))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn revoke_non_existing_share_link_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .revoke_share_link(with_uid(RevokeShareLinkRequest { id: 1 }, "1"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn revoke_share_link_not_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(share_link_model())));
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_update()
+        .never();
+
+    let res = controller(mock_contexts)
+        .revoke_share_link(with_uid(RevokeShareLinkRequest { id: 1 }, "2"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn get_shared_project_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_share_link(&mut mock_contexts, share_link_model());
+    expect_project(&mut mock_contexts);
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Default::default()));
+
+    mock_contexts
+        .in_use_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(Some(in_use::Model {
+                project_id: 1,
+                session_id: 1,
+                latest_activity: Utc::now().naive_utc(),
+                expires_at: (Utc::now() + Duration::minutes(5)).naive_utc(),
+            }))
+        });
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_all_by_project_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(vec![]));
+
+    let res = controller(mock_contexts)
+        .get_shared_project(Request::new(GetSharedProjectRequest {
+            token: "share_token".to_owned(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let project = res.project.unwrap();
+    assert_eq!(project.id, 1);
+    assert_eq!(project.owner_id, 0);
+    assert!(!res.in_use);
+}
+
+#[tokio::test]
+async fn get_shared_project_unusable_share_link_returns_err() {
+    let unusable_share_links = [
+        share_link::Model {
+            revoked: true,
+            ..share_link_model()
+        },
+        share_link::Model {
+            expires_at: Some((Utc::now() - Duration::minutes(1)).naive_utc()),
+            ..share_link_model()
+        },
+    ];
+
+    for share_link in unusable_share_links {
+        let mut mock_contexts = get_mock_contexts();
+
+        expect_share_link(&mut mock_contexts, share_link);
+
+        mock_contexts
+            .project_context_mock
+            .expect_get_by_id()
+            .never();
+
+        let res = controller(mock_contexts)
+            .get_shared_project(Request::new(GetSharedProjectRequest {
+                token: "share_token".to_owned(),
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(res.code(), Code::NotFound);
+    }
+}
+
+#[tokio::test]
+async fn get_shared_project_unknown_token_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .share_link_context_mock
+        .expect_get_by_token()
+        .returning(|_| Ok(None));
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .never();
+
+    let res = controller(mock_contexts)
+        .get_shared_project(Request::new(GetSharedProjectRequest {
+            token: "unknown_token".to_owned(),
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn list_shared_query_results_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_share_link(&mut mock_contexts, share_link_model());
+    expect_query(&mut mock_contexts, 1);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_get_page_by_query_id()
+        .with(predicate::eq(1), predicate::eq(0), predicate::eq(20))
+        .returning(|query_id, _, _| {
+            Ok((
+                vec![query_result::Model {
+                    id: 1,
+                    query_id,
+                    user_id: Some(1),
+                    result: serde_json::json!({ "Success": {} }),
+                    settings: serde_json::json!({ "disable_clock_reduction": true }),
+                    components_hash: "e3b0c442".to_owned(),
+                    created_at: Default::default(),
+                }],
+                1,
+            ))
+        });
+
+    let res = controller(mock_contexts)
+        .list_shared_query_results(Request::new(ListSharedQueryResultsRequest {
+            token: "share_token".to_owned(),
+            query_id: 1,
+            page: 0,
+            page_size: 0,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.total, 1);
+    assert_eq!(res.query_results[0].query_id, 1);
+    assert_eq!(res.query_results[0].user_id, None);
+}
+
+#[tokio::test]
+async fn list_shared_query_results_other_project_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_share_link(&mut mock_contexts, share_link_model());
+    expect_query(&mut mock_contexts, 2);
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_get_page_by_query_id()
+        .never();
+
+    let res = controller(mock_contexts)
+        .list_shared_query_results(Request::new(ListSharedQueryResultsRequest {
+            token: "share_token".to_owned(),
+            query_id: 1,
+            page: 0,
+            page_size: 0,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn list_shared_query_results_revoked_share_link_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_share_link(
+        &mut mock_contexts,
+        share_link::Model {
+            revoked: true,
+            ..share_link_model()
+        },
+    );
+
+    mock_contexts.query_context_mock.expect_get_by_id().never();
+
+    let res = controller(mock_contexts)
+        .list_shared_query_results(Request::new(ListSharedQueryResultsRequest {
+            token: "share_token".to_owned(),
+            query_id: 1,
+            page: 0,
+            page_size: 0,
+        }))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
@@ -0,0 +1,1039 @@
+use crate::api::server::protobuf::{
+    ComponentsInfo, CreateSimulationSessionRequest, Decision, DeleteSimulationSessionRequest,
+    ExportSimulationTraceRequest, GetSimulationSessionRequest, ListSimulationSessionsRequest,
+    RenameSimulationSessionRequest, ReplaySimulationTraceRequest, SimulationStepResponse,
+    StartCounterexampleSimulationRequest, TakeSimulationSessionStepRequest,
+};
+use crate::api::simulation_trace::{SimulationTrace, SimulationTraceStep};
+use crate::controllers::controller_impls::SimulationController;
+use crate::controllers::controller_traits::SimulationControllerTrait;
+use crate::entities::{access, counterexample, query, simulation_session, simulation_step};
+use crate::tests::controllers::helpers::{
+    disguise_context_mocks, disguise_service_mocks, get_mock_contexts, get_mock_services,
+    MockContexts, MockServices,
+};
+use mockall::predicate;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request, Response, Status};
+
+fn decision(action: &str) -> Decision {
+    Decision {
+        source: None,
+        action: action.to_owned(),
+        edges: vec![],
+        destination: None,
+    }
+}
+
+fn create_simulation_session(user_id: i32) -> simulation_session::Model {
+    simulation_session::Model {
+        id: 1,
+        project_id: 1,
+        user_id,
+        name: "simulation".to_owned(),
+        component_composition: "(A || B)".to_owned(),
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    }
+}
+
+fn create_simulation_step(
+    id: i32,
+    chosen_decision: Option<Decision>,
+    decision_points: Vec<Decision>,
+) -> simulation_step::Model {
+    simulation_step::Model {
+        id,
+        simulation_session_id: 1,
+        chosen_decision: chosen_decision.map(|decision| serde_json::to_value(decision).unwrap()),
+        decision_points: serde_json::to_value(decision_points).unwrap(),
+        created_at: Default::default(),
+    }
+}
+
+/// Gives user 1 access to project 1.
+fn expect_access(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(move |_, _| {
+            Ok(Some(
+                access::Model {
+                    id: 1,
+                    role: "Reader".to_owned(),
+                    project_id: 1,
+                    user_id: 1,
+                }
+                .into(),
+            ))
+        });
+}
+
+fn expect_components(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(ComponentsInfo {
+                components: vec![],
+                components_hash: 0,
+            })
+        });
+}
+
+fn expect_simulation_session(mock_contexts: &mut MockContexts, user_id: i32) {
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_simulation_session(user_id))));
+}
+
+fn controller(mock_contexts: MockContexts, mock_services: MockServices) -> SimulationController {
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+    SimulationController::new(contexts, services)
+}
+
+fn with_uid<T>(message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str("1").unwrap());
+    request
+}
+
+#[tokio::test]
+async fn create_simulation_session_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .withf(|simulation_session_id, request| {
+            let simulation_info = request.get_ref().simulation_info.as_ref().unwrap();
+            *simulation_session_id == Some(1)
+                && simulation_info.user_id == 1
+                && simulation_info.component_composition == "(A || B)"
+        })
+        .times(1)
+        .returning(|_, _| {
+            Ok(Response::new(SimulationStepResponse {
+                new_decision_points: vec![decision("a")],
+            }))
+        });
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_create()
+        .withf(|simulation_session| {
+            simulation_session.user_id == 1 && simulation_session.name == "simulation"
+        })
+        .times(1)
+        .returning(|_| Ok(create_simulation_session(1)));
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_create()
+        .with(predicate::eq(create_simulation_step(
+            Default::default(),
+            None,
+            vec![decision("a")],
+        )))
+        .times(1)
+        .returning(|step| Ok(simulation_step::Model { id: 1, ..step }));
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_update()
+        .returning(Ok);
+
+    let request = with_uid(CreateSimulationSessionRequest {
+        project_id: 1,
+        name: "simulation".to_owned(),
+        component_composition: "(A || B)".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .create_simulation_session(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.simulation_session.unwrap().id, 1);
+    assert_eq!(res.steps.len(), 1);
+    assert_eq!(res.steps[0].decision_points, vec![decision("a")]);
+}
+
+#[tokio::test]
+async fn create_simulation_session_empty_name_uses_composition() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .returning(|_, _| Ok(Response::new(SimulationStepResponse::default())));
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_create()
+        .withf(|simulation_session| simulation_session.name == "(A || B)")
+        .times(1)
+        .returning(|_| Ok(create_simulation_session(1)));
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_create()
+        .returning(Ok);
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_update()
+        .returning(Ok);
+
+    let request = with_uid(CreateSimulationSessionRequest {
+        project_id: 1,
+        name: " ".to_owned(),
+        component_composition: "(A || B)".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .create_simulation_session(request)
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn create_simulation_session_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .times(0);
+
+    let request = with_uid(CreateSimulationSessionRequest {
+        project_id: 1,
+        name: "simulation".to_owned(),
+        component_composition: "(A || B)".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .create_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn create_simulation_session_empty_composition_returns_err() {
+    let request = with_uid(CreateSimulationSessionRequest {
+        project_id: 1,
+        name: "simulation".to_owned(),
+        component_composition: "".to_owned(),
+    });
+
+    let res = controller(get_mock_contexts(), get_mock_services())
+        .create_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn create_simulation_session_reveaal_error_is_not_saved() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .returning(|_, _| Err(Status::invalid_argument("bad composition")));
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_create()
+        .returning(|_| Ok(create_simulation_session(1)));
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(create_simulation_session(1)));
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_create()
+        .times(0);
+
+    let request = with_uid(CreateSimulationSessionRequest {
+        project_id: 1,
+        name: "simulation".to_owned(),
+        component_composition: "(A ||".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .create_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn take_simulation_session_step_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_get_all_by_simulation_session_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![create_simulation_step(
+                1,
+                None,
+                vec![decision("a"), decision("b")],
+            )])
+        });
+
+    mock_services
+        .reveaal_service_mock
+        .expect_take_simulation_step()
+        .withf(|simulation_session_id, request| {
+            let message = request.get_ref();
+            *simulation_session_id == Some(1)
+                && message.chosen_decision == Some(decision("b"))
+                && message
+                    .simulation_info
+                    .as_ref()
+                    .unwrap()
+                    .component_composition
+                    == "(A || B)"
+        })
+        .times(1)
+        .returning(|_, _| {
+            Ok(Response::new(SimulationStepResponse {
+                new_decision_points: vec![decision("c")],
+            }))
+        });
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_create()
+        .with(predicate::eq(create_simulation_step(
+            Default::default(),
+            Some(decision("b")),
+            vec![decision("c")],
+        )))
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_update()
+        .times(1)
+        .returning(Ok);
+
+    let request = with_uid(TakeSimulationSessionStepRequest {
+        id: 1,
+        chosen_decision: Some(decision("b")),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .take_simulation_session_step(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.chosen_decision, Some(decision("b")));
+    assert_eq!(res.decision_points, vec![decision("c")]);
+}
+
+#[tokio::test]
+async fn take_simulation_session_step_unknown_decision_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+    expect_access(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_get_all_by_simulation_session_id()
+        .returning(|_| Ok(vec![create_simulation_step(1, None, vec![decision("a")])]));
+
+    mock_services
+        .reveaal_service_mock
+        .expect_take_simulation_step()
+        .times(0);
+
+    let request = with_uid(TakeSimulationSessionStepRequest {
+        id: 1,
+        chosen_decision: Some(decision("b")),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .take_simulation_session_step(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn take_simulation_session_step_of_other_user_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 2);
+
+    let request = with_uid(TakeSimulationSessionStepRequest {
+        id: 1,
+        chosen_decision: Some(decision("a")),
+    });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .take_simulation_session_step(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn get_simulation_session_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+    expect_access(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_get_all_by_simulation_session_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                create_simulation_step(1, None, vec![decision("a")]),
+                create_simulation_step(2, Some(decision("a")), vec![decision("b")]),
+            ])
+        });
+
+    let request = with_uid(GetSimulationSessionRequest { id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_simulation_session(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.simulation_session.unwrap().name, "simulation");
+    assert_eq!(res.steps.len(), 2);
+    assert_eq!(res.steps[0].chosen_decision, None);
+    assert_eq!(res.steps[1].chosen_decision, Some(decision("a")));
+    assert_eq!(res.steps[1].decision_points, vec![decision("b")]);
+}
+
+#[tokio::test]
+async fn get_simulation_session_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let request = with_uid(GetSimulationSessionRequest { id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn get_simulation_session_non_existing_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    let request = with_uid(GetSimulationSessionRequest { id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .get_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn list_simulation_sessions_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_access(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_get_all_by_project_id_and_user_id()
+        .with(predicate::eq(1), predicate::eq(1))
+        .returning(|_, _| Ok(vec![create_simulation_session(1)]));
+
+    let request = with_uid(ListSimulationSessionsRequest { project_id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .list_simulation_sessions(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.simulation_sessions.len(), 1);
+}
+
+#[tokio::test]
+async fn list_simulation_sessions_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let request = with_uid(ListSimulationSessionsRequest { project_id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .list_simulation_sessions(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn rename_simulation_session_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+    expect_access(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_update()
+        .with(predicate::eq(simulation_session::Model {
+            name: "renamed".to_owned(),
+            ..create_simulation_session(1)
+        }))
+        .times(1)
+        .returning(Ok);
+
+    let request = with_uid(RenameSimulationSessionRequest {
+        id: 1,
+        name: "renamed".to_owned(),
+    });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .rename_simulation_session(request)
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn rename_simulation_session_empty_name_returns_err() {
+    let request = with_uid(RenameSimulationSessionRequest {
+        id: 1,
+        name: "".to_owned(),
+    });
+
+    let res = controller(get_mock_contexts(), get_mock_services())
+        .rename_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn delete_simulation_session_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+    expect_access(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(create_simulation_session(1)));
+
+    let mut mock_services = get_mock_services();
+
+    mock_services
+        .reveaal_service_mock
+        .expect_end_simulation()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| ());
+
+    let request = with_uid(DeleteSimulationSessionRequest { id: 1 });
+
+    let res = controller(mock_contexts, mock_services)
+        .delete_simulation_session(request)
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_simulation_session_of_other_user_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 2);
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_delete()
+        .times(0);
+
+    let request = with_uid(DeleteSimulationSessionRequest { id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .delete_simulation_session(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+/// A trace that starts with decision `a` and, after taking it, offers decision `b`.
+fn trace() -> SimulationTrace {
+    SimulationTrace::new(
+        "(A || B)".to_owned(),
+        vec![
+            SimulationTraceStep {
+                chosen_decision: None,
+                decision_points: vec![decision("a")],
+            },
+            SimulationTraceStep {
+                chosen_decision: Some(decision("a")),
+                decision_points: vec![decision("b")],
+            },
+        ],
+    )
+}
+
+fn replay_request(trace: &SimulationTrace) -> Request<ReplaySimulationTraceRequest> {
+    with_uid(ReplaySimulationTraceRequest {
+        project_id: 1,
+        trace: serde_json::to_string(trace).unwrap(),
+    })
+}
+
+fn expect_start_simulation(mock_services: &mut MockServices, decision_points: Vec<Decision>) {
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .times(1)
+        .returning(move |_, _| {
+            Ok(Response::new(SimulationStepResponse {
+                new_decision_points: decision_points.clone(),
+            }))
+        });
+}
+
+#[tokio::test]
+async fn export_simulation_trace_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 1);
+    expect_access(&mut mock_contexts);
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_get_all_by_simulation_session_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(vec![
+                create_simulation_step(1, None, vec![decision("a")]),
+                create_simulation_step(2, Some(decision("a")), vec![decision("b")]),
+            ])
+        });
+
+    let request = with_uid(ExportSimulationTraceRequest { id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .export_simulation_trace(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(SimulationTrace::parse(&res.trace).unwrap(), trace());
+}
+
+#[tokio::test]
+async fn export_simulation_trace_of_other_user_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_simulation_session(&mut mock_contexts, 2);
+
+    let request = with_uid(ExportSimulationTraceRequest { id: 1 });
+
+    let res = controller(mock_contexts, get_mock_services())
+        .export_simulation_trace(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_matching_trace_has_no_divergence() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+    expect_start_simulation(&mut mock_services, vec![decision("a")]);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_take_simulation_step()
+        .withf(|_, request| request.get_ref().chosen_decision == Some(decision("a")))
+        .times(1)
+        .returning(|_, _| {
+            Ok(Response::new(SimulationStepResponse {
+                new_decision_points: vec![decision("b")],
+            }))
+        });
+
+    let res = controller(mock_contexts, mock_services)
+        .replay_simulation_trace(replay_request(&trace()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.divergence, None);
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_reports_first_divergent_step() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+    expect_start_simulation(&mut mock_services, vec![decision("a")]);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_take_simulation_step()
+        .times(1)
+        .returning(|_, _| {
+            Ok(Response::new(SimulationStepResponse {
+                new_decision_points: vec![decision("c")],
+            }))
+        });
+
+    let res = controller(mock_contexts, mock_services)
+        .replay_simulation_trace(replay_request(&trace()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let divergence = res.divergence.unwrap();
+    assert_eq!(divergence.step, 1);
+    assert_eq!(divergence.expected_decision_points, vec![decision("b")]);
+    assert_eq!(divergence.actual_decision_points, vec![decision("c")]);
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_stops_at_divergent_start() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+    expect_start_simulation(&mut mock_services, vec![decision("c")]);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_take_simulation_step()
+        .times(0);
+
+    let res = controller(mock_contexts, mock_services)
+        .replay_simulation_trace(replay_request(&trace()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.divergence.unwrap().step, 0);
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_rejected_step_diverges() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+    expect_start_simulation(&mut mock_services, vec![decision("a")]);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_take_simulation_step()
+        .returning(|_, _| Err(Status::invalid_argument("edge no longer exists")));
+
+    let res = controller(mock_contexts, mock_services)
+        .replay_simulation_trace(replay_request(&trace()))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let divergence = res.divergence.unwrap();
+    assert_eq!(divergence.step, 1);
+    assert_eq!(divergence.reason, "edge no longer exists");
+    assert!(divergence.actual_decision_points.is_empty());
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_unavailable_reveaal_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .returning(|_, _| Err(Status::unavailable("reveaal is down")));
+
+    let res = controller(mock_contexts, mock_services)
+        .replay_simulation_trace(replay_request(&trace()))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::Unavailable);
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_invalid_trace_returns_err() {
+    let request = with_uid(ReplaySimulationTraceRequest {
+        project_id: 1,
+        trace: "not a trace".to_owned(),
+    });
+
+    let res = controller(get_mock_contexts(), get_mock_services())
+        .replay_simulation_trace(request)
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn replay_simulation_trace_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let res = controller(mock_contexts, get_mock_services())
+        .replay_simulation_trace(replay_request(&trace()))
+        .await;
+
+    assert_eq!(res.unwrap_err().code(), Code::PermissionDenied);
+}
+
+fn expect_counterexample(mock_contexts: &mut MockContexts, kind: &str, system: Option<&str>) {
+    let counterexample = counterexample::Model {
+        id: 1,
+        query_id: 1,
+        query_result_id: 1,
+        kind: kind.to_owned(),
+        system: system.map(str::to_owned),
+        reason: None,
+        actions: serde_json::json!([]),
+        state: None,
+        path: serde_json::json!([]),
+        created_at: Default::default(),
+    };
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(counterexample.clone())));
+
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| {
+            Ok(Some(query::Model {
+                id: 1,
+                string: "refinement: (A || B) <= C".to_owned(),
+                result: None,
+                outdated: false,
+                project_id: 1,
+                settings: None,
+                timeout_secs: None,
+            }))
+        });
+}
+
+#[tokio::test]
+async fn start_counterexample_simulation_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_counterexample(&mut mock_contexts, "REFINEMENT", Some("(A || B) <= C"));
+    expect_access(&mut mock_contexts);
+    expect_components(&mut mock_contexts);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .withf(|_, request| {
+            let simulation_info = request.get_ref().simulation_info.as_ref().unwrap();
+            simulation_info.component_composition == "(A || B)"
+        })
+        .times(1)
+        .returning(|_, _| {
+            Ok(Response::new(SimulationStepResponse {
+                new_decision_points: vec![decision("a")],
+            }))
+        });
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_create()
+        .withf(|simulation_session| {
+            simulation_session.component_composition == "(A || B)"
+                && simulation_session.name == "Counterexample of refinement: (A || B) <= C"
+        })
+        .times(1)
+        .returning(|_| Ok(create_simulation_session(1)));
+
+    mock_contexts
+        .simulation_step_context_mock
+        .expect_create()
+        .times(1)
+        .returning(|step| Ok(simulation_step::Model { id: 1, ..step }));
+
+    mock_contexts
+        .simulation_session_context_mock
+        .expect_update()
+        .returning(Ok);
+
+    let request = with_uid(StartCounterexampleSimulationRequest {
+        counterexample_id: 1,
+        name: "".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .start_counterexample_simulation(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(res.simulation_session.unwrap().id, 1);
+    assert_eq!(res.steps[0].decision_points, vec![decision("a")]);
+}
+
+#[tokio::test]
+async fn start_counterexample_simulation_without_system_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_counterexample(&mut mock_contexts, "REACHABILITY", None);
+    expect_access(&mut mock_contexts);
+
+    mock_services
+        .reveaal_service_mock
+        .expect_start_simulation()
+        .times(0);
+
+    let request = with_uid(StartCounterexampleSimulationRequest {
+        counterexample_id: 1,
+        name: "".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .start_counterexample_simulation(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn start_counterexample_simulation_no_access_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    expect_counterexample(&mut mock_contexts, "DETERMINISM", Some("A"));
+
+    mock_contexts
+        .access_context_mock
+        .expect_get_access_by_uid_and_project_id()
+        .returning(|_, _| Ok(None));
+
+    let request = with_uid(StartCounterexampleSimulationRequest {
+        counterexample_id: 1,
+        name: "".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .start_counterexample_simulation(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn start_counterexample_simulation_non_existing_counterexample_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .counterexample_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    let request = with_uid(StartCounterexampleSimulationRequest {
+        counterexample_id: 1,
+        name: "".to_owned(),
+    });
+
+    let res = controller(mock_contexts, mock_services)
+        .start_counterexample_simulation(request)
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
@@ -0,0 +1,452 @@
+use crate::api::server::protobuf::{
+    AddTeamMembersRequest, CreateTeamRequest, DeleteTeamRequest, GetTeamRequest,
+    RemoveTeamMemberRequest, TeamMemberInfo,
+};
+use crate::controllers::controller_impls::TeamController;
+use crate::controllers::controller_traits::TeamControllerTrait;
+use crate::entities::{team, team_member, user};
+use crate::tests::controllers::helpers::{disguise_context_mocks, get_mock_contexts, MockContexts};
+use mockall::predicate;
+use sea_orm::DbErr;
+use std::str::FromStr;
+use tonic::{metadata, Code, Request};
+
+fn controller(mock_contexts: MockContexts) -> TeamController {
+    TeamController::new(disguise_context_mocks(mock_contexts))
+}
+
+fn with_uid<T>(message: T, uid: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("uid", metadata::MetadataValue::from_str(uid).unwrap());
+    request
+}
+
+/// A team owned by the user with id 1.
+fn team_model() -> team::Model {
+    team::Model {
+        id: 1,
+        name: "Verification".to_owned(),
+        owner_id: 1,
+    }
+}
+
+fn user_model(id: i32) -> user::Model {
+    user::Model {
+        id,
+        email: format!("user{}@example.com", id),
+        username: format!("user{}", id),
+        password: "password".to_owned(),
+    }
+}
+
+fn expect_team(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .team_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(|_| Ok(Some(team_model())));
+}
+
+fn expect_members(mock_contexts: &mut MockContexts, user_ids: Vec<i32>) {
+    mock_contexts
+        .team_context_mock
+        .expect_get_members()
+        .with(predicate::eq(1))
+        .returning(move |team_id| {
+            Ok(user_ids
+                .iter()
+                .map(|&user_id| team_member::Model { team_id, user_id })
+                .collect())
+        });
+}
+
+#[tokio::test]
+async fn create_team_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .team_context_mock
+        .expect_create()
+        .with(predicate::eq(team::Model {
+            id: Default::default(),
+            ..team_model()
+        }))
+        .returning(|_| Ok(team_model()));
+
+    mock_contexts
+        .team_context_mock
+        .expect_add_members()
+        .with(predicate::eq(1), predicate::eq(vec![1]))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let res = controller(mock_contexts)
+        .create_team(with_uid(
+            CreateTeamRequest {
+                name: " Verification ".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(res.get_ref().team_id, 1);
+}
+
+#[tokio::test]
+async fn create_team_empty_name_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts.team_context_mock.expect_create().never();
+
+    let res = controller(mock_contexts)
+        .create_team(with_uid(
+            CreateTeamRequest {
+                name: "  ".to_owned(),
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn get_team_returns_members() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+    expect_members(&mut mock_contexts, vec![1, 2]);
+
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_ids()
+        .with(predicate::eq(vec![1, 2]))
+        .returning(|ids| Ok(ids.into_iter().map(user_model).collect()));
+
+    let res = controller(mock_contexts)
+        .get_team(with_uid(GetTeamRequest { team_id: 1 }, "2"))
+        .await
+        .unwrap();
+
+    assert_eq!(res.get_ref().team.as_ref().unwrap().name, "Verification");
+    assert_eq!(
+        res.get_ref().members,
+        vec![
+            TeamMemberInfo {
+                user_id: 1,
+                username: "user1".to_owned(),
+            },
+            TeamMemberInfo {
+                user_id: 2,
+                username: "user2".to_owned(),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn get_team_not_member_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+    expect_members(&mut mock_contexts, vec![1]);
+
+    let res = controller(mock_contexts)
+        .get_team(with_uid(GetTeamRequest { team_id: 1 }, "2"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn get_non_existing_team_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .team_context_mock
+        .expect_get_by_id()
+        .returning(|_| Ok(None));
+
+    let res = controller(mock_contexts)
+        .get_team(with_uid(GetTeamRequest { team_id: 1 }, "1"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn list_teams_returns_teams_of_user() {
+    let mut mock_contexts = get_mock_contexts();
+
+    mock_contexts
+        .team_context_mock
+        .expect_get_all_by_uid()
+        .with(predicate::eq(2))
+        .returning(|_| Ok(vec![team_model()]));
+
+    let res = controller(mock_contexts)
+        .list_teams(with_uid((), "2"))
+        .await
+        .unwrap();
+
+    assert_eq!(res.get_ref().teams.len(), 1);
+    assert_eq!(res.get_ref().teams[0].owner_id, 1);
+}
+
+#[tokio::test]
+async fn delete_team_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts
+        .team_context_mock
+        .expect_delete()
+        .with(predicate::eq(1))
+        .times(1)
+        .returning(|_| Ok(team_model()));
+
+    let res = controller(mock_contexts)
+        .delete_team(with_uid(DeleteTeamRequest { team_id: 1 }, "1"))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn delete_team_not_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts.team_context_mock.expect_delete().never();
+
+    let res = controller(mock_contexts)
+        .delete_team(with_uid(DeleteTeamRequest { team_id: 1 }, "2"))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn add_team_members_skips_existing_members() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+    expect_members(&mut mock_contexts, vec![1, 2]);
+
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_username()
+        .returning(|username| {
+            Ok(Some(user_model(
+                username.trim_start_matches("user").parse().unwrap(),
+            )))
+        });
+
+    mock_contexts
+        .team_context_mock
+        .expect_add_members()
+        .with(predicate::eq(1), predicate::eq(vec![3]))
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let res = controller(mock_contexts)
+        .add_team_members(with_uid(
+            AddTeamMembersRequest {
+                team_id: 1,
+                usernames: vec!["user2".to_owned(), "user3".to_owned(), "user3".to_owned()],
+            },
+            "1",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn add_team_members_unknown_username_adds_none() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+    expect_members(&mut mock_contexts, vec![1]);
+
+    mock_contexts
+        .user_context_mock
+        .expect_get_by_username()
+        .returning(|username| match username.as_str() {
+            "user2" => Ok(Some(user_model(2))),
+            _ => Ok(None),
+        });
+
+    mock_contexts.team_context_mock.expect_add_members().never();
+
+    let res = controller(mock_contexts)
+        .add_team_members(with_uid(
+            AddTeamMembersRequest {
+                team_id: 1,
+                usernames: vec!["user2".to_owned(), "nobody".to_owned()],
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn add_team_members_not_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts.team_context_mock.expect_add_members().never();
+
+    let res = controller(mock_contexts)
+        .add_team_members(with_uid(
+            AddTeamMembersRequest {
+                team_id: 1,
+                usernames: vec!["user3".to_owned()],
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn remove_team_member_by_owner_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts
+        .team_context_mock
+        .expect_remove_member()
+        .with(predicate::eq(1), predicate::eq(2))
+        .times(1)
+        .returning(|team_id, user_id| Ok(team_member::Model { team_id, user_id }));
+
+    let res = controller(mock_contexts)
+        .remove_team_member(with_uid(
+            RemoveTeamMemberRequest {
+                team_id: 1,
+                user_id: 2,
+            },
+            "1",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn remove_team_member_leaving_returns_ok() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts
+        .team_context_mock
+        .expect_remove_member()
+        .with(predicate::eq(1), predicate::eq(2))
+        .times(1)
+        .returning(|team_id, user_id| Ok(team_member::Model { team_id, user_id }));
+
+    let res = controller(mock_contexts)
+        .remove_team_member(with_uid(
+            RemoveTeamMemberRequest {
+                team_id: 1,
+                user_id: 2,
+            },
+            "2",
+        ))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn remove_other_team_member_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts
+        .team_context_mock
+        .expect_remove_member()
+        .never();
+
+    let res = controller(mock_contexts)
+        .remove_team_member(with_uid(
+            RemoveTeamMemberRequest {
+                team_id: 1,
+                user_id: 3,
+            },
+            "2",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn remove_team_owner_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts
+        .team_context_mock
+        .expect_remove_member()
+        .never();
+
+    let res = controller(mock_contexts)
+        .remove_team_member(with_uid(
+            RemoveTeamMemberRequest {
+                team_id: 1,
+                user_id: 1,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::FailedPrecondition);
+}
+
+#[tokio::test]
+async fn remove_non_member_returns_err() {
+    let mut mock_contexts = get_mock_contexts();
+
+    expect_team(&mut mock_contexts);
+
+    mock_contexts
+        .team_context_mock
+        .expect_remove_member()
+        .returning(|_, _| Err(DbErr::RecordNotFound("No record was deleted".into())));
+
+    let res = controller(mock_contexts)
+        .remove_team_member(with_uid(
+            RemoveTeamMemberRequest {
+                team_id: 1,
+                user_id: 2,
+            },
+            "1",
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(res.code(), Code::NotFound);
+}
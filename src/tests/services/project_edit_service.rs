@@ -0,0 +1,56 @@
+use crate::api::server::protobuf::{project_edit_event::Change, ProjectEditEvent};
+use crate::services::service_impls::ProjectEditService;
+use crate::services::service_traits::ProjectEditServiceTrait;
+use futures::StreamExt;
+use tonic::Code;
+
+fn create_event(project_id: i32, revision: i32) -> ProjectEditEvent {
+    ProjectEditEvent {
+        project_id,
+        revision,
+        change: Some(Change::ComponentDeleted(1)),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn subscribe_receives_published_edits_in_order_test() {
+    let project_edit_service = ProjectEditService::new();
+
+    let mut events = project_edit_service.subscribe(1);
+
+    project_edit_service.publish(create_event(1, 1));
+    project_edit_service.publish(create_event(1, 2));
+
+    assert_eq!(events.next().await.unwrap().unwrap(), create_event(1, 1));
+    assert_eq!(events.next().await.unwrap().unwrap(), create_event(1, 2));
+}
+
+#[tokio::test]
+async fn subscribe_skips_edits_of_other_projects_test() {
+    let project_edit_service = ProjectEditService::new();
+
+    let mut events = project_edit_service.subscribe(1);
+
+    project_edit_service.publish(create_event(2, 1));
+    project_edit_service.publish(create_event(1, 1));
+
+    assert_eq!(events.next().await.unwrap().unwrap(), create_event(1, 1));
+}
+
+#[tokio::test]
+async fn subscribe_lagging_collaborator_is_ended_test() {
+    let project_edit_service = ProjectEditService::new();
+
+    let mut events = project_edit_service.subscribe(1);
+
+    for revision in 0..2000 {
+        project_edit_service.publish(create_event(1, revision));
+    }
+
+    assert_eq!(
+        events.next().await.unwrap().unwrap_err().code(),
+        Code::DataLoss
+    );
+    assert!(events.next().await.is_none());
+}
@@ -0,0 +1,83 @@
+use crate::api::server::protobuf::query_event::Kind;
+use crate::api::server::protobuf::QueryEvent;
+use crate::services::service_impls::QueryEventService;
+use crate::services::service_traits::QueryEventServiceTrait;
+use futures::StreamExt;
+
+fn create_event(query_id: i32, project_id: i32, kind: Kind) -> QueryEvent {
+    QueryEvent {
+        query_id,
+        project_id,
+        kind: kind.into(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn subscribe_receives_published_events_test() {
+    let query_event_service = QueryEventService::new();
+
+    let mut events = query_event_service.subscribe(1);
+
+    query_event_service.publish(create_event(1, 1, Kind::Started));
+    query_event_service.publish(create_event(1, 1, Kind::Finished));
+
+    assert_eq!(
+        events.next().await.unwrap().unwrap(),
+        create_event(1, 1, Kind::Started)
+    );
+    assert_eq!(
+        events.next().await.unwrap().unwrap(),
+        create_event(1, 1, Kind::Finished)
+    );
+}
+
+#[tokio::test]
+async fn subscribe_skips_events_of_other_projects_test() {
+    let query_event_service = QueryEventService::new();
+
+    let mut events = query_event_service.subscribe(1);
+
+    query_event_service.publish(create_event(2, 2, Kind::Outdated));
+    query_event_service.publish(create_event(1, 1, Kind::Outdated));
+
+    assert_eq!(
+        events.next().await.unwrap().unwrap(),
+        create_event(1, 1, Kind::Outdated)
+    );
+}
+
+#[tokio::test]
+async fn subscribe_multiple_subscribers_receive_same_event_test() {
+    let query_event_service = QueryEventService::new();
+
+    let mut first_events = query_event_service.subscribe(1);
+    let mut second_events = query_event_service.subscribe(1);
+
+    query_event_service.publish(create_event(1, 1, Kind::Failed));
+
+    assert_eq!(
+        first_events.next().await.unwrap().unwrap(),
+        create_event(1, 1, Kind::Failed)
+    );
+    assert_eq!(
+        second_events.next().await.unwrap().unwrap(),
+        create_event(1, 1, Kind::Failed)
+    );
+}
+
+#[tokio::test]
+async fn publish_without_subscribers_test() {
+    let query_event_service = QueryEventService::new();
+
+    query_event_service.publish(create_event(1, 1, Kind::Started));
+
+    let mut events = query_event_service.subscribe(1);
+
+    query_event_service.publish(create_event(2, 1, Kind::Started));
+
+    assert_eq!(
+        events.next().await.unwrap().unwrap(),
+        create_event(2, 1, Kind::Started)
+    );
+}
@@ -0,0 +1,420 @@
+use crate::api::server::protobuf::query_event::Kind;
+use crate::api::server::protobuf::query_response::{self, Result};
+use crate::api::server::protobuf::QueryResponse;
+use crate::entities::sea_orm_active_enums::QueryJobStatus;
+use crate::entities::{project, query, query_cache, query_job};
+use crate::services::service_impls::query_job_service::run_query_job;
+use crate::services::service_traits::QueryRun;
+use crate::tests::controllers::helpers::{
+    disguise_context_mocks, disguise_service_mocks, get_mock_contexts, get_mock_services,
+    MockContexts, MockServices,
+};
+use mockall::predicate;
+use tokio::sync::watch;
+use tonic::{Response, Status};
+
+fn create_query_job(status: QueryJobStatus) -> query_job::Model {
+    query_job::Model {
+        id: 1,
+        query_id: 1,
+        project_id: 1,
+        user_id: 1,
+        status,
+        result: None,
+        error: None,
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    }
+}
+
+/// Sets up the query and project that the query job refers to, with nothing in the result cache.
+fn expect_query_and_project(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .query_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(query::Model {
+                id: 1,
+                string: "consistency: A".to_string(),
+                result: None,
+                outdated: true,
+                project_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+            }))
+        });
+
+    mock_contexts
+        .project_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| {
+            Ok(Some(project::Model {
+                id: 1,
+                name: "project".to_string(),
+                owner_id: 1,
+                settings: Default::default(),
+                timeout_secs: None,
+                revision: 0,
+                collaborative: false,
+            }))
+        });
+
+    mock_contexts
+        .component_context_mock
+        .expect_get_components_info_by_project_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Default::default()));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .returning(move |_| Ok(None));
+
+    mock_contexts
+        .query_cache_context_mock
+        .expect_create()
+        .returning(Ok);
+}
+
+/// Makes the lookup of the query job return a queued job, and expects the job to be moved on to running.
+fn expect_queued_job(mock_contexts: &mut MockContexts) {
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Queued))));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(create_query_job(QueryJobStatus::Running)),
+            predicate::eq(QueryJobStatus::Queued),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+}
+
+/// Expects the query to be run once, as a run that is already cancelled if `cancelled` is set.
+fn expect_query_run(mock_services: &mut MockServices, cancelled: bool) {
+    mock_services
+        .query_run_service_mock
+        .expect_start()
+        .with(predicate::eq(1), predicate::eq(Some(1)))
+        .times(1)
+        .returning(move |_, _| QueryRun::new(watch::channel(cancelled).1, || {}));
+}
+
+/// Expects exactly one event of each of the given kinds to be published.
+fn expect_events(mock_services: &mut MockServices, kinds: &[Kind]) {
+    for kind in kinds {
+        let kind = i32::from(*kind);
+        mock_services
+            .query_event_service_mock
+            .expect_publish()
+            .withf(move |event| event.kind == kind && event.query_id == 1)
+            .times(1)
+            .returning(|_| ());
+    }
+}
+
+async fn run(mock_contexts: MockContexts, mock_services: MockServices) {
+    let contexts = disguise_context_mocks(mock_contexts);
+    let services = disguise_service_mocks(mock_services);
+
+    run_query_job(
+        &contexts,
+        &services.reveaal_service,
+        &services.query_event_service,
+        &services.query_run_service,
+        1,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn run_query_job_stores_result() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let result = Result::Success(query_response::Success {});
+    let result_json = serde_json::to_value(result.clone()).unwrap();
+
+    expect_queued_job(&mut mock_contexts);
+    expect_query_and_project(&mut mock_contexts);
+
+    expect_query_run(&mut mock_services, false);
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| {
+            Ok(Response::new(QueryResponse {
+                query_id: 1,
+                info: Default::default(),
+                result: Some(result.clone()),
+            }))
+        });
+
+    let query_result = result_json.clone();
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(move |id, result, outdated| *id == 1 && *result == query_result && !outdated)
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    let query_result = result_json.clone();
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .withf(move |record| {
+            record.query_id == 1 && record.user_id == Some(1) && record.result == query_result
+        })
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(query_job::Model {
+                result: Some(result_json),
+                ..create_query_job(QueryJobStatus::Done)
+            }),
+            predicate::eq(QueryJobStatus::Running),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    expect_events(&mut mock_services, &[Kind::Started, Kind::Finished]);
+
+    run(mock_contexts, mock_services).await;
+}
+
+#[tokio::test]
+async fn run_query_job_reveaal_error_fails_job() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_queued_job(&mut mock_contexts);
+    expect_query_and_project(&mut mock_contexts);
+
+    expect_query_run(&mut mock_services, false);
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| Err(Status::unavailable("reveaal is down")));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(query_job::Model {
+                error: Some("reveaal is down".to_string()),
+                ..create_query_job(QueryJobStatus::Failed)
+            }),
+            predicate::eq(QueryJobStatus::Running),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    expect_events(&mut mock_services, &[Kind::Started, Kind::Failed]);
+
+    run(mock_contexts, mock_services).await;
+}
+
+#[tokio::test]
+async fn run_query_job_skips_cancelled_job() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Cancelled))));
+
+    run(mock_contexts, mock_services).await;
+}
+
+#[tokio::test]
+async fn run_query_job_skips_job_taken_by_other_worker() {
+    let mut mock_contexts = get_mock_contexts();
+    let mock_services = get_mock_services();
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_get_by_id()
+        .with(predicate::eq(1))
+        .returning(move |_| Ok(Some(create_query_job(QueryJobStatus::Queued))));
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .times(1)
+        .returning(|_, _| Ok(false));
+
+    run(mock_contexts, mock_services).await;
+}
+
+#[tokio::test]
+async fn run_query_job_keeps_job_cancelled_while_running() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_queued_job(&mut mock_contexts);
+    expect_query_and_project(&mut mock_contexts);
+
+    expect_query_run(&mut mock_services, false);
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| {
+            Ok(Response::new(QueryResponse {
+                query_id: 1,
+                info: Default::default(),
+                result: Some(Result::Success(query_response::Success {})),
+            }))
+        });
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    // The job was cancelled in the meantime, so it is no longer running
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(query_job::Model {
+                result: Some(
+                    serde_json::to_value(Result::Success(query_response::Success {})).unwrap(),
+                ),
+                ..create_query_job(QueryJobStatus::Done)
+            }),
+            predicate::eq(QueryJobStatus::Running),
+        )
+        .times(1)
+        .returning(|_, _| Ok(false));
+
+    expect_events(&mut mock_services, &[Kind::Started, Kind::Finished]);
+
+    run(mock_contexts, mock_services).await;
+}
+
+#[tokio::test]
+async fn run_query_job_uses_cached_result() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    let result = Result::Success(query_response::Success {});
+    let result_json = serde_json::to_value(result).unwrap();
+
+    expect_queued_job(&mut mock_contexts);
+
+    let cached_result = result_json.clone();
+    mock_contexts
+        .query_cache_context_mock
+        .expect_get_by_hash()
+        .times(1)
+        .returning(move |hash| {
+            Ok(Some(query_cache::Model {
+                id: 1,
+                hash: hash.to_string(),
+                result: cached_result.clone(),
+                created_at: Default::default(),
+            }))
+        });
+    expect_query_and_project(&mut mock_contexts);
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(query_job::Model {
+                result: Some(result_json),
+                ..create_query_job(QueryJobStatus::Done)
+            }),
+            predicate::eq(QueryJobStatus::Running),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    expect_events(&mut mock_services, &[Kind::Started, Kind::Finished]);
+
+    run(mock_contexts, mock_services).await;
+}
+
+#[tokio::test]
+async fn run_query_job_cancelled_query_cancels_job() {
+    let mut mock_contexts = get_mock_contexts();
+    let mut mock_services = get_mock_services();
+
+    expect_queued_job(&mut mock_contexts);
+    expect_query_and_project(&mut mock_contexts);
+
+    expect_query_run(&mut mock_services, true);
+    mock_services
+        .reveaal_service_mock
+        .expect_send_query()
+        .returning(move |_| {
+            Ok(Response::new(QueryResponse {
+                query_id: 1,
+                info: Default::default(),
+                result: Some(Result::Success(query_response::Success {})),
+            }))
+        });
+
+    mock_contexts
+        .query_context_mock
+        .expect_update_result()
+        .withf(|_, result, outdated| *outdated && result.to_string().contains("cancelled"))
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+
+    mock_contexts
+        .query_result_context_mock
+        .expect_create()
+        .times(1)
+        .returning(Ok);
+
+    mock_contexts
+        .query_job_context_mock
+        .expect_update_if_status()
+        .with(
+            predicate::eq(query_job::Model {
+                error: Some("Query was cancelled".to_string()),
+                ..create_query_job(QueryJobStatus::Cancelled)
+            }),
+            predicate::eq(QueryJobStatus::Running),
+        )
+        .times(1)
+        .returning(|_, _| Ok(true));
+
+    expect_events(&mut mock_services, &[Kind::Started, Kind::Cancelled]);
+
+    run(mock_contexts, mock_services).await;
+}
@@ -0,0 +1,161 @@
+use crate::services::service_impls::QueryRunService;
+use crate::services::service_traits::QueryRunServiceTrait;
+use std::future::pending;
+use std::time::Duration;
+use tokio::time::timeout;
+use tonic::{Code, Status};
+
+const WAIT: Duration = Duration::from_millis(50);
+
+#[tokio::test]
+async fn cancel_no_runs_returns_false() {
+    let service = QueryRunService::new();
+
+    assert!(!service.cancel(1));
+}
+
+#[tokio::test]
+async fn cancel_running_query_cancels_run() {
+    let service = QueryRunService::new();
+    let mut run = service.start(1, None);
+
+    assert!(service.cancel(1));
+    assert!(timeout(WAIT, run.cancelled()).await.is_ok());
+}
+
+#[tokio::test]
+async fn cancel_cancels_every_run_of_query() {
+    let service = QueryRunService::new();
+    let mut first = service.start(1, None);
+    let mut second = service.start(1, None);
+
+    service.cancel(1);
+
+    assert!(timeout(WAIT, first.cancelled()).await.is_ok());
+    assert!(timeout(WAIT, second.cancelled()).await.is_ok());
+}
+
+#[tokio::test]
+async fn cancel_does_not_cancel_other_queries() {
+    let service = QueryRunService::new();
+    let mut run = service.start(2, None);
+
+    assert!(!service.cancel(1));
+    assert!(timeout(WAIT, run.cancelled()).await.is_err());
+}
+
+#[tokio::test]
+async fn cancel_after_run_is_dropped_returns_false() {
+    let service = QueryRunService::new();
+    let run = service.start(1, None);
+
+    drop(run);
+
+    assert!(!service.cancel(1));
+}
+
+#[tokio::test]
+async fn cancel_after_one_of_two_runs_is_dropped_returns_true() {
+    let service = QueryRunService::new();
+    let first = service.start(1, None);
+    let _second = service.start(1, None);
+
+    drop(first);
+
+    assert!(service.cancel(1));
+}
+
+#[tokio::test]
+async fn start_after_cancel_is_not_cancelled() {
+    let service = QueryRunService::new();
+    let _cancelled = service.start(1, None);
+    service.cancel(1);
+
+    let mut run = service.start(1, None);
+
+    assert!(timeout(WAIT, run.cancelled()).await.is_err());
+}
+
+#[tokio::test]
+async fn dropping_cancelled_run_does_not_deregister_new_run() {
+    let service = QueryRunService::new();
+    let cancelled = service.start(1, None);
+    service.cancel(1);
+    let _run = service.start(1, None);
+
+    drop(cancelled);
+
+    assert!(service.cancel(1));
+}
+
+#[tokio::test]
+async fn cancel_job_no_run_returns_false() {
+    let service = QueryRunService::new();
+    let _run = service.start(1, None);
+
+    assert!(!service.cancel_job(1));
+}
+
+#[tokio::test]
+async fn cancel_job_cancels_only_run_of_job() {
+    let service = QueryRunService::new();
+    let mut job_run = service.start(1, Some(7));
+    let mut other_run = service.start(1, None);
+    let mut other_job_run = service.start(1, Some(8));
+
+    assert!(service.cancel_job(7));
+    assert!(timeout(WAIT, job_run.cancelled()).await.is_ok());
+    assert!(timeout(WAIT, other_run.cancelled()).await.is_err());
+    assert!(timeout(WAIT, other_job_run.cancelled()).await.is_err());
+    assert!(service.cancel(1));
+}
+
+#[tokio::test]
+async fn cancel_cancels_runs_of_jobs() {
+    let service = QueryRunService::new();
+    let mut job_run = service.start(1, Some(7));
+
+    assert!(service.cancel(1));
+    assert!(timeout(WAIT, job_run.cancelled()).await.is_ok());
+}
+
+#[tokio::test]
+async fn cancel_job_after_run_is_dropped_returns_false() {
+    let service = QueryRunService::new();
+    let run = service.start(1, Some(7));
+
+    drop(run);
+
+    assert!(!service.cancel_job(7));
+}
+
+#[tokio::test]
+async fn run_finished_call_returns_result() {
+    let service = QueryRunService::new();
+    let mut run = service.start(1, None);
+
+    let res = run.run(async { Ok::<_, Status>(1) }, None).await;
+
+    assert_eq!(res.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn run_slow_call_times_out() {
+    let service = QueryRunService::new();
+    let mut run = service.start(1, None);
+
+    let res = run.run(pending::<Result<(), Status>>(), Some(WAIT)).await;
+
+    assert_eq!(res.unwrap_err().code(), Code::DeadlineExceeded);
+}
+
+#[tokio::test]
+async fn run_cancelled_call_returns_cancelled() {
+    let service = QueryRunService::new();
+    let mut run = service.start(1, None);
+    service.cancel(1);
+
+    let res = run.run(async { Ok::<_, Status>(1) }, None).await;
+
+    assert_eq!(res.unwrap_err().code(), Code::Cancelled);
+}
@@ -1,9 +1,408 @@
+#![cfg(test)]
+
+use crate::api::server::protobuf::ecdar_backend_server::{EcdarBackend, EcdarBackendServer};
+use crate::api::server::protobuf::{
+    QueryRequest, QueryResponse, SimulationInfo, SimulationStartRequest, SimulationStepRequest,
+    SimulationStepResponse, UserTokenResponse,
+};
+use crate::services::service_impls::reveaal_service::probe;
+use crate::services::service_impls::{ReveaalService, ReveaalServiceConfig};
+use crate::services::service_traits::{ReveaalHealth, ReveaalServiceTrait};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tonic::transport::{Endpoint, Server};
+use tonic::{Request, Response, Status};
+
 // use crate::api::server::server::QueryResponse;
 // use wiremock_grpc::generate;
 // use wiremock_grpc::*;
 //
 // generate!("EcdarBackend", MyMockServer);
 
+// Nothing listens on port 1, so connections to it are refused right away
+const UNREACHABLE_ADDRESS: &str = "http://127.0.0.1:1";
+
+fn test_config() -> ReveaalServiceConfig {
+    ReveaalServiceConfig {
+        connect_timeout: Duration::from_secs(1),
+        keep_alive_timeout: Duration::from_secs(1),
+        health_check_interval: Duration::from_millis(10),
+        ..Default::default()
+    }
+}
+
+/// A stand-in for Reveaal that counts the requests it receives.
+#[derive(Clone, Default)]
+struct FakeReveaal {
+    calls: Arc<AtomicUsize>,
+    /// How long queries take to answer
+    delay: Duration,
+}
+
+#[tonic::async_trait]
+impl EcdarBackend for FakeReveaal {
+    async fn get_user_token(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<UserTokenResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(UserTokenResponse::default()))
+    }
+
+    async fn send_query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        Ok(Response::new(QueryResponse {
+            query_id: request.into_inner().query_id,
+            ..Default::default()
+        }))
+    }
+
+    async fn start_simulation(
+        &self,
+        _request: Request<SimulationStartRequest>,
+    ) -> Result<Response<SimulationStepResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(SimulationStepResponse::default()))
+    }
+
+    async fn take_simulation_step(
+        &self,
+        _request: Request<SimulationStepRequest>,
+    ) -> Result<Response<SimulationStepResponse>, Status> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::new(SimulationStepResponse::default()))
+    }
+}
+
+/// Serves the fake on a free local port and returns its address.
+async fn serve(fake: FakeReveaal) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let incoming = futures::stream::unfold(listener, |listener| async move {
+        let stream = listener.accept().await.map(|(stream, _)| stream);
+        Some((stream, listener))
+    });
+
+    tokio::spawn(
+        Server::builder()
+            .add_service(EcdarBackendServer::new(fake))
+            .serve_with_incoming(incoming),
+    );
+    address
+}
+
+async fn wait_for_call(fake: &FakeReveaal) {
+    while fake.calls.load(Ordering::SeqCst) == 0 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+fn simulation_info(user_id: i32) -> Option<SimulationInfo> {
+    Some(SimulationInfo {
+        component_composition: "A || B".to_string(),
+        components_info: None,
+        user_id,
+    })
+}
+
+#[tokio::test]
+async fn new_invalid_address_returns_err() {
+    let res = ReveaalService::new(&["not a uri"], test_config());
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn new_one_invalid_address_among_valid_returns_err() {
+    let res = ReveaalService::new(&[UNREACHABLE_ADDRESS, "not a uri"], test_config());
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn new_does_not_connect() {
+    let res = ReveaalService::new(&[UNREACHABLE_ADDRESS], test_config());
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn probe_unreachable_backend_returns_not_serving() {
+    let channel = Endpoint::from_static(UNREACHABLE_ADDRESS).connect_lazy();
+
+    let health = probe(channel, Duration::from_secs(1)).await;
+
+    assert!(matches!(health, ReveaalHealth::NotServing(_)));
+}
+
+#[tokio::test]
+async fn probe_reachable_backend_returns_serving_without_calling_it() {
+    let fake = FakeReveaal::default();
+    let address = serve(fake.clone()).await;
+    let channel = Endpoint::from_shared(address).unwrap().connect_lazy();
+
+    let health = probe(channel, Duration::from_secs(1)).await;
+
+    assert_eq!(health, ReveaalHealth::Serving);
+    assert_eq!(fake.calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn health_unreachable_backend_is_reported_not_serving() {
+    let service = ReveaalService::new(&[UNREACHABLE_ADDRESS], test_config()).unwrap();
+
+    let mut health = service.health();
+    for _ in 0..100 {
+        if health[0].1 != ReveaalHealth::Unknown {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        health = service.health();
+    }
+
+    assert_eq!(health[0].0, UNREACHABLE_ADDRESS);
+    assert!(matches!(health[0].1, ReveaalHealth::NotServing(_)));
+}
+
+#[tokio::test]
+async fn health_returns_every_backend() {
+    let address = serve(FakeReveaal::default()).await;
+    let service = ReveaalService::new(&[UNREACHABLE_ADDRESS, &address], test_config()).unwrap();
+
+    let addresses: Vec<String> = service
+        .health()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(addresses, vec![UNREACHABLE_ADDRESS.to_string(), address]);
+}
+
+#[tokio::test]
+async fn send_query_unreachable_backend_returns_err() {
+    let service = ReveaalService::new(&[UNREACHABLE_ADDRESS], test_config()).unwrap();
+
+    let res = service
+        .send_query(Request::new(QueryRequest::default()))
+        .await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn send_query_unreachable_backend_fails_over_to_next_backend() {
+    let fake = FakeReveaal::default();
+    let address = serve(fake.clone()).await;
+    let service = ReveaalService::new(&[UNREACHABLE_ADDRESS, &address], test_config()).unwrap();
+
+    for query_id in 0..2 {
+        let res = service
+            .send_query(Request::new(QueryRequest {
+                query_id,
+                ..Default::default()
+            }))
+            .await;
+
+        assert_eq!(res.unwrap().into_inner().query_id, query_id);
+    }
+
+    assert_eq!(fake.calls.load(Ordering::SeqCst), 2);
+    assert!(matches!(
+        service.health()[0].1,
+        ReveaalHealth::NotServing(_)
+    ));
+}
+
+#[tokio::test]
+async fn send_query_busy_backend_sends_to_least_outstanding_backend() {
+    let busy = FakeReveaal {
+        delay: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let idle = FakeReveaal::default();
+    let addresses = [serve(busy.clone()).await, serve(idle.clone()).await];
+    let service = ReveaalService::new(&[&addresses[0], &addresses[1]], test_config()).unwrap();
+
+    let (slow, res) = tokio::join!(
+        service.send_query(Request::new(QueryRequest::default())),
+        async {
+            wait_for_call(&busy).await;
+            service
+                .send_query(Request::new(QueryRequest::default()))
+                .await
+        }
+    );
+
+    assert!(slow.is_ok());
+    assert!(res.is_ok());
+    assert_eq!(busy.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(idle.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn send_query_without_timeout_is_not_capped() {
+    // The query takes longer than any timeout of the connection
+    let slow = FakeReveaal {
+        delay: Duration::from_millis(1500),
+        ..Default::default()
+    };
+    let address = serve(slow).await;
+    let service = ReveaalService::new(&[&address], test_config()).unwrap();
+
+    let res = service
+        .send_query(Request::new(QueryRequest::default()))
+        .await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn send_query_with_timeout_returns_err_when_exceeded() {
+    let slow = FakeReveaal {
+        delay: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let address = serve(slow).await;
+    let service = ReveaalService::new(&[&address], test_config()).unwrap();
+
+    let mut request = Request::new(QueryRequest::default());
+    request.set_timeout(Duration::from_millis(50));
+
+    let res = service.send_query(request).await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn take_simulation_step_is_pinned_to_backend_that_started_simulation() {
+    let busy = FakeReveaal {
+        delay: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let idle = FakeReveaal::default();
+    let addresses = [serve(busy.clone()).await, serve(idle.clone()).await];
+    let service = ReveaalService::new(&[&addresses[0], &addresses[1]], test_config()).unwrap();
+
+    // Start the simulation on the second backend while the first is busy
+    let (slow, res) = tokio::join!(
+        service.send_query(Request::new(QueryRequest::default())),
+        async {
+            wait_for_call(&busy).await;
+            service
+                .start_simulation(
+                    Some(1),
+                    Request::new(SimulationStartRequest {
+                        simulation_info: simulation_info(1),
+                    }),
+                )
+                .await
+        }
+    );
+    assert!(slow.is_ok());
+    assert!(res.is_ok());
+
+    // Both backends are now idle, so unpinned requests would go to the first
+    for _ in 0..3 {
+        service
+            .take_simulation_step(
+                Some(1),
+                Request::new(SimulationStepRequest {
+                    simulation_info: simulation_info(1),
+                    chosen_decision: None,
+                }),
+            )
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(busy.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(idle.calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn take_simulation_step_after_end_simulation_is_not_pinned() {
+    let busy = FakeReveaal {
+        delay: Duration::from_millis(500),
+        ..Default::default()
+    };
+    let idle = FakeReveaal::default();
+    let addresses = [serve(busy.clone()).await, serve(idle.clone()).await];
+    let service = ReveaalService::new(&[&addresses[0], &addresses[1]], test_config()).unwrap();
+
+    // Start the simulation on the second backend while the first is busy
+    let (slow, res) = tokio::join!(
+        service.send_query(Request::new(QueryRequest::default())),
+        async {
+            wait_for_call(&busy).await;
+            service
+                .start_simulation(
+                    Some(1),
+                    Request::new(SimulationStartRequest {
+                        simulation_info: simulation_info(1),
+                    }),
+                )
+                .await
+        }
+    );
+    assert!(slow.is_ok());
+    assert!(res.is_ok());
+
+    service.end_simulation(1).await;
+
+    // Neither the ended session nor another session is pinned to the second backend
+    for simulation_session_id in [1, 2] {
+        service
+            .take_simulation_step(
+                Some(simulation_session_id),
+                Request::new(SimulationStepRequest {
+                    simulation_info: simulation_info(1),
+                    chosen_decision: None,
+                }),
+            )
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(busy.calls.load(Ordering::SeqCst), 3);
+    assert_eq!(idle.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn take_simulation_step_not_started_fails_over_to_next_backend() {
+    let fake = FakeReveaal::default();
+    let address = serve(fake.clone()).await;
+    let service = ReveaalService::new(&[UNREACHABLE_ADDRESS, &address], test_config()).unwrap();
+
+    let res = service
+        .take_simulation_step(
+            None,
+            Request::new(SimulationStepRequest {
+                simulation_info: simulation_info(1),
+                chosen_decision: None,
+            }),
+        )
+        .await;
+
+    assert!(res.is_ok());
+    assert_eq!(fake.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn reveaal_health_display() {
+    assert_eq!(ReveaalHealth::Unknown.to_string(), "unknown");
+    assert_eq!(ReveaalHealth::Serving.to_string(), "serving");
+    assert_eq!(
+        ReveaalHealth::NotServing("refused".to_string()).to_string(),
+        "not serving: refused"
+    );
+}
+
 #[ignore]
 #[tokio::test]
 async fn send_query_test_correct_query_returns_ok() {